@@ -0,0 +1,78 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Captures build metadata for `linthis --version --json`.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!(
+        "cargo:rustc-env=LINTHIS_GIT_COMMIT={}",
+        git_commit().unwrap_or_default()
+    );
+    println!("cargo:rustc-env=LINTHIS_BUILD_DATE={}", build_date());
+    println!(
+        "cargo:rustc-env=LINTHIS_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Rebuild when HEAD moves (only watch paths that exist, or cargo reruns every build)
+    for path in [".git/HEAD", ".git/refs"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+/// Short SHA of the commit being built, if built from a git checkout
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8(output.stdout).ok()?;
+    Some(sha.trim().to_string())
+}
+
+/// Build date (UTC, YYYY-MM-DD), honoring SOURCE_DATE_EPOCH for reproducible builds
+fn build_date() -> String {
+    let secs = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,275 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Baselines of accepted issues (`linthis --baseline-update`).
+//!
+//! Each entry is an issue fingerprint: file (relative to the project root),
+//! tool, rule, message and the trimmed source line. Line numbers are left out
+//! so entries survive code moving within a file. Identical issues in a file
+//! are kept once each. Runs with a baseline hide the issues it accepts.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::types::LintIssue;
+use crate::{LintisError, Result};
+
+/// Where the baseline is kept unless another file is given
+pub const DEFAULT_BASELINE_FILE: &str = ".linthis/baseline.json";
+
+/// Fingerprint of one accepted issue
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Path relative to the project root, with `/` separators
+    pub file: String,
+    pub source: Option<String>,
+    pub code: Option<String>,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_line: Option<String>,
+}
+
+impl BaselineEntry {
+    /// Fingerprint of `issue`, its path taken relative to `root`
+    pub fn from_issue(issue: &LintIssue, root: &Path) -> Self {
+        let file = crate::utils::relative_path(&issue.file_path, root);
+        Self {
+            file: file.to_string_lossy().replace('\\', "/"),
+            source: issue.source.clone(),
+            code: issue.code.clone(),
+            message: issue.message.clone(),
+            code_line: issue.code_line.as_ref().map(|l| l.trim().to_string()),
+        }
+    }
+}
+
+/// The accepted issues of a project
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Sorted entries
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// How a baseline changed when it was rewritten
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BaselineUpdate {
+    /// Entries whose issue no longer occurs (fixed)
+    pub removed: usize,
+    /// Issues not in the old baseline (new but accepted)
+    pub added: usize,
+    /// Entries in the new baseline
+    pub total: usize,
+}
+
+impl Baseline {
+    /// Baseline accepting exactly these issues
+    pub fn from_issues(issues: &[LintIssue], root: &Path) -> Self {
+        let mut entries: Vec<BaselineEntry> = issues
+            .iter()
+            .map(|issue| BaselineEntry::from_issue(issue, root))
+            .collect();
+        entries.sort();
+        Self { entries }
+    }
+
+    /// Drop the issues this baseline accepts, each entry matching at most
+    /// one issue. Returns how many were dropped.
+    pub fn filter(&self, issues: &mut Vec<LintIssue>, root: &Path) -> usize {
+        let mut remaining: BTreeMap<&BaselineEntry, usize> = BTreeMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry).or_default() += 1;
+        }
+        let before = issues.len();
+        issues.retain(
+            |issue| match remaining.get_mut(&BaselineEntry::from_issue(issue, root)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            },
+        );
+        before - issues.len()
+    }
+
+    /// Read a baseline; a missing file is an empty baseline.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            LintisError::Config(format!(
+                "{} is not a linthis baseline: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Entries removed from and added to `self` to get `new`
+    pub fn diff(&self, new: &Baseline) -> BaselineUpdate {
+        let mut counts: BTreeMap<&BaselineEntry, isize> = BTreeMap::new();
+        for entry in &self.entries {
+            *counts.entry(entry).or_default() -= 1;
+        }
+        for entry in &new.entries {
+            *counts.entry(entry).or_default() += 1;
+        }
+        BaselineUpdate {
+            removed: counts
+                .values()
+                .filter(|c| **c < 0)
+                .map(|c| c.unsigned_abs())
+                .sum(),
+            added: counts
+                .values()
+                .filter(|c| **c > 0)
+                .map(|c| c.unsigned_abs())
+                .sum(),
+            total: new.entries.len(),
+        }
+    }
+}
+
+/// Rewrite the baseline at `path` to accept exactly `issues`, found in the
+/// project at `root`.
+pub fn update_baseline(path: &Path, issues: &[LintIssue], root: &Path) -> Result<BaselineUpdate> {
+    let old = Baseline::load(path)?;
+    let new = Baseline::from_issues(issues, root);
+    new.save(path)?;
+    Ok(old.diff(&new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::types::Severity;
+    use std::path::PathBuf;
+
+    fn issue(file: &str, line: usize, code: &str) -> LintIssue {
+        LintIssue::new(
+            PathBuf::from(file),
+            line,
+            format!("{} found", code),
+            Severity::Warning,
+        )
+        .with_source("ruff".to_string())
+        .with_code(code.to_string())
+    }
+
+    #[test]
+    fn test_update_after_fixing_and_introducing_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".linthis/baseline.json");
+        let root = dir.path();
+
+        let first = vec![
+            issue("a.py", 1, "F401"),
+            issue("a.py", 5, "E501"),
+            issue("b.py", 2, "E501"),
+        ];
+        let update = update_baseline(&path, &first, root).unwrap();
+        assert_eq!(
+            update,
+            BaselineUpdate {
+                removed: 0,
+                added: 3,
+                total: 3
+            }
+        );
+
+        // F401 fixed; the E501 moving down a line is the same entry
+        let fixed = vec![issue("a.py", 6, "E501"), issue("b.py", 2, "E501")];
+        let update = update_baseline(&path, &fixed, root).unwrap();
+        assert_eq!(
+            update,
+            BaselineUpdate {
+                removed: 1,
+                added: 0,
+                total: 2
+            }
+        );
+        assert_eq!(Baseline::load(&path).unwrap().entries.len(), 2);
+
+        // A second identical E501 in b.py is a new entry
+        let introduced = vec![
+            issue("a.py", 6, "E501"),
+            issue("b.py", 2, "E501"),
+            issue("b.py", 9, "E501"),
+        ];
+        let update = update_baseline(&path, &introduced, root).unwrap();
+        assert_eq!(
+            update,
+            BaselineUpdate {
+                removed: 0,
+                added: 1,
+                total: 3
+            }
+        );
+        assert_eq!(
+            Baseline::load(&path).unwrap(),
+            Baseline::from_issues(&introduced, root)
+        );
+    }
+
+    #[test]
+    fn test_entries_are_relative_to_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.py"), "import os\n").unwrap();
+
+        let absolute = issue(&root.join("src/a.py").to_string_lossy(), 1, "F401");
+        let baseline = Baseline::from_issues(std::slice::from_ref(&absolute), &root);
+        assert_eq!(baseline.entries[0].file, "src/a.py");
+    }
+
+    #[test]
+    fn test_filter_drops_each_accepted_issue_once() {
+        let root = Path::new("/project");
+        let baseline = Baseline::from_issues(
+            &[
+                issue("/project/a.py", 1, "E501"),
+                issue("/project/b.py", 2, "F401"),
+            ],
+            root,
+        );
+
+        let mut issues = vec![
+            issue("/project/a.py", 3, "E501"),
+            issue("/project/a.py", 7, "E501"),
+            issue("/project/c.py", 1, "F401"),
+        ];
+        assert_eq!(baseline.filter(&mut issues, root), 1);
+        let left: Vec<_> = issues
+            .iter()
+            .map(|i| (i.file_path.to_string_lossy().into_owned(), i.line))
+            .collect();
+        assert_eq!(
+            left,
+            vec![
+                ("/project/a.py".to_string(), 7),
+                ("/project/c.py".to_string(), 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Python security checker using bandit.
+//!
+//! Bandit is opt-in: add it to the Python checker chain, e.g.
+//! `[python] checkers = ["ruff", "bandit"]`.
+
+use crate::checkers::{CheckResult, Checker, CheckerError};
+use crate::utils::capture::{tool_output, ToolCapture};
+use crate::utils::types::{LintIssue, Severity};
+use crate::utils::{apply_memory_limit, check_tool_status_with_limit, tool_diagnostics};
+use crate::Language;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+/// Bandit JSON output structure
+#[derive(Debug, Deserialize)]
+struct BanditOutput {
+    #[serde(default)]
+    results: Vec<BanditResult>,
+}
+
+/// Bandit JSON output finding structure
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct BanditResult {
+    filename: String,
+    test_id: String,
+    test_name: String,
+    issue_severity: String,
+    issue_confidence: String,
+    issue_text: String,
+    line_number: usize,
+    #[serde(default)]
+    col_offset: Option<usize>,
+    #[serde(default)]
+    more_info: Option<String>,
+}
+
+/// Python security checker using bandit.
+pub struct BanditChecker {
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+    /// Address-space limit of the linter in MB (`tool_memory_limit_mb`)
+    memory_limit_mb: Option<u64>,
+}
+
+impl BanditChecker {
+    pub fn new() -> Self {
+        Self {
+            capture: None,
+            memory_limit_mb: None,
+        }
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Cap the linter's memory at `limit_mb` (None = unlimited)
+    pub fn with_memory_limit(mut self, limit_mb: Option<u64>) -> Self {
+        self.memory_limit_mb = limit_mb;
+        self
+    }
+
+    /// Find bandit configuration file
+    fn find_bandit_config(path: &Path) -> Option<PathBuf> {
+        let mut current = if path.is_file() {
+            path.parent()?.to_path_buf()
+        } else {
+            path.to_path_buf()
+        };
+
+        let config_names = [
+            ".linthis/configs/python/bandit.yaml", // Plugin config (highest priority)
+            ".linthis/configs/python/.bandit.yaml",
+            "bandit.yaml",
+            ".bandit.yaml",
+        ];
+
+        loop {
+            for config_name in &config_names {
+                let config_path = current.join(config_name);
+                if config_path.exists() {
+                    return Some(config_path);
+                }
+            }
+
+            if !current.pop() {
+                break;
+            }
+        }
+
+        None
+    }
+
+    /// Parse bandit JSON output and extract issues.
+    ///
+    /// Empty output means nothing was reported; anything else that is not
+    /// bandit JSON is a [`CheckerError::ParseError`].
+    fn parse_bandit_json_output(&self, output: &str) -> CheckResult<Vec<LintIssue>> {
+        if output.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let bandit_output: BanditOutput =
+            serde_json::from_str(output).map_err(|_| CheckerError::parse("bandit", output))?;
+
+        Ok(bandit_output
+            .results
+            .into_iter()
+            .map(|result| {
+                let mut issue = LintIssue::new(
+                    PathBuf::from(&result.filename),
+                    result.line_number,
+                    result.issue_text,
+                    Self::map_severity(&result.issue_severity),
+                )
+                .with_source("bandit".to_string())
+                .with_code(result.test_id);
+
+                // Bandit columns are 0-based
+                if let Some(col) = result.col_offset {
+                    issue = issue.with_column(col + 1);
+                }
+                if let Some(url) = result.more_info {
+                    issue = issue.with_suggestion(format!("See {}", url));
+                }
+                issue
+            })
+            .collect())
+    }
+
+    /// Map bandit issue severity to severity level.
+    ///
+    /// - HIGH -> Error
+    /// - MEDIUM -> Warning
+    /// - LOW (and unknown) -> Info
+    fn map_severity(severity: &str) -> Severity {
+        match severity.to_ascii_uppercase().as_str() {
+            "HIGH" => Severity::Error,
+            "MEDIUM" => Severity::Warning,
+            _ => Severity::Info,
+        }
+    }
+}
+
+impl Default for BanditChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checker for BanditChecker {
+    fn name(&self) -> &str {
+        "bandit"
+    }
+
+    fn supported_languages(&self) -> &[Language] {
+        &[Language::Python]
+    }
+
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        let mut cmd = Command::new("bandit");
+        cmd.args(["-f", "json", "-q"]);
+
+        if let Some(config_path) = self.config_path(path) {
+            cmd.arg("-c").arg(config_path);
+        }
+
+        // bandit exits with 1 when issues are found, so only stdout matters
+        cmd.arg(path);
+        let output = tool_output(
+            self.capture.as_deref(),
+            "bandit",
+            path,
+            apply_memory_limit(&mut cmd, self.memory_limit_mb),
+        )
+        .map_err(|e| CheckerError::spawn("bandit", e))?;
+        check_tool_status_with_limit("bandit", &output, &[1], self.memory_limit_mb)?;
+
+        self.parse_bandit_json_output(&tool_diagnostics("bandit", &output))
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("bandit")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        Self::find_bandit_config(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_mapping() {
+        assert_eq!(BanditChecker::map_severity("HIGH"), Severity::Error);
+        assert_eq!(BanditChecker::map_severity("MEDIUM"), Severity::Warning);
+        assert_eq!(BanditChecker::map_severity("LOW"), Severity::Info);
+        assert_eq!(BanditChecker::map_severity("UNDEFINED"), Severity::Info);
+    }
+
+    #[test]
+    fn test_parse_bandit_json_output() {
+        let checker = BanditChecker::new();
+        let json = r#"{
+            "errors": [],
+            "generated_at": "2024-05-01T10:00:00Z",
+            "metrics": {"_totals": {"SEVERITY.HIGH": 1, "SEVERITY.LOW": 1}},
+            "results": [
+                {
+                    "code": "4 subprocess.call(cmd, shell=True)\n",
+                    "col_offset": 0,
+                    "end_col_offset": 33,
+                    "filename": "app.py",
+                    "issue_confidence": "HIGH",
+                    "issue_cwe": {"id": 78, "link": "https://cwe.mitre.org/data/definitions/78.html"},
+                    "issue_severity": "HIGH",
+                    "issue_text": "subprocess call with shell=True identified, security issue.",
+                    "line_number": 4,
+                    "line_range": [4],
+                    "more_info": "https://bandit.readthedocs.io/en/latest/plugins/b602_subprocess_popen_with_shell_equals_true.html",
+                    "test_id": "B602",
+                    "test_name": "subprocess_popen_with_shell_equals_true"
+                },
+                {
+                    "code": "1 import subprocess\n",
+                    "col_offset": 0,
+                    "filename": "app.py",
+                    "issue_confidence": "HIGH",
+                    "issue_severity": "LOW",
+                    "issue_text": "Consider possible security implications associated with the subprocess module.",
+                    "line_number": 1,
+                    "line_range": [1],
+                    "test_id": "B404",
+                    "test_name": "blacklist"
+                }
+            ]
+        }"#;
+
+        let issues = checker.parse_bandit_json_output(json).unwrap();
+        assert_eq!(issues.len(), 2);
+
+        let issue = &issues[0];
+        assert_eq!(issue.file_path, PathBuf::from("app.py"));
+        assert_eq!(issue.line, 4);
+        assert_eq!(issue.column, Some(1));
+        assert_eq!(issue.code, Some("B602".to_string()));
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.source, Some("bandit".to_string()));
+        assert!(issue.message.contains("shell=True"));
+
+        assert_eq!(issues[1].severity, Severity::Info);
+        assert_eq!(issues[1].code, Some("B404".to_string()));
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        let checker = BanditChecker::new();
+        assert!(checker.parse_bandit_json_output("").unwrap().is_empty());
+        assert!(matches!(
+            checker.parse_bandit_json_output("not json"),
+            Err(CheckerError::ParseError { .. })
+        ));
+    }
+}
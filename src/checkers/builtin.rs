@@ -0,0 +1,459 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Built-in checks that need no external tools.
+//!
+//! Enabled from the `[builtin_checks]` config table, e.g.
+//! `[builtin_checks] whitespace = true` or `max_function_lines = 80`.
+//! Issues are reported with source "linthis".
+
+use crate::checkers::{CheckResult, Checker};
+use crate::fixers::includes::{IncludeCategory, IncludeOrderFixer};
+use crate::utils::types::{LintIssue, Severity};
+use crate::Language;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Toggles and parameters for the built-in checks (all off by default,
+/// except `final_newline`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuiltinChecksConfig {
+    /// Report trailing whitespace
+    #[serde(default)]
+    pub whitespace: Option<bool>,
+    /// Report files that don't end with exactly one newline (on unless
+    /// set to false)
+    #[serde(default)]
+    pub final_newline: Option<bool>,
+    /// Report indentation mixing tabs and spaces
+    #[serde(default)]
+    pub mixed_indentation: Option<bool>,
+    /// Report functions longer than this many lines
+    #[serde(default)]
+    pub max_function_lines: Option<usize>,
+    /// Report C/C++ include blocks out of group order
+    #[serde(default)]
+    pub include_order: Option<bool>,
+    /// Include group order for `include_order` and C/C++ `organize_imports`
+    /// (default: own-header, c-system, cpp-system, other-library, project)
+    #[serde(default)]
+    pub include_groups: Option<Vec<IncludeCategory>>,
+}
+
+impl BuiltinChecksConfig {
+    /// Whether any built-in check is enabled
+    pub fn any_enabled(&self) -> bool {
+        self.whitespace == Some(true)
+            || self.final_newline_enabled()
+            || self.mixed_indentation == Some(true)
+            || self.max_function_lines.is_some_and(|max| max > 0)
+            || self.include_order == Some(true)
+    }
+
+    /// Whether the final newline check runs (opt-out)
+    pub fn final_newline_enabled(&self) -> bool {
+        self.final_newline != Some(false)
+    }
+
+    /// Include order fixer for the configured groups
+    pub fn include_order_fixer(&self) -> IncludeOrderFixer {
+        match &self.include_groups {
+            Some(groups) => IncludeOrderFixer::new(groups.clone()),
+            None => IncludeOrderFixer::default(),
+        }
+    }
+
+    /// Merge another configuration into this one.
+    /// Values set in `other` override values in `self`.
+    pub fn merge(&mut self, other: BuiltinChecksConfig) {
+        if other.whitespace.is_some() {
+            self.whitespace = other.whitespace;
+        }
+        if other.final_newline.is_some() {
+            self.final_newline = other.final_newline;
+        }
+        if other.mixed_indentation.is_some() {
+            self.mixed_indentation = other.mixed_indentation;
+        }
+        if other.max_function_lines.is_some() {
+            self.max_function_lines = other.max_function_lines;
+        }
+        if other.include_order.is_some() {
+            self.include_order = other.include_order;
+        }
+        if other.include_groups.is_some() {
+            self.include_groups = other.include_groups;
+        }
+    }
+}
+
+/// Checker running the enabled built-in checks.
+pub struct BuiltinChecker {
+    config: BuiltinChecksConfig,
+}
+
+impl BuiltinChecker {
+    pub fn new(config: BuiltinChecksConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run the enabled checks over file content.
+    fn check_content(&self, path: &Path, content: &str) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        let issue = |line: usize, message: &str, code: &str| {
+            LintIssue::new(
+                path.to_path_buf(),
+                line,
+                message.to_string(),
+                Severity::Warning,
+            )
+            .with_source("linthis".to_string())
+            .with_code(code.to_string())
+        };
+
+        for (idx, line) in content.lines().enumerate() {
+            let line_num = idx + 1;
+            if self.config.whitespace == Some(true) && line.ends_with([' ', '\t']) {
+                let column = line.trim_end_matches([' ', '\t']).chars().count() + 1;
+                issues.push(
+                    issue(line_num, "Trailing whitespace", "trailing-whitespace")
+                        .with_column(column),
+                );
+            }
+            if self.config.mixed_indentation == Some(true) {
+                let indent = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+                if indent.contains(' ') && indent.contains('\t') {
+                    issues.push(issue(
+                        line_num,
+                        "Indentation mixes tabs and spaces",
+                        "mixed-indentation",
+                    ));
+                }
+            }
+        }
+
+        if self.config.final_newline_enabled() && !content.trim().is_empty() {
+            let body = content.trim_end_matches(['\n', '\r']);
+            if !content.ends_with('\n') {
+                issues.push(issue(
+                    content.lines().count(),
+                    "No newline at end of file",
+                    "final-newline",
+                ));
+            } else if content[body.len()..].matches('\n').count() > 1 {
+                issues.push(issue(
+                    body.lines().count() + 1,
+                    "Trailing blank lines at end of file",
+                    "final-newline",
+                ));
+            }
+        }
+
+        if let Some(max) = self.config.max_function_lines.filter(|max| *max > 0) {
+            for (start, name, len) in function_spans(content, Language::from_path(path)) {
+                if len > max {
+                    issues.push(issue(
+                        start,
+                        &format!("Function `{}` has {} lines (max {})", name, len, max),
+                        "max-function-lines",
+                    ));
+                }
+            }
+        }
+
+        let is_c_family = matches!(
+            Language::from_path(path),
+            Some(Language::Cpp | Language::ObjectiveC)
+        );
+        if self.config.include_order == Some(true) && is_c_family {
+            let fixer = self.config.include_order_fixer();
+            for line in fixer.misordered_lines(path, content) {
+                issues.push(issue(
+                    line,
+                    "Includes are not grouped and sorted in the configured order",
+                    "include-order",
+                ));
+            }
+        }
+
+        issues.sort_by_key(|i| (i.line, i.column));
+        issues
+    }
+}
+
+/// Find functions as (1-based start line, name, length in lines).
+///
+/// Python functions span their indented body; other languages span from the
+/// signature to the matching closing brace.
+fn function_spans(content: &str, lang: Option<Language>) -> Vec<(usize, String, usize)> {
+    let lines: Vec<&str> = content.lines().collect();
+    match lang {
+        Some(Language::Python) => python_function_spans(&lines),
+        Some(lang) => brace_function_spans(&lines, lang),
+        None => Vec::new(),
+    }
+}
+
+fn python_function_spans(lines: &[&str]) -> Vec<(usize, String, usize)> {
+    let def_re = Regex::new(r"^(\s*)(?:async\s+)?def\s+(\w+)").unwrap();
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+
+    let mut spans = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(caps) = def_re.captures(line) else {
+            continue;
+        };
+        let indent = caps[1].len();
+        // The body ends before the next non-blank line at the def's indentation
+        let mut end = idx;
+        for (offset, body) in lines[idx + 1..].iter().enumerate() {
+            if body.trim().is_empty() {
+                continue;
+            }
+            if indent_of(body) <= indent {
+                break;
+            }
+            end = idx + 1 + offset;
+        }
+        spans.push((idx + 1, caps[2].to_string(), end - idx + 1));
+    }
+    spans
+}
+
+fn brace_function_spans(lines: &[&str], lang: Language) -> Vec<(usize, String, usize)> {
+    let pattern = match lang {
+        Language::Rust => r"\bfn\s+(\w+)",
+        Language::Go => r"^func\s+(?:\([^)]*\)\s*)?(\w+)",
+        Language::JavaScript | Language::TypeScript => r"\bfunction\s*\*?\s*(\w+)",
+        Language::Cpp | Language::ObjectiveC | Language::Java => {
+            r"^\s*(?:[\w:<>,\*&\[\]]+\s+)+\**&*([A-Za-z_][\w:~]*)\s*\([^;]*$"
+        }
+        Language::Python => return Vec::new(),
+    };
+    let signature_re = Regex::new(pattern).unwrap();
+    let keyword_re = Regex::new(r"^\s*(?:if|else|for|while|switch|return|catch|do)\b").unwrap();
+
+    let mut spans = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        let caps = match signature_re.captures(line) {
+            Some(caps) if !keyword_re.is_match(line) => caps,
+            _ => {
+                idx += 1;
+                continue;
+            }
+        };
+
+        // Count braces from the signature until the body closes
+        let mut depth = 0usize;
+        let mut opened = false;
+        let mut end = None;
+        'scan: for (offset, body) in lines[idx..].iter().enumerate() {
+            for ch in body.chars() {
+                match ch {
+                    '{' => {
+                        depth += 1;
+                        opened = true;
+                    }
+                    '}' if opened => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 {
+                            end = Some(idx + offset);
+                            break 'scan;
+                        }
+                    }
+                    ';' if !opened => break 'scan,
+                    _ => {}
+                }
+            }
+        }
+
+        match end {
+            Some(end) => {
+                spans.push((idx + 1, caps[1].to_string(), end - idx + 1));
+                // Nested functions (closures, inner fns) are part of this one
+                idx = end + 1;
+            }
+            None => idx += 1,
+        }
+    }
+    spans
+}
+
+impl Checker for BuiltinChecker {
+    fn name(&self) -> &str {
+        "linthis"
+    }
+
+    fn supported_languages(&self) -> &[Language] {
+        &[
+            Language::Cpp,
+            Language::ObjectiveC,
+            Language::Java,
+            Language::Python,
+            Language::Rust,
+            Language::Go,
+            Language::JavaScript,
+            Language::TypeScript,
+        ]
+    }
+
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        let content = std::fs::read_to_string(path)?;
+        let (content, _) = crate::utils::bom::strip_bom(&content);
+        Ok(self.check_content(path, content))
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "def short():\n    return 1  \n\ndef long():\n    a = 1\n\t  b = 2\n    c = 3\n    return a + b + c";
+
+    fn codes(config: BuiltinChecksConfig, path: &str, content: &str) -> Vec<(usize, String)> {
+        BuiltinChecker::new(config)
+            .check_content(Path::new(path), content)
+            .into_iter()
+            .map(|i| (i.line, i.code.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_only_final_newline_enabled_by_default() {
+        let config = BuiltinChecksConfig::default();
+        assert!(config.any_enabled());
+        assert_eq!(
+            codes(config, "sample.py", SAMPLE),
+            vec![(8, "final-newline".to_string())]
+        );
+
+        let config = BuiltinChecksConfig {
+            final_newline: Some(false),
+            ..Default::default()
+        };
+        assert!(!config.any_enabled());
+        assert!(codes(config, "sample.py", SAMPLE).is_empty());
+    }
+
+    #[test]
+    fn test_final_newline_reports_trailing_blank_lines() {
+        let config = BuiltinChecksConfig::default();
+        assert_eq!(
+            codes(config.clone(), "a.py", "x = 1\n\n\n"),
+            vec![(2, "final-newline".to_string())]
+        );
+        assert_eq!(
+            codes(config.clone(), "a.py", "x = 1\r\n\r\n"),
+            vec![(2, "final-newline".to_string())]
+        );
+        assert!(codes(config.clone(), "a.py", "x = 1\n").is_empty());
+        assert!(codes(config, "a.py", "").is_empty());
+    }
+
+    #[test]
+    fn test_whitespace_and_final_newline() {
+        let config = BuiltinChecksConfig {
+            whitespace: Some(true),
+            final_newline: Some(true),
+            ..Default::default()
+        };
+        assert!(config.any_enabled());
+        assert_eq!(
+            codes(config, "sample.py", SAMPLE),
+            vec![
+                (2, "trailing-whitespace".to_string()),
+                (8, "final-newline".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mixed_indentation_and_function_length() {
+        let config = BuiltinChecksConfig {
+            final_newline: Some(false),
+            mixed_indentation: Some(true),
+            max_function_lines: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            codes(config, "sample.py", SAMPLE),
+            vec![
+                (4, "max-function-lines".to_string()),
+                (6, "mixed-indentation".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_spans_brace_languages() {
+        let rust = "fn main() {\n    let x = 1;\n    if x > 0 {\n        println!(\"{}\", x);\n    }\n}\n\nfn tiny() {}\n";
+        assert_eq!(
+            function_spans(rust, Some(Language::Rust)),
+            vec![(1, "main".to_string(), 6), (8, "tiny".to_string(), 1)]
+        );
+
+        let go = "package main\n\nfunc (s *Server) Run() error {\n\treturn nil\n}\n";
+        assert_eq!(
+            function_spans(go, Some(Language::Go)),
+            vec![(3, "Run".to_string(), 3)]
+        );
+
+        let cpp = "int add(int a, int b);\n\nint add(int a, int b)\n{\n    return a + b;\n}\n";
+        assert_eq!(
+            function_spans(cpp, Some(Language::Cpp)),
+            vec![(3, "add".to_string(), 4)]
+        );
+    }
+
+    #[test]
+    fn test_include_order_only_checks_c_family() {
+        let config = BuiltinChecksConfig {
+            include_order: Some(true),
+            ..Default::default()
+        };
+        let content = "#include \"util.h\"\n\n#include <vector>\n\nint x;\n";
+        assert_eq!(
+            codes(config.clone(), "src/main.cc", content),
+            vec![(1, "include-order".to_string())]
+        );
+        assert!(codes(config.clone(), "main.py", content).is_empty());
+
+        // Project headers first
+        let config = BuiltinChecksConfig {
+            include_groups: Some(vec![IncludeCategory::Project]),
+            ..config
+        };
+        assert!(codes(config, "src/main.cc", content).is_empty());
+    }
+
+    #[test]
+    fn test_merge_overrides_set_values() {
+        let mut base = BuiltinChecksConfig {
+            whitespace: Some(true),
+            max_function_lines: Some(50),
+            ..Default::default()
+        };
+        base.merge(BuiltinChecksConfig {
+            whitespace: Some(false),
+            final_newline: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(base.whitespace, Some(false));
+        assert_eq!(base.final_newline, Some(true));
+        assert_eq!(base.max_function_lines, Some(50));
+    }
+}
@@ -10,14 +10,18 @@
 
 //! C/C++ language checker using clang-tidy or cpplint.
 
-use crate::checkers::Checker;
-use crate::utils::types::{LintIssue, Severity};
-use crate::{Language, Result};
+use crate::checkers::{CheckResult, Checker, CheckerError};
+use crate::utils::capture::{tool_output, ToolCapture};
+use crate::utils::clang::{ClangPin, ClangToolCache};
+use crate::utils::types::{LintIssue, RelatedInfo, RelatedKind, Severity};
+use crate::utils::{apply_memory_limit, check_tool_status_with_limit, tool_diagnostics};
+use crate::Language;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 /// Cpplint configuration for different languages
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CpplintConfig {
     /// Line length limit
     pub linelength: Option<u32>,
@@ -25,17 +29,10 @@ pub struct CpplintConfig {
     pub filter: Option<String>,
 }
 
-impl Default for CpplintConfig {
-    fn default() -> Self {
-        Self {
-            linelength: None,
-            filter: None,
-        }
-    }
-}
-
 /// C/C++ checker using clang-tidy (preferred) or cpplint.
 pub struct CppChecker {
+    /// Force a single tool instead of preferring clang-tidy over cpplint
+    tool: Option<CppLintTool>,
     /// Custom .clang-tidy config path
     config_path: Option<PathBuf>,
     /// Custom compile_commands.json directory path
@@ -44,6 +41,23 @@ pub struct CppChecker {
     cpplint_cpp_config: CpplintConfig,
     /// Cpplint config for Objective-C files
     cpplint_oc_config: CpplintConfig,
+    /// Ignore all config files (clang-tidy with an inline default config)
+    isolated: bool,
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+    /// Address-space limit of the linter in MB (`tool_memory_limit_mb`)
+    memory_limit_mb: Option<u64>,
+    /// clang-tidy version pin from linthis config
+    clang_pin: Option<ClangPin>,
+    /// Resolved clang binaries, possibly shared with the rest of the run
+    clang: Arc<ClangToolCache>,
+}
+
+/// Individual C/C++ lint tool, used when chaining checkers explicitly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CppLintTool {
+    ClangTidy,
+    Cpplint,
 }
 
 impl CppChecker {
@@ -55,10 +69,16 @@ impl CppChecker {
         let clang_tidy_config = Self::find_plugin_clang_tidy_config();
 
         Self {
+            tool: None,
             config_path: clang_tidy_config,
             compile_commands_dir: None,
             cpplint_cpp_config: cpp_config,
             cpplint_oc_config: oc_config,
+            isolated: false,
+            capture: None,
+            memory_limit_mb: None,
+            clang_pin: None,
+            clang: Arc::default(),
         }
     }
 
@@ -205,6 +225,57 @@ impl CppChecker {
         Some(CpplintConfig { linelength, filter })
     }
 
+    /// Only run the given tool (no clang-tidy → cpplint fallback)
+    pub fn with_tool(mut self, tool: CppLintTool) -> Self {
+        self.tool = Some(tool);
+        self
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Cap the linter's memory at `limit_mb` (None = unlimited)
+    pub fn with_memory_limit(mut self, limit_mb: Option<u64>) -> Self {
+        self.memory_limit_mb = limit_mb;
+        self
+    }
+
+    /// Pin the clang-tidy version (overrides `.clang-format-version`)
+    pub fn with_clang_pin(mut self, pin: Option<ClangPin>) -> Self {
+        self.clang_pin = pin;
+        self
+    }
+
+    /// Share resolved clang binaries and printed warnings with `cache`
+    pub fn with_clang_cache(mut self, cache: Arc<ClangToolCache>) -> Self {
+        self.clang = cache;
+        self
+    }
+
+    /// Whether clang-tidy is used when no tool is forced; when replaying,
+    /// whichever tool was recorded
+    fn prefers_clang_tidy(&self, path: &Path) -> bool {
+        match self.capture.as_deref() {
+            Some(capture) if capture.is_replay() => capture.has_recording("clang-tidy"),
+            _ => self.has_clang_tidy(path),
+        }
+    }
+
+    /// Ignore .clang-tidy files and linthis cpplint settings, using the
+    /// tools' defaults
+    pub fn with_isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        if isolated {
+            self.config_path = None;
+            self.cpplint_cpp_config = CpplintConfig::default();
+            self.cpplint_oc_config = CpplintConfig::default();
+        }
+        self
+    }
+
     /// Set custom .clang-tidy config path
     pub fn with_config(mut self, path: PathBuf) -> Self {
         self.config_path = Some(path);
@@ -373,15 +444,26 @@ impl CppChecker {
         None
     }
 
-    /// Check if clang-tidy is available
-    fn has_clang_tidy() -> bool {
-        Command::new("clang-tidy")
+    /// Check if clang-tidy (as resolved for the given path) is available
+    fn has_clang_tidy(&self, path: &Path) -> bool {
+        Command::new(self.clang_tidy_binary(path))
             .arg("--version")
             .output()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 
+    /// The clang-tidy binary to run for the given path
+    fn clang_tidy_binary(&self, path: &Path) -> PathBuf {
+        self.clang
+            .resolve("clang-tidy", self.clang_pin.as_ref(), path)
+    }
+
+    /// Current directory, used when resolving tools without a specific file
+    fn current_dir() -> PathBuf {
+        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+    }
+
     /// Check if cpplint is available
     fn has_cpplint() -> bool {
         Command::new("cpplint")
@@ -392,19 +474,52 @@ impl CppChecker {
     }
 
     /// Run clang-tidy on a file (check only, no fix)
-    fn run_clang_tidy(&self, path: &Path) -> Result<Vec<LintIssue>> {
+    fn run_clang_tidy(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
         // Skip clang-tidy if LINTHIS_SKIP_CLANG_TIDY env var is set
         if std::env::var("LINTHIS_SKIP_CLANG_TIDY").is_ok() {
             return Ok(vec![]);
         }
 
-        let mut cmd = Command::new("clang-tidy");
+        self.run_clang_tidy_with(&self.clang_tidy_binary(path), path)
+    }
+
+    /// Run the given clang-tidy binary on a file
+    fn run_clang_tidy_with(&self, binary: &Path, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        let mut cmd = self.clang_tidy_command(binary, path);
+        let output = tool_output(
+            self.capture.as_deref(),
+            "clang-tidy",
+            path,
+            apply_memory_limit(&mut cmd, self.memory_limit_mb),
+        )
+        .map_err(|e| CheckerError::spawn("clang-tidy", e))?;
+        // clang-tidy exits 1 when it reports errors
+        check_tool_status_with_limit("clang-tidy", &output, &[1], self.memory_limit_mb)?;
+
+        let issues = Self::parse_clang_tidy_output(&tool_diagnostics("clang-tidy", &output), path);
+
+        Ok(issues)
+    }
+
+    /// The .clang-tidy config for a file: specified, or found above it
+    fn clang_tidy_config(&self, path: &Path) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        self.config_path
+            .clone()
+            .or_else(|| Self::find_clang_tidy_config(path))
+    }
+
+    /// Build the clang-tidy invocation for a file
+    pub(crate) fn clang_tidy_command(&self, binary: &Path, path: &Path) -> Command {
+        let mut cmd = Command::new(binary);
         cmd.arg(path);
 
-        // Add config file if specified or found
-        if let Some(ref config) = self.config_path {
-            cmd.arg(format!("--config-file={}", config.display()));
-        } else if let Some(config) = Self::find_clang_tidy_config(path) {
+        // Add config file if specified or found (an inline config stops the lookup)
+        if self.isolated {
+            cmd.arg("--config={}");
+        } else if let Some(config) = self.clang_tidy_config(path) {
             cmd.arg(format!("--config-file={}", config.display()));
         }
 
@@ -417,19 +532,11 @@ impl CppChecker {
             // Use -- to separate clang-tidy args from compiler args
             cmd.arg("--");
         }
-
-        let output = cmd
-            .output()
-            .map_err(|e| crate::LintisError::Checker(format!("Failed to run clang-tidy: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let issues = Self::parse_clang_tidy_output(&stdout, path);
-
-        Ok(issues)
+        cmd
     }
 
     /// Run cpplint on a file with language-specific config
-    fn run_cpplint(&self, path: &Path) -> Result<Vec<LintIssue>> {
+    fn run_cpplint(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
         let mut cmd = Command::new("cpplint");
 
         // Select config based on file type (Objective-C vs C++)
@@ -457,25 +564,48 @@ impl CppChecker {
 
         cmd.arg(path);
 
-        let output = cmd
-            .output()
-            .map_err(|e| crate::LintisError::Checker(format!("Failed to run cpplint: {}", e)))?;
+        let output = tool_output(
+            self.capture.as_deref(),
+            "cpplint",
+            path,
+            apply_memory_limit(&mut cmd, self.memory_limit_mb),
+        )
+        .map_err(|e| CheckerError::spawn("cpplint", e))?;
+        check_tool_status_with_limit("cpplint", &output, &[1], self.memory_limit_mb)?;
 
         // cpplint outputs to stderr
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let issues = Self::parse_cpplint_output(&stderr, path);
+        let issues = Self::parse_cpplint_output(&tool_diagnostics("cpplint", &output), path);
 
         Ok(issues)
     }
 
     /// Parse clang-tidy output
     /// Format: file:line:col: severity: message [check-name]
+    ///
+    /// Notes following a warning or error are attached to it as related
+    /// info; notes of a filtered-out diagnostic are dropped with it.
     fn parse_clang_tidy_output(output: &str, file_path: &Path) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
+        let mut issues: Vec<LintIssue> = Vec::new();
+        // Whether the last warning/error was kept; None before the first one
+        let mut primary_kept: Option<bool> = None;
 
         for line in output.lines() {
-            if let Some(issue) = Self::parse_clang_tidy_line(line, file_path) {
+            let is_primary = line.contains(": warning:") || line.contains(": error:");
+            let issue = Self::parse_clang_tidy_line(line, file_path);
+            if is_primary {
+                primary_kept = Some(issue.is_some());
+            }
+            let Some(issue) = issue else {
+                continue;
+            };
+            if is_primary || primary_kept.is_none() {
                 issues.push(issue);
+            } else if primary_kept == Some(true) {
+                let related = RelatedInfo::new(RelatedKind::Note, issue.message)
+                    .with_location(issue.file_path, issue.line);
+                if let Some(primary) = issues.last_mut() {
+                    primary.related.push(related);
+                }
             }
         }
 
@@ -483,9 +613,9 @@ impl CppChecker {
     }
 
     fn parse_clang_tidy_line(line: &str, default_path: &Path) -> Option<LintIssue> {
-        // clang-tidy format: file:line:col: warning/error: message [check-name]
+        // clang-tidy format: file:line:col: warning/error/note: message [check-name]
         // Example: test.cpp:10:5: warning: use nullptr [modernize-use-nullptr]
-        if !line.contains(": warning:") && !line.contains(": error:") {
+        if !line.contains(": warning:") && !line.contains(": error:") && !line.contains(": note:") {
             return None;
         }
 
@@ -520,6 +650,8 @@ impl CppChecker {
 
         let severity = if severity_str.contains("error") {
             Severity::Error
+        } else if severity_str == "note" {
+            Severity::Hint
         } else {
             Severity::Warning
         };
@@ -648,7 +780,12 @@ impl Default for CppChecker {
 
 impl Checker for CppChecker {
     fn name(&self) -> &str {
-        if Self::has_clang_tidy() {
+        match self.tool {
+            Some(CppLintTool::ClangTidy) => return "clang-tidy",
+            Some(CppLintTool::Cpplint) => return "cpplint",
+            None => {}
+        }
+        if self.prefers_clang_tidy(&Self::current_dir()) {
             "clang-tidy"
         } else {
             "cpplint"
@@ -659,11 +796,18 @@ impl Checker for CppChecker {
         &[Language::Cpp, Language::ObjectiveC]
     }
 
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>> {
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        match self.tool {
+            Some(CppLintTool::ClangTidy) => return self.run_clang_tidy(path),
+            Some(CppLintTool::Cpplint) => return self.run_cpplint(path),
+            None => {}
+        }
+
         // Prefer clang-tidy if available, fall back to cpplint
-        if Self::has_clang_tidy() {
+        if self.prefers_clang_tidy(path) {
             self.run_clang_tidy(path)
-        } else if Self::has_cpplint() {
+        } else if self.capture.as_deref().is_some_and(ToolCapture::is_replay) || Self::has_cpplint()
+        {
             self.run_cpplint(path)
         } else {
             // Neither tool available
@@ -672,7 +816,25 @@ impl Checker for CppChecker {
     }
 
     fn is_available(&self) -> bool {
-        Self::has_clang_tidy() || Self::has_cpplint()
+        match self.tool {
+            Some(CppLintTool::ClangTidy) => self.has_clang_tidy(&Self::current_dir()),
+            Some(CppLintTool::Cpplint) => Self::has_cpplint(),
+            None => self.has_clang_tidy(&Self::current_dir()) || Self::has_cpplint(),
+        }
+    }
+
+    fn binary(&self) -> PathBuf {
+        match self.name() {
+            "clang-tidy" => self.clang_tidy_binary(&Self::current_dir()),
+            name => PathBuf::from(name),
+        }
+    }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        match self.name() {
+            "clang-tidy" => self.clang_tidy_config(path),
+            _ => None,
+        }
     }
 }
 
@@ -854,12 +1016,63 @@ mod tests {
     fn test_parse_clang_tidy_note_line() {
         let line = "test.cpp:10:5: note: previous declaration is here";
         let default_path = Path::new("default.cpp");
-        let result = CppChecker::parse_clang_tidy_line(line, default_path);
-        assert!(result.is_none()); // notes are not warnings or errors
+        let issue = CppChecker::parse_clang_tidy_line(line, default_path).unwrap();
+        assert_eq!(issue.line, 10);
+        assert_eq!(issue.severity, Severity::Hint);
+        assert_eq!(issue.message, "previous declaration is here");
+    }
+
+    #[test]
+    fn test_parse_clang_tidy_notes_attach_to_warning() {
+        let output = "\
+test.cpp:10:5: warning: 'x' shadows a variable [bugprone-shadow]
+    int x = 2;
+        ^
+test.cpp:3:9: note: previous declaration is here
+third_party/lib.h:1:1: warning: vendored [misc-vendored]
+test.cpp:4:1: note: belongs to the vendored warning
+";
+        let issues = CppChecker::parse_clang_tidy_output(output, Path::new("default.cpp"));
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 10);
+        assert_eq!(issues[0].related.len(), 1);
+        let note = &issues[0].related[0];
+        assert_eq!(note.kind, RelatedKind::Note);
+        assert_eq!(note.line, Some(3));
+        assert_eq!(note.message, "previous declaration is here");
     }
 
     // ==================== parse_cpplint_line tests ====================
 
+    #[cfg(unix)]
+    #[test]
+    fn test_cpplint_diagnostics_on_stderr_are_parsed() {
+        // A fake cpplint: findings on stderr, progress on stdout
+        let output = Command::new("sh")
+            .args([
+                "-c",
+                "echo 'Done processing a.cc'; \
+                 echo 'a.cc:3:  Missing space around =  [whitespace/operators] [4]' >&2; \
+                 echo 'Total errors found: 1' >&2; exit 1",
+            ])
+            .output()
+            .unwrap();
+        check_tool_status_with_limit("cpplint", &output, &[1], None).unwrap();
+
+        let issues = CppChecker::parse_cpplint_output(
+            &tool_diagnostics("cpplint", &output),
+            Path::new("a.cc"),
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 3);
+        assert_eq!(issues[0].code.as_deref(), Some("whitespace/operators"));
+
+        // Reading stdout instead would report the file as clean
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(CppChecker::parse_cpplint_output(&stdout, Path::new("a.cc")).is_empty());
+    }
+
     #[test]
     fn test_parse_cpplint_standard_warning() {
         let line = "test.cpp:10: Missing space after comma [whitespace/comma] [3]";
@@ -956,6 +1169,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_clang_tidy_command_passes_plugin_config() {
+        let cache_config = PathBuf::from("/cache/linthis/plugins/org-style/cpp/.clang-tidy");
+        let checker = CppChecker::new().with_config(cache_config.clone());
+        let cmd = checker.clang_tidy_command(Path::new("clang-tidy"), Path::new("main.cpp"));
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        assert!(args.contains(&format!("--config-file={}", cache_config.display()).into()));
+    }
+
     #[test]
     fn test_cpp_checker_with_compile_commands_dir() {
         let checker = CppChecker::new().with_compile_commands_dir(PathBuf::from("/build"));
@@ -981,4 +1203,28 @@ mod tests {
         let checker = CppChecker::new().with_cpplint_oc_config(config);
         assert_eq!(checker.cpplint_oc_config.linelength, Some(200));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_crashing_clang_tidy_is_reported_as_failure() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("main.cpp");
+        std::fs::write(&source, "int main() { return 0; }\n").unwrap();
+        let fake = dir.path().join("clang-tidy");
+        std::fs::write(&fake, "#!/bin/sh\necho 'Stack dump:' >&2\nexit 139\n").unwrap();
+        std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let checker = CppChecker::new();
+        let err = checker.run_clang_tidy_with(&fake, &source).unwrap_err();
+        assert!(matches!(
+            err,
+            CheckerError::ToolCrashed {
+                code: Some(139),
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("exit code 139"));
+    }
 }
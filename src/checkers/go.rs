@@ -9,24 +9,105 @@
 // substantial portions of the Software.
 
 //! Go language checker using golangci-lint or go vet.
-
-use crate::checkers::Checker;
+//!
+//! The linter runs over packages rather than single files. [`prefetch`]
+//! lints the files being checked per file, per package directory or per
+//! module, depending on the configured [`Invocation`]; results are split per
+//! file and served from a cache.
+
+use crate::checkers::{CheckResult, Checker, CheckerError, Invocation};
+use crate::utils::capture::{tool_output, ToolCapture};
+use crate::utils::tool_diagnostics;
 use crate::utils::types::{LintIssue, Severity};
-use crate::{Language, Result};
-use std::collections::HashMap;
+use crate::Language;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+
+/// Timeout passed to golangci-lint (its default of 1m is too short for cold caches)
+const GOLANGCI_TIMEOUT: &str = "5m";
+
+/// Lint results for one Go module
+#[derive(Debug)]
+struct ModuleIssues {
+    /// Package directories or files linted so far (None = the whole module)
+    linted: Option<HashSet<PathBuf>>,
+    /// How files of this module are linted
+    invocation: Invocation,
+    /// Issues keyed by canonical file path
+    by_file: HashMap<PathBuf, Vec<LintIssue>>,
+}
+
+impl ModuleIssues {
+    fn new(invocation: Invocation) -> Self {
+        Self {
+            linted: Some(HashSet::new()),
+            invocation,
+            by_file: HashMap::new(),
+        }
+    }
+
+    /// Check if `file`, or the package containing it, has been linted.
+    fn covers(&self, file: &Path) -> bool {
+        covered_by(self.linted.as_ref(), file)
+    }
+
+    /// Record the results of a linter run covering `coverage`.
+    ///
+    /// Issues in files outside the run's coverage, or already recorded by an
+    /// earlier run, are dropped.
+    fn record(&mut self, coverage: Option<HashSet<PathBuf>>, issues: Vec<LintIssue>) {
+        for (file, file_issues) in demux_issues(issues) {
+            if covered_by(coverage.as_ref(), &file) && !self.covers(&file) {
+                self.by_file.entry(file).or_default().extend(file_issues);
+            }
+        }
+        self.linted = match (self.linted.take(), coverage) {
+            (Some(mut linted), Some(paths)) => {
+                linted.extend(paths);
+                Some(linted)
+            }
+            _ => None,
+        };
+    }
+}
+
+/// Check if `file` is one of `paths` or in one of them (None = everything).
+fn covered_by(paths: Option<&HashSet<PathBuf>>, file: &Path) -> bool {
+    match paths {
+        None => true,
+        Some(paths) => paths.contains(file) || file.parent().is_some_and(|dir| paths.contains(dir)),
+    }
+}
 
 /// Cache for Go lint results per module root
-static GO_LINT_CACHE: Mutex<Option<HashMap<PathBuf, Vec<LintIssue>>>> = Mutex::new(None);
+static GO_LINT_CACHE: Mutex<Option<HashMap<PathBuf, ModuleIssues>>> = Mutex::new(None);
 
 /// Go checker using golangci-lint (preferred) or go vet.
-pub struct GoChecker;
+pub struct GoChecker {
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+}
 
 impl GoChecker {
     pub fn new() -> Self {
-        Self
+        Self { capture: None }
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Whether golangci-lint (rather than go vet) lints; when replaying,
+    /// whether it was recorded
+    fn uses_golangci_lint(capture: Option<&ToolCapture>) -> bool {
+        match capture {
+            Some(capture) if capture.is_replay() => capture.has_recording("golangci-lint"),
+            _ => Self::has_golangci_lint(),
+        }
     }
 
     /// Find the go.mod for a given file path (Go module root)
@@ -77,39 +158,78 @@ impl GoChecker {
         None
     }
 
-    /// Run golangci-lint on a Go module
-    fn run_golangci_lint(module_root: &Path) -> Result<Vec<LintIssue>> {
+    /// Shared golangci-lint cache directory, unless the user set GOLANGCI_LINT_CACHE
+    pub(crate) fn golangci_cache_dir() -> Option<PathBuf> {
+        if std::env::var_os("GOLANGCI_LINT_CACHE").is_some() {
+            return None;
+        }
+        directories::ProjectDirs::from("", "", "linthis")
+            .map(|dirs| dirs.cache_dir().join("golangci-lint"))
+    }
+
+    /// Run golangci-lint on packages of a Go module
+    fn run_golangci_lint(
+        module_root: &Path,
+        packages: &[String],
+        capture: Option<&ToolCapture>,
+    ) -> CheckResult<Vec<LintIssue>> {
         let mut cmd = Command::new("golangci-lint");
-        cmd.args(["run", "--out-format=line-number", "./..."]);
+        cmd.args([
+            "run",
+            "--out-format=line-number",
+            "--timeout",
+            GOLANGCI_TIMEOUT,
+        ]);
 
         // Add config file if found
         if let Some(config_path) = Self::find_golangci_config(module_root) {
             cmd.arg("-c").arg(config_path);
         }
 
-        let output = cmd
-            .current_dir(module_root)
-            .output()
-            .map_err(|e| {
-                crate::LintisError::Checker(format!("Failed to run golangci-lint: {}", e))
-            })?;
+        // Reuse golangci-lint's analysis cache across invocations
+        if let Some(cache_dir) = Self::golangci_cache_dir() {
+            cmd.env("GOLANGCI_LINT_CACHE", cache_dir);
+        }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let issues = Self::parse_golangci_output(&stdout, module_root);
+        cmd.args(packages);
+
+        let output = tool_output(
+            capture,
+            "golangci-lint",
+            module_root,
+            cmd.current_dir(module_root),
+        )
+        .map_err(|e| CheckerError::spawn("golangci-lint", e))?;
+        if is_golangci_timeout(&output) {
+            return Err(CheckerError::Timeout {
+                tool: "golangci-lint".to_string(),
+            });
+        }
+
+        let issues =
+            Self::parse_golangci_output(&tool_diagnostics("golangci-lint", &output), module_root);
 
         Ok(issues)
     }
 
-    /// Run go vet on a Go module (fallback)
-    fn run_go_vet(module_root: &Path) -> Result<Vec<LintIssue>> {
-        let output = Command::new("go")
-            .args(["vet", "./..."])
-            .current_dir(module_root)
-            .output()
-            .map_err(|e| crate::LintisError::Checker(format!("Failed to run go vet: {}", e)))?;
-
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let issues = Self::parse_go_vet_output(&stderr, module_root);
+    /// Run go vet on packages of a Go module (fallback)
+    fn run_go_vet(
+        module_root: &Path,
+        packages: &[String],
+        capture: Option<&ToolCapture>,
+    ) -> CheckResult<Vec<LintIssue>> {
+        let output = tool_output(
+            capture,
+            "go vet",
+            module_root,
+            Command::new("go")
+                .arg("vet")
+                .args(packages)
+                .current_dir(module_root),
+        )
+        .map_err(|e| CheckerError::spawn("go", e))?;
+
+        let issues = Self::parse_go_vet_output(&tool_diagnostics("go vet", &output), module_root);
 
         Ok(issues)
     }
@@ -194,31 +314,178 @@ impl GoChecker {
         Some(issue)
     }
 
-    /// Get cached issues for a module, running linter if not cached
-    fn get_cached_issues(module_root: &Path) -> Result<Vec<LintIssue>> {
+    /// Run the linter on packages of a module.
+    /// Prefer golangci-lint if available, fall back to go vet.
+    fn run_linter(
+        module_root: &Path,
+        packages: &[String],
+        capture: Option<&ToolCapture>,
+    ) -> CheckResult<Vec<LintIssue>> {
+        if Self::uses_golangci_lint(capture) {
+            Self::run_golangci_lint(module_root, packages, capture)
+        } else {
+            Self::run_go_vet(module_root, packages, capture)
+        }
+    }
+
+    /// Get cached issues for a file, linting it if not cached.
+    ///
+    /// Modules that were not prefetched are linted as a whole.
+    fn get_cached_issues(
+        module_root: &Path,
+        file: &Path,
+        capture: Option<&ToolCapture>,
+    ) -> CheckResult<Vec<LintIssue>> {
         let mut cache = GO_LINT_CACHE.lock().unwrap();
-        if cache.is_none() {
-            *cache = Some(HashMap::new());
+        let cache_map = cache.get_or_insert_with(HashMap::new);
+
+        let module = cache_map
+            .entry(module_root.to_path_buf())
+            .or_insert_with(|| ModuleIssues::new(Invocation::PerProject));
+        if !module.covers(file) {
+            let files = [file.to_path_buf()];
+            for (args, coverage) in invocation_batches(module_root, &files, module.invocation) {
+                let issues = Self::run_linter(module_root, &args, capture)?;
+                module.record(coverage, issues);
+            }
         }
 
-        let cache_map = cache.as_mut().unwrap();
-        if let Some(issues) = cache_map.get(module_root) {
-            return Ok(issues.clone());
+        Ok(cache_map
+            .get(module_root)
+            .and_then(|module| module.by_file.get(file))
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// Group Go files by module root (directory containing go.mod).
+///
+/// Files outside any module are left out.
+pub fn group_files_by_module(files: &[PathBuf]) -> BTreeMap<PathBuf, Vec<PathBuf>> {
+    let mut groups: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for file in files {
+        let file = file.canonicalize().unwrap_or_else(|_| file.clone());
+        if let Some(root) = GoChecker::find_module_root(&file) {
+            groups.entry(root).or_default().push(file);
         }
+    }
+    groups
+}
 
-        // Run linter and cache results
-        // Prefer golangci-lint if available, fall back to go vet
-        let issues = if Self::has_golangci_lint() {
-            Self::run_golangci_lint(module_root)?
-        } else {
-            Self::run_go_vet(module_root)?
-        };
+/// Group Go files by package directory (a Go package is one directory).
+pub fn group_files_by_package(files: &[PathBuf]) -> BTreeMap<PathBuf, Vec<PathBuf>> {
+    let mut groups: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for file in files {
+        if let Some(dir) = file.parent() {
+            groups
+                .entry(dir.to_path_buf())
+                .or_default()
+                .push(file.clone());
+        }
+    }
+    groups
+}
 
-        cache_map.insert(module_root.to_path_buf(), issues.clone());
-        Ok(issues)
+/// Linter runs needed to lint `files` of one module: the package or file
+/// arguments of each run and the package directories or files it covers
+/// (None = the whole module).
+fn invocation_batches(
+    module_root: &Path,
+    files: &[PathBuf],
+    invocation: Invocation,
+) -> Vec<(Vec<String>, Option<HashSet<PathBuf>>)> {
+    match invocation {
+        Invocation::PerProject => vec![(vec!["./...".to_string()], None)],
+        Invocation::PerDir => {
+            let dirs = group_files_by_package(files).into_keys().collect();
+            vec![(package_patterns(module_root, files), Some(dirs))]
+        }
+        Invocation::PerFile => files
+            .iter()
+            .filter_map(|file| {
+                let relative = file.strip_prefix(module_root).ok()?;
+                let arg = relative.to_string_lossy().replace('\\', "/");
+                Some((vec![arg], Some(HashSet::from([file.clone()]))))
+            })
+            .collect(),
     }
 }
 
+/// Package patterns (e.g. `./cmd/app`) selecting the packages of `files`
+/// relative to the module root.
+fn package_patterns(module_root: &Path, files: &[PathBuf]) -> Vec<String> {
+    files
+        .iter()
+        .filter_map(|file| file.parent()?.strip_prefix(module_root).ok())
+        .map(|dir| {
+            if dir.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                format!("./{}", dir.to_string_lossy().replace('\\', "/"))
+            }
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// golangci-lint exits 4 when `--timeout` is exceeded
+fn is_golangci_timeout(output: &std::process::Output) -> bool {
+    output.status.code() == Some(4)
+        || String::from_utf8_lossy(&output.stderr).contains("Timeout exceeded")
+}
+
+/// Split module-wide results into issues per canonical file path.
+fn demux_issues(issues: Vec<LintIssue>) -> HashMap<PathBuf, Vec<LintIssue>> {
+    let mut by_file: HashMap<PathBuf, Vec<LintIssue>> = HashMap::new();
+    for issue in issues {
+        let key = issue
+            .file_path
+            .canonicalize()
+            .unwrap_or_else(|_| issue.file_path.clone());
+        by_file.entry(key).or_default().push(issue);
+    }
+    by_file
+}
+
+/// Lint `files` module by module, as `invocation` says, and cache the results
+/// for later `check` calls.
+///
+/// With [`Invocation::PerDir`] each module is linted in one run covering the
+/// packages of its files; [`Invocation::PerProject`] lints the whole module
+/// and [`Invocation::PerFile`] runs the linter once per file.
+///
+/// Modules that are already cached are skipped.
+pub fn prefetch(
+    files: &[PathBuf],
+    invocation: Invocation,
+    capture: Option<&ToolCapture>,
+) -> CheckResult<()> {
+    for (module_root, module_files) in group_files_by_module(files) {
+        let already_cached = GO_LINT_CACHE
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|cache| cache.contains_key(&module_root));
+        if already_cached {
+            continue;
+        }
+
+        let mut module = ModuleIssues::new(invocation);
+        for (args, coverage) in invocation_batches(&module_root, &module_files, invocation) {
+            let issues = GoChecker::run_linter(&module_root, &args, capture)?;
+            module.record(coverage, issues);
+        }
+
+        GO_LINT_CACHE
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(module_root, module);
+    }
+    Ok(())
+}
+
 impl Default for GoChecker {
     fn default() -> Self {
         Self::new()
@@ -227,7 +494,7 @@ impl Default for GoChecker {
 
 impl Checker for GoChecker {
     fn name(&self) -> &str {
-        if Self::has_golangci_lint() {
+        if Self::uses_golangci_lint(self.capture.as_deref()) {
             "golangci-lint"
         } else {
             "go vet"
@@ -238,7 +505,7 @@ impl Checker for GoChecker {
         &[Language::Go]
     }
 
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>> {
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
         // Find the Go module root
         let module_root = match Self::find_module_root(path) {
             Some(root) => root,
@@ -248,25 +515,9 @@ impl Checker for GoChecker {
             }
         };
 
-        // Get all issues for this module (cached)
-        let all_issues = Self::get_cached_issues(&module_root)?;
-
-        // Normalize paths for comparison
+        // Issues for this file, from the module-wide run (cached)
         let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-
-        // Filter issues for this specific file
-        let file_issues: Vec<LintIssue> = all_issues
-            .into_iter()
-            .filter(|issue| {
-                let issue_canonical = issue
-                    .file_path
-                    .canonicalize()
-                    .unwrap_or_else(|_| issue.file_path.clone());
-                issue_canonical == canonical_path
-            })
-            .collect();
-
-        Ok(file_issues)
+        Self::get_cached_issues(&module_root, &canonical_path, self.capture.as_deref())
     }
 
     fn is_available(&self) -> bool {
@@ -278,6 +529,21 @@ impl Checker for GoChecker {
                 .map(|o| o.status.success())
                 .unwrap_or(false)
     }
+
+    fn binary(&self) -> PathBuf {
+        if Self::uses_golangci_lint(self.capture.as_deref()) {
+            PathBuf::from("golangci-lint")
+        } else {
+            PathBuf::from("go")
+        }
+    }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        if !Self::has_golangci_lint() {
+            return None;
+        }
+        Self::find_golangci_config(&Self::find_module_root(path)?)
+    }
 }
 
 /// Clear the Go lint cache (useful for testing or forcing re-run)
@@ -285,3 +551,160 @@ pub fn clear_go_lint_cache() {
     let mut cache = GO_LINT_CACHE.lock().unwrap();
     *cache = None;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_group_files_by_module() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        write(&root.join("svc/go.mod"), "module example.com/svc\n");
+        write(&root.join("lib/go.mod"), "module example.com/lib\n");
+
+        let files: Vec<PathBuf> = [
+            "svc/main.go",
+            "svc/api/handler.go",
+            "lib/util.go",
+            "loose.go",
+        ]
+        .iter()
+        .map(|rel| {
+            let path = root.join(rel);
+            write(&path, "package x\n");
+            path
+        })
+        .collect();
+
+        let groups = group_files_by_module(&files);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[&root.join("svc")],
+            vec![root.join("svc/main.go"), root.join("svc/api/handler.go")]
+        );
+        assert_eq!(groups[&root.join("lib")], vec![root.join("lib/util.go")]);
+
+        assert_eq!(
+            package_patterns(&root.join("svc"), &groups[&root.join("svc")]),
+            vec![".", "./api"]
+        );
+    }
+
+    #[test]
+    fn test_demux_issues_attributes_to_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        write(&root.join("main.go"), "package main\n");
+        write(&root.join("api/handler.go"), "package api\n");
+
+        let output = "main.go:3:2: ineffectual assignment to err (ineffassign)\n\
+                      api/handler.go:10:1: exported function Handle should have comment (revive)\n\
+                      api/handler.go:12:5: error return value is not checked (errcheck)\n\
+                      level=warning msg=\"[runner] skipped\"\n";
+        let by_file = demux_issues(GoChecker::parse_golangci_output(output, &root));
+
+        assert_eq!(by_file.len(), 2);
+        assert_eq!(by_file[&root.join("main.go")].len(), 1);
+        let handler = &by_file[&root.join("api/handler.go")];
+        assert_eq!(handler.len(), 2);
+        assert_eq!(handler[1].line, 12);
+        assert_eq!(handler[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_module_issues_covers() {
+        let partial = ModuleIssues {
+            linted: Some(HashSet::from([PathBuf::from("/m/api")])),
+            ..ModuleIssues::new(Invocation::PerDir)
+        };
+        assert!(partial.covers(Path::new("/m/api/handler.go")));
+        assert!(!partial.covers(Path::new("/m/main.go")));
+        let whole = ModuleIssues {
+            linted: None,
+            ..ModuleIssues::new(Invocation::PerProject)
+        };
+        assert!(whole.covers(Path::new("/m/main.go")));
+    }
+
+    #[test]
+    fn test_per_dir_invocation_groups_files_by_package() {
+        let root = PathBuf::from("/m");
+        let files: Vec<PathBuf> = ["main.go", "util.go", "api/handler.go", "api/routes.go"]
+            .iter()
+            .map(|rel| root.join(rel))
+            .collect();
+
+        let groups = group_files_by_package(&files);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[&root],
+            vec![root.join("main.go"), root.join("util.go")]
+        );
+        assert_eq!(groups[&root.join("api")].len(), 2);
+
+        // One run for all packages, covering their directories
+        let batches = invocation_batches(&root, &files, Invocation::PerDir);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].0, vec![".", "./api"]);
+        assert_eq!(
+            batches[0].1,
+            Some(HashSet::from([root.clone(), root.join("api")]))
+        );
+
+        let per_file = invocation_batches(&root, &files, Invocation::PerFile);
+        assert_eq!(per_file.len(), 4);
+        assert_eq!(per_file[2].0, vec!["api/handler.go"]);
+        assert_eq!(
+            invocation_batches(&root, &files, Invocation::PerProject),
+            vec![(vec!["./...".to_string()], None)]
+        );
+    }
+
+    #[test]
+    fn test_module_issues_record_attributes_package_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        write(&root.join("api/handler.go"), "package api\n");
+        write(&root.join("api/routes.go"), "package api\n");
+        write(&root.join("db/store.go"), "package db\n");
+
+        // A package run reports a cross-file issue in a file that wasn't
+        // passed on its own, plus noise from a package outside the run
+        let output = "api/routes.go:4:6: func `unusedHelper` is unused (unused)\n\
+                      db/store.go:1:1: package comment should be of the form (revive)\n";
+        let mut module = ModuleIssues::new(Invocation::PerDir);
+        module.record(
+            Some(HashSet::from([root.join("api")])),
+            GoChecker::parse_golangci_output(output, &root),
+        );
+
+        assert!(module.covers(&root.join("api/handler.go")));
+        assert!(!module.covers(&root.join("db/store.go")));
+        assert_eq!(module.by_file[&root.join("api/routes.go")].len(), 1);
+        assert!(!module.by_file.contains_key(&root.join("db/store.go")));
+
+        // A later run over the same package doesn't duplicate issues
+        module.record(None, GoChecker::parse_golangci_output(output, &root));
+        assert_eq!(module.by_file[&root.join("api/routes.go")].len(), 1);
+        assert_eq!(module.by_file[&root.join("db/store.go")].len(), 1);
+        assert!(module.covers(&root.join("db/store.go")));
+    }
+
+    #[test]
+    fn test_binary_follows_go_vet_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        // A bundle without golangci-lint recordings lints with go vet
+        let capture = ToolCapture::replay(dir.path(), dir.path()).unwrap();
+        let checker = GoChecker::new().with_capture(Some(Arc::new(capture)));
+
+        assert_eq!(checker.name(), "go vet");
+        assert_eq!(checker.binary(), PathBuf::from("go"));
+    }
+}
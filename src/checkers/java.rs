@@ -10,18 +10,30 @@
 
 //! Java language checker using checkstyle.
 
-use crate::checkers::Checker;
+use crate::checkers::{CheckResult, Checker, CheckerError};
+use crate::utils::capture::{tool_output, ToolCapture};
+use crate::utils::tool_diagnostics;
 use crate::utils::types::{LintIssue, Severity};
-use crate::{Language, Result};
-use std::path::Path;
+use crate::Language;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 /// Java checker using checkstyle.
-pub struct JavaChecker;
+pub struct JavaChecker {
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+}
 
 impl JavaChecker {
     pub fn new() -> Self {
-        Self
+        Self { capture: None }
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
     }
 
     /// Find checkstyle configuration file in the project
@@ -143,9 +155,9 @@ impl Checker for JavaChecker {
         &[Language::Java]
     }
 
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>> {
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
         // Find checkstyle configuration file
-        let config_arg = if let Some(config_path) = Self::find_checkstyle_config(path) {
+        let config_arg = if let Some(config_path) = self.config_path(path) {
             vec!["-c".to_string(), config_path.to_string_lossy().to_string()]
         } else {
             // Use Google checks as default (built-in to checkstyle)
@@ -153,18 +165,16 @@ impl Checker for JavaChecker {
         };
 
         // Try to use checkstyle if available
-        let output = Command::new("checkstyle")
-            .args(&config_arg)
-            .arg(path)
-            .output()
-            .map_err(|e| crate::LintisError::Checker(format!("Failed to run checkstyle: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let output = tool_output(
+            self.capture.as_deref(),
+            "checkstyle",
+            path,
+            Command::new("checkstyle").args(&config_arg).arg(path),
+        )
+        .map_err(|e| CheckerError::spawn("checkstyle", e))?;
 
         // Checkstyle outputs to stdout for issues, stderr for errors
-        let combined = format!("{}{}", stdout, stderr);
-        let issues = self.parse_checkstyle_output(&combined, path);
+        let issues = self.parse_checkstyle_output(&tool_diagnostics("checkstyle", &output), path);
 
         Ok(issues)
     }
@@ -176,4 +186,8 @@ impl Checker for JavaChecker {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        Self::find_checkstyle_config(path)
+    }
 }
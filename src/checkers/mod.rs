@@ -10,6 +10,8 @@
 
 //! Language-specific linter implementations.
 
+pub mod bandit;
+pub mod builtin;
 pub mod cpp;
 pub mod go;
 pub mod java;
@@ -18,10 +20,12 @@ pub mod rust;
 pub mod traits;
 pub mod typescript;
 
-pub use cpp::CppChecker;
+pub use bandit::BanditChecker;
+pub use builtin::{BuiltinChecker, BuiltinChecksConfig};
+pub use cpp::{CppChecker, CppLintTool};
 pub use go::GoChecker;
 pub use java::JavaChecker;
 pub use python::PythonChecker;
 pub use rust::RustChecker;
-pub use traits::Checker;
+pub use traits::{CheckResult, Checker, CheckerError, Invocation};
 pub use typescript::TypeScriptChecker;
@@ -13,12 +13,15 @@
 //! Ruff is an extremely fast Python linter written in Rust, offering
 //! 10-100x speed improvements over flake8 with 800+ built-in rules.
 
-use crate::checkers::Checker;
+use crate::checkers::{CheckResult, Checker, CheckerError};
+use crate::utils::capture::{tool_output, ToolCapture};
 use crate::utils::types::{LintIssue, Severity};
-use crate::{Language, Result};
+use crate::utils::{apply_memory_limit, check_tool_status_with_limit, tool_diagnostics};
+use crate::Language;
 use serde::Deserialize;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 /// Ruff JSON output location structure
 #[derive(Debug, Deserialize)]
@@ -59,11 +62,68 @@ struct RuffIssue {
 }
 
 /// Python checker using ruff.
-pub struct PythonChecker;
+pub struct PythonChecker {
+    /// Explicit ruff config path (e.g. from a plugin), bypassing discovery
+    config_path: Option<PathBuf>,
+    /// Ignore all config files (`ruff --isolated`)
+    isolated: bool,
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+    /// Address-space limit of the linter in MB (`tool_memory_limit_mb`)
+    memory_limit_mb: Option<u64>,
+}
 
 impl PythonChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            config_path: None,
+            isolated: false,
+            capture: None,
+            memory_limit_mb: None,
+        }
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Cap the linter's memory at `limit_mb` (None = unlimited)
+    pub fn with_memory_limit(mut self, limit_mb: Option<u64>) -> Self {
+        self.memory_limit_mb = limit_mb;
+        self
+    }
+
+    /// Ignore all ruff config files and use ruff's defaults
+    pub fn with_isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
+    /// Set an explicit ruff config path
+    pub fn with_config(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Build the ruff invocation for a file
+    pub(crate) fn command(&self, path: &Path) -> Command {
+        let mut cmd = Command::new("ruff");
+        cmd.args(["check", "--output-format", "json"]);
+
+        if self.isolated {
+            cmd.arg("--isolated").arg(path);
+            return cmd;
+        }
+
+        // Explicit config first, then try to find one
+        if let Some(config_path) = Checker::config_path(self, path) {
+            cmd.arg("--config").arg(config_path);
+        }
+
+        cmd.arg(path);
+        cmd
     }
 
     /// Find Ruff configuration file
@@ -99,14 +159,22 @@ impl PythonChecker {
     }
 
     /// Parse ruff JSON output and extract issues.
-    fn parse_ruff_json_output(&self, output: &str, _file_path: &Path) -> Vec<LintIssue> {
+    ///
+    /// Empty output means nothing was reported; anything else that is not a
+    /// JSON array of issues is a [`CheckerError::ParseError`].
+    fn parse_ruff_json_output(
+        &self,
+        output: &str,
+        _file_path: &Path,
+    ) -> CheckResult<Vec<LintIssue>> {
         let mut issues = Vec::new();
+        if output.trim().is_empty() {
+            return Ok(issues);
+        }
 
         // Parse JSON array of issues
-        let ruff_issues: Vec<RuffIssue> = match serde_json::from_str(output) {
-            Ok(issues) => issues,
-            Err(_) => return issues, // Return empty on parse error
-        };
+        let ruff_issues: Vec<RuffIssue> =
+            serde_json::from_str(output).map_err(|_| CheckerError::parse("ruff", output))?;
 
         for ruff_issue in ruff_issues {
             let severity = self.map_code_to_severity(&ruff_issue.code);
@@ -129,7 +197,7 @@ impl PythonChecker {
             issues.push(issue);
         }
 
-        issues
+        Ok(issues)
     }
 
     /// Map ruff error code prefix to severity level.
@@ -191,24 +259,19 @@ impl Checker for PythonChecker {
         &[Language::Python]
     }
 
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>> {
-        let mut cmd = Command::new("ruff");
-        cmd.args(["check", "--output-format", "json"]);
-
-        // Try to find ruff config
-        if let Some(config_path) = Self::find_ruff_config(path) {
-            cmd.arg("--config").arg(config_path);
-        }
-
-        let output = cmd
-            .arg(path)
-            .output()
-            .map_err(|e| crate::LintisError::Checker(format!("Failed to run ruff: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let issues = self.parse_ruff_json_output(&stdout, path);
-
-        Ok(issues)
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        let mut cmd = self.command(path);
+        let output = tool_output(
+            self.capture.as_deref(),
+            "ruff",
+            path,
+            apply_memory_limit(&mut cmd, self.memory_limit_mb),
+        )
+        .map_err(|e| CheckerError::spawn("ruff", e))?;
+        // ruff exits 1 for violations and 2 for abnormal termination
+        check_tool_status_with_limit("ruff", &output, &[1], self.memory_limit_mb)?;
+
+        self.parse_ruff_json_output(&tool_diagnostics("ruff", &output), path)
     }
 
     fn is_available(&self) -> bool {
@@ -218,6 +281,15 @@ impl Checker for PythonChecker {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        self.config_path
+            .clone()
+            .or_else(|| Self::find_ruff_config(path))
+    }
 }
 
 #[cfg(test)]
@@ -266,7 +338,9 @@ mod tests {
             }
         ]"#;
 
-        let issues = checker.parse_ruff_json_output(json, Path::new("test.py"));
+        let issues = checker
+            .parse_ruff_json_output(json, Path::new("test.py"))
+            .unwrap();
         assert_eq!(issues.len(), 1);
 
         let issue = &issues[0];
@@ -282,17 +356,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_command_passes_plugin_config() {
+        let cache_config = PathBuf::from("/cache/linthis/plugins/org-style/python/ruff.toml");
+        let checker = PythonChecker::new().with_config(cache_config.clone());
+        let cmd = checker.command(Path::new("app.py"));
+        let args: Vec<_> = cmd.get_args().collect();
+        let pos = args.iter().position(|a| *a == "--config").unwrap();
+        assert_eq!(args[pos + 1], cache_config.as_os_str());
+    }
+
     #[test]
     fn test_parse_empty_output() {
         let checker = PythonChecker::new();
-        let issues = checker.parse_ruff_json_output("[]", Path::new("test.py"));
+        let issues = checker
+            .parse_ruff_json_output("[]", Path::new("test.py"))
+            .unwrap();
         assert!(issues.is_empty());
     }
 
     #[test]
     fn test_parse_invalid_json() {
         let checker = PythonChecker::new();
-        let issues = checker.parse_ruff_json_output("not valid json", Path::new("test.py"));
-        assert!(issues.is_empty());
+        let result = checker.parse_ruff_json_output("not valid json", Path::new("test.py"));
+        assert!(matches!(result, Err(CheckerError::ParseError { .. })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replay_reproduces_recorded_issues() {
+        use crate::utils::capture::ToolCapture;
+
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("bundle");
+        let runs = dir.path().join("runs");
+        // A fake ruff that counts its runs
+        let script = format!(
+            "echo run >> {}; echo '{}'; exit 1",
+            runs.display(),
+            r#"[{"code":"F401","message":"`os` imported but unused","location":{"row":1,"column":8},"end_location":{"row":1,"column":10},"filename":"a.py"}]"#
+        );
+        let command = || {
+            let mut cmd = Command::new("sh");
+            cmd.args(["-c", &script]);
+            cmd
+        };
+        let parse = |output: &std::process::Output| {
+            PythonChecker::new()
+                .parse_ruff_json_output(&tool_diagnostics("ruff", output), Path::new("a.py"))
+                .unwrap()
+        };
+
+        // Recorded in one checkout, replayed in another
+        let (here, there) = (dir.path().join("here"), dir.path().join("there"));
+        let recorder = ToolCapture::record(&bundle, &here).unwrap();
+        let original = parse(
+            &recorder
+                .output("ruff", &here.join("src/a.py"), &mut command())
+                .unwrap(),
+        );
+        assert_eq!(original.len(), 1);
+        assert!(bundle.join("00000-ruff.json").is_file());
+
+        let replayer = ToolCapture::replay(&bundle, &there).unwrap();
+        assert!(replayer.has_recording("ruff"));
+        assert!(!replayer.has_recording("bandit"));
+        // Another file's run is not replayed from this recording
+        assert!(replayer
+            .output("ruff", &there.join("src/b.py"), &mut command())
+            .is_err());
+        let replayed = replayer
+            .output("ruff", &there.join("src/a.py"), &mut command())
+            .unwrap();
+        assert_eq!(replayed.status.code(), Some(1));
+        assert_eq!(
+            serde_json::to_value(parse(&replayed)).unwrap(),
+            serde_json::to_value(&original).unwrap()
+        );
+        // The tool only ran while recording, and each recording replays once
+        assert_eq!(std::fs::read_to_string(&runs).unwrap(), "run\n");
+        assert!(replayer
+            .output("ruff", &there.join("src/a.py"), &mut command())
+            .is_err());
     }
 }
@@ -10,23 +10,35 @@
 
 //! Rust language checker using clippy.
 
-use crate::checkers::Checker;
-use crate::utils::types::{LintIssue, Severity};
-use crate::{Language, Result};
+use crate::checkers::{CheckResult, Checker, CheckerError};
+use crate::utils::capture::{tool_output, ToolCapture};
+use crate::utils::tool_diagnostics;
+use crate::utils::types::{LintIssue, RelatedInfo, RelatedKind, Severity};
+use crate::Language;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 /// Cache for clippy results per project root
 static CLIPPY_CACHE: Mutex<Option<HashMap<PathBuf, Vec<LintIssue>>>> = Mutex::new(None);
 
 /// Rust checker using cargo clippy.
-pub struct RustChecker;
+pub struct RustChecker {
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+}
 
 impl RustChecker {
     pub fn new() -> Self {
-        Self
+        Self { capture: None }
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
     }
 
     /// Find the Cargo.toml for a given file path
@@ -55,75 +67,107 @@ impl RustChecker {
     }
 
     /// Run cargo clippy on a project and cache the results
-    fn run_cargo_clippy(project_root: &Path) -> Result<Vec<LintIssue>> {
-        let output = Command::new("cargo")
-            .args(["clippy", "--message-format=short", "--", "-D", "warnings"])
-            .current_dir(project_root)
-            .output()
-            .map_err(|e| {
-                crate::LintisError::Checker(format!("Failed to run cargo clippy: {}", e))
-            })?;
-
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let issues = Self::parse_clippy_output(&stderr, project_root);
+    fn run_cargo_clippy(
+        project_root: &Path,
+        capture: Option<&ToolCapture>,
+    ) -> CheckResult<Vec<LintIssue>> {
+        let output = tool_output(
+            capture,
+            "clippy",
+            project_root,
+            Command::new("cargo")
+                .args(["clippy", "--message-format=json", "--", "-D", "warnings"])
+                .current_dir(project_root),
+        )
+        .map_err(|e| CheckerError::spawn("cargo", e))?;
+
+        let issues = Self::parse_clippy_output(&tool_diagnostics("clippy", &output), project_root);
 
         Ok(issues)
     }
 
-    /// Parse clippy output and extract issues.
+    /// Parse clippy JSON output (one cargo message per line) and extract issues.
     fn parse_clippy_output(output: &str, project_root: &Path) -> Vec<LintIssue> {
-        let mut issues = Vec::new();
-
-        // Clippy short format: file:line:col: severity: message
-        for line in output.lines() {
-            if let Some(issue) = Self::parse_clippy_line(line, project_root) {
-                issues.push(issue);
-            }
-        }
-
-        issues
+        output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter(|msg| msg["reason"] == "compiler-message")
+            .filter_map(|msg| Self::parse_diagnostic(&msg["message"], project_root))
+            .collect()
     }
 
-    fn parse_clippy_line(line: &str, project_root: &Path) -> Option<LintIssue> {
-        // Short format: path:line:col: severity: message
-        // Example: src/main.rs:10:5: warning: unused variable `x`
-        if !line.contains(": warning:") && !line.contains(": error:") {
-            return None;
+    /// Convert a rustc diagnostic to one issue, however many lines and spans
+    /// it renders to: the primary span gives the location, notes and help
+    /// messages become related info, and the first help with a suggested
+    /// replacement becomes the suggestion. Diagnostics without a location
+    /// (such as "N warnings emitted") are skipped.
+    fn parse_diagnostic(diagnostic: &Value, project_root: &Path) -> Option<LintIssue> {
+        let severity = match diagnostic["level"].as_str()? {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "note" | "help" => Severity::Hint,
+            _ => return None,
+        };
+        let (file, line, column) = Self::primary_span(diagnostic, project_root)?;
+        let message = diagnostic["message"].as_str()?.to_string();
+
+        let mut issue = LintIssue::new(file, line, message, severity)
+            .with_source("clippy".to_string())
+            .with_column(column);
+        if let Some(code) = diagnostic["code"]["code"].as_str() {
+            issue = issue.with_code(code.to_string());
         }
 
-        let parts: Vec<&str> = line.splitn(4, ':').collect();
-        if parts.len() < 4 {
-            return None;
+        for child in diagnostic["children"].as_array().into_iter().flatten() {
+            let Some(message) = child["message"].as_str() else {
+                continue;
+            };
+            if issue.suggestion.is_none() && child["level"] == "help" {
+                if let Some(replacement) = Self::suggested_replacement(child) {
+                    issue = issue.with_suggestion(format!("{}: `{}`", message, replacement));
+                }
+            }
+            let kind = if child["level"] == "help" {
+                RelatedKind::Help
+            } else {
+                RelatedKind::Note
+            };
+            let mut related = RelatedInfo::new(kind, message.to_string());
+            if let Some((file, line, _)) = Self::primary_span(child, project_root) {
+                related = related.with_location(file, line);
+            }
+            issue = issue.with_related(related);
         }
 
-        let relative_path = PathBuf::from(parts[0]);
-        let file_path = project_root.join(relative_path);
-        let line_num = parts[1].trim().parse::<usize>().ok()?;
-        let col = parts[2].trim().parse::<usize>().ok();
-
-        let rest = parts[3];
-        let (severity, message) = if rest.contains("warning:") {
-            let msg = rest.trim_start_matches(" warning:").trim();
-            (Severity::Warning, msg.to_string())
-        } else if rest.contains("error:") {
-            let msg = rest.trim_start_matches(" error:").trim();
-            (Severity::Error, msg.to_string())
-        } else {
-            return None;
-        };
-
-        let mut issue = LintIssue::new(file_path, line_num, message, severity)
-            .with_source("clippy".to_string());
+        Some(issue)
+    }
 
-        if let Some(c) = col {
-            issue = issue.with_column(c);
-        }
+    /// Replacement text of a help message's spans, if it suggests one
+    fn suggested_replacement(child: &Value) -> Option<&str> {
+        child["spans"]
+            .as_array()?
+            .iter()
+            .find_map(|span| span["suggested_replacement"].as_str())
+    }
 
-        Some(issue)
+    /// File, line and column of a diagnostic's primary span
+    fn primary_span(diagnostic: &Value, project_root: &Path) -> Option<(PathBuf, usize, usize)> {
+        let spans = diagnostic["spans"].as_array()?;
+        let span = spans
+            .iter()
+            .find(|s| s["is_primary"] == true)
+            .or_else(|| spans.first())?;
+        let file = project_root.join(span["file_name"].as_str()?);
+        let line = span["line_start"].as_u64()? as usize;
+        let column = span["column_start"].as_u64().unwrap_or(1) as usize;
+        Some((file, line, column))
     }
 
     /// Get cached issues for a project, running clippy if not cached
-    fn get_cached_issues(project_root: &Path) -> Result<Vec<LintIssue>> {
+    fn get_cached_issues(
+        project_root: &Path,
+        capture: Option<&ToolCapture>,
+    ) -> CheckResult<Vec<LintIssue>> {
         let mut cache = CLIPPY_CACHE.lock().unwrap();
         if cache.is_none() {
             *cache = Some(HashMap::new());
@@ -135,7 +179,7 @@ impl RustChecker {
         }
 
         // Run clippy and cache results
-        let issues = Self::run_cargo_clippy(project_root)?;
+        let issues = Self::run_cargo_clippy(project_root, capture)?;
         cache_map.insert(project_root.to_path_buf(), issues.clone());
         Ok(issues)
     }
@@ -156,7 +200,7 @@ impl Checker for RustChecker {
         &[Language::Rust]
     }
 
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>> {
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
         // Find the Cargo project root
         let project_root = match Self::find_cargo_root(path) {
             Some(root) => root,
@@ -167,7 +211,7 @@ impl Checker for RustChecker {
         };
 
         // Get all issues for this project (cached)
-        let all_issues = Self::get_cached_issues(&project_root)?;
+        let all_issues = Self::get_cached_issues(&project_root, self.capture.as_deref())?;
 
         // Normalize paths for comparison
         let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
@@ -194,6 +238,14 @@ impl Checker for RustChecker {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn binary(&self) -> PathBuf {
+        PathBuf::from("cargo-clippy")
+    }
+
+    fn tool_version(&self) -> Option<String> {
+        crate::utils::tool_version(Path::new("cargo"), &["clippy", "--version"])
+    }
 }
 
 /// Clear the clippy cache (useful for testing or forcing re-run)
@@ -201,3 +253,63 @@ pub fn clear_clippy_cache() {
     let mut cache = CLIPPY_CACHE.lock().unwrap();
     *cache = None;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clippy_json_keeps_notes_and_help() {
+        let output = [
+            r#"{"reason":"compiler-artifact","package_id":"app 0.1.0"}"#,
+            r#"{"reason":"compiler-message","message":{"message":"mismatched types","level":"error","spans":[{"file_name":"src/main.rs","line_start":4,"column_start":18,"is_primary":true},{"file_name":"src/main.rs","line_start":4,"column_start":12,"is_primary":false}],"children":[{"message":"expected because of this","level":"note","spans":[{"file_name":"src/lib.rs","line_start":2,"column_start":5,"is_primary":true}],"children":[]},{"message":"try using a conversion method","level":"help","spans":[],"children":[]}]}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"1 warning emitted","level":"warning","spans":[],"children":[]}}"#,
+            "not json",
+        ]
+        .join("\n");
+        let issues = RustChecker::parse_clippy_output(&output, Path::new("/work/app"));
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.file_path, Path::new("/work/app/src/main.rs"));
+        assert_eq!((issue.line, issue.column), (4, Some(18)));
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.message, "mismatched types");
+        assert_eq!(
+            issue.related,
+            vec![
+                RelatedInfo::new(RelatedKind::Note, "expected because of this".to_string())
+                    .with_location(PathBuf::from("/work/app/src/lib.rs"), 2),
+                RelatedInfo::new(
+                    RelatedKind::Help,
+                    "try using a conversion method".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_multi_span_clippy_diagnostic_as_one_issue() {
+        // Captured from `cargo clippy --message-format=json`; renders as
+        // eight lines of terminal output
+        let output = r#"{"reason":"compiler-message","package_id":"app 0.1.0","manifest_path":"/work/app/Cargo.toml","target":{"kind":["lib"],"name":"app","src_path":"/work/app/src/lib.rs"},"message":{"rendered":"warning: the loop variable `i` is only used to index `v`\n --> src/lib.rs:3:14\n  |\n3 |     for i in 0..v.len() {\n  |              ^^^^^^^^^^\n  |\n  = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#needless_range_loop\n  = note: `#[warn(clippy::needless_range_loop)]` on by default\nhelp: consider using an iterator\n  |\n3 |     for <item> in &v {\n  |         ~~~~~~    ~~\n\n","$message_type":"diagnostic","children":[{"children":[],"code":null,"level":"help","message":"for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#needless_range_loop","rendered":null,"spans":[]},{"children":[],"code":null,"level":"note","message":"`#[warn(clippy::needless_range_loop)]` on by default","rendered":null,"spans":[]},{"children":[],"code":null,"level":"help","message":"consider using an iterator","rendered":null,"spans":[{"byte_end":41,"byte_start":40,"column_end":10,"column_start":9,"expansion":null,"file_name":"src/lib.rs","is_primary":true,"label":null,"line_end":3,"line_start":3,"suggested_replacement":"<item>","suggestion_applicability":"HasPlaceholders","text":[]},{"byte_end":55,"byte_start":45,"column_end":24,"column_start":14,"expansion":null,"file_name":"src/lib.rs","is_primary":true,"label":null,"line_end":3,"line_start":3,"suggested_replacement":"&v","suggestion_applicability":"HasPlaceholders","text":[]}]}],"code":{"code":"clippy::needless_range_loop","explanation":null},"level":"warning","message":"the loop variable `i` is only used to index `v`","spans":[{"byte_end":55,"byte_start":45,"column_end":24,"column_start":14,"expansion":null,"file_name":"src/lib.rs","is_primary":true,"label":null,"line_end":3,"line_start":3,"suggested_replacement":null,"suggestion_applicability":null,"text":[{"highlight_end":24,"highlight_start":14,"text":"    for i in 0..v.len() {"}]}]}}"#;
+        let issues = RustChecker::parse_clippy_output(output, Path::new("/work/app"));
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.file_path, Path::new("/work/app/src/lib.rs"));
+        assert_eq!((issue.line, issue.column), (3, Some(14)));
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(
+            issue.message,
+            "the loop variable `i` is only used to index `v`"
+        );
+        assert_eq!(issue.code.as_deref(), Some("clippy::needless_range_loop"));
+        assert_eq!(
+            issue.suggestion.as_deref(),
+            Some("consider using an iterator: `<item>`")
+        );
+        assert_eq!(issue.related.len(), 3);
+        assert_eq!(issue.related[1].kind, RelatedKind::Note);
+    }
+}
@@ -11,8 +11,109 @@
 //! Checker trait definition for language-specific linters.
 
 use crate::utils::types::LintIssue;
-use crate::{Language, Result};
-use std::path::Path;
+use crate::{Language, LintisError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Why a checker could not produce results.
+///
+/// Lets callers tell a clean file (`Ok` with no issues) apart from a tool
+/// that is missing or broken.
+#[derive(Error, Debug)]
+pub enum CheckerError {
+    /// The tool binary could not be found
+    #[error("{tool} not found")]
+    ToolNotFound { tool: String },
+
+    /// The tool crashed, was killed or exited with an unexpected code
+    /// (`code` is `None` when terminated by a signal)
+    #[error("{message}")]
+    ToolCrashed { code: Option<i32>, message: String },
+
+    /// The tool ran but its output could not be parsed
+    #[error("Failed to parse {tool} output: {raw}")]
+    ParseError { tool: String, raw: String },
+
+    /// The tool did not finish in time
+    #[error("{tool} timed out")]
+    Timeout { tool: String },
+
+    /// The tool ran out of the memory allowed by `tool_memory_limit_mb`
+    #[error("{tool} exceeded the memory limit of {limit_mb} MB and was stopped")]
+    MemoryLimit { tool: String, limit_mb: u64 },
+
+    /// Any other error (e.g. the file could not be read)
+    #[error(transparent)]
+    Other(#[from] LintisError),
+}
+
+impl From<std::io::Error> for CheckerError {
+    fn from(e: std::io::Error) -> Self {
+        CheckerError::Other(LintisError::Io(e))
+    }
+}
+
+impl CheckerError {
+    /// Map an error starting `tool`: a missing binary is [`CheckerError::ToolNotFound`]
+    pub fn spawn(tool: &str, e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            CheckerError::ToolNotFound {
+                tool: tool.to_string(),
+            }
+        } else {
+            CheckerError::Other(LintisError::Checker(format!(
+                "Failed to run {}: {}",
+                tool, e
+            )))
+        }
+    }
+
+    /// Output that failed to parse, trimmed to a readable excerpt
+    pub fn parse(tool: &str, raw: &str) -> Self {
+        let raw = raw.trim();
+        let excerpt: String = raw.chars().take(200).collect();
+        CheckerError::ParseError {
+            tool: tool.to_string(),
+            raw: if excerpt.len() < raw.len() {
+                excerpt + "..."
+            } else {
+                excerpt
+            },
+        }
+    }
+
+    /// Whether the tool ran but failed (crash, timeout, memory limit or
+    /// unreadable output), as opposed to being missing or the file being
+    /// unreadable
+    pub fn is_tool_failure(&self) -> bool {
+        matches!(
+            self,
+            CheckerError::ToolCrashed { .. }
+                | CheckerError::ParseError { .. }
+                | CheckerError::Timeout { .. }
+                | CheckerError::MemoryLimit { .. }
+        )
+    }
+}
+
+/// Result of a checker run
+pub type CheckResult<T> = std::result::Result<T, CheckerError>;
+
+/// How a checker hands files to its tool.
+///
+/// Some linters (golangci-lint, go vet) analyze whole packages and report
+/// more with directory or project input, e.g. unused package-level symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Invocation {
+    /// One tool run per file
+    PerFile,
+    /// One tool run covering the directories (packages) of the checked files
+    PerDir,
+    /// One tool run over the whole project (e.g. a Go module's `./...`)
+    PerProject,
+}
 
 /// Trait for implementing language-specific checkers (linters).
 ///
@@ -31,8 +132,8 @@ pub trait Checker: Send + Sync {
     /// * `path` - Path to the file to check
     ///
     /// # Returns
-    /// A vector of lint issues, or an error if the check failed.
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>>;
+    /// A vector of lint issues, or a [`CheckerError`] if the check failed.
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>>;
 
     /// Check if this checker supports the given language.
     fn supports(&self, lang: Language) -> bool {
@@ -41,4 +142,19 @@ pub trait Checker: Send + Sync {
 
     /// Check if the external linter tool is available.
     fn is_available(&self) -> bool;
+
+    /// Returns the executable of the external linter tool.
+    fn binary(&self) -> PathBuf {
+        PathBuf::from(self.name())
+    }
+
+    /// Returns the version of the external linter tool, if it can be detected.
+    fn tool_version(&self) -> Option<String> {
+        crate::utils::tool_version(&self.binary(), &["--version"])
+    }
+
+    /// Returns the config file the tool uses for `path`, if it takes one.
+    fn config_path(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
 }
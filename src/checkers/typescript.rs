@@ -10,18 +10,69 @@
 
 //! TypeScript/JavaScript language checker using eslint.
 
-use crate::checkers::Checker;
+use crate::checkers::{CheckResult, Checker, CheckerError};
+use crate::utils::capture::{tool_output, ToolCapture};
+use crate::utils::tool_diagnostics;
 use crate::utils::types::{LintIssue, Severity};
-use crate::{Language, Result};
-use std::path::Path;
+use crate::Language;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, OnceLock};
 
 /// TypeScript/JavaScript checker using eslint.
-pub struct TypeScriptChecker;
+pub struct TypeScriptChecker {
+    /// Explicit eslint config path (e.g. from a plugin), bypassing discovery
+    config_path: Option<PathBuf>,
+    /// Ignore all config files (`--no-eslintrc` / `--no-config-lookup`)
+    isolated: bool,
+    /// Records or replays the linter's runs (`--record` / `--replay`)
+    capture: Option<Arc<ToolCapture>>,
+}
 
 impl TypeScriptChecker {
     pub fn new() -> Self {
-        Self
+        Self {
+            config_path: None,
+            isolated: false,
+            capture: None,
+        }
+    }
+
+    /// Record or replay the linter's runs through `capture`
+    pub fn with_capture(mut self, capture: Option<Arc<ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Ignore all eslint config files and use eslint's defaults
+    pub fn with_isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
+    /// Set an explicit eslint config path
+    pub fn with_config(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Build the eslint invocation for a file
+    pub(crate) fn command(&self, path: &Path) -> Command {
+        let mut cmd = Command::new("eslint");
+        cmd.args(["--format", "json", "--no-error-on-unmatched-pattern"]);
+
+        if self.isolated {
+            cmd.arg(no_config_flag()).arg(path);
+            return cmd;
+        }
+
+        // Explicit config first, then try to find one
+        if let Some(config_path) = Checker::config_path(self, path) {
+            cmd.arg("--config").arg(config_path);
+        }
+
+        cmd.arg(path);
+        cmd
     }
 
     /// Find ESLint configuration file
@@ -121,6 +172,43 @@ impl TypeScriptChecker {
     }
 }
 
+/// eslint's flag for ignoring config files: `--no-config-lookup` for flat
+/// config (the default since eslint 9), `--no-eslintrc` before that.
+fn no_config_flag() -> &'static str {
+    static FLAG: OnceLock<&'static str> = OnceLock::new();
+    FLAG.get_or_init(|| {
+        let version = Command::new("eslint")
+            .arg("--version")
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+            .unwrap_or_default();
+        no_config_flag_for(
+            &version,
+            std::env::var("ESLINT_USE_FLAT_CONFIG").ok().as_deref(),
+        )
+    })
+}
+
+/// Pick the no-config flag for an `eslint --version` output ("v9.1.0"),
+/// honoring an explicit `ESLINT_USE_FLAT_CONFIG` setting.
+fn no_config_flag_for(version: &str, use_flat_config: Option<&str>) -> &'static str {
+    let flat = match use_flat_config {
+        Some(value) => value == "true",
+        None => version
+            .trim()
+            .trim_start_matches('v')
+            .split('.')
+            .next()
+            .and_then(|major| major.parse::<u32>().ok())
+            .is_some_and(|major| major >= 9),
+    };
+    if flat {
+        "--no-config-lookup"
+    } else {
+        "--no-eslintrc"
+    }
+}
+
 impl Default for TypeScriptChecker {
     fn default() -> Self {
         Self::new()
@@ -136,22 +224,21 @@ impl Checker for TypeScriptChecker {
         &[Language::TypeScript, Language::JavaScript]
     }
 
-    fn check(&self, path: &Path) -> Result<Vec<LintIssue>> {
-        let mut cmd = Command::new("eslint");
-        cmd.args(["--format", "json", "--no-error-on-unmatched-pattern"]);
-
-        // Try to find eslint config
-        if let Some(config_path) = Self::find_eslint_config(path) {
-            cmd.arg("-c").arg(config_path);
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        // eslint reports explicitly passed ignored files instead of skipping them
+        if crate::utils::is_ignored_by(path, ".eslintignore") {
+            return Ok(Vec::new());
         }
 
-        let output = cmd
-            .arg(path)
-            .output()
-            .map_err(|e| crate::LintisError::Checker(format!("Failed to run eslint: {}", e)))?;
+        let output = tool_output(
+            self.capture.as_deref(),
+            "eslint",
+            path,
+            &mut self.command(path),
+        )
+        .map_err(|e| CheckerError::spawn("eslint", e))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let issues = self.parse_eslint_output(&stdout, path);
+        let issues = self.parse_eslint_output(&tool_diagnostics("eslint", &output), path);
 
         Ok(issues)
     }
@@ -163,4 +250,31 @@ impl Checker for TypeScriptChecker {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        self.config_path
+            .clone()
+            .or_else(|| Self::find_eslint_config(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_config_flag_for_eslint_version() {
+        assert_eq!(no_config_flag_for("v9.1.0\n", None), "--no-config-lookup");
+        assert_eq!(no_config_flag_for("v8.57.0\n", None), "--no-eslintrc");
+        assert_eq!(no_config_flag_for("", None), "--no-eslintrc");
+        // An explicit flat-config switch wins over the version
+        assert_eq!(
+            no_config_flag_for("v8.57.0", Some("true")),
+            "--no-config-lookup"
+        );
+        assert_eq!(no_config_flag_for("v9.1.0", Some("false")), "--no-eslintrc");
+    }
 }
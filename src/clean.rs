@@ -0,0 +1,166 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! `linthis clean`: purge caches and update-check timestamps.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::plugin::{AutoSyncManager, PluginCache};
+use crate::self_update::SelfUpdateManager;
+
+/// Something `linthis clean` removes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanKind {
+    /// Cloned plugin repositories
+    PluginCache,
+    /// Cached file lists of previous runs
+    ScanCache,
+    /// golangci-lint analysis cache
+    GoLintCache,
+    /// Last plugin auto-sync check
+    SyncTimestamp,
+    /// Last self-update check
+    UpdateTimestamp,
+}
+
+impl CleanKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CleanKind::PluginCache => "plugin cache",
+            CleanKind::ScanCache => "scan cache",
+            CleanKind::GoLintCache => "golangci-lint cache",
+            CleanKind::SyncTimestamp => "plugin sync timestamp",
+            CleanKind::UpdateTimestamp => "self-update timestamp",
+        }
+    }
+}
+
+/// A file or directory removed (or, with a dry run, that would be)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanedPath {
+    pub kind: CleanKind,
+    pub path: PathBuf,
+    /// Bytes reclaimed
+    pub size: u64,
+}
+
+/// Where linthis keeps its caches and timestamps on this machine
+pub fn clean_targets() -> Vec<(CleanKind, PathBuf)> {
+    let mut targets = Vec::new();
+    if let Ok(cache) = PluginCache::new() {
+        targets.push((CleanKind::PluginCache, cache.cache_dir().to_path_buf()));
+    }
+    if let Some(dir) = crate::utils::walker::scan_cache_dir() {
+        targets.push((CleanKind::ScanCache, dir));
+    }
+    if let Some(dir) = crate::checkers::GoChecker::golangci_cache_dir() {
+        targets.push((CleanKind::GoLintCache, dir));
+    }
+    if let Ok(sync) = AutoSyncManager::new() {
+        targets.push((CleanKind::SyncTimestamp, sync.timestamp_file_path().clone()));
+    }
+    if let Some(update) = SelfUpdateManager::try_new() {
+        targets.push((
+            CleanKind::UpdateTimestamp,
+            update.timestamp_file_path().to_path_buf(),
+        ));
+    }
+    targets
+}
+
+/// Remove the targets that exist; with `dry_run`, only measure them.
+/// Returns what was (or would be) removed.
+pub fn clean(targets: &[(CleanKind, PathBuf)], dry_run: bool) -> io::Result<Vec<CleanedPath>> {
+    let mut cleaned = Vec::new();
+    for (kind, path) in targets {
+        if !path.exists() {
+            continue;
+        }
+        let size = path_size(path);
+        if !dry_run {
+            match kind {
+                CleanKind::PluginCache => PluginCache::with_dir(path.clone())
+                    .clear_all()
+                    .map_err(|e| io::Error::other(e.to_string()))?,
+                _ if path.is_dir() => fs::remove_dir_all(path)?,
+                _ => fs::remove_file(path)?,
+            }
+        }
+        cleaned.push(CleanedPath {
+            kind: *kind,
+            path: path.clone(),
+            size,
+        });
+    }
+    Ok(cleaned)
+}
+
+/// Size of a file, or of everything under a directory
+fn path_size(path: &Path) -> u64 {
+    if !path.is_dir() {
+        return fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    }
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_removes_timestamps_and_reports_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let sync = dir.path().join(".plugin_sync_last_check");
+        let update = dir.path().join(".self_update_last_check");
+        let plugins = dir.path().join("plugins");
+        fs::write(&sync, "1700000000").unwrap();
+        fs::write(&update, "1700000000").unwrap();
+        fs::create_dir_all(plugins.join("github.com/org/config")).unwrap();
+        fs::write(
+            plugins.join("github.com/org/config/ruff.toml"),
+            "x".repeat(100),
+        )
+        .unwrap();
+
+        let targets = vec![
+            (CleanKind::PluginCache, plugins.clone()),
+            (CleanKind::ScanCache, dir.path().join("walk")),
+            (CleanKind::SyncTimestamp, sync.clone()),
+            (CleanKind::UpdateTimestamp, update.clone()),
+        ];
+
+        // A dry run only reports
+        let planned = clean(&targets, true).unwrap();
+        assert!(sync.exists() && update.exists() && plugins.exists());
+
+        let cleaned = clean(&targets, false).unwrap();
+        assert_eq!(planned, cleaned);
+        assert_eq!(
+            cleaned.iter().map(|c| (c.kind, c.size)).collect::<Vec<_>>(),
+            vec![
+                (CleanKind::PluginCache, 100),
+                (CleanKind::SyncTimestamp, 10),
+                (CleanKind::UpdateTimestamp, 10),
+            ]
+        );
+        assert!(!sync.exists() && !update.exists() && !plugins.exists());
+
+        // Nothing left to clean
+        assert!(clean(&targets, false).unwrap().is_empty());
+    }
+}
@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use toml_edit::{value, Array, DocumentMut};
 
-use super::Config;
+use super::{CodeYmlMigration, Config};
 
 /// Get config file path based on global flag
 fn get_config_path(global: bool) -> crate::Result<PathBuf> {
@@ -474,6 +474,78 @@ pub fn handle_config_list(verbose: bool, global: bool) -> ExitCode {
     ExitCode::SUCCESS
 }
 
+/// Translate a `.code.yml` and write the excludes to `output`.
+///
+/// A new config file gets the fully commented TOML; an existing one has the
+/// excludes appended (skipping duplicates) so its other settings are kept.
+fn migrate_code_yml(input: &Path, output: &Path) -> crate::Result<CodeYmlMigration> {
+    let content = fs::read_to_string(input).map_err(|e| {
+        crate::LintisError::Config(format!("Failed to read {}: {}", input.display(), e))
+    })?;
+    let migration = CodeYmlMigration::from_code_yml(&content)?;
+
+    if output.exists() {
+        let mut doc = load_toml_doc(output)?;
+        if !doc.contains_key("excludes") {
+            doc["excludes"] = toml_edit::Item::Value(toml_edit::Value::Array(Array::new()));
+        }
+        let arr = doc
+            .get_mut("excludes")
+            .and_then(|item| item.as_array_mut())
+            .ok_or_else(|| {
+                crate::LintisError::Config("Field 'excludes' is not an array".to_string())
+            })?;
+        for (glob, _, _) in &migration.excludes {
+            if !arr.iter().any(|v| v.as_str() == Some(glob)) {
+                arr.push(glob.as_str());
+            }
+        }
+        save_toml_doc(output, &doc)?;
+    } else {
+        if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| {
+                crate::LintisError::Config(format!("Failed to create config directory: {}", e))
+            })?;
+        }
+        fs::write(output, migration.to_toml()).map_err(|e| {
+            crate::LintisError::Config(format!("Failed to write config file: {}", e))
+        })?;
+    }
+
+    Ok(migration)
+}
+
+/// Migrate a CodeCC `.code.yml` to linthis configuration
+pub fn handle_config_migrate(input: &Path, output: &Path) -> ExitCode {
+    let migration = match migrate_code_yml(input, output) {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    println!(
+        "{} Migrated {} exclude pattern(s) from {} to {}",
+        "✓".green(),
+        migration.excludes.len(),
+        input.display(),
+        output.display().to_string().bold()
+    );
+    if !migration.unmapped.is_empty() {
+        eprintln!(
+            "{}: {} field(s) could not be migrated:",
+            "Warning".yellow(),
+            migration.unmapped.len()
+        );
+        for field in &migration.unmapped {
+            eprintln!("  - {}", field);
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
 /// Fallback for home directory if dirs crate is not available
 mod dirs {
     use std::path::PathBuf;
@@ -575,4 +647,54 @@ mod tests {
         assert!(parse_scalar_value("verbose", "true").is_ok());
         assert!(parse_scalar_value("verbose", "xyz").is_err());
     }
+
+    #[test]
+    fn test_migrate_code_yml_writes_excludes() {
+        let dir = tempdir().unwrap();
+        let input = dir.path().join(".code.yml");
+        fs::write(
+            &input,
+            r#"# CodeCC paths
+source:
+  test_source:
+    filepath_regex: ['.*_test\.py$', ".*/tests/.*"]
+  third_party_source:
+    filepath_regex: ["vendor/.*", "lib(a|b)/.*"]
+  unit_test_source:
+    filepath_regex: [".*/ut/.*"]
+checkers:
+  - pylint
+"#,
+        )
+        .unwrap();
+        let output = dir.path().join(".linthis").join("config.toml");
+
+        let migration = migrate_code_yml(&input, &output).unwrap();
+        assert_eq!(
+            migration.unmapped,
+            vec![
+                "source.third_party_source.filepath_regex: lib(a|b)/.*",
+                "source.unit_test_source",
+                "checkers",
+            ]
+        );
+        let written = fs::read_to_string(&output).unwrap();
+        assert!(written.contains("#   CodeCC paths"));
+        assert!(written.contains("# test_source: .*_test\\.py$"));
+        let config = Config::load(&output).unwrap();
+        assert_eq!(
+            config.excludes,
+            vec!["**/*_test.py", "**/tests/**", "vendor/**"]
+        );
+
+        // Migrating into an existing config appends without duplicates
+        fs::write(&output, "max_complexity = 10\nexcludes = [\"vendor/**\"]\n").unwrap();
+        migrate_code_yml(&input, &output).unwrap();
+        let config = Config::load(&output).unwrap();
+        assert_eq!(config.max_complexity, Some(10));
+        assert_eq!(
+            config.excludes,
+            vec!["vendor/**", "**/*_test.py", "**/tests/**"]
+        );
+    }
 }
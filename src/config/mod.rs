@@ -23,6 +23,7 @@ pub mod cli;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -39,6 +40,10 @@ pub struct Config {
     #[serde(default, alias = "exclude")]
     pub excludes: Vec<String>,
 
+    /// Built-in default excludes to drop (e.g. "vendor/**")
+    #[serde(default)]
+    pub default_excludes_remove: Vec<String>,
+
     /// Maximum cyclomatic complexity allowed
     #[serde(default)]
     pub max_complexity: Option<u32>,
@@ -51,10 +56,66 @@ pub struct Config {
     #[serde(default)]
     pub verbose: Option<bool>,
 
+    /// Files re-linted after formatting: "formatted" (default), "none" or "all"
+    #[serde(default)]
+    pub recheck: Option<crate::RecheckScope>,
+
+    /// Allow formatters and fixers to modify files (default: true).
+    /// With `false`, runs are report-only.
+    #[serde(default)]
+    pub autofix: Option<bool>,
+
+    /// Skip empty and whitespace-only files (default: true)
+    #[serde(default)]
+    pub skip_empty: Option<bool>,
+
+    /// Exclude test files by each language's conventions (default: false).
+    /// Also accepted as `exclude_tests`.
+    #[serde(default, alias = "exclude_tests")]
+    pub skip_tests: Option<bool>,
+
+    /// Fail the run (exit code 2) when a linter or formatter is missing,
+    /// instead of warning and skipping it
+    #[serde(default)]
+    pub require_tools: Option<bool>,
+
+    /// Abort when a run would process more files than this (no limit by default)
+    #[serde(default)]
+    pub max_files: Option<usize>,
+
+    /// Keep ("preserve", default) or drop ("remove") the UTF-8 BOM of files
+    /// that formatters and fixers rewrite
+    #[serde(default)]
+    pub bom: Option<crate::utils::bom::BomPolicy>,
+
+    /// Cap the memory of clang-tidy, cpplint, ruff and bandit at this many
+    /// MB; a tool stopped at the limit is reported as a warning (Unix only)
+    #[serde(default)]
+    pub tool_memory_limit_mb: Option<u64>,
+
+    /// Context lines around each change in `--show-diff` diffs (default: 3)
+    #[serde(default)]
+    pub diff_context: Option<usize>,
+
+    /// Severity per rule, e.g. `"E501" = "info"`, or scoped to a tool with
+    /// `"cpplint:build/*" = "error"` (trailing `*` matches a code prefix)
+    #[serde(default)]
+    pub severity_overrides: std::collections::HashMap<String, crate::utils::types::Severity>,
+
+    /// Sort and group imports in a separate pass before formatting
+    /// (ruff's isort rules for Python, goimports for Go, `[builtin_checks]`
+    /// include groups for C/C++; default: false)
+    #[serde(default)]
+    pub organize_imports: Option<bool>,
+
     /// Source configuration (compatible with CodeCC .code.yml)
     #[serde(default)]
     pub source: Option<SourceConfig>,
 
+    /// Built-in checks run in addition to the language tools
+    #[serde(default)]
+    pub builtin_checks: crate::checkers::BuiltinChecksConfig,
+
     /// Language-specific overrides (flattened to root level)
     #[serde(default, flatten)]
     pub language_overrides: LanguageOverrides,
@@ -156,6 +217,24 @@ pub struct LanguageConfig {
     /// Max complexity override
     #[serde(default)]
     pub max_complexity: Option<u32>,
+    /// Checkers to run in sequence (empty = language default)
+    #[serde(default)]
+    pub checkers: Vec<String>,
+    /// File extensions detected as this language (empty = built-in set)
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Lowest issue severity that fails the run for this language
+    /// ("error", "warning" or "info"; default: error, or warning with --fail-on-warnings)
+    #[serde(default)]
+    pub fail_on: Option<crate::utils::types::Severity>,
+    /// Allow formatters and fixers to modify files of this language
+    /// (overrides the global `autofix`)
+    #[serde(default)]
+    pub autofix: Option<bool>,
+    /// How files are handed to the checker: "per-file", "per-dir" or
+    /// "per-project" (honored by the Go checker; default: per-dir)
+    #[serde(default)]
+    pub invocation: Option<crate::checkers::Invocation>,
 }
 
 /// C/C++/Objective-C language configuration with cpplint support
@@ -170,12 +249,30 @@ pub struct CppLanguageConfig {
     /// Max complexity override
     #[serde(default)]
     pub max_complexity: Option<u32>,
+    /// Checkers to run in sequence (empty = clang-tidy, falling back to cpplint)
+    #[serde(default)]
+    pub checkers: Vec<String>,
+    /// File extensions detected as this language (empty = built-in set)
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Lowest issue severity that fails the run for this language
+    /// ("error", "warning" or "info"; default: error, or warning with --fail-on-warnings)
+    #[serde(default)]
+    pub fail_on: Option<crate::utils::types::Severity>,
+    /// Allow formatters and fixers to modify files of this language
+    /// (overrides the global `autofix`)
+    #[serde(default)]
+    pub autofix: Option<bool>,
     /// Cpplint line length (default: 80)
     #[serde(default)]
     pub linelength: Option<u32>,
     /// Cpplint filter rules (e.g., "-build/c++11,-build/header_guard")
     #[serde(default)]
     pub cpplint_filter: Option<String>,
+    /// Expected clang-format/clang-tidy version (e.g., "17") or path to a
+    /// clang-format binary. Overrides `.clang-format-version`.
+    #[serde(default)]
+    pub clang_format_version: Option<String>,
 }
 
 impl LanguageOverrides {
@@ -198,6 +295,251 @@ impl LanguageOverrides {
         merge_lang!(cpp);
         merge_lang!(oc);
     }
+
+    /// Configured checker chain for a language (empty = language default)
+    pub fn checkers(&self, lang: crate::Language) -> Vec<String> {
+        use crate::Language;
+
+        let lang_checkers =
+            |c: &Option<LanguageConfig>| c.as_ref().map(|c| c.checkers.clone()).unwrap_or_default();
+        let cpp_checkers = |c: &Option<CppLanguageConfig>| {
+            c.as_ref().map(|c| c.checkers.clone()).unwrap_or_default()
+        };
+
+        match lang {
+            Language::Rust => lang_checkers(&self.rust),
+            Language::Python => lang_checkers(&self.python),
+            Language::TypeScript => lang_checkers(&self.typescript),
+            Language::JavaScript => lang_checkers(&self.javascript),
+            Language::Go => lang_checkers(&self.go),
+            Language::Java => lang_checkers(&self.java),
+            Language::Cpp => cpp_checkers(&self.cpp),
+            Language::ObjectiveC => cpp_checkers(&self.oc),
+        }
+    }
+
+    /// Configured checker chains for all languages that set one
+    pub fn checker_chains(&self) -> std::collections::HashMap<crate::Language, Vec<String>> {
+        LANGUAGES
+            .into_iter()
+            .map(|lang| (lang, self.checkers(lang)))
+            .filter(|(_, checkers)| !checkers.is_empty())
+            .collect()
+    }
+
+    /// clang tool version pin (`[cpp]` first, then `[oc]`); relative binary
+    /// paths are resolved against `base_dir`
+    pub fn clang_pin(&self, base_dir: &Path) -> Option<crate::utils::clang::ClangPin> {
+        self.cpp
+            .as_ref()
+            .and_then(|c| c.clang_format_version.as_deref())
+            .or_else(|| {
+                self.oc
+                    .as_ref()
+                    .and_then(|c| c.clang_format_version.as_deref())
+            })
+            .and_then(|v| crate::utils::clang::ClangPin::parse(v, base_dir))
+    }
+
+    /// Configured extension set for a language (empty = built-in set)
+    pub fn extensions(&self, lang: crate::Language) -> Vec<String> {
+        use crate::Language;
+
+        let lang_extensions = |c: &Option<LanguageConfig>| {
+            c.as_ref().map(|c| c.extensions.clone()).unwrap_or_default()
+        };
+        let cpp_extensions = |c: &Option<CppLanguageConfig>| {
+            c.as_ref().map(|c| c.extensions.clone()).unwrap_or_default()
+        };
+
+        let extensions = match lang {
+            Language::Rust => lang_extensions(&self.rust),
+            Language::Python => lang_extensions(&self.python),
+            Language::TypeScript => lang_extensions(&self.typescript),
+            Language::JavaScript => lang_extensions(&self.javascript),
+            Language::Go => lang_extensions(&self.go),
+            Language::Java => lang_extensions(&self.java),
+            Language::Cpp => cpp_extensions(&self.cpp),
+            Language::ObjectiveC => cpp_extensions(&self.oc),
+        };
+
+        // Accept ".h" as well as "h"
+        extensions
+            .iter()
+            .map(|e| e.trim_start_matches('.').to_lowercase())
+            .collect()
+    }
+
+    /// Configured extension sets for all languages that override them
+    pub fn extension_overrides(&self) -> std::collections::HashMap<crate::Language, Vec<String>> {
+        LANGUAGES
+            .into_iter()
+            .map(|lang| (lang, self.extensions(lang)))
+            .filter(|(_, extensions)| !extensions.is_empty())
+            .collect()
+    }
+
+    /// Configured exit-code threshold for a language, if any
+    pub fn fail_on(&self, lang: crate::Language) -> Option<crate::utils::types::Severity> {
+        use crate::Language;
+
+        let lang_fail_on = |c: &Option<LanguageConfig>| c.as_ref().and_then(|c| c.fail_on);
+        let cpp_fail_on = |c: &Option<CppLanguageConfig>| c.as_ref().and_then(|c| c.fail_on);
+
+        match lang {
+            Language::Rust => lang_fail_on(&self.rust),
+            Language::Python => lang_fail_on(&self.python),
+            Language::TypeScript => lang_fail_on(&self.typescript),
+            Language::JavaScript => lang_fail_on(&self.javascript),
+            Language::Go => lang_fail_on(&self.go),
+            Language::Java => lang_fail_on(&self.java),
+            Language::Cpp => cpp_fail_on(&self.cpp),
+            Language::ObjectiveC => cpp_fail_on(&self.oc),
+        }
+    }
+
+    /// Configured exit-code thresholds for all languages that set one
+    pub fn fail_on_thresholds(
+        &self,
+    ) -> std::collections::HashMap<crate::Language, crate::utils::types::Severity> {
+        LANGUAGES
+            .into_iter()
+            .filter_map(|lang| self.fail_on(lang).map(|severity| (lang, severity)))
+            .collect()
+    }
+
+    /// Configured enable/disable switch for a language, if any
+    pub fn enabled(&self, lang: crate::Language) -> Option<bool> {
+        use crate::Language;
+
+        let lang_enabled = |c: &Option<LanguageConfig>| c.as_ref().and_then(|c| c.enabled);
+        let cpp_enabled = |c: &Option<CppLanguageConfig>| c.as_ref().and_then(|c| c.enabled);
+
+        match lang {
+            Language::Rust => lang_enabled(&self.rust),
+            Language::Python => lang_enabled(&self.python),
+            Language::TypeScript => lang_enabled(&self.typescript),
+            Language::JavaScript => lang_enabled(&self.javascript),
+            Language::Go => lang_enabled(&self.go),
+            Language::Java => lang_enabled(&self.java),
+            Language::Cpp => cpp_enabled(&self.cpp),
+            Language::ObjectiveC => cpp_enabled(&self.oc),
+        }
+    }
+
+    /// Configured autofix switch for a language, if any
+    pub fn autofix(&self, lang: crate::Language) -> Option<bool> {
+        use crate::Language;
+
+        let lang_autofix = |c: &Option<LanguageConfig>| c.as_ref().and_then(|c| c.autofix);
+        let cpp_autofix = |c: &Option<CppLanguageConfig>| c.as_ref().and_then(|c| c.autofix);
+
+        match lang {
+            Language::Rust => lang_autofix(&self.rust),
+            Language::Python => lang_autofix(&self.python),
+            Language::TypeScript => lang_autofix(&self.typescript),
+            Language::JavaScript => lang_autofix(&self.javascript),
+            Language::Go => lang_autofix(&self.go),
+            Language::Java => lang_autofix(&self.java),
+            Language::Cpp => cpp_autofix(&self.cpp),
+            Language::ObjectiveC => cpp_autofix(&self.oc),
+        }
+    }
+
+    /// Configured autofix switches for all languages that set one
+    pub fn autofix_overrides(&self) -> std::collections::HashMap<crate::Language, bool> {
+        LANGUAGES
+            .into_iter()
+            .filter_map(|lang| self.autofix(lang).map(|autofix| (lang, autofix)))
+            .collect()
+    }
+
+    /// Configured checker invocation mode for a language, if any
+    pub fn invocation(&self, lang: crate::Language) -> Option<crate::checkers::Invocation> {
+        use crate::Language;
+
+        let lang_invocation = |c: &Option<LanguageConfig>| c.as_ref().and_then(|c| c.invocation);
+
+        match lang {
+            Language::Rust => lang_invocation(&self.rust),
+            Language::Python => lang_invocation(&self.python),
+            Language::TypeScript => lang_invocation(&self.typescript),
+            Language::JavaScript => lang_invocation(&self.javascript),
+            Language::Go => lang_invocation(&self.go),
+            Language::Java => lang_invocation(&self.java),
+            Language::Cpp | Language::ObjectiveC => None,
+        }
+    }
+
+    /// Configured checker invocation modes for all languages that set one
+    pub fn invocations(
+        &self,
+    ) -> std::collections::HashMap<crate::Language, crate::checkers::Invocation> {
+        LANGUAGES
+            .into_iter()
+            .filter_map(|lang| self.invocation(lang).map(|mode| (lang, mode)))
+            .collect()
+    }
+}
+
+/// Languages that can be configured in `LanguageOverrides`, i.e. every
+/// supported language ([`crate::Language::ALL`])
+pub(crate) const LANGUAGES: [crate::Language; 8] = [
+    crate::Language::Rust,
+    crate::Language::Python,
+    crate::Language::TypeScript,
+    crate::Language::JavaScript,
+    crate::Language::Go,
+    crate::Language::Java,
+    crate::Language::Cpp,
+    crate::Language::ObjectiveC,
+];
+
+/// Layer of the merged configuration, lowest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayer {
+    BuiltIn,
+    User,
+    Project,
+}
+
+impl ConfigLayer {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConfigLayer::BuiltIn => "built-in",
+            ConfigLayer::User => "user",
+            ConfigLayer::Project => "project",
+        }
+    }
+}
+
+/// What happened to a config source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigStatus {
+    Loaded,
+    NotFound,
+    /// The file exists but could not be loaded; [`Config::load_merged`]
+    /// ignores it, [`Config::try_load_merged`] fails
+    Failed(String),
+}
+
+/// A config source considered by [`Config::load_merged`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSource {
+    pub layer: ConfigLayer,
+    /// Config file; None for the built-in defaults
+    pub path: Option<PathBuf>,
+    pub status: ConfigStatus,
+}
+
+impl ConfigSource {
+    pub fn new(layer: ConfigLayer, path: Option<PathBuf>, status: ConfigStatus) -> Self {
+        Self {
+            layer,
+            path,
+            status,
+        }
+    }
 }
 
 impl Config {
@@ -214,8 +556,7 @@ impl Config {
         let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
         match ext {
-            "yml" | "yaml" => serde_yaml::from_str(&content)
-                .map_err(|e| crate::LintisError::Config(format!("Failed to parse YAML: {}", e))),
+            "yml" | "yaml" => Self::parse_yaml(&content),
             "toml" => toml::from_str(&content)
                 .map_err(|e| crate::LintisError::Config(format!("Failed to parse TOML: {}", e))),
             "json" => serde_json::from_str(&content)
@@ -227,6 +568,52 @@ impl Config {
         }
     }
 
+    /// Parse a YAML config (e.g. a CodeCC `.code.yml`).
+    ///
+    /// Anchors, aliases and `<<` merge keys are resolved. Empty documents (such as
+    /// a leading or trailing `---`) are ignored; more than one non-empty document
+    /// is rejected with a clear error.
+    pub fn parse_yaml(content: &str) -> crate::Result<Self> {
+        let Some(value) = Self::parse_yaml_value(content)? else {
+            return Ok(Self::default());
+        };
+        serde_yaml::from_value(value)
+            .map_err(|e| crate::LintisError::Config(format!("Failed to parse YAML: {}", e)))
+    }
+
+    /// Parse a single-document YAML config into a value with merge keys resolved.
+    ///
+    /// Returns None when the content has no non-empty document.
+    fn parse_yaml_value(content: &str) -> crate::Result<Option<serde_yaml::Value>> {
+        use serde::Deserialize as _;
+
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            // Empty documents deserialize as None
+            let value = Option::<serde_yaml::Value>::deserialize(document)
+                .map_err(|e| crate::LintisError::Config(format!("Failed to parse YAML: {}", e)))?;
+            if let Some(value) = value.filter(|v| !v.is_null()) {
+                documents.push(value);
+            }
+        }
+
+        if documents.len() > 1 {
+            return Err(crate::LintisError::Config(format!(
+                "YAML config contains {} documents, but only a single document is supported \
+                 (remove the extra '---' separators)",
+                documents.len()
+            )));
+        }
+
+        let Some(mut value) = documents.pop() else {
+            return Ok(None);
+        };
+        value.apply_merge().map_err(|e| {
+            crate::LintisError::Config(format!("Failed to resolve YAML merge keys: {}", e))
+        })?;
+        Ok(Some(value))
+    }
+
     /// Load built-in default configuration
     pub fn built_in_defaults() -> Self {
         Config {
@@ -235,26 +622,68 @@ impl Config {
         }
     }
 
+    /// Path of the user-level configuration (~/.linthis/config.toml)
+    pub fn user_config_path() -> Option<PathBuf> {
+        Some(dirs::home_dir()?.join(".linthis").join("config.toml"))
+    }
+
     /// Load user-level configuration from ~/.linthis/config.toml
     pub fn load_user_config() -> Option<Self> {
-        let home = dirs::home_dir()?;
-        let config_path = home.join(".linthis").join("config.toml");
-        if config_path.exists() {
-            Self::load(&config_path).ok()
-        } else {
-            None
+        Self::user_config_sources(Self::user_config_path()?).0
+    }
+
+    /// Load the user config at `path`, with the source tried
+    fn user_config_sources(path: PathBuf) -> (Option<Self>, ConfigSource) {
+        if !path.exists() {
+            return (
+                None,
+                ConfigSource::new(ConfigLayer::User, Some(path), ConfigStatus::NotFound),
+            );
+        }
+        match Self::load(&path) {
+            Ok(config) => (
+                Some(config),
+                ConfigSource::new(ConfigLayer::User, Some(path), ConfigStatus::Loaded),
+            ),
+            Err(e) => (
+                None,
+                ConfigSource::new(
+                    ConfigLayer::User,
+                    Some(path),
+                    ConfigStatus::Failed(e.to_string()),
+                ),
+            ),
         }
     }
 
     /// Load project-level configuration from the given directory
     /// Searches for .linthis/config.toml in the start directory and parent directories
     pub fn load_project_config(start_dir: &Path) -> Option<Self> {
+        Self::project_config_sources(start_dir).0
+    }
+
+    /// Load the project config, with every file tried: configs that fail to
+    /// load are skipped in favour of the next one up the tree.
+    fn project_config_sources(start_dir: &Path) -> (Option<Self>, Vec<ConfigSource>) {
+        let mut sources = Vec::new();
         let mut current = start_dir.to_path_buf();
         loop {
             let config_path = current.join(".linthis").join("config.toml");
             if config_path.exists() {
-                if let Ok(config) = Self::load(&config_path) {
-                    return Some(config);
+                match Self::load(&config_path) {
+                    Ok(config) => {
+                        sources.push(ConfigSource::new(
+                            ConfigLayer::Project,
+                            Some(config_path),
+                            ConfigStatus::Loaded,
+                        ));
+                        return (Some(config), sources);
+                    }
+                    Err(e) => sources.push(ConfigSource::new(
+                        ConfigLayer::Project,
+                        Some(config_path),
+                        ConfigStatus::Failed(e.to_string()),
+                    )),
                 }
             }
 
@@ -263,7 +692,14 @@ impl Config {
             }
         }
 
-        None
+        if sources.is_empty() {
+            sources.push(ConfigSource::new(
+                ConfigLayer::Project,
+                Some(Self::project_config_path(start_dir)),
+                ConfigStatus::NotFound,
+            ));
+        }
+        (None, sources)
     }
 
     /// Merge another configuration into this one.
@@ -279,6 +715,8 @@ impl Config {
 
         // Merge exclude patterns (append, don't replace)
         self.excludes.extend(other.excludes);
+        self.default_excludes_remove
+            .extend(other.default_excludes_remove);
 
         // Override scalar values
         if other.max_complexity.is_some() {
@@ -290,9 +728,41 @@ impl Config {
         if other.verbose.is_some() {
             self.verbose = other.verbose;
         }
+        if other.recheck.is_some() {
+            self.recheck = other.recheck;
+        }
+        if other.autofix.is_some() {
+            self.autofix = other.autofix;
+        }
+        if other.skip_empty.is_some() {
+            self.skip_empty = other.skip_empty;
+        }
+        if other.skip_tests.is_some() {
+            self.skip_tests = other.skip_tests;
+        }
+        if other.require_tools.is_some() {
+            self.require_tools = other.require_tools;
+        }
+        if other.max_files.is_some() {
+            self.max_files = other.max_files;
+        }
+        if other.bom.is_some() {
+            self.bom = other.bom;
+        }
+        if other.tool_memory_limit_mb.is_some() {
+            self.tool_memory_limit_mb = other.tool_memory_limit_mb;
+        }
+        if other.diff_context.is_some() {
+            self.diff_context = other.diff_context;
+        }
+        self.severity_overrides.extend(other.severity_overrides);
+        if other.organize_imports.is_some() {
+            self.organize_imports = other.organize_imports;
+        }
         if other.source.is_some() {
             self.source = other.source;
         }
+        self.builtin_checks.merge(other.builtin_checks);
 
         // Merge language overrides
         self.language_overrides.merge(other.language_overrides);
@@ -323,19 +793,68 @@ impl Config {
     /// Load and merge configuration from all sources with proper precedence.
     /// Precedence: CLI > project > user > built-in
     pub fn load_merged(project_dir: &Path) -> Self {
+        Self::load_merged_with_sources(project_dir).0
+    }
+
+    /// [`Config::load_merged`], also returning every source considered,
+    /// lowest precedence first (`--config-path-resolution`)
+    pub fn load_merged_with_sources(project_dir: &Path) -> (Self, Vec<ConfigSource>) {
+        Self::merge_sources(Self::user_config_path(), project_dir)
+    }
+
+    /// [`Config::load_merged_with_sources`], failing with
+    /// [`crate::LintisError::Config`] if a user or project config exists but
+    /// can't be loaded, instead of skipping it.
+    pub fn try_load_merged(project_dir: &Path) -> crate::Result<(Self, Vec<ConfigSource>)> {
+        let (config, sources) = Self::load_merged_with_sources(project_dir);
+        Self::check_sources(&sources)?;
+        Ok((config, sources))
+    }
+
+    /// Fail on the first source that could not be loaded
+    fn check_sources(sources: &[ConfigSource]) -> crate::Result<()> {
+        for source in sources {
+            if let (ConfigStatus::Failed(reason), Some(path)) = (&source.status, &source.path) {
+                // The reason is a rendered LintisError::Config already
+                let reason = reason
+                    .strip_prefix("Configuration error: ")
+                    .unwrap_or(reason);
+                return Err(crate::LintisError::Config(format!(
+                    "invalid {} config {}: {}",
+                    source.layer.label(),
+                    path.display(),
+                    reason
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_sources(user_path: Option<PathBuf>, project_dir: &Path) -> (Self, Vec<ConfigSource>) {
         let mut config = Self::built_in_defaults();
+        let mut sources = vec![ConfigSource::new(
+            ConfigLayer::BuiltIn,
+            None,
+            ConfigStatus::Loaded,
+        )];
 
         // Layer 2: User config
-        if let Some(user_config) = Self::load_user_config() {
-            config.merge(user_config);
+        if let Some(path) = user_path {
+            let (user_config, source) = Self::user_config_sources(path);
+            sources.push(source);
+            if let Some(user_config) = user_config {
+                config.merge(user_config);
+            }
         }
 
         // Layer 3: Project config
-        if let Some(project_config) = Self::load_project_config(project_dir) {
+        let (project_config, project_sources) = Self::project_config_sources(project_dir);
+        sources.extend(project_sources);
+        if let Some(project_config) = project_config {
             config.merge(project_config);
         }
 
-        config
+        (config, sources)
     }
 
     /// Generate a default configuration file content
@@ -343,7 +862,8 @@ impl Config {
         r#"# Linthis Configuration
 # See https://github.com/zhlinh/linthis for documentation
 
-# Languages to check (empty = auto-detect all supported languages)
+# Languages to check (empty = auto-detect all supported languages).
+# --lang replaces them, or only narrows them with --languages-from-config-only
 # languages = ["rust", "python", "typescript"]
 
 # Files or directories to include (glob patterns)
@@ -352,12 +872,65 @@ impl Config {
 # Patterns to exclude (in addition to defaults)
 excludes = []
 
+# Built-in default excludes to lint anyway (e.g. vendored code you own)
+# default_excludes_remove = ["vendor/**"]
+
 # Maximum cyclomatic complexity allowed
 max_complexity = 20
 
-# Format preset: "google", "standard", or "airbnb"
+# Format preset: "google", "standard", "airbnb", or "none"
 # preset = "google"
 
+# Files re-linted after formatting: "formatted" (default), "none" or "all"
+# With "none", issues fixed by formatting are not measured
+# recheck = "formatted"
+
+# Set to false to never modify files (report-only, even without --check-only).
+# Can also be set per language, e.g. [cpp] autofix = false
+# autofix = true
+
+# Skip empty and whitespace-only files
+# skip_empty = true
+
+# Exclude test files (foo_test.go, test_foo.py, *.spec.ts, tests/**, ...)
+# skip_tests = false
+
+# Fail (exit code 2) when a linter or formatter is missing instead of skipping it
+# require_tools = false
+
+# Abort when a run finds more files than this (--force or --no-max-files runs anyway)
+# max_files = 20000
+
+# UTF-8 BOM of rewritten files: "preserve" (default) or "remove"
+# bom = "preserve"
+
+# Memory cap in MB for clang-tidy, cpplint, ruff and bandit (Unix only);
+# a tool stopped at the cap is reported as a warning
+# tool_memory_limit_mb = 4096
+
+# Context lines around each change in --show-diff diffs
+# diff_context = 3
+
+# Sort and group imports before formatting (ruff --select I for Python,
+# goimports for Go, include groups for C/C++)
+# organize_imports = false
+
+# Built-in checks (no external tools needed), off by default except final_newline
+# [builtin_checks]
+# whitespace = true          # trailing whitespace
+# final_newline = false      # exactly one newline at end of file (fixed when formatting)
+# mixed_indentation = true   # tabs and spaces in the same indent
+# max_function_lines = 80
+# include_order = true       # C/C++ include groups (reordered with organize_imports)
+# include_groups = ["own-header", "c-system", "cpp-system", "other-library", "project"]
+
+# Severity per rule; "tool:code" scopes it to one tool, a trailing * matches
+# a code prefix. Syntax and compile errors (e.g. "ruff:E9*") are errors by
+# default; entries here take precedence.
+# [severity_overrides]
+# "cpplint:build/*" = "error"
+# "E501" = "info"
+
 # Plugin configuration
 # [plugins]
 # sources = [
@@ -371,6 +944,18 @@ max_complexity = 20
 
 # [python]
 # excludes = ["*_test.py"]
+# checkers = ["ruff", "bandit"]  # opt in to bandit security scanning
+
+# [go]
+# fail_on = "warning"  # fail on warnings for Go even without --fail-on-warnings
+# invocation = "per-project"  # lint the whole module (per-file, per-dir or per-project)
+
+# [java]
+# autofix = false  # report Java issues without formatting Java files
+
+# [cpp]
+# checkers = ["clang-tidy", "cpplint"]  # run in sequence, issues merged
+# extensions = ["c", "cc", "cpp", "hpp"]  # replaces the default set (here: skip .h)
 "#
         .to_string()
     }
@@ -379,6 +964,336 @@ max_complexity = 20
     pub fn project_config_path(project_dir: &Path) -> PathBuf {
         project_dir.join(".linthis").join("config.toml")
     }
+
+    /// Whether files of a language are processed: it is listed in `languages`
+    /// (or the list is empty) and not disabled with `[lang] enabled = false`
+    pub fn language_enabled(&self, lang: crate::Language) -> bool {
+        (self.languages.is_empty()
+            || self
+                .languages
+                .iter()
+                .any(|name| crate::Language::from_name(name) == Some(lang)))
+            && self.language_overrides.enabled(lang) != Some(false)
+    }
+
+    /// Languages a run processes (empty = all).
+    ///
+    /// Normally only `--lang` (`cli`) selects languages. With `config_only`,
+    /// the configured `languages` are an allow-list: `--lang` can only narrow
+    /// it, so files of other languages are never processed. A configured list
+    /// naming no supported language is a [`crate::LintisError::Config`].
+    pub fn run_languages(
+        &self,
+        cli: &[crate::Language],
+        config_only: bool,
+    ) -> crate::Result<Vec<crate::Language>> {
+        let configured: Vec<crate::Language> = LANGUAGES
+            .into_iter()
+            .filter(|lang| {
+                self.languages
+                    .iter()
+                    .any(|name| crate::Language::from_name(name) == Some(*lang))
+            })
+            .collect();
+        if configured.is_empty() && !self.languages.is_empty() {
+            let mut names: Vec<&str> = self.languages.iter().map(String::as_str).collect();
+            names.sort_unstable();
+            return Err(crate::LintisError::Config(format!(
+                "no supported language in configured languages: {}",
+                names.join(", ")
+            )));
+        }
+        Ok(
+            match (config_only && !configured.is_empty(), cli.is_empty()) {
+                (false, _) => cli.to_vec(),
+                (true, true) => configured,
+                (true, false) => cli
+                    .iter()
+                    .copied()
+                    .filter(|lang| configured.contains(lang))
+                    .collect(),
+            },
+        )
+    }
+}
+
+/// Directory-scoped configs: `.linthis/config.toml` files in subdirectories
+/// of a project, resolved per directory and cached.
+///
+/// The overlay for a directory merges every config between the project root
+/// (exclusive) and the directory (inclusive), outermost first. Overlay
+/// excludes are rebased onto the project root.
+#[derive(Debug)]
+pub struct DirConfigs {
+    root: PathBuf,
+    cache: std::sync::Mutex<std::collections::HashMap<PathBuf, Option<Arc<Config>>>>,
+}
+
+impl DirConfigs {
+    /// Create a resolver for subdirectories of `root`, whose own config is
+    /// already applied
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: absolute_path(root),
+            cache: Default::default(),
+        }
+    }
+
+    /// The project root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolved overlay for files in `dir`, or `None` if no subdirectory
+    /// config applies
+    pub fn resolve(&self, dir: &Path) -> Option<Arc<Config>> {
+        self.resolve_absolute(&absolute_path(dir))
+    }
+
+    fn resolve_absolute(&self, dir: &Path) -> Option<Arc<Config>> {
+        if dir == self.root || !dir.starts_with(&self.root) {
+            return None;
+        }
+        if let Some(cached) = self.cache.lock().unwrap().get(dir) {
+            return cached.clone();
+        }
+
+        let parent = dir.parent().and_then(|p| self.resolve_absolute(p));
+        let config_path = Config::project_config_path(dir);
+        let own = config_path
+            .exists()
+            .then(|| Config::load(&config_path).ok())
+            .flatten();
+        let resolved = match own {
+            None => parent,
+            Some(mut own) => {
+                let rel = dir.strip_prefix(&self.root).unwrap_or(dir);
+                own.excludes = own
+                    .excludes
+                    .iter()
+                    .map(|pattern| rebase_glob(rel, pattern))
+                    .collect();
+                let mut merged = parent.map(|p| (*p).clone()).unwrap_or_default();
+                merged.merge(own);
+                Some(Arc::new(merged))
+            }
+        };
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), resolved.clone());
+        resolved
+    }
+}
+
+/// Make a path absolute (resolving symlinks where possible)
+fn absolute_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    })
+}
+
+/// Rebase a glob from a subdirectory config onto the project root.
+///
+/// Patterns without a `/` match file names anywhere below the subdirectory.
+fn rebase_glob(dir: &Path, pattern: &str) -> String {
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    if pattern.contains('/') {
+        format!("{}/{}", dir, pattern.trim_start_matches("./"))
+    } else {
+        format!("{}/**/{}", dir, pattern)
+    }
+}
+
+/// Result of translating a CodeCC `.code.yml` into linthis configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeYmlMigration {
+    /// Exclude globs as (glob, source category, original regex)
+    pub excludes: Vec<(String, String, String)>,
+    /// Fields or patterns that have no linthis equivalent
+    pub unmapped: Vec<String>,
+    /// Comment lines from the original file
+    pub comments: Vec<String>,
+}
+
+/// `.code.yml` source categories translated into excludes
+const CODE_YML_SOURCE_KEYS: [&str; 3] =
+    ["test_source", "auto_generate_source", "third_party_source"];
+
+impl CodeYmlMigration {
+    /// Translate `.code.yml` content.
+    ///
+    /// `filepath_regex` entries of test_source, auto_generate_source and
+    /// third_party_source become exclude globs. Regexes that can't be expressed
+    /// as globs and any other fields are reported in `unmapped`.
+    pub fn from_code_yml(content: &str) -> crate::Result<Self> {
+        let mut migration = Self {
+            comments: content
+                .lines()
+                .map(str::trim)
+                .filter(|line| line.starts_with('#'))
+                .map(|line| line.trim_start_matches('#').trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            ..Default::default()
+        };
+
+        let Some(value) = Config::parse_yaml_value(content)? else {
+            return Ok(migration);
+        };
+        let serde_yaml::Value::Mapping(root) = value else {
+            return Err(crate::LintisError::Config(
+                ".code.yml must be a mapping".to_string(),
+            ));
+        };
+
+        for (key, value) in &root {
+            let key = yaml_key(key);
+            if key != "source" {
+                migration.unmapped.push(key);
+                continue;
+            }
+            let Some(source) = value.as_mapping() else {
+                migration.unmapped.push("source".to_string());
+                continue;
+            };
+
+            for (category, patterns) in source {
+                let category = yaml_key(category);
+                if !CODE_YML_SOURCE_KEYS.contains(&category.as_str()) {
+                    migration.unmapped.push(format!("source.{}", category));
+                    continue;
+                }
+                let regexes = patterns
+                    .get("filepath_regex")
+                    .and_then(|r| r.as_sequence())
+                    .map(|seq| seq.iter().filter_map(|r| r.as_str()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                if let Some(fields) = patterns.as_mapping() {
+                    for field in fields.keys().map(yaml_key) {
+                        if field != "filepath_regex" {
+                            migration
+                                .unmapped
+                                .push(format!("source.{}.{}", category, field));
+                        }
+                    }
+                }
+
+                for regex in regexes {
+                    match regex_to_glob(regex) {
+                        Some(glob) => {
+                            migration
+                                .excludes
+                                .push((glob, category.clone(), regex.to_string()))
+                        }
+                        None => migration
+                            .unmapped
+                            .push(format!("source.{}.filepath_regex: {}", category, regex)),
+                    }
+                }
+            }
+        }
+
+        Ok(migration)
+    }
+
+    /// Render the migrated configuration as TOML.
+    ///
+    /// Each exclude keeps its original regex as a comment; original comments and
+    /// unmapped fields are listed in the header.
+    pub fn to_toml(&self) -> String {
+        let mut out = String::from("# Linthis Configuration\n# Migrated from .code.yml\n");
+        if !self.comments.is_empty() {
+            out.push_str("#\n# Original comments:\n");
+            for comment in &self.comments {
+                out.push_str(&format!("#   {}\n", comment));
+            }
+        }
+        if !self.unmapped.is_empty() {
+            out.push_str("#\n# Not migrated (no linthis equivalent):\n");
+            for field in &self.unmapped {
+                out.push_str(&format!("#   {}\n", field));
+            }
+        }
+
+        out.push_str("\nexcludes = [\n");
+        for (glob, category, regex) in &self.excludes {
+            out.push_str(&format!(
+                "    {},  # {}: {}\n",
+                toml_string(glob),
+                category,
+                regex
+            ));
+        }
+        out.push_str("]\n");
+        out
+    }
+}
+
+/// Render a YAML mapping key as a string
+fn yaml_key(key: &serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Quote a string as a TOML basic string
+fn toml_string(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+/// Translate a path regex into an equivalent glob, if it only uses `.*`,
+/// `[^/]*`, `.` and escaped literals. The regex is taken to match the whole path.
+fn regex_to_glob(regex: &str) -> Option<String> {
+    let body = regex.strip_prefix('^').unwrap_or(regex);
+    let body = body.strip_suffix('$').unwrap_or(body);
+
+    let mut glob = String::new();
+    let mut rest = body;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest
+            .strip_prefix(".*")
+            .or_else(|| rest.strip_prefix("[^/]*"))
+        {
+            let any_depth = rest.starts_with(".*");
+            if any_depth && glob.is_empty() && after.starts_with('/') {
+                glob.push_str("**");
+            } else if any_depth && glob.is_empty() {
+                glob.push_str("**/*");
+            } else if any_depth && glob.ends_with('/') && after.is_empty() {
+                glob.push_str("**");
+            } else {
+                glob.push('*');
+            }
+            rest = after;
+            continue;
+        }
+
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '\\' => {
+                let escaped = rest.chars().next()?;
+                if escaped.is_ascii_alphanumeric() || "*?[]{}".contains(escaped) {
+                    // Character classes like \d, or glob metacharacters
+                    return None;
+                }
+                glob.push(escaped);
+                rest = &rest[escaped.len_utf8()..];
+            }
+            '.' => glob.push('?'),
+            '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' => return None,
+            _ => glob.push(c),
+        }
+    }
+
+    (!glob.is_empty()).then_some(glob)
 }
 
 // Add dirs crate for home directory
@@ -423,6 +1338,24 @@ mod tests {
             vec!["*.log".to_string(), "*.tmp".to_string()]
         );
         assert_eq!(base.preset, Some("google".to_string()));
+
+        // Switches set from TOML override, and survive merging unset ones
+        base.merge(
+            toml::from_str(
+                r#"
+autofix = false
+organize_imports = true
+bom = "remove"
+"#,
+            )
+            .unwrap(),
+        );
+        base.merge(Config::default());
+        assert_eq!(base.autofix, Some(false));
+        assert_eq!(base.organize_imports, Some(true));
+        assert_eq!(base.bom, Some(crate::utils::bom::BomPolicy::Remove));
+        base.merge(toml::from_str("autofix = true").unwrap());
+        assert_eq!(base.autofix, Some(true));
     }
 
     #[test]
@@ -611,6 +1544,71 @@ mod tests {
 
     // ==================== project_config_path tests ====================
 
+    #[test]
+    fn test_load_merged_reports_sources_in_order() {
+        let home = tempfile::tempdir().unwrap();
+        let user_path = home.path().join(".linthis/config.toml");
+        std::fs::create_dir_all(user_path.parent().unwrap()).unwrap();
+        std::fs::write(&user_path, "max_complexity = 15\npreset = \"google\"\n").unwrap();
+
+        let repo = tempfile::tempdir().unwrap();
+        let project_path = repo.path().join(".linthis/config.toml");
+        let nested_path = repo.path().join("app/.linthis/config.toml");
+        std::fs::create_dir_all(nested_path.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(project_path.parent().unwrap()).unwrap();
+        std::fs::write(&project_path, "max_complexity = 10\n").unwrap();
+        std::fs::write(&nested_path, "max_complexity = [").unwrap();
+
+        let (config, sources) =
+            Config::merge_sources(Some(user_path.clone()), &repo.path().join("app"));
+        assert_eq!(config.max_complexity, Some(10));
+        assert_eq!(config.preset.as_deref(), Some("google"));
+
+        let summary: Vec<_> = sources
+            .iter()
+            .map(|s| (s.layer, s.path.clone(), s.status == ConfigStatus::Loaded))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ConfigLayer::BuiltIn, None, true),
+                (ConfigLayer::User, Some(user_path), true),
+                (ConfigLayer::Project, Some(nested_path), false),
+                (ConfigLayer::Project, Some(project_path), true),
+            ]
+        );
+        assert!(matches!(sources[2].status, ConfigStatus::Failed(_)));
+
+        // Nothing found
+        let empty = tempfile::tempdir().unwrap();
+        let missing = empty.path().join("missing.toml");
+        let (_, sources) = Config::merge_sources(Some(missing.clone()), empty.path());
+        assert_eq!(
+            sources[1],
+            ConfigSource::new(ConfigLayer::User, Some(missing), ConfigStatus::NotFound)
+        );
+        assert_eq!(sources[2].status, ConfigStatus::NotFound);
+    }
+
+    #[test]
+    fn test_failed_config_source_is_a_config_error() {
+        let repo = tempfile::tempdir().unwrap();
+        let project_path = repo.path().join(".linthis/config.toml");
+        std::fs::create_dir_all(project_path.parent().unwrap()).unwrap();
+        std::fs::write(&project_path, "max_complexity = 10\n").unwrap();
+        let (_, sources) = Config::merge_sources(None, repo.path());
+        assert!(Config::check_sources(&sources).is_ok());
+
+        std::fs::write(&project_path, "max_complexity = [").unwrap();
+        let (_, sources) = Config::merge_sources(None, repo.path());
+        let err = Config::check_sources(&sources).unwrap_err();
+        assert!(matches!(err, crate::LintisError::Config(_)));
+        assert_eq!(err.exit_code(), crate::exit_code::CONFIG_ERROR);
+        assert!(err
+            .to_string()
+            .contains(&project_path.display().to_string()));
+    }
+
     #[test]
     fn test_project_config_path() {
         let project_dir = Path::new("/home/user/project");
@@ -621,6 +1619,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_run_languages_from_config_only() {
+        use crate::utils::walker::{walk_relative, write_test_tree, WalkerConfig};
+        use crate::Language;
+
+        let dir = tempfile::tempdir().unwrap();
+        write_test_tree(
+            dir.path(),
+            &["src/main.rs", "tools/gen.py", "web/app.ts", "cmd/main.go"],
+        );
+        let config: Config = toml::from_str(r#"languages = ["rust", "python"]"#).unwrap();
+        let walk = |languages: Vec<Language>| {
+            let walker_config = WalkerConfig {
+                languages,
+                ..Default::default()
+            };
+            walk_relative(dir.path(), &walker_config)
+        };
+        let rust_and_python = vec![Path::new("src/main.rs"), Path::new("tools/gen.py")];
+
+        // The configured languages only restrict runs in strict mode
+        assert_eq!(walk(config.run_languages(&[], false).unwrap()).len(), 4);
+        assert_eq!(
+            walk(config.run_languages(&[], true).unwrap()),
+            rust_and_python
+        );
+
+        // --lang selects freely, unless the configured list is an allow-list
+        let cli = [Language::Go, Language::Rust];
+        assert_eq!(walk(config.run_languages(&cli, false).unwrap()).len(), 2);
+        assert_eq!(
+            config.run_languages(&cli, true).unwrap(),
+            vec![Language::Rust]
+        );
+        assert!(config
+            .run_languages(&[Language::Go], true)
+            .unwrap()
+            .is_empty());
+
+        // Nothing configured: nothing to enforce
+        let unrestricted = Config::default();
+        assert_eq!(
+            unrestricted.run_languages(&cli, true).unwrap(),
+            cli.to_vec()
+        );
+        assert_eq!(
+            walk(unrestricted.run_languages(&[], true).unwrap()).len(),
+            4
+        );
+
+        // Only unknown languages configured: a config error, not "everything"
+        let unknown: Config = toml::from_str(r#"languages = ["kotlin"]"#).unwrap();
+        let err = unknown.run_languages(&[], true).unwrap_err();
+        assert!(matches!(err, crate::LintisError::Config(_)));
+        assert!(err.to_string().contains("kotlin"));
+    }
+
     // ==================== Config merge edge cases ====================
 
     #[test]
@@ -767,6 +1822,248 @@ mod tests {
         assert_eq!(source.third_party_source.filepath_regex.len(), 1);
     }
 
+    #[test]
+    fn test_source_config_from_code_yml_with_anchors() {
+        let yaml = r#"
+---
+common_excludes: &common
+  filepath_regex:
+    - ".*/third_party/.*"
+    - ".*/vendor/.*"
+
+source:
+  test_source:
+    filepath_regex: [".*_test\\.go$", ".*/tests/.*"]
+  auto_generate_source:
+    <<: *common
+  third_party_source: *common
+"#;
+
+        let config = Config::parse_yaml(yaml).unwrap();
+        let source = config.source.unwrap();
+        assert_eq!(source.test_source.filepath_regex.len(), 2);
+        assert_eq!(
+            source.auto_generate_source.filepath_regex,
+            vec![".*/third_party/.*", ".*/vendor/.*"]
+        );
+        assert_eq!(
+            source.third_party_source.filepath_regex,
+            source.auto_generate_source.filepath_regex
+        );
+    }
+
+    #[test]
+    fn test_parse_yaml_ignores_empty_documents() {
+        let yaml = "---\nmax_complexity: 15\n---\n";
+        let config = Config::parse_yaml(yaml).unwrap();
+        assert_eq!(config.max_complexity, Some(15));
+
+        assert!(Config::parse_yaml("").unwrap().max_complexity.is_none());
+    }
+
+    #[test]
+    fn test_parse_yaml_rejects_multiple_documents() {
+        let yaml = "max_complexity: 15\n---\nmax_complexity: 30\n";
+        let err = Config::parse_yaml(yaml).unwrap_err().to_string();
+        assert!(err.contains("2 documents"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_load_code_yml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".code.yml");
+        std::fs::write(
+            &path,
+            "source:\n  test_source:\n    filepath_regex: [\".*_test\\\\.py$\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.source.unwrap().test_source.filepath_regex.len(), 1);
+    }
+
+    #[test]
+    fn test_regex_to_glob() {
+        assert_eq!(
+            regex_to_glob(r".*_test\.go$").as_deref(),
+            Some("**/*_test.go")
+        );
+        assert_eq!(
+            regex_to_glob(r".*/tests/.*").as_deref(),
+            Some("**/tests/**")
+        );
+        assert_eq!(regex_to_glob(r"^vendor/.*").as_deref(), Some("vendor/**"));
+        assert_eq!(
+            regex_to_glob(r"src/[^/]*\.pb\.go").as_deref(),
+            Some("src/*.pb.go")
+        );
+        assert_eq!(regex_to_glob(r"gen/file.h").as_deref(), Some("gen/file?h"));
+        assert_eq!(regex_to_glob(r"\d+\.js"), None);
+        assert_eq!(regex_to_glob(r"(a|b)/.*"), None);
+        assert_eq!(regex_to_glob(r".+\.min\.js"), None);
+    }
+
+    #[test]
+    fn test_fail_on_thresholds_from_toml() {
+        use crate::utils::types::Severity;
+
+        let config: Config = toml::from_str(
+            r#"
+[go]
+fail_on = "warning"
+
+[rust]
+fail_on = "error"
+
+[cpp]
+fail_on = "info"
+"#,
+        )
+        .unwrap();
+        let thresholds = config.language_overrides.fail_on_thresholds();
+        assert_eq!(thresholds.len(), 3);
+        assert_eq!(thresholds[&crate::Language::Go], Severity::Warning);
+        assert_eq!(thresholds[&crate::Language::Rust], Severity::Error);
+        assert_eq!(thresholds[&crate::Language::Cpp], Severity::Info);
+        assert!(toml::from_str::<Config>("[go]\nfail_on = \"sometimes\"").is_err());
+    }
+
+    #[test]
+    fn test_invocation_from_toml() {
+        use crate::checkers::Invocation;
+
+        let config: Config = toml::from_str("[go]\ninvocation = \"per-project\"").unwrap();
+        let invocations = config.language_overrides.invocations();
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[&crate::Language::Go], Invocation::PerProject);
+        assert!(toml::from_str::<Config>("[go]\ninvocation = \"per-module\"").is_err());
+    }
+
+    #[test]
+    fn test_dir_configs_merge_nested_overlays() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "web/.linthis/config.toml",
+            "excludes = [\"gen/**\"]\n[python]\nenabled = false\n",
+        );
+        write(
+            "web/api/.linthis/config.toml",
+            "excludes = [\"*.tmp\"]\n[python]\nenabled = true\n",
+        );
+        std::fs::create_dir_all(root.join("web/static")).unwrap();
+
+        let configs = DirConfigs::new(root);
+        assert!(configs.resolve(root).is_none());
+
+        let web = configs.resolve(&root.join("web/static")).unwrap();
+        assert!(!web.language_enabled(crate::Language::Python));
+        assert!(web.language_enabled(crate::Language::Rust));
+        assert_eq!(web.excludes, vec!["web/gen/**"]);
+
+        let api = configs.resolve(&root.join("web/api")).unwrap();
+        assert!(api.language_enabled(crate::Language::Python));
+        assert_eq!(api.excludes, vec!["web/gen/**", "web/api/**/*.tmp"]);
+    }
+
+    #[test]
+    fn test_autofix_overrides_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+autofix = false
+
+[cpp]
+autofix = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.autofix, Some(false));
+        let overrides = config.language_overrides.autofix_overrides();
+        assert_eq!(overrides.get(&crate::Language::Cpp), Some(&true));
+        assert_eq!(overrides.len(), 1);
+    }
+
+    #[test]
+    fn test_builtin_checks_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[builtin_checks]
+whitespace = true
+max_function_lines = 60
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.builtin_checks.whitespace, Some(true));
+        assert_eq!(config.builtin_checks.max_function_lines, Some(60));
+        assert_eq!(config.builtin_checks.final_newline, None);
+        assert!(config.language_overrides.python.is_none());
+    }
+
+    #[test]
+    fn test_recheck_from_toml() {
+        let config: Config = toml::from_str(r#"recheck = "none""#).unwrap();
+        assert_eq!(config.recheck, Some(crate::RecheckScope::None));
+
+        let mut base = Config::default();
+        base.merge(config);
+        assert_eq!(base.recheck, Some(crate::RecheckScope::None));
+
+        assert!(toml::from_str::<Config>(r#"recheck = "sometimes""#).is_err());
+    }
+
+    #[test]
+    fn test_language_checker_chains_from_toml() {
+        let toml_str = r#"
+            [python]
+            checkers = ["ruff", "mypy"]
+
+            [cpp]
+            checkers = ["clang-tidy", "cpplint"]
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let chains = config.language_overrides.checker_chains();
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[&crate::Language::Python], vec!["ruff", "mypy"]);
+        assert_eq!(
+            config.language_overrides.checkers(crate::Language::Cpp),
+            vec!["clang-tidy", "cpplint"]
+        );
+        assert!(config
+            .language_overrides
+            .checkers(crate::Language::Rust)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_language_extension_overrides_from_toml() {
+        let toml_str = r#"
+            [cpp]
+            extensions = ["c", "cc", "cpp", ".HPP"]
+
+            [python]
+            extensions = ["py"]
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let overrides = config.language_overrides.extension_overrides();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(
+            overrides[&crate::Language::Cpp],
+            vec!["c", "cc", "cpp", "hpp"]
+        );
+        assert_eq!(overrides[&crate::Language::Python], vec!["py"]);
+        assert!(config
+            .language_overrides
+            .extensions(crate::Language::Go)
+            .is_empty());
+    }
+
     // ==================== CppLanguageConfig tests ====================
 
     #[test]
@@ -0,0 +1,319 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Copyright header fixer shared by all languages.
+//!
+//! The header template is plain text with a `{year}` placeholder. It is
+//! commented with the language's comment style and inserted where the
+//! language allows it: scripts keep their `#!` line (and Python its
+//! encoding declaration) first, so inserting a header never breaks them.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::utils::bom::{read_source, write_source, BomPolicy};
+use crate::{Language, LintisError, Result};
+
+/// How header lines are commented
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// Every line starts with the prefix, e.g. `//` or `#`
+    Line(String),
+    /// A block comment: opening line, per-line prefix, closing line
+    Block {
+        start: String,
+        prefix: String,
+        end: String,
+    },
+}
+
+/// Tokens that open a comment; templates starting with one are already
+/// commented and used as they are
+const COMMENT_TOKENS: &[&str] = &["//", "/*", "#"];
+
+impl CommentStyle {
+    /// Default comment style for a language
+    pub fn for_language(language: Language) -> Self {
+        match language {
+            Language::Python => CommentStyle::Line("#".to_string()),
+            Language::Cpp
+            | Language::ObjectiveC
+            | Language::Java
+            | Language::Rust
+            | Language::Go
+            | Language::JavaScript
+            | Language::TypeScript => CommentStyle::Line("//".to_string()),
+        }
+    }
+
+    /// Comment out `text` line by line; empty lines stay empty. Templates
+    /// that already start with a comment token are kept as they are.
+    pub fn comment(&self, text: &str) -> Vec<String> {
+        let lines: Vec<&str> = text.lines().collect();
+        let commented = lines
+            .iter()
+            .map(|l| l.trim_start())
+            .find(|l| !l.is_empty())
+            .is_some_and(|l| COMMENT_TOKENS.iter().any(|token| l.starts_with(token)));
+        if commented {
+            return lines.iter().map(|l| l.to_string()).collect();
+        }
+        let prefixed = |prefix: &str, line: &str| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        };
+        match self {
+            CommentStyle::Line(prefix) => {
+                let prefix = format!("{} ", prefix);
+                lines.iter().map(|l| prefixed(&prefix, l)).collect()
+            }
+            CommentStyle::Block { start, prefix, end } => {
+                let mut out = vec![start.clone()];
+                out.extend(lines.iter().map(|l| prefixed(prefix, l)));
+                out.push(end.clone());
+                out
+            }
+        }
+    }
+}
+
+/// Where the header goes in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderPlacement {
+    /// First line of the file
+    Top,
+    /// After a leading `#!` interpreter line
+    AfterShebang,
+    /// After a leading `#!` line and a PEP 263 encoding declaration, which
+    /// Python only honours on the first two lines
+    AfterShebangAndEncoding,
+}
+
+impl HeaderPlacement {
+    /// Default placement for a language
+    pub fn for_language(language: Language) -> Self {
+        match language {
+            Language::Python => HeaderPlacement::AfterShebangAndEncoding,
+            Language::Rust | Language::JavaScript | Language::TypeScript => {
+                HeaderPlacement::AfterShebang
+            }
+            Language::Cpp | Language::ObjectiveC | Language::Java | Language::Go => {
+                HeaderPlacement::Top
+            }
+        }
+    }
+
+    /// Index of the line the header is inserted before
+    pub fn insertion_line(&self, lines: &[String]) -> usize {
+        let mut index = 0;
+        if *self == HeaderPlacement::Top {
+            return index;
+        }
+        // `#![...]` is a Rust inner attribute, not an interpreter line
+        if lines
+            .first()
+            .is_some_and(|l| l.starts_with("#!") && !l.starts_with("#!["))
+        {
+            index = 1;
+        }
+        if *self == HeaderPlacement::AfterShebangAndEncoding
+            && index < 2
+            && lines.get(index).is_some_and(|l| is_encoding_line(l))
+        {
+            index += 1;
+        }
+        index
+    }
+}
+
+/// Whether a line is a PEP 263 encoding declaration (`# -*- coding: utf-8 -*-`)
+fn is_encoding_line(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#') && (line.contains("coding:") || line.contains("coding="))
+}
+
+/// Inserts a copyright header into files that don't have one
+#[derive(Debug, Clone)]
+pub struct CopyrightFixer {
+    /// Header text with a `{year}` placeholder
+    template: String,
+    styles: HashMap<Language, CommentStyle>,
+    placements: HashMap<Language, HeaderPlacement>,
+    bom: BomPolicy,
+}
+
+impl CopyrightFixer {
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            styles: HashMap::new(),
+            placements: HashMap::new(),
+            bom: BomPolicy::default(),
+        }
+    }
+
+    /// Keep or drop the BOM of files this fixer rewrites
+    pub fn with_bom_policy(mut self, bom: BomPolicy) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Override the comment style for a language
+    pub fn with_style(mut self, language: Language, style: CommentStyle) -> Self {
+        self.styles.insert(language, style);
+        self
+    }
+
+    /// Override where the header goes for a language
+    pub fn with_placement(mut self, language: Language, placement: HeaderPlacement) -> Self {
+        self.placements.insert(language, placement);
+        self
+    }
+
+    /// Comment style used for a language
+    pub fn style(&self, language: Language) -> CommentStyle {
+        self.styles
+            .get(&language)
+            .cloned()
+            .unwrap_or_else(|| CommentStyle::for_language(language))
+    }
+
+    /// Placement used for a language
+    pub fn placement(&self, language: Language) -> HeaderPlacement {
+        self.placements
+            .get(&language)
+            .copied()
+            .unwrap_or_else(|| HeaderPlacement::for_language(language))
+    }
+
+    /// The commented header for a language, with `{year}` filled in
+    pub fn header_lines(&self, language: Language) -> Vec<String> {
+        let year = chrono::Utc::now().format("%Y").to_string();
+        self.style(language)
+            .comment(&self.template.replace("{year}", &year))
+    }
+
+    /// Insert the header into `lines` unless the first lines already
+    /// mention a copyright. Returns whether anything was inserted.
+    pub fn insert_header(&self, lines: &mut Vec<String>, language: Language) -> bool {
+        let has_copyright = lines
+            .iter()
+            .take(10)
+            .any(|l| l.to_lowercase().contains("copyright"));
+        if has_copyright || self.template.trim().is_empty() {
+            return false;
+        }
+
+        let at = self.placement(language).insertion_line(lines);
+        let mut header = self.header_lines(language);
+        if at > 0 && lines.get(at - 1).is_some_and(|l| !l.trim().is_empty()) {
+            header.insert(0, String::new());
+        }
+        if lines.get(at).is_some_and(|l| !l.trim().is_empty()) {
+            header.push(String::new());
+        }
+        lines.splice(at..at, header);
+        true
+    }
+
+    /// Insert the header into a file. Returns whether the file was changed.
+    pub fn fix_file(&self, path: &Path, language: Language) -> Result<bool> {
+        let (content, had_bom) = read_source(path)
+            .map_err(|e| LintisError::Formatter(format!("Failed to read file: {}", e)))?;
+
+        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+        if !self.insert_header(&mut lines, language) {
+            return Ok(false);
+        }
+
+        let mut result = lines.join("\n");
+        if content.ends_with('\n') || content.is_empty() {
+            result.push('\n');
+        }
+        write_source(path, &result, had_bom, self.bom)
+            .map_err(|e| LintisError::Formatter(format!("Failed to write file: {}", e)))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_python_header_goes_after_shebang_and_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tool.py");
+        fs::write(
+            &path,
+            "#!/usr/bin/env python3\n# -*- coding: utf-8 -*-\nimport sys\n",
+        )
+        .unwrap();
+
+        let fixer = CopyrightFixer::new("Copyright (c) Example Inc.");
+        assert!(fixer.fix_file(&path, Language::Python).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "#!/usr/bin/env python3\n# -*- coding: utf-8 -*-\n\n\
+             # Copyright (c) Example Inc.\n\nimport sys\n"
+        );
+
+        // Already has a header
+        assert!(!fixer.fix_file(&path, Language::Python).unwrap());
+    }
+
+    #[test]
+    fn test_rust_header_goes_on_top() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        fs::write(&path, "#![allow(dead_code)]\nfn main() {}\n").unwrap();
+
+        let fixer = CopyrightFixer::new("Copyright (c) Example Inc.\n\nMIT License");
+        assert!(fixer.fix_file(&path, Language::Rust).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "// Copyright (c) Example Inc.\n\n// MIT License\n\n\
+             #![allow(dead_code)]\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_block_style() {
+        let fixer = CopyrightFixer::new("Copyright {year}").with_style(
+            Language::Java,
+            CommentStyle::Block {
+                start: "/*".to_string(),
+                prefix: " * ".to_string(),
+                end: " */".to_string(),
+            },
+        );
+        let mut lines = vec!["package a;".to_string()];
+        assert!(fixer.insert_header(&mut lines, Language::Java));
+        assert_eq!(lines[0], "/*");
+        assert!(lines[1].starts_with(" * Copyright 2"));
+        assert_eq!(lines[2], " */");
+        assert_eq!(lines[3], "");
+        assert_eq!(lines[4], "package a;");
+    }
+
+    #[test]
+    fn test_commented_template_is_kept_as_is() {
+        let template = "/*\n * Copyright (c) Example Inc.\n */";
+        let fixer = CopyrightFixer::new(template);
+        assert_eq!(
+            fixer.header_lines(Language::Java),
+            vec!["/*", " * Copyright (c) Example Inc.", " */"]
+        );
+    }
+}
@@ -16,7 +16,6 @@
 //! - `legal/copyright`: Inserts copyright header
 
 use std::env;
-use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -25,6 +24,10 @@ use std::sync::Mutex;
 
 use regex::Regex;
 
+use crate::fixers::copyright::CopyrightFixer;
+use crate::utils::bom::{read_source, write_source, BomPolicy};
+use crate::Language;
+
 // Installation state: 0 = not checked, 1 = installing, 2 = installed, 3 = failed
 static CPPLINT_INSTALL_STATE: AtomicU8 = AtomicU8::new(0);
 static INSTALL_LOCK: Mutex<()> = Mutex::new(());
@@ -75,6 +78,7 @@ pub struct CpplintFixer {
     cached_username: Option<String>,
     /// Whether the current file is Objective-C (skip unsafe fixes)
     is_objc: bool,
+    bom: BomPolicy,
 }
 
 impl CpplintFixer {
@@ -83,6 +87,7 @@ impl CpplintFixer {
             config: CpplintFixerConfig::default(),
             cached_username: None,
             is_objc: false,
+            bom: BomPolicy::default(),
         }
     }
 
@@ -91,9 +96,15 @@ impl CpplintFixer {
             config,
             cached_username: None,
             is_objc: false,
+            bom: BomPolicy::default(),
         }
     }
 
+    /// Keep or drop the BOM of files this fixer rewrites
+    pub fn set_bom_policy(&mut self, bom: BomPolicy) {
+        self.bom = bom;
+    }
+
     /// Set whether the current file is Objective-C
     /// This will skip unsafe fix categories (like readability/casting)
     pub fn set_is_objc(&mut self, is_objc: bool) {
@@ -140,7 +151,7 @@ impl CpplintFixer {
 
             // Run pip install with progress output
             let mut child = match Command::new(pip_cmd)
-                .args(&["install", "cpplint", "--upgrade"])
+                .args(["install", "cpplint", "--upgrade"])
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
                 .spawn()
@@ -155,7 +166,7 @@ impl CpplintFixer {
             // Read and display output
             if let Some(stderr) = child.stderr.take() {
                 let reader = BufReader::new(stderr);
-                for line in reader.lines().flatten() {
+                for line in reader.lines().map_while(|l| l.ok()) {
                     // Filter and display relevant progress information
                     if line.contains("Collecting")
                         || line.contains("Downloading")
@@ -206,14 +217,13 @@ impl CpplintFixer {
             let state = CPPLINT_INSTALL_STATE.load(Ordering::SeqCst);
 
             match state {
+                // First time detection - try to auto-install
+                0 if Self::try_install_cpplint() => {
+                    // Installation successful, continue
+                }
                 0 => {
-                    // First time detection - try to auto-install
-                    if Self::try_install_cpplint() {
-                        // Installation successful, continue
-                    } else {
-                        // Installation failed, skip cpplint
-                        return Vec::new();
-                    }
+                    // Installation failed, skip cpplint
+                    return Vec::new();
                 }
                 1 => {
                     // Installation in progress (another thread), skip for now
@@ -373,8 +383,8 @@ impl CpplintFixer {
             );
         }
 
-        let content =
-            fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let (content, had_bom) =
+            read_source(path).map_err(|e| format!("Failed to read file: {}", e))?;
 
         let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
         let mut modified = false;
@@ -398,10 +408,10 @@ impl CpplintFixer {
                             }
                             modified = true;
                         }
-                    } else if self.config.header_guard_mode == HeaderGuardMode::PragmaOnce {
-                        if self.convert_to_pragma_once(&mut lines) {
-                            modified = true;
-                        }
+                    } else if self.config.header_guard_mode == HeaderGuardMode::PragmaOnce
+                        && self.convert_to_pragma_once(&mut lines)
+                    {
+                        modified = true;
                     }
                 }
                 "readability/todo" => {
@@ -492,7 +502,8 @@ impl CpplintFixer {
 
         if modified {
             let new_content = lines.join("\n") + if content.ends_with('\n') { "\n" } else { "" };
-            fs::write(path, new_content).map_err(|e| format!("Failed to write file: {}", e))?;
+            write_source(path, &new_content, had_bom, self.bom)
+                .map_err(|e| format!("Failed to write file: {}", e))?;
         }
 
         Ok(modified)
@@ -631,7 +642,7 @@ impl CpplintFixer {
 
         // Add #endif at the end
         // Ensure there's an empty line before #endif
-        if !lines.last().map_or(true, |l| l.trim().is_empty()) {
+        if !lines.last().is_none_or(|l| l.trim().is_empty()) {
             lines.push(String::new());
         }
         lines.push(format!("#endif  // {}", guard_name));
@@ -726,37 +737,15 @@ impl CpplintFixer {
 
     /// Fix copyright based on cpplint error
     fn fix_copyright_from_error(&self, lines: &mut Vec<String>) -> bool {
-        // Check if copyright already exists
-        let first_lines: String = lines
-            .iter()
-            .take(10)
-            .cloned()
-            .collect::<Vec<_>>()
-            .join("\n");
-        if first_lines.to_lowercase().contains("copyright") {
+        let Some(template) = &self.config.copyright_template else {
             return false;
-        }
-
-        // Get copyright template
-        let template = match &self.config.copyright_template {
-            Some(t) => t.clone(),
-            None => return false,
         };
-
-        // Replace {year} with current year
-        let year = chrono::Utc::now().format("%Y").to_string();
-        let copyright = template.replace("{year}", &year);
-
-        // Insert at the beginning
-        let copyright_lines: Vec<String> = copyright.lines().map(|s| s.to_string()).collect();
-
-        // Insert copyright lines at the beginning
-        for (i, cline) in copyright_lines.into_iter().enumerate() {
-            lines.insert(i, cline);
-        }
-        lines.insert(copyright.lines().count(), String::new()); // Add empty line after copyright
-
-        true
+        let language = if self.is_objc {
+            Language::ObjectiveC
+        } else {
+            Language::Cpp
+        };
+        CopyrightFixer::new(template.as_str()).insert_header(lines, language)
     }
 
     /// Fix C-style cast to C++ style cast
@@ -854,9 +843,7 @@ impl CpplintFixer {
         loop {
             // Find next // starting from search_start
             let rest = &line[search_start..];
-            let Some(rel_pos) = rest.find("//") else {
-                return None;
-            };
+            let rel_pos = rest.find("//")?;
 
             let abs_pos = search_start + rel_pos;
             let before_comment = &line[..abs_pos];
@@ -963,7 +950,7 @@ impl CpplintFixer {
 
         // Find the position of the lone semicolon (only whitespace before it)
         // Pattern: start with whitespace, then a semicolon, optionally followed by comment or whitespace
-        if let Some(re) = Regex::new(r"^(\s*);(\s*(?://.*)?)?$").ok() {
+        if let Ok(re) = Regex::new(r"^(\s*);(\s*(?://.*)?)?$") {
             if let Some(caps) = re.captures(line) {
                 let indent = caps.get(1).map(|m| m.as_str()).unwrap_or("");
                 let suffix = caps.get(2).map(|m| m.as_str()).unwrap_or("");
@@ -1038,7 +1025,7 @@ impl CpplintFixer {
         // Use regex to find = without proper spacing
         // Match: not preceded by space + = + not followed by space or =
         // But avoid ==, !=, <=, >=, +=, -=, etc.
-        if let Some(re) = Regex::new(r"([^\s=!<>+\-*/%&|^])=([^=\s])").ok() {
+        if let Ok(re) = Regex::new(r"([^\s=!<>+\-*/%&|^])=([^=\s])") {
             let result = re.replace_all(line, "$1 = $2").to_string();
             if result != *line {
                 lines[line_idx] = result;
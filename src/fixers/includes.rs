@@ -0,0 +1,421 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Include ordering for C/C++ files.
+//!
+//! An include block is a run of `#include` lines, possibly separated by
+//! blank lines. Each block is grouped by category (own header, C system,
+//! C++ system, other libraries, project) in the policy's order, sorted
+//! within each group, with a blank line between groups. Includes never move
+//! across other lines such as `#ifdef`, so conditional includes stay put.
+//!
+//! `[builtin_checks] include_order = true` reports misordered blocks; with
+//! `organize_imports` the blocks are reordered before formatting.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::formatters::Formatter;
+use crate::utils::bom::{read_source, write_source, BomPolicy};
+use crate::utils::types::FormatResult;
+use crate::{Language, LintisError, Result};
+
+/// Kind of included header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IncludeCategory {
+    /// The header of the file being compiled (`foo.cc` including `foo.h`)
+    OwnHeader,
+    /// C standard and POSIX headers (`<stdio.h>`, `<sys/types.h>`)
+    CSystem,
+    /// C++ standard headers (`<vector>`, `<cstdio>`)
+    CppSystem,
+    /// Other `<...>` headers (`<gtest/gtest.h>`)
+    OtherLibrary,
+    /// `"..."` headers of the project
+    Project,
+}
+
+impl IncludeCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncludeCategory::OwnHeader => "own header",
+            IncludeCategory::CSystem => "C system",
+            IncludeCategory::CppSystem => "C++ system",
+            IncludeCategory::OtherLibrary => "other libraries",
+            IncludeCategory::Project => "project",
+        }
+    }
+}
+
+/// Default group order (Google C++ style)
+pub const DEFAULT_INCLUDE_ORDER: [IncludeCategory; 5] = [
+    IncludeCategory::OwnHeader,
+    IncludeCategory::CSystem,
+    IncludeCategory::CppSystem,
+    IncludeCategory::OtherLibrary,
+    IncludeCategory::Project,
+];
+
+/// C standard library and common POSIX headers
+const C_SYSTEM_HEADERS: &[&str] = &[
+    "assert.h",
+    "complex.h",
+    "ctype.h",
+    "errno.h",
+    "fenv.h",
+    "float.h",
+    "inttypes.h",
+    "iso646.h",
+    "limits.h",
+    "locale.h",
+    "math.h",
+    "setjmp.h",
+    "signal.h",
+    "stdalign.h",
+    "stdarg.h",
+    "stdatomic.h",
+    "stdbool.h",
+    "stddef.h",
+    "stdint.h",
+    "stdio.h",
+    "stdlib.h",
+    "stdnoreturn.h",
+    "string.h",
+    "tgmath.h",
+    "threads.h",
+    "time.h",
+    "uchar.h",
+    "wchar.h",
+    "wctype.h",
+    "dirent.h",
+    "dlfcn.h",
+    "fcntl.h",
+    "glob.h",
+    "grp.h",
+    "netdb.h",
+    "poll.h",
+    "pthread.h",
+    "pwd.h",
+    "regex.h",
+    "sched.h",
+    "semaphore.h",
+    "spawn.h",
+    "strings.h",
+    "syslog.h",
+    "termios.h",
+    "unistd.h",
+    "utime.h",
+];
+
+/// Directories of POSIX/OS headers (`<sys/types.h>`, `<netinet/in.h>`)
+const C_SYSTEM_DIRS: &[&str] = &["sys/", "netinet/", "arpa/", "net/"];
+
+/// Header file extensions
+const HEADER_EXTENSIONS: &[&str] = &["h", "hh", "hpp", "hxx"];
+
+fn include_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"^\s*#\s*include\s*([<"])([^>"]+)[>"]"#).unwrap())
+}
+
+/// Category of `#include <header>` (`angled`) or `#include "header"` in `path`
+pub fn categorize(path: &Path, header: &str, angled: bool) -> IncludeCategory {
+    let header_path = Path::new(header);
+    let is_header = |p: &Path| {
+        p.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| HEADER_EXTENSIONS.contains(&e))
+    };
+    if !is_header(path)
+        && is_header(header_path)
+        && header_path.file_stem().is_some()
+        && header_path.file_stem() == path.file_stem()
+    {
+        return IncludeCategory::OwnHeader;
+    }
+    if !angled {
+        return IncludeCategory::Project;
+    }
+    if C_SYSTEM_HEADERS.contains(&header) || C_SYSTEM_DIRS.iter().any(|d| header.starts_with(d)) {
+        IncludeCategory::CSystem
+    } else if header_path.extension().is_none() {
+        IncludeCategory::CppSystem
+    } else {
+        IncludeCategory::OtherLibrary
+    }
+}
+
+/// Reports and fixes include ordering in C/C++ files
+#[derive(Debug, Clone)]
+pub struct IncludeOrderFixer {
+    order: Vec<IncludeCategory>,
+    bom: BomPolicy,
+}
+
+impl Default for IncludeOrderFixer {
+    fn default() -> Self {
+        Self::new(DEFAULT_INCLUDE_ORDER.to_vec())
+    }
+}
+
+impl IncludeOrderFixer {
+    /// Group includes in this order; categories left out go last.
+    pub fn new(order: Vec<IncludeCategory>) -> Self {
+        Self {
+            order,
+            bom: BomPolicy::default(),
+        }
+    }
+
+    /// Keep or drop the BOM of files this fixer rewrites
+    pub fn with_bom_policy(mut self, bom: BomPolicy) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    fn rank(&self, category: IncludeCategory) -> usize {
+        self.order
+            .iter()
+            .position(|c| *c == category)
+            .unwrap_or(self.order.len())
+    }
+
+    /// Include blocks as (first line index, line count), blank lines
+    /// inside a block included
+    fn blocks(lines: &[&str]) -> Vec<(usize, usize)> {
+        let mut blocks = Vec::new();
+        let mut idx = 0;
+        while idx < lines.len() {
+            if !include_regex().is_match(lines[idx]) {
+                idx += 1;
+                continue;
+            }
+            let start = idx;
+            let mut end = idx + 1;
+            let mut scan = end;
+            while scan < lines.len() {
+                if include_regex().is_match(lines[scan]) {
+                    end = scan + 1;
+                } else if !lines[scan].trim().is_empty() {
+                    break;
+                }
+                scan += 1;
+            }
+            blocks.push((start, end - start));
+            idx = end;
+        }
+        blocks
+    }
+
+    /// A block's lines in policy order, groups separated by blank lines
+    fn ordered_block(&self, path: &Path, block: &[&str]) -> Vec<String> {
+        let mut includes: Vec<(usize, &str, &str)> = block
+            .iter()
+            .filter_map(|line| {
+                let caps = include_regex().captures(line)?;
+                let header = caps.get(2)?.as_str();
+                let category = categorize(path, header, &caps[1] == "<");
+                Some((self.rank(category), header, *line))
+            })
+            .collect();
+        includes.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+        let mut ordered = Vec::new();
+        let mut group = None;
+        for (rank, _, line) in includes {
+            if group.is_some_and(|g| g != rank) {
+                ordered.push(String::new());
+            }
+            group = Some(rank);
+            ordered.push(line.to_string());
+        }
+        ordered
+    }
+
+    /// Lines (1-indexed) starting include blocks that are out of order
+    pub fn misordered_lines(&self, path: &Path, content: &str) -> Vec<usize> {
+        let lines: Vec<&str> = content.lines().collect();
+        Self::blocks(&lines)
+            .into_iter()
+            .filter(|&(start, len)| {
+                let block = &lines[start..start + len];
+                self.ordered_block(path, block) != block
+            })
+            .map(|(start, _)| start + 1)
+            .collect()
+    }
+
+    /// Content with every include block in policy order
+    pub fn reorder(&self, path: &Path, content: &str) -> String {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut result: Vec<String> = Vec::with_capacity(lines.len());
+        let mut next = 0;
+        for (start, len) in Self::blocks(&lines) {
+            result.extend(lines[next..start].iter().map(|l| l.to_string()));
+            result.extend(self.ordered_block(path, &lines[start..start + len]));
+            next = start + len;
+        }
+        result.extend(lines[next..].iter().map(|l| l.to_string()));
+
+        let mut output = result.join("\n");
+        if content.ends_with('\n') {
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Reorder the includes of a file. Returns whether it changed.
+    pub fn fix_file(&self, path: &Path) -> Result<bool> {
+        let (content, had_bom) = read_source(path)
+            .map_err(|e| LintisError::Formatter(format!("Failed to read file: {}", e)))?;
+        let reordered = self.reorder(path, &content);
+        if reordered == content {
+            return Ok(false);
+        }
+        write_source(path, &reordered, had_bom, self.bom)
+            .map_err(|e| LintisError::Formatter(format!("Failed to write file: {}", e)))?;
+        Ok(true)
+    }
+}
+
+impl Formatter for IncludeOrderFixer {
+    fn name(&self) -> &str {
+        "include-order"
+    }
+
+    fn supported_languages(&self) -> &[Language] {
+        &[Language::Cpp, Language::ObjectiveC]
+    }
+
+    fn format(&self, path: &Path) -> Result<FormatResult> {
+        if self.fix_file(path)? {
+            Ok(FormatResult::changed(path.to_path_buf()))
+        } else {
+            Ok(FormatResult::unchanged(path.to_path_buf()))
+        }
+    }
+
+    fn check(&self, path: &Path) -> Result<bool> {
+        let (content, _) = read_source(path)
+            .map_err(|e| LintisError::Formatter(format!("Failed to read file: {}", e)))?;
+        Ok(!self.misordered_lines(path, &content).is_empty())
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn binary(&self) -> PathBuf {
+        PathBuf::from("linthis")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categorize() {
+        let path = Path::new("src/foo/bar.cc");
+        assert_eq!(
+            categorize(path, "foo/bar.h", false),
+            IncludeCategory::OwnHeader
+        );
+        assert_eq!(categorize(path, "stdio.h", true), IncludeCategory::CSystem);
+        assert_eq!(
+            categorize(path, "sys/types.h", true),
+            IncludeCategory::CSystem
+        );
+        assert_eq!(categorize(path, "vector", true), IncludeCategory::CppSystem);
+        assert_eq!(
+            categorize(path, "gtest/gtest.h", true),
+            IncludeCategory::OtherLibrary
+        );
+        assert_eq!(
+            categorize(path, "foo/util.h", false),
+            IncludeCategory::Project
+        );
+        // A header has no own header
+        assert_eq!(
+            categorize(Path::new("bar.h"), "bar.h", false),
+            IncludeCategory::Project
+        );
+    }
+
+    #[test]
+    fn test_reorder_scrambled_include_block() {
+        let path = Path::new("src/widget.cc");
+        let content = "\
+// Copyright
+#include \"base/logging.h\"
+#include <vector>
+#include <gtest/gtest.h>
+
+#include <stdio.h>
+#include \"src/widget.h\"
+#include <string>
+#include \"base/arena.h\"
+
+namespace app {
+#ifdef DEBUG
+#include <assert.h>
+#endif
+}  // namespace app
+";
+        let fixer = IncludeOrderFixer::default();
+        assert_eq!(fixer.misordered_lines(path, content), vec![2]);
+
+        let reordered = fixer.reorder(path, content);
+        assert_eq!(
+            reordered,
+            "\
+// Copyright
+#include \"src/widget.h\"
+
+#include <stdio.h>
+
+#include <string>
+#include <vector>
+
+#include <gtest/gtest.h>
+
+#include \"base/arena.h\"
+#include \"base/logging.h\"
+
+namespace app {
+#ifdef DEBUG
+#include <assert.h>
+#endif
+}  // namespace app
+"
+        );
+        assert!(fixer.misordered_lines(path, &reordered).is_empty());
+        assert_eq!(fixer.reorder(path, &reordered), reordered);
+    }
+
+    #[test]
+    fn test_custom_order() {
+        let path = Path::new("main.cc");
+        let fixer =
+            IncludeOrderFixer::new(vec![IncludeCategory::Project, IncludeCategory::CppSystem]);
+        let reordered = fixer.reorder(
+            path,
+            "#include <map>\n#include <stdlib.h>\n#include \"app.h\"\n",
+        );
+        assert_eq!(
+            reordered,
+            "#include \"app.h\"\n\n#include <map>\n\n#include <stdlib.h>\n"
+        );
+    }
+}
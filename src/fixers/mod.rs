@@ -10,8 +10,14 @@
 
 //! Auto-fixers for various linting issues.
 
+pub mod copyright;
 pub mod cpplint;
+pub mod includes;
 pub mod source;
+pub mod whitespace;
 
+pub use copyright::{CommentStyle, CopyrightFixer, HeaderPlacement};
 pub use cpplint::CpplintFixer;
+pub use includes::{IncludeCategory, IncludeOrderFixer};
 pub use source::SourceFixer;
+pub use whitespace::WhitespaceFixer;
@@ -11,9 +11,9 @@
 //! Source code fixers for cpplint issues (C/C++/Objective-C).
 //! These fixers handle issues that clang-format doesn't fix.
 
+use crate::utils::bom::{read_source, write_source, BomPolicy};
 use crate::utils::unicode::{break_text_at_width, get_column_width};
 use crate::Result;
-use std::fs;
 use std::path::Path;
 use std::process::Command;
 
@@ -25,8 +25,8 @@ impl SourceFixer {
     /// clang-format doesn't fix non-ASCII (e.g., Chinese) comments
     /// NOTE: This only modifies actual comments, not `//` inside string literals
     /// Uses the same detection logic as cpplint's IsCppString function
-    pub fn fix_comment_spacing(path: &Path) -> Result<()> {
-        let content = fs::read_to_string(path)
+    pub fn fix_comment_spacing(path: &Path, bom: BomPolicy) -> Result<()> {
+        let (content, had_bom) = read_source(path)
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         let mut modified = false;
@@ -46,7 +46,7 @@ impl SourceFixer {
             if content.ends_with('\n') {
                 result.push('\n');
             }
-            fs::write(path, result).map_err(|e| {
+            write_source(path, &result, had_bom, bom).map_err(|e| {
                 crate::LintisError::Formatter(format!("Failed to write file: {}", e))
             })?;
         }
@@ -92,9 +92,7 @@ impl SourceFixer {
         loop {
             // Find next // starting from search_start
             let rest = &line[search_start..];
-            let Some(rel_pos) = rest.find("//") else {
-                return None;
-            };
+            let rel_pos = rest.find("//")?;
 
             let abs_pos = search_start + rel_pos;
             let before_comment = &line[..abs_pos];
@@ -129,8 +127,8 @@ impl SourceFixer {
 
     /// Fix TODO comments using git blame for author
     /// Converts "TODO:" or "TODO(user):" to "TODO(blame_author):"
-    pub fn fix_todo_comments(path: &Path) -> Result<()> {
-        let content = fs::read_to_string(path)
+    pub fn fix_todo_comments(path: &Path, bom: BomPolicy) -> Result<()> {
+        let (content, had_bom) = read_source(path)
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         let mut result = Vec::new();
@@ -189,7 +187,7 @@ impl SourceFixer {
             } else {
                 new_content
             };
-            fs::write(path, final_content).map_err(|e| {
+            write_source(path, &final_content, had_bom, bom).map_err(|e| {
                 crate::LintisError::Formatter(format!("Failed to write file: {}", e))
             })?;
         }
@@ -217,8 +215,8 @@ impl SourceFixer {
 
         let stdout = String::from_utf8_lossy(&output.stdout);
         for line in stdout.lines() {
-            if line.starts_with("author ") {
-                return Some(line[7..].to_string());
+            if let Some(author) = line.strip_prefix("author ") {
+                return Some(author.to_string());
             }
         }
 
@@ -245,8 +243,8 @@ impl SourceFixer {
 
     /// Fix lone semicolons: remove lines that contain only whitespace and a semicolon
     /// cpplint warns: "Line contains only semicolon."
-    pub fn fix_lone_semicolon(path: &Path) -> Result<()> {
-        let content = fs::read_to_string(path)
+    pub fn fix_lone_semicolon(path: &Path, bom: BomPolicy) -> Result<()> {
+        let (content, had_bom) = read_source(path)
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         let mut result = String::with_capacity(content.len());
@@ -270,7 +268,7 @@ impl SourceFixer {
 
         // Only write if changed
         if modified {
-            fs::write(path, result).map_err(|e| {
+            write_source(path, &result, had_bom, bom).map_err(|e| {
                 crate::LintisError::Formatter(format!("Failed to write file: {}", e))
             })?;
         }
@@ -280,8 +278,8 @@ impl SourceFixer {
 
     /// Fix long comment lines by breaking them at appropriate points
     /// Handles Chinese comments which clang-format can't reflow properly
-    pub fn fix_long_comments(path: &Path, max_length: usize) -> Result<()> {
-        let content = fs::read_to_string(path)
+    pub fn fix_long_comments(path: &Path, max_length: usize, bom: BomPolicy) -> Result<()> {
+        let (content, had_bom) = read_source(path)
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         let mut result = String::with_capacity(content.len());
@@ -313,7 +311,7 @@ impl SourceFixer {
         }
 
         if modified {
-            fs::write(path, result).map_err(|e| {
+            write_source(path, &result, had_bom, bom).map_err(|e| {
                 crate::LintisError::Formatter(format!("Failed to write file: {}", e))
             })?;
         }
@@ -426,8 +424,8 @@ impl SourceFixer {
 
     /// Fix pragma separator lines: convert "-- -- --" style to standard "#pragma mark -" format
     /// cpplint warns about "Extra space for operator --" but these are visual separators
-    pub fn fix_pragma_separators(path: &Path) -> Result<()> {
-        let content = fs::read_to_string(path)
+    pub fn fix_pragma_separators(path: &Path, bom: BomPolicy) -> Result<()> {
+        let (content, had_bom) = read_source(path)
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         let mut result = String::with_capacity(content.len());
@@ -491,7 +489,7 @@ impl SourceFixer {
         }
 
         if modified {
-            fs::write(path, result).map_err(|e| {
+            write_source(path, &result, had_bom, bom).map_err(|e| {
                 crate::LintisError::Formatter(format!("Failed to write file: {}", e))
             })?;
         }
@@ -503,6 +501,7 @@ impl SourceFixer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -521,35 +520,35 @@ mod tests {
     #[test]
     fn test_fix_comment_spacing_basic() {
         let file = create_temp_file("//comment\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "// comment\n");
     }
 
     #[test]
     fn test_fix_comment_spacing_already_has_space() {
         let file = create_temp_file("// already spaced\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "// already spaced\n");
     }
 
     #[test]
     fn test_fix_comment_spacing_triple_slash() {
         let file = create_temp_file("///doc comment\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "/// doc comment\n");
     }
 
     #[test]
     fn test_fix_comment_spacing_chinese() {
         let file = create_temp_file("//中文注释\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "// 中文注释\n");
     }
 
     #[test]
     fn test_fix_comment_spacing_empty_comment() {
         let file = create_temp_file("//\ncode();\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "//\ncode();\n");
     }
 
@@ -557,7 +556,7 @@ mod tests {
     fn test_fix_comment_spacing_preserves_url() {
         // URLs like https:// should NOT be modified
         let file = create_temp_file("return @\"https://example.com\";\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "return @\"https://example.com\";\n");
     }
 
@@ -568,7 +567,7 @@ NSString *url2 = @"http://example.com/path";
 NSString *url3 = @"file:///local/path";
 "#;
         let file = create_temp_file(content);
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), content);
     }
 
@@ -576,7 +575,7 @@ NSString *url3 = @"file:///local/path";
     fn test_fix_comment_spacing_url_and_comment() {
         // Should preserve URL but fix comment
         let file = create_temp_file("NSString *url = @\"https://example.com\"; //comment\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(
             read_temp_file(&file),
             "NSString *url = @\"https://example.com\"; // comment\n"
@@ -587,7 +586,7 @@ NSString *url3 = @"file:///local/path";
     fn test_fix_comment_spacing_string_with_slashes() {
         // // inside a string should NOT be modified
         let file = create_temp_file("char *path = \"path//to//file\";\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "char *path = \"path//to//file\";\n");
     }
 
@@ -595,7 +594,7 @@ NSString *url3 = @"file:///local/path";
     fn test_fix_comment_spacing_escaped_quote() {
         // Handle escaped quotes correctly
         let file = create_temp_file("char *s = \"he said \\\"hello//world\\\"\";\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(
             read_temp_file(&file),
             "char *s = \"he said \\\"hello//world\\\"\";\n"
@@ -606,7 +605,7 @@ NSString *url3 = @"file:///local/path";
     fn test_fix_comment_spacing_char_literal() {
         // Don't get confused by single quotes
         let file = create_temp_file("char c = '/'; //comment\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "char c = '/'; // comment\n");
     }
 
@@ -614,7 +613,7 @@ NSString *url3 = @"file:///local/path";
     fn test_fix_comment_spacing_quote_in_char_literal() {
         // '"' should not affect string detection
         let file = create_temp_file("char c = '\"'; //comment\n");
-        SourceFixer::fix_comment_spacing(file.path()).unwrap();
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "char c = '\"'; // comment\n");
     }
 
@@ -636,21 +635,21 @@ NSString *url3 = @"file:///local/path";
     #[test]
     fn test_fix_lone_semicolon_removes() {
         let file = create_temp_file("code();\n;\nmore();\n");
-        SourceFixer::fix_lone_semicolon(file.path()).unwrap();
+        SourceFixer::fix_lone_semicolon(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "code();\nmore();\n");
     }
 
     #[test]
     fn test_fix_lone_semicolon_with_whitespace() {
         let file = create_temp_file("code();\n   ;   \nmore();\n");
-        SourceFixer::fix_lone_semicolon(file.path()).unwrap();
+        SourceFixer::fix_lone_semicolon(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "code();\nmore();\n");
     }
 
     #[test]
     fn test_fix_lone_semicolon_keeps_valid() {
         let file = create_temp_file("for(;;) {}\nwhile(1);\n");
-        SourceFixer::fix_lone_semicolon(file.path()).unwrap();
+        SourceFixer::fix_lone_semicolon(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "for(;;) {}\nwhile(1);\n");
     }
 
@@ -659,7 +658,7 @@ NSString *url3 = @"file:///local/path";
     #[test]
     fn test_fix_long_comments_short_line() {
         let file = create_temp_file("// short comment\n");
-        SourceFixer::fix_long_comments(file.path(), 120).unwrap();
+        SourceFixer::fix_long_comments(file.path(), 120, BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "// short comment\n");
     }
 
@@ -668,7 +667,7 @@ NSString *url3 = @"file:///local/path";
         // Create a comment that exceeds 80 chars
         let long_comment = format!("// {}\n", "x".repeat(100));
         let file = create_temp_file(&long_comment);
-        SourceFixer::fix_long_comments(file.path(), 80).unwrap();
+        SourceFixer::fix_long_comments(file.path(), 80, BomPolicy::Preserve).unwrap();
         let result = read_temp_file(&file);
         // Should be broken into multiple lines
         assert!(result.lines().count() > 1);
@@ -680,7 +679,7 @@ NSString *url3 = @"file:///local/path";
         // 50 Chinese chars = 100 columns
         let chinese_comment = format!("// {}\n", "中".repeat(50));
         let file = create_temp_file(&chinese_comment);
-        SourceFixer::fix_long_comments(file.path(), 80).unwrap();
+        SourceFixer::fix_long_comments(file.path(), 80, BomPolicy::Preserve).unwrap();
         let result = read_temp_file(&file);
         // Should be broken due to column width exceeding 80
         assert!(result.lines().count() > 1 || result.lines().any(|l| l.len() < 100));
@@ -691,7 +690,7 @@ NSString *url3 = @"file:///local/path";
     #[test]
     fn test_fix_pragma_mark_separator() {
         let file = create_temp_file("#pragma mark - - - -\n");
-        SourceFixer::fix_pragma_separators(file.path()).unwrap();
+        SourceFixer::fix_pragma_separators(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "#pragma mark -\n");
     }
 
@@ -699,14 +698,28 @@ NSString *url3 = @"file:///local/path";
     fn test_fix_pragma_with_section_name() {
         // Test non-mark pragma with separator pattern
         let file = create_temp_file("#pragma webview delegate-- -- --\n");
-        SourceFixer::fix_pragma_separators(file.path()).unwrap();
+        SourceFixer::fix_pragma_separators(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "#pragma mark - webview delegate\n");
     }
 
     #[test]
     fn test_fix_pragma_keeps_normal() {
         let file = create_temp_file("#pragma mark - Normal Section\n");
-        SourceFixer::fix_pragma_separators(file.path()).unwrap();
+        SourceFixer::fix_pragma_separators(file.path(), BomPolicy::Preserve).unwrap();
         assert_eq!(read_temp_file(&file), "#pragma mark - Normal Section\n");
     }
+
+    // ==================== BOM handling tests ====================
+
+    #[test]
+    fn test_fixers_keep_bom_on_first_line() {
+        let file = create_temp_file("\u{feff}//comment\nint a;\n");
+        SourceFixer::fix_comment_spacing(file.path(), BomPolicy::Preserve).unwrap();
+        assert_eq!(read_temp_file(&file), "\u{feff}// comment\nint a;\n");
+
+        // The BOM is not part of the first line, so line-start matches work
+        let file = create_temp_file("\u{feff}#pragma mark - - - -\n");
+        SourceFixer::fix_pragma_separators(file.path(), BomPolicy::Preserve).unwrap();
+        assert_eq!(read_temp_file(&file), "\u{feff}#pragma mark -\n");
+    }
 }
@@ -0,0 +1,94 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Whitespace fixer for the built-in `final-newline` check.
+//!
+//! Files end with exactly one newline: a missing one is added and trailing
+//! blank lines are dropped. Files using `\r\n` keep that line ending.
+
+use std::path::Path;
+
+use crate::utils::bom::{read_source, write_source, BomPolicy};
+use crate::{LintisError, Result};
+
+/// Fixes what the built-in whitespace checks report
+#[derive(Debug, Clone, Default)]
+pub struct WhitespaceFixer {
+    bom: BomPolicy,
+}
+
+impl WhitespaceFixer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep or drop the BOM of files this fixer rewrites
+    pub fn with_bom_policy(mut self, bom: BomPolicy) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Content ending with exactly one newline. Empty and blank-only
+    /// content is left as it is.
+    pub fn fix_final_newline(&self, content: &str) -> String {
+        let body = content.trim_end_matches(['\n', '\r']);
+        if body.trim().is_empty() {
+            return content.to_string();
+        }
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        format!("{}{}", body, newline)
+    }
+
+    /// Fix the end of a file. Returns whether it changed.
+    pub fn fix_file(&self, path: &Path) -> Result<bool> {
+        let (content, had_bom) = read_source(path)
+            .map_err(|e| LintisError::Formatter(format!("Failed to read file: {}", e)))?;
+        let fixed = self.fix_final_newline(&content);
+        if fixed == content {
+            return Ok(false);
+        }
+        write_source(path, &fixed, had_bom, self.bom)
+            .map_err(|e| LintisError::Formatter(format!("Failed to write file: {}", e)))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_fix_final_newline() {
+        let fixer = WhitespaceFixer::new();
+        assert_eq!(fixer.fix_final_newline("x = 1"), "x = 1\n");
+        assert_eq!(fixer.fix_final_newline("x = 1\n\n\n"), "x = 1\n");
+        assert_eq!(fixer.fix_final_newline("x = 1\r\n\r\n"), "x = 1\r\n");
+        assert_eq!(fixer.fix_final_newline("x = 1\n"), "x = 1\n");
+        assert_eq!(fixer.fix_final_newline(""), "");
+        assert_eq!(fixer.fix_final_newline("\n\n"), "\n\n");
+    }
+
+    #[test]
+    fn test_fix_file_keeps_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.py");
+        fs::write(&path, "\u{feff}import os\n\n").unwrap();
+
+        let fixer = WhitespaceFixer::new();
+        assert!(fixer.fix_file(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\u{feff}import os\n");
+        assert!(!fixer.fix_file(&path).unwrap());
+    }
+}
@@ -13,12 +13,14 @@
 use crate::fixers::cpplint::{CpplintFixer, CpplintFixerConfig, HeaderGuardMode};
 use crate::fixers::source::SourceFixer;
 use crate::formatters::Formatter;
+use crate::utils::bom::BomPolicy;
+use crate::utils::clang::{ClangPin, ClangToolCache};
 use crate::utils::types::FormatResult;
 use crate::{Language, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 /// C/C++ formatter using clang-format, clang-tidy --fix, and cpplint fixer.
 pub struct CppFormatter {
@@ -30,6 +32,15 @@ pub struct CppFormatter {
     compile_commands_dir: Option<PathBuf>,
     /// Cpplint fixer instance (wrapped in Mutex for interior mutability)
     cpplint_fixer: Mutex<CpplintFixer>,
+    /// Ignore .clang-format/.clang-tidy files and use the built-in Google style
+    isolated: bool,
+    /// Keep or drop the BOM of files the fixers rewrite
+    bom: BomPolicy,
+    /// clang-format version pin from linthis config
+    clang_pin: Option<ClangPin>,
+    /// Resolved clang binaries and `-style=` arguments, possibly shared with
+    /// the rest of the run
+    clang: Arc<ClangToolCache>,
 }
 
 impl CppFormatter {
@@ -39,9 +50,39 @@ impl CppFormatter {
             use_cpplint_fix: true,    // Enable by default
             compile_commands_dir: None,
             cpplint_fixer: Mutex::new(CpplintFixer::new()),
+            isolated: false,
+            bom: BomPolicy::default(),
+            clang_pin: None,
+            clang: Arc::default(),
         }
     }
 
+    /// Pin the clang-format version (overrides `.clang-format-version`)
+    pub fn with_clang_pin(mut self, pin: Option<ClangPin>) -> Self {
+        self.clang_pin = pin;
+        self
+    }
+
+    /// Share resolved clang binaries, `-style=` arguments and printed
+    /// warnings with `cache`
+    pub fn with_clang_cache(mut self, cache: Arc<ClangToolCache>) -> Self {
+        self.clang = cache;
+        self
+    }
+
+    /// Keep or drop the BOM of files the fixers rewrite
+    pub fn with_bom_policy(mut self, bom: BomPolicy) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Ignore .clang-format and .clang-tidy files, formatting with the
+    /// built-in Google style
+    pub fn with_isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
     /// Enable or disable clang-tidy --fix
     pub fn with_clang_tidy_fix(mut self, enable: bool) -> Self {
         self.use_clang_tidy_fix = enable;
@@ -79,15 +120,20 @@ impl CppFormatter {
         self
     }
 
-    /// Check if clang-tidy is available
-    fn has_clang_tidy() -> bool {
-        Command::new("clang-tidy")
+    /// Check if clang-tidy (as resolved for the given path) is available
+    fn has_clang_tidy(&self, path: &Path) -> bool {
+        Command::new(self.clang_tool("clang-tidy", path))
             .arg("--version")
             .output()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 
+    /// The binary to run for a clang tool when processing the given path
+    fn clang_tool(&self, tool: &str, path: &Path) -> PathBuf {
+        self.clang.resolve(tool, self.clang_pin.as_ref(), path)
+    }
+
     /// Find .clang-tidy config file by walking up from file path
     fn find_clang_tidy_config(start_path: &Path) -> Option<PathBuf> {
         let mut current = if start_path.is_file() {
@@ -238,19 +284,55 @@ impl CppFormatter {
         None
     }
 
+    /// The .clang-format config for a file, unless configs are ignored
+    fn clang_format_config(&self, path: &Path, language: &str) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        Self::find_clang_format_config(path, language)
+    }
+
+    /// Build a clang-format invocation with the style for a file, without
+    /// the file argument
+    pub(crate) fn clang_format_command(&self, path: &Path, language: &str) -> Command {
+        let binary = self.clang_tool("clang-format", path);
+        let mut cmd = Command::new(&binary);
+
+        // Use language-specific config if found, otherwise fall back to Google style.
+        // Configs linthis generated are reduced to what the installed version
+        // supports; user configs are passed as they are.
+        match self.clang_format_config(path, language) {
+            Some(config_path) if Self::is_generated_config(&config_path) => {
+                cmd.arg(self.clang.generated_style_arg(&binary, &config_path))
+            }
+            Some(config_path) => cmd.arg(format!("-style=file:{}", config_path.display())),
+            None => cmd.arg("-style=Google"),
+        };
+        cmd
+    }
+
+    /// Whether a .clang-format was generated by linthis (under .linthis/configs)
+    fn is_generated_config(config_path: &Path) -> bool {
+        config_path
+            .ancestors()
+            .any(|dir| dir.ends_with(".linthis/configs"))
+    }
+
     /// Run clang-tidy --fix on a file
     fn run_clang_tidy_fix(&self, path: &Path) -> Result<bool> {
-        if !Self::has_clang_tidy() {
+        if !self.has_clang_tidy(path) {
             return Ok(false);
         }
 
-        let mut cmd = Command::new("clang-tidy");
+        let mut cmd = Command::new(self.clang_tool("clang-tidy", path));
         cmd.arg(path);
         cmd.arg("--fix");
         cmd.arg("--fix-errors"); // Also fix errors, not just warnings
 
-        // Add config file if found
-        if let Some(config) = Self::find_clang_tidy_config(path) {
+        // Add config file if found (an inline config stops the lookup)
+        if self.isolated {
+            cmd.arg("--config={}");
+        } else if let Some(config) = Self::find_clang_tidy_config(path) {
             cmd.arg(format!("--config-file={}", config.display()));
         }
 
@@ -280,8 +362,9 @@ impl Default for CppFormatter {
 
 impl Formatter for CppFormatter {
     fn name(&self) -> &str {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
         match (
-            self.use_clang_tidy_fix && Self::has_clang_tidy(),
+            self.use_clang_tidy_fix && self.has_clang_tidy(&cwd),
             self.use_cpplint_fix,
         ) {
             (true, true) => "clang-format + clang-tidy + cpplint-fix",
@@ -309,6 +392,7 @@ impl Formatter for CppFormatter {
         if self.use_cpplint_fix {
             if let Ok(mut fixer) = self.cpplint_fixer.lock() {
                 fixer.set_is_objc(language == "oc");
+                fixer.set_bom_policy(self.bom);
                 let _ = fixer.fix_file(path); // Ignore errors, continue with other fixes
             }
         }
@@ -320,19 +404,14 @@ impl Formatter for CppFormatter {
         }
 
         // Step 3: Run clang-format (-i modifies in place)
-        let mut cmd = Command::new("clang-format");
-        cmd.arg("-i");
-
-        // Use language-specific config if found, otherwise fall back to Google style
-        if let Some(config_path) = Self::find_clang_format_config(path, language) {
-            cmd.arg(format!("-style=file:{}", config_path.display()));
-        } else {
-            cmd.arg("-style=Google");
-        }
-
-        let output = cmd.arg(path).output().map_err(|e| {
-            crate::LintisError::Formatter(format!("Failed to run clang-format: {}", e))
-        })?;
+        let output = self
+            .clang_format_command(path, language)
+            .arg("-i")
+            .arg(path)
+            .output()
+            .map_err(|e| {
+                crate::LintisError::Formatter(format!("Failed to run clang-format: {}", e))
+            })?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -344,22 +423,22 @@ impl Formatter for CppFormatter {
 
         // Step 4: Fix comment spacing (clang-format doesn't fix non-ASCII comments like Chinese)
         // This fixes "//comment" -> "// comment" for all characters
-        SourceFixer::fix_comment_spacing(path)?;
+        SourceFixer::fix_comment_spacing(path, self.bom)?;
 
         // Step 5: Fix TODO comments (add username from git blame)
-        SourceFixer::fix_todo_comments(path)?;
+        SourceFixer::fix_todo_comments(path, self.bom)?;
 
         // Step 6: Fix lone semicolons (remove lines with only semicolon)
-        SourceFixer::fix_lone_semicolon(path)?;
+        SourceFixer::fix_lone_semicolon(path, self.bom)?;
 
         // Step 7: Fix long comment lines (break at appropriate points)
         // OC uses 150 char limit, C++ uses 120
         let max_line_length = if language == "oc" { 150 } else { 120 };
-        SourceFixer::fix_long_comments(path, max_line_length)?;
+        SourceFixer::fix_long_comments(path, max_line_length, self.bom)?;
 
         // Step 8: Fix pragma separators (OC only) - convert "-- -- --" to "#pragma mark -"
         if language == "oc" {
-            SourceFixer::fix_pragma_separators(path)?;
+            SourceFixer::fix_pragma_separators(path, self.bom)?;
         }
 
         // Read new content and compare
@@ -383,32 +462,52 @@ impl Formatter for CppFormatter {
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         // Run clang-format to get formatted output (without -i)
-        let mut cmd = Command::new("clang-format");
+        let output = self
+            .clang_format_command(path, language)
+            .arg(path)
+            .output()
+            .map_err(|e| {
+                crate::LintisError::Formatter(format!("Failed to run clang-format: {}", e))
+            })?;
 
-        // Use language-specific config if found, otherwise fall back to Google style
-        if let Some(config_path) = Self::find_clang_format_config(path, language) {
-            cmd.arg(format!("-style=file:{}", config_path.display()));
-        } else {
-            cmd.arg("-style=Google");
+        // A config clang-format rejects would otherwise look like a file
+        // that needs formatting
+        if !output.status.success() {
+            return Err(crate::LintisError::Formatter(format!(
+                "clang-format failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
         }
 
-        let output = cmd.arg(path).output().map_err(|e| {
-            crate::LintisError::Formatter(format!("Failed to run clang-format: {}", e))
-        })?;
-
         let formatted = String::from_utf8_lossy(&output.stdout);
 
         // If they differ, file needs formatting
         Ok(current != formatted.as_ref())
     }
 
+    fn supports_stdout(&self) -> bool {
+        // clang-format alone could print, but the cpplint, clang-tidy and
+        // source fixers rewrite the file in place
+        false
+    }
+
     fn is_available(&self) -> bool {
-        Command::new("clang-format")
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Command::new(self.clang_tool("clang-format", &cwd))
             .arg("--version")
             .output()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn binary(&self) -> PathBuf {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        self.clang_tool("clang-format", &cwd)
+    }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        self.clang_format_config(path, Self::detect_language(path))
+    }
 }
 
 impl CppFormatter {
@@ -515,7 +614,7 @@ impl CppFormatter {
             if bytes[i] == b'N' && bytes[i + 1] == b'S' {
                 let next_char = bytes[i + 2];
                 // Check if next char is uppercase A-Z (ASCII 65-90)
-                if (b'A'..=b'Z').contains(&next_char) {
+                if next_char.is_ascii_uppercase() {
                     // Make sure it's not part of a longer identifier before "NS"
                     // (i.e., NS should be at word boundary)
                     if i == 0 || !is_identifier_char(bytes[i - 1]) {
@@ -86,6 +86,11 @@ impl Formatter for GoFormatter {
         Ok(!stdout.trim().is_empty())
     }
 
+    fn supports_stdout(&self) -> bool {
+        // gofmt prints unless given -w
+        true
+    }
+
     fn is_available(&self) -> bool {
         Command::new("gofmt")
             .arg("-h")
@@ -93,4 +98,9 @@ impl Formatter for GoFormatter {
             .map(|_| true) // gofmt -h returns error but we just check if command exists
             .unwrap_or(false)
     }
+
+    fn tool_version(&self) -> Option<String> {
+        // gofmt has no version flag; it ships with (and matches) the go toolchain
+        crate::utils::tool_version(Path::new("go"), &["version"])
+    }
 }
@@ -0,0 +1,168 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Import organizers, run as a separate pass before the main formatter.
+//!
+//! Python imports are sorted with ruff's isort rules (`ruff check --select I
+//! --fix`), Go imports with `goimports`.
+
+use crate::formatters::Formatter;
+use crate::utils::types::FormatResult;
+use crate::{Language, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Sorts and groups imports without touching other formatting.
+pub struct ImportOrganizer {
+    language: Language,
+}
+
+impl ImportOrganizer {
+    /// The import organizer for a language, if it has one.
+    pub fn for_language(language: Language) -> Option<Self> {
+        matches!(language, Language::Python | Language::Go).then_some(Self { language })
+    }
+
+    /// Command rewriting the file's imports in place
+    fn fix_command(&self, path: &Path) -> Command {
+        let mut cmd = Command::new(self.binary());
+        match self.language {
+            Language::Python => {
+                // --exit-zero: remaining import issues aren't a failure here
+                cmd.args(["check", "--select", "I", "--fix", "--exit-zero", "--quiet"]);
+            }
+            _ => {
+                cmd.arg("-w");
+            }
+        }
+        cmd.arg(path);
+        cmd
+    }
+
+    /// Command reporting whether the file's imports need organizing
+    fn check_command(&self, path: &Path) -> Command {
+        let mut cmd = Command::new(self.binary());
+        match self.language {
+            Language::Python => {
+                cmd.args(["check", "--select", "I", "--quiet"]);
+            }
+            _ => {
+                cmd.arg("-l");
+            }
+        }
+        cmd.arg(path);
+        cmd
+    }
+}
+
+impl Formatter for ImportOrganizer {
+    fn name(&self) -> &str {
+        match self.language {
+            Language::Python => "ruff-isort",
+            _ => "goimports",
+        }
+    }
+
+    fn supported_languages(&self) -> &[Language] {
+        std::slice::from_ref(&self.language)
+    }
+
+    fn format(&self, path: &Path) -> Result<FormatResult> {
+        let original = fs::read_to_string(path)
+            .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
+
+        let output = self.fix_command(path).output().map_err(|e| {
+            crate::LintisError::Formatter(format!("Failed to run {}: {}", self.name(), e))
+        })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(FormatResult::error(
+                path.to_path_buf(),
+                format!("{} failed: {}", self.name(), stderr),
+            ));
+        }
+
+        let new_content = fs::read_to_string(path).map_err(|e| {
+            crate::LintisError::Formatter(format!("Failed to read formatted file: {}", e))
+        })?;
+        if original == new_content {
+            Ok(FormatResult::unchanged(path.to_path_buf()))
+        } else {
+            Ok(FormatResult::changed(path.to_path_buf()))
+        }
+    }
+
+    fn check(&self, path: &Path) -> Result<bool> {
+        let output = self.check_command(path).output().map_err(|e| {
+            crate::LintisError::Formatter(format!("Failed to run {}: {}", self.name(), e))
+        })?;
+        Ok(match self.language {
+            // ruff exits 1 when there are unsorted imports
+            Language::Python => !output.status.success(),
+            // goimports -l lists files whose imports would change
+            _ => !output.stdout.is_empty(),
+        })
+    }
+
+    fn supports_stdout(&self) -> bool {
+        // goimports prints unless given -w; ruff's isort fix only rewrites
+        self.language == Language::Go
+    }
+
+    fn is_available(&self) -> bool {
+        crate::utils::find_executable(&self.binary()).is_some()
+    }
+
+    fn binary(&self) -> PathBuf {
+        match self.language {
+            Language::Python => PathBuf::from("ruff"),
+            _ => PathBuf::from("goimports"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_python_and_go_have_organizers() {
+        assert!(ImportOrganizer::for_language(Language::Python).is_some());
+        assert!(ImportOrganizer::for_language(Language::Go).is_some());
+        assert!(ImportOrganizer::for_language(Language::Rust).is_none());
+    }
+
+    #[test]
+    fn test_fix_commands() {
+        let args = |lang| -> Vec<String> {
+            let organizer = ImportOrganizer::for_language(lang).unwrap();
+            let cmd = organizer.fix_command(Path::new("src/main"));
+            std::iter::once(cmd.get_program())
+                .chain(cmd.get_args())
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(
+            args(Language::Python),
+            [
+                "ruff",
+                "check",
+                "--select",
+                "I",
+                "--fix",
+                "--exit-zero",
+                "--quiet",
+                "src/main"
+            ]
+        );
+        assert_eq!(args(Language::Go), ["goimports", "-w", "src/main"]);
+    }
+}
@@ -14,7 +14,7 @@ use crate::formatters::Formatter;
 use crate::utils::types::FormatResult;
 use crate::{Language, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Java formatter using clang-format.
@@ -143,6 +143,11 @@ impl Formatter for JavaFormatter {
         Ok(current != formatted.as_ref())
     }
 
+    fn supports_stdout(&self) -> bool {
+        // clang-format prints unless given -i
+        true
+    }
+
     fn is_available(&self) -> bool {
         Command::new("clang-format")
             .arg("--version")
@@ -150,4 +155,8 @@ impl Formatter for JavaFormatter {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        Self::find_clang_format_config(path)
+    }
 }
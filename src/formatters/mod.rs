@@ -12,6 +12,7 @@
 
 pub mod cpp;
 pub mod go;
+pub mod imports;
 pub mod java;
 pub mod python;
 pub mod rust;
@@ -20,6 +21,7 @@ pub mod typescript;
 
 pub use cpp::CppFormatter;
 pub use go::GoFormatter;
+pub use imports::ImportOrganizer;
 pub use java::JavaFormatter;
 pub use python::PythonFormatter;
 pub use rust::RustFormatter;
@@ -17,15 +17,47 @@ use crate::formatters::Formatter;
 use crate::utils::types::FormatResult;
 use crate::{Language, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Python formatter using ruff.
-pub struct PythonFormatter;
+pub struct PythonFormatter {
+    config_path: Option<PathBuf>,
+    /// Ignore all config files (`ruff format --isolated`)
+    isolated: bool,
+}
 
 impl PythonFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            config_path: None,
+            isolated: false,
+        }
+    }
+
+    /// Ignore all ruff config files and use ruff's defaults
+    pub fn with_isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
+    /// Set an explicit ruff config path
+    pub fn with_config(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Build a `ruff format` invocation for a file
+    pub(crate) fn command(&self, args: &[&str], path: &Path) -> Command {
+        let mut cmd = Command::new("ruff");
+        cmd.arg("format").args(args);
+        if self.isolated {
+            cmd.arg("--isolated");
+        } else if let Some(config_path) = &self.config_path {
+            cmd.arg("--config").arg(config_path);
+        }
+        cmd.arg(path);
+        cmd
     }
 }
 
@@ -50,9 +82,8 @@ impl Formatter for PythonFormatter {
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         // Run ruff format (uses project config or defaults)
-        let output = Command::new("ruff")
-            .args(["format"])
-            .arg(path)
+        let output = self
+            .command(&[], path)
             .output()
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to run ruff: {}", e)))?;
 
@@ -78,9 +109,8 @@ impl Formatter for PythonFormatter {
 
     fn check(&self, path: &Path) -> Result<bool> {
         // Run ruff format in check mode
-        let output = Command::new("ruff")
-            .args(["format", "--check"])
-            .arg(path)
+        let output = self
+            .command(&["--check"], path)
             .output()
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to run ruff: {}", e)))?;
 
@@ -88,6 +118,11 @@ impl Formatter for PythonFormatter {
         Ok(!output.status.success())
     }
 
+    fn supports_stdout(&self) -> bool {
+        // ruff format - reads stdin and prints the result
+        true
+    }
+
     fn is_available(&self) -> bool {
         Command::new("ruff")
             .arg("--version")
@@ -95,4 +130,11 @@ impl Formatter for PythonFormatter {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn config_path(&self, _path: &Path) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        self.config_path.clone()
+    }
 }
@@ -85,6 +85,11 @@ impl Formatter for RustFormatter {
         Ok(!output.status.success())
     }
 
+    fn supports_stdout(&self) -> bool {
+        // rustfmt --emit stdout
+        true
+    }
+
     fn is_available(&self) -> bool {
         Command::new("rustfmt")
             .arg("--version")
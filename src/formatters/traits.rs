@@ -12,7 +12,7 @@
 
 use crate::utils::types::FormatResult;
 use crate::{Language, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Trait for implementing language-specific formatters.
 ///
@@ -43,6 +43,17 @@ pub trait Formatter: Send + Sync {
     /// true if the file would be modified, false otherwise.
     fn check(&self, path: &Path) -> Result<bool>;
 
+    /// Whether `check` reports without writing the file.
+    fn supports_check(&self) -> bool {
+        true
+    }
+
+    /// Whether the tool can print the formatted content to stdout instead
+    /// of rewriting the file (gofmt without `-w`, clang-format without `-i`).
+    fn supports_stdout(&self) -> bool {
+        false
+    }
+
     /// Check if this formatter supports the given language.
     fn supports(&self, lang: Language) -> bool {
         self.supported_languages().contains(&lang)
@@ -50,4 +61,19 @@ pub trait Formatter: Send + Sync {
 
     /// Check if the external formatter tool is available.
     fn is_available(&self) -> bool;
+
+    /// Returns the executable of the external formatter tool.
+    fn binary(&self) -> PathBuf {
+        PathBuf::from(self.name())
+    }
+
+    /// Returns the version of the external formatter tool, if it can be detected.
+    fn tool_version(&self) -> Option<String> {
+        crate::utils::tool_version(&self.binary(), &["--version"])
+    }
+
+    /// Returns the config file linthis passes to the tool for `path`, if any.
+    fn config_path(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
 }
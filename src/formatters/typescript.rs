@@ -11,18 +11,51 @@
 //! TypeScript/JavaScript language formatter using prettier.
 
 use crate::formatters::Formatter;
+use crate::utils::is_ignored_by;
 use crate::utils::types::FormatResult;
 use crate::{Language, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// TypeScript/JavaScript formatter using prettier.
-pub struct TypeScriptFormatter;
+pub struct TypeScriptFormatter {
+    config_path: Option<PathBuf>,
+    /// Ignore all config files (`--no-config --no-editorconfig`)
+    isolated: bool,
+}
 
 impl TypeScriptFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            config_path: None,
+            isolated: false,
+        }
+    }
+
+    /// Ignore all prettier config files and use prettier's defaults
+    pub fn with_isolated(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
+    /// Set an explicit prettier config path
+    pub fn with_config(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Build a prettier invocation for a file
+    pub(crate) fn command(&self, mode: &str, path: &Path) -> Command {
+        let mut cmd = Command::new("prettier");
+        cmd.arg(mode);
+        if self.isolated {
+            cmd.args(["--no-config", "--no-editorconfig"]);
+        } else if let Some(config_path) = &self.config_path {
+            cmd.arg("--config").arg(config_path);
+        }
+        cmd.arg(path);
+        cmd
     }
 }
 
@@ -42,14 +75,18 @@ impl Formatter for TypeScriptFormatter {
     }
 
     fn format(&self, path: &Path) -> Result<FormatResult> {
+        // prettier only applies .prettierignore relative to its working directory
+        if is_ignored_by(path, ".prettierignore") {
+            return Ok(FormatResult::unchanged(path.to_path_buf()));
+        }
+
         // Read original content for comparison
         let original = fs::read_to_string(path)
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to read file: {}", e)))?;
 
         // Run prettier
-        let output = Command::new("prettier")
-            .args(["--write"])
-            .arg(path)
+        let output = self
+            .command("--write", path)
             .output()
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to run prettier: {}", e)))?;
 
@@ -74,10 +111,13 @@ impl Formatter for TypeScriptFormatter {
     }
 
     fn check(&self, path: &Path) -> Result<bool> {
+        if is_ignored_by(path, ".prettierignore") {
+            return Ok(false);
+        }
+
         // Run prettier in check mode
-        let output = Command::new("prettier")
-            .args(["--check"])
-            .arg(path)
+        let output = self
+            .command("--check", path)
             .output()
             .map_err(|e| crate::LintisError::Formatter(format!("Failed to run prettier: {}", e)))?;
 
@@ -85,6 +125,11 @@ impl Formatter for TypeScriptFormatter {
         Ok(!output.status.success())
     }
 
+    fn supports_stdout(&self) -> bool {
+        // prettier prints unless given --write
+        true
+    }
+
     fn is_available(&self) -> bool {
         Command::new("prettier")
             .arg("--version")
@@ -92,4 +137,43 @@ impl Formatter for TypeScriptFormatter {
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
+
+    fn config_path(&self, _path: &Path) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        self.config_path.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prettierignore_file_is_not_formatted() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".prettierignore"), "vendor.js\n").unwrap();
+        let file = dir.path().join("vendor.js");
+        let content = "const   x  =  {a:1}\n";
+        fs::write(&file, content).unwrap();
+
+        let formatter = TypeScriptFormatter::new();
+        let result = formatter.format(&file).unwrap();
+        assert!(!result.changed);
+        assert!(!formatter.check(&file).unwrap());
+        assert_eq!(fs::read_to_string(&file).unwrap(), content);
+    }
+
+    #[test]
+    fn test_explicit_config_is_passed() {
+        let formatter =
+            TypeScriptFormatter::new().with_config(PathBuf::from("/tmp/prettierrc.json"));
+        let cmd = formatter.command("--check", Path::new("app.ts"));
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            ["--check", "--config", "/tmp/prettierrc.json", "app.ts"]
+        );
+    }
 }
@@ -10,34 +10,43 @@
 
 //! Linthis - A fast, cross-platform multi-language linter and formatter.
 
+pub mod baseline;
 pub mod benchmark;
 pub mod checkers;
+pub mod clean;
 pub mod config;
 pub mod fixers;
 pub mod formatters;
 pub mod plugin;
 pub mod presets;
+pub mod registry;
 pub mod self_update;
 pub mod utils;
 
-use std::collections::HashSet;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 /// Track which tool warnings have been shown (to avoid duplicate warnings)
-static WARNED_TOOLS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+static WARNED_TOOLS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
 
 use checkers::{
-    Checker, CppChecker, GoChecker, JavaChecker, PythonChecker, RustChecker, TypeScriptChecker,
+    BanditChecker, Checker, CheckerError, CppChecker, CppLintTool, GoChecker, JavaChecker,
+    PythonChecker, RustChecker, TypeScriptChecker,
 };
 use formatters::{
     CppFormatter, Formatter, GoFormatter, JavaFormatter, PythonFormatter, RustFormatter,
     TypeScriptFormatter,
 };
-use utils::types::RunResult;
-use utils::walker::{walk_paths, WalkerConfig};
+use utils::types::{FileTiming, RunResult};
+use utils::walker::{
+    drop_blank_files, language_for_path, scan_cache_dir, walk_paths_cached, WalkerConfig,
+};
 
 #[derive(Error, Debug)]
 pub enum LintisError {
@@ -55,10 +64,55 @@ pub enum LintisError {
 
     #[error("Unsupported language: {0}")]
     UnsupportedLanguage(String),
+
+    /// A git-dependent mode (e.g. `--staged`) was requested without git installed
+    #[error("git not found: {0} requires git on PATH")]
+    GitNotFound(String),
+
+    /// A git-dependent mode was requested outside a git repository
+    #[error("not a git repository: {0} must run inside a git repository")]
+    NotAGitRepo(String),
+
+    /// The walk found more files than `max_files` allows
+    #[error(
+        "found {found} files, more than max_files ({limit}); check the paths, \
+         or pass --no-max-files to run anyway"
+    )]
+    TooManyFiles { found: usize, limit: usize },
+}
+
+impl LintisError {
+    /// Process exit code for a run that failed with this error: usage and
+    /// configuration problems get [`exit_code::CONFIG_ERROR`], everything
+    /// else [`exit_code::TOOL_ERROR`].
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            LintisError::Config(_)
+            | LintisError::UnsupportedLanguage(_)
+            | LintisError::GitNotFound(_)
+            | LintisError::NotAGitRepo(_)
+            | LintisError::TooManyFiles { .. } => exit_code::CONFIG_ERROR,
+            LintisError::Io(_) | LintisError::Checker(_) | LintisError::Formatter(_) => {
+                exit_code::TOOL_ERROR
+            }
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, LintisError>;
 
+/// Exit codes of a linthis run
+pub mod exit_code {
+    /// Nothing failed the run
+    pub const SUCCESS: i32 = 0;
+    /// Issues at or above the failure threshold, or unformatted files
+    pub const ISSUES: i32 = 1;
+    /// A linter or formatter failed, or a required tool is missing
+    pub const TOOL_ERROR: i32 = 2;
+    /// Bad command-line usage or configuration
+    pub const CONFIG_ERROR: i32 = 3;
+}
+
 /// Supported programming languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -74,6 +128,9 @@ pub enum Language {
 }
 
 impl Language {
+    /// Every supported language
+    pub const ALL: [Language; 8] = config::LANGUAGES;
+
     pub fn from_extension(ext: &str) -> Option<Self> {
         match ext.to_lowercase().as_str() {
             // Note: .h files need special handling via from_path() for smart detection
@@ -205,7 +262,7 @@ impl Language {
             if bytes[i] == b'N' && bytes[i + 1] == b'S' {
                 let next_char = bytes[i + 2];
                 // Check if next char is uppercase A-Z (ASCII 65-90)
-                if (b'A'..=b'Z').contains(&next_char) {
+                if next_char.is_ascii_uppercase() {
                     // Make sure it's not part of a longer identifier before "NS"
                     // (i.e., NS should be at word boundary)
                     if i == 0 || !Self::is_identifier_char(bytes[i - 1]) {
@@ -262,6 +319,43 @@ impl Language {
             Language::TypeScript => &["ts", "tsx", "mts", "cts"],
         }
     }
+
+    /// Glob patterns of test files by the language's conventions.
+    ///
+    /// Rust unit tests live inside `#[cfg(test)]` modules and can't be
+    /// excluded by path; only integration tests and `*_test.rs` files match.
+    pub fn test_file_patterns(&self) -> &'static [&'static str] {
+        match self {
+            Language::Cpp => &["*_test.cc", "*_test.cpp", "*_unittest.cc", "*_unittest.cpp"],
+            Language::ObjectiveC => &["*Tests.m", "*Test.m", "*Tests.mm", "*Test.mm"],
+            Language::Java => &["*Test.java", "*Tests.java", "src/test/**", "**/src/test/**"],
+            Language::Python => &[
+                "test_*.py",
+                "*_test.py",
+                "conftest.py",
+                "tests/**",
+                "**/tests/**",
+            ],
+            Language::Rust => &["tests/**", "**/tests/**", "*_test.rs"],
+            Language::Go => &["*_test.go"],
+            Language::JavaScript => &[
+                "*.test.js",
+                "*.spec.js",
+                "*.test.jsx",
+                "*.spec.jsx",
+                "*.test.mjs",
+                "*.spec.mjs",
+                "**/__tests__/**",
+            ],
+            Language::TypeScript => &[
+                "*.test.ts",
+                "*.spec.ts",
+                "*.test.tsx",
+                "*.spec.tsx",
+                "**/__tests__/**",
+            ],
+        }
+    }
 }
 
 /// Run mode for linthis
@@ -273,6 +367,68 @@ pub enum RunMode {
     CheckOnly,
     /// Run only formatting
     FormatOnly,
+    /// Check formatting without modifying files (fails if any file would change)
+    FormatCheck,
+}
+
+/// Which files are re-linted after formatting in [`RunMode::Both`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecheckScope {
+    /// Re-lint only files the formatter changed (default)
+    #[default]
+    Formatted,
+    /// Skip the recheck and keep first-pass issues. Issues fixed by formatting
+    /// are then not measured, so `issues_fixed` is an estimate (reported as 0).
+    None,
+    /// Re-lint every file
+    All,
+}
+
+impl RecheckScope {
+    /// Parse a recheck scope name ("formatted", "none", "all").
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "formatted" => Some(RecheckScope::Formatted),
+            "none" => Some(RecheckScope::None),
+            "all" => Some(RecheckScope::All),
+            _ => None,
+        }
+    }
+
+    /// Whether a file should be re-linted in the recheck phase.
+    pub fn should_recheck(&self, was_formatted: bool) -> bool {
+        match self {
+            RecheckScope::Formatted => was_formatted,
+            RecheckScope::None => false,
+            RecheckScope::All => true,
+        }
+    }
+}
+
+/// When progress is shown while running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressMode {
+    /// Live spinner on a terminal, periodic plain lines otherwise (default)
+    #[default]
+    Auto,
+    /// Always show the live spinner
+    Always,
+    /// Never show progress
+    Never,
+}
+
+impl ProgressMode {
+    /// Parse a progress mode name ("auto", "always", "never").
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(ProgressMode::Auto),
+            "always" => Some(ProgressMode::Always),
+            "never" => Some(ProgressMode::Never),
+            _ => None,
+        }
+    }
 }
 
 /// Progress information for callbacks
@@ -299,14 +455,96 @@ pub struct RunOptions {
     pub languages: Vec<Language>,
     /// Exclusion patterns
     pub exclude_patterns: Vec<String>,
+    /// Patterns of files linted despite every exclusion (`--audit`)
+    pub audit_patterns: Vec<String>,
     /// Verbose output
     pub verbose: bool,
     /// Quiet mode (no progress output)
     pub quiet: bool,
     /// Active plugins (name only, for display)
     pub plugins: Vec<String>,
+    /// Plugin or preset-generated config files by language and tool name
+    /// (e.g. `ruff`), passed to the tool explicitly instead of relying on
+    /// config discovery
+    pub tool_configs: HashMap<(Language, String), PathBuf>,
+    /// Run tools with their built-in defaults, ignoring all config files
+    /// (ruff `--isolated`, eslint/prettier without config lookup,
+    /// clang-format with an inline style)
+    pub config_free: bool,
     /// Fail on warnings (treat warnings as errors)
     pub fail_on_warnings: bool,
+    /// Record the N slowest files (0 = disabled)
+    pub slowest: usize,
+    /// Checker chain per language (missing or empty = language default)
+    pub checkers: HashMap<Language, Vec<String>>,
+    /// File extensions per language replacing the built-in sets
+    pub extensions: HashMap<Language, Vec<String>>,
+    /// Recheck phase scope for RunMode::Both
+    pub recheck: RecheckScope,
+    /// Skip formatting files with unstaged changes
+    pub safe: bool,
+    /// Check partially staged files as their staged content (`--staged`)
+    pub staged: bool,
+    /// Built-in checks run in addition to the language tools
+    pub builtin_checks: checkers::BuiltinChecksConfig,
+    /// Per-language lowest severity that fails the run
+    pub fail_on: HashMap<Language, utils::types::Severity>,
+    /// Stop at the first tool error instead of collecting all of them
+    pub fail_fast: bool,
+    /// Allow formatters and fixers to modify files (false = report-only)
+    pub autofix: bool,
+    /// In RunMode::Both, leave files with Error-severity lint issues unformatted
+    pub no_format_on_error: bool,
+    /// Per-language autofix switches overriding `autofix`
+    pub autofix_overrides: HashMap<Language, bool>,
+    /// Per-language checker invocation modes (see [`checkers::Invocation`])
+    pub invocation: HashMap<Language, checkers::Invocation>,
+    /// Sort and group Python/Go imports in a separate pass before formatting
+    pub organize_imports: bool,
+    /// Re-run the formatter on files it changed and warn when the second
+    /// pass changes them again
+    pub verify_idempotent: bool,
+    /// Reuse the cached file list when no walked directory changed (`--scan-cache`)
+    pub scan_cache: bool,
+    /// Skip empty and whitespace-only files
+    pub skip_empty: bool,
+    /// Fail the run (exit code 2) when a needed tool is missing
+    pub require_tools: bool,
+    /// Record a unified diff of each formatting change in `FormatResult.diff`
+    pub format_diff: bool,
+    /// Context lines around each change in those diffs
+    pub diff_context: usize,
+    /// Abort before processing when the walk finds more files (None = no limit)
+    pub max_files: Option<usize>,
+    /// Keep or drop the UTF-8 BOM of files that formatters and fixers rewrite
+    pub bom: utils::bom::BomPolicy,
+    /// Memory limit of spawned linters in MB (None = unlimited, Unix only)
+    pub tool_memory_limit_mb: Option<u64>,
+    /// clang-format/clang-tidy version pin from the `[cpp]`/`[oc]` config
+    /// (None = use `.clang-format-version` files)
+    pub clang_pin: Option<utils::clang::ClangPin>,
+    /// Save each linter's command line and output to this directory (`--record`)
+    pub record_dir: Option<PathBuf>,
+    /// Feed linter output saved with `--record` to the parsers instead of
+    /// running the linters (`--replay`); takes precedence over `record_dir`
+    pub replay_dir: Option<PathBuf>,
+    /// Hide the issues accepted by this baseline (see [`baseline`])
+    pub baseline: Option<PathBuf>,
+    /// Fail the run when it has more warnings than this (None = unlimited)
+    pub max_warnings: Option<usize>,
+    /// Severity per rule, optionally scoped to a source (`[severity_overrides]`)
+    pub severity_overrides: HashMap<String, utils::types::Severity>,
+    /// Project root whose config these options already include. Configs in
+    /// its subdirectories are merged over them for the files below
+    /// (None = no directory-scoped configs)
+    pub config_overlay_root: Option<PathBuf>,
+    /// Progress display mode
+    pub progress: ProgressMode,
+    /// Rewrite reported paths relative to this directory (None = as walked)
+    pub relative_to: Option<PathBuf>,
+    /// Language of every file, bypassing detection from the path
+    /// (`--stdin-language`)
+    pub language_override: Option<Language>,
 }
 
 impl std::fmt::Debug for RunOptions {
@@ -316,9 +554,45 @@ impl std::fmt::Debug for RunOptions {
             .field("mode", &self.mode)
             .field("languages", &self.languages)
             .field("exclude_patterns", &self.exclude_patterns)
+            .field("audit_patterns", &self.audit_patterns)
             .field("verbose", &self.verbose)
             .field("quiet", &self.quiet)
             .field("plugins", &self.plugins)
+            .field("tool_configs", &self.tool_configs)
+            .field("config_free", &self.config_free)
+            .field("slowest", &self.slowest)
+            .field("checkers", &self.checkers)
+            .field("extensions", &self.extensions)
+            .field("recheck", &self.recheck)
+            .field("safe", &self.safe)
+            .field("staged", &self.staged)
+            .field("builtin_checks", &self.builtin_checks)
+            .field("fail_on", &self.fail_on)
+            .field("fail_fast", &self.fail_fast)
+            .field("autofix", &self.autofix)
+            .field("no_format_on_error", &self.no_format_on_error)
+            .field("autofix_overrides", &self.autofix_overrides)
+            .field("invocation", &self.invocation)
+            .field("organize_imports", &self.organize_imports)
+            .field("verify_idempotent", &self.verify_idempotent)
+            .field("scan_cache", &self.scan_cache)
+            .field("skip_empty", &self.skip_empty)
+            .field("require_tools", &self.require_tools)
+            .field("format_diff", &self.format_diff)
+            .field("diff_context", &self.diff_context)
+            .field("max_files", &self.max_files)
+            .field("bom", &self.bom)
+            .field("tool_memory_limit_mb", &self.tool_memory_limit_mb)
+            .field("clang_pin", &self.clang_pin)
+            .field("record_dir", &self.record_dir)
+            .field("replay_dir", &self.replay_dir)
+            .field("baseline", &self.baseline)
+            .field("max_warnings", &self.max_warnings)
+            .field("severity_overrides", &self.severity_overrides)
+            .field("config_overlay_root", &self.config_overlay_root)
+            .field("progress", &self.progress)
+            .field("relative_to", &self.relative_to)
+            .field("language_override", &self.language_override)
             .finish()
     }
 }
@@ -330,23 +604,264 @@ impl Default for RunOptions {
             mode: RunMode::Both,
             languages: Vec::new(),
             exclude_patterns: Vec::new(),
+            audit_patterns: Vec::new(),
             verbose: false,
             quiet: false,
             plugins: Vec::new(),
+            tool_configs: HashMap::new(),
+            config_free: false,
             fail_on_warnings: false,
+            slowest: 0,
+            checkers: HashMap::new(),
+            extensions: HashMap::new(),
+            recheck: RecheckScope::default(),
+            safe: false,
+            staged: false,
+            builtin_checks: checkers::BuiltinChecksConfig::default(),
+            fail_on: HashMap::new(),
+            fail_fast: false,
+            autofix: true,
+            no_format_on_error: false,
+            autofix_overrides: HashMap::new(),
+            invocation: HashMap::new(),
+            organize_imports: false,
+            verify_idempotent: false,
+            scan_cache: false,
+            skip_empty: true,
+            require_tools: false,
+            format_diff: false,
+            diff_context: utils::diff::DEFAULT_CONTEXT_LINES,
+            max_files: None,
+            bom: utils::bom::BomPolicy::default(),
+            tool_memory_limit_mb: None,
+            clang_pin: None,
+            record_dir: None,
+            replay_dir: None,
+            baseline: None,
+            max_warnings: None,
+            severity_overrides: HashMap::new(),
+            config_overlay_root: None,
+            progress: ProgressMode::default(),
+            relative_to: None,
+            language_override: None,
+        }
+    }
+}
+
+/// Get the checkers to run, in order, for a given language.
+///
+/// `names` is the configured checker chain; when empty the language's default
+/// checker is used. User-registered checkers (see
+/// [`registry::register_language_checker`]) take precedence over both.
+fn get_checkers(
+    lang: Language,
+    file: &Path,
+    names: &[String],
+    tools: ToolEnv,
+) -> Vec<Box<dyn Checker>> {
+    if let Some(checker) = registry::language_checker(lang, file) {
+        return vec![checker];
+    }
+    if names.is_empty() {
+        return get_default_checker(lang, tools).into_iter().collect();
+    }
+    names
+        .iter()
+        .filter_map(|name| {
+            let checker = get_named_checker(lang, name, tools);
+            if checker.is_none() && should_warn_tool(&format!("unknown-checker-{}", name)) {
+                eprintln!(
+                    "\x1b[33mWarning\x1b[0m: Unknown {} checker '{}', skipping",
+                    lang.name(),
+                    name
+                );
+            }
+            checker
+        })
+        .collect()
+}
+
+/// Get a checker by name (as used in a language's `checkers` list).
+///
+/// Checkers registered by name take precedence over built-in ones.
+fn get_named_checker(lang: Language, name: &str, tools: ToolEnv) -> Option<Box<dyn Checker>> {
+    if let Some(checker) = registry::named_checker(name) {
+        return Some(checker);
+    }
+    match (lang, name) {
+        (Language::Rust, "clippy") => {
+            Some(Box::new(RustChecker::new().with_capture(tools.capture())))
+        }
+        (Language::Python, "ruff") => Some(Box::new(ruff_checker(tools))),
+        (Language::Python, "bandit") => Some(Box::new(
+            BanditChecker::new()
+                .with_capture(tools.capture())
+                .with_memory_limit(tools.memory_limit_mb),
+        )),
+        (Language::TypeScript | Language::JavaScript, "eslint") => {
+            Some(Box::new(eslint_checker(lang, tools)))
+        }
+        (Language::Go, "golangci-lint") => {
+            Some(Box::new(GoChecker::new().with_capture(tools.capture())))
+        }
+        (Language::Java, "checkstyle") => {
+            Some(Box::new(JavaChecker::new().with_capture(tools.capture())))
+        }
+        (Language::Cpp | Language::ObjectiveC, "clang-tidy") => Some(Box::new(
+            cpp_checker(lang, tools).with_tool(CppLintTool::ClangTidy),
+        )),
+        (Language::Cpp | Language::ObjectiveC, "cpplint") => Some(Box::new(
+            CppChecker::new()
+                .with_isolated(tools.isolated)
+                .with_capture(tools.capture())
+                .with_memory_limit(tools.memory_limit_mb)
+                .with_tool(CppLintTool::Cpplint),
+        )),
+        _ => None,
+    }
+}
+
+/// Get the default checker for a given language.
+fn get_default_checker(lang: Language, tools: ToolEnv) -> Option<Box<dyn Checker>> {
+    match lang {
+        Language::Rust => Some(Box::new(RustChecker::new().with_capture(tools.capture()))),
+        Language::Python => Some(Box::new(ruff_checker(tools))),
+        Language::TypeScript | Language::JavaScript => Some(Box::new(eslint_checker(lang, tools))),
+        Language::Go => Some(Box::new(GoChecker::new().with_capture(tools.capture()))),
+        Language::Java => Some(Box::new(JavaChecker::new().with_capture(tools.capture()))),
+        Language::Cpp | Language::ObjectiveC => Some(Box::new(cpp_checker(lang, tools))),
+    }
+}
+
+/// Plugin config files by language and tool name (see [`RunOptions::tool_configs`])
+type ToolConfigs = HashMap<(Language, String), PathBuf>;
+
+/// Config settings handed to built-in tools
+#[derive(Debug, Clone, Copy, Default)]
+struct ToolEnv<'a> {
+    /// Explicit config files (None = no explicit configs)
+    configs: Option<&'a ToolConfigs>,
+    /// Ignore all config files (`--config-free`)
+    isolated: bool,
+    /// Records or replays linter runs (`--record` / `--replay`)
+    capture: Option<&'a Arc<utils::capture::ToolCapture>>,
+    /// Memory limit of spawned linters in MB (`tool_memory_limit_mb`)
+    memory_limit_mb: Option<u64>,
+    /// BOM policy of files the fixers rewrite
+    bom: utils::bom::BomPolicy,
+    /// clang-format/clang-tidy version pin from the config
+    clang_pin: Option<&'a utils::clang::ClangPin>,
+    /// clang binaries and `-style=` arguments resolved during the run
+    clang: Option<&'a Arc<utils::clang::ClangToolCache>>,
+}
+
+impl<'a> ToolEnv<'a> {
+    fn of(options: &'a RunOptions) -> Self {
+        Self {
+            configs: Some(&options.tool_configs),
+            isolated: options.config_free,
+            capture: None,
+            memory_limit_mb: options.tool_memory_limit_mb,
+            bom: options.bom,
+            clang_pin: options.clang_pin.as_ref(),
+            clang: None,
         }
     }
+
+    /// Send linter runs through `capture`
+    fn with_capture(mut self, capture: Option<&'a Arc<utils::capture::ToolCapture>>) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    /// Share resolved clang tools through `clang`
+    fn with_clang_cache(mut self, clang: &'a Arc<utils::clang::ClangToolCache>) -> Self {
+        self.clang = Some(clang);
+        self
+    }
+
+    fn capture(&self) -> Option<Arc<utils::capture::ToolCapture>> {
+        self.capture.cloned()
+    }
+
+    fn clang_cache(&self) -> Arc<utils::clang::ClangToolCache> {
+        self.clang.cloned().unwrap_or_default()
+    }
+
+    /// Explicit config file for a tool, never set when isolated
+    fn config(&self, lang: Language, tool: &str) -> Option<PathBuf> {
+        if self.isolated {
+            return None;
+        }
+        self.configs?.get(&(lang, tool.to_string())).cloned()
+    }
+}
+
+fn ruff_checker(tools: ToolEnv) -> PythonChecker {
+    let checker = PythonChecker::new()
+        .with_isolated(tools.isolated)
+        .with_capture(tools.capture())
+        .with_memory_limit(tools.memory_limit_mb);
+    match tools.config(Language::Python, "ruff") {
+        Some(path) => checker.with_config(path),
+        None => checker,
+    }
+}
+
+fn eslint_checker(lang: Language, tools: ToolEnv) -> TypeScriptChecker {
+    let checker = TypeScriptChecker::new()
+        .with_isolated(tools.isolated)
+        .with_capture(tools.capture());
+    match tools.config(lang, "eslint") {
+        Some(path) => checker.with_config(path),
+        None => checker,
+    }
+}
+
+fn cpp_checker(lang: Language, tools: ToolEnv) -> CppChecker {
+    let checker = CppChecker::new()
+        .with_clang_pin(tools.clang_pin.cloned())
+        .with_clang_cache(tools.clang_cache())
+        .with_isolated(tools.isolated)
+        .with_capture(tools.capture())
+        .with_memory_limit(tools.memory_limit_mb);
+    match tools.config(lang, "clang-tidy") {
+        Some(path) => checker.with_config(path),
+        None => checker,
+    }
+}
+
+fn ruff_formatter(tools: ToolEnv) -> PythonFormatter {
+    let formatter = PythonFormatter::new().with_isolated(tools.isolated);
+    match tools.config(Language::Python, "ruff") {
+        Some(path) => formatter.with_config(path),
+        None => formatter,
+    }
+}
+
+fn prettier_formatter(lang: Language, tools: ToolEnv) -> TypeScriptFormatter {
+    let formatter = TypeScriptFormatter::new().with_isolated(tools.isolated);
+    match tools.config(lang, "prettier") {
+        Some(path) => formatter.with_config(path),
+        None => formatter,
+    }
 }
 
-/// Get the checker for a given language.
-fn get_checker(lang: Language) -> Option<Box<dyn Checker>> {
+/// Get the formatter for a language, passing it any explicit tool config.
+fn get_configured_formatter(lang: Language, tools: ToolEnv) -> Option<Box<dyn Formatter>> {
     match lang {
-        Language::Rust => Some(Box::new(RustChecker::new())),
-        Language::Python => Some(Box::new(PythonChecker::new())),
-        Language::TypeScript | Language::JavaScript => Some(Box::new(TypeScriptChecker::new())),
-        Language::Go => Some(Box::new(GoChecker::new())),
-        Language::Java => Some(Box::new(JavaChecker::new())),
-        Language::Cpp | Language::ObjectiveC => Some(Box::new(CppChecker::new())),
+        Language::Python => Some(Box::new(ruff_formatter(tools))),
+        Language::TypeScript | Language::JavaScript => {
+            Some(Box::new(prettier_formatter(lang, tools)))
+        }
+        Language::Cpp | Language::ObjectiveC => Some(Box::new(
+            CppFormatter::new()
+                .with_clang_pin(tools.clang_pin.cloned())
+                .with_clang_cache(tools.clang_cache())
+                .with_isolated(tools.isolated)
+                .with_bom_policy(tools.bom),
+        )),
+        _ => get_formatter(lang),
     }
 }
 
@@ -362,6 +877,110 @@ fn get_formatter(lang: Language) -> Option<Box<dyn Formatter>> {
     }
 }
 
+/// Version information for one external tool linthis can run.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ToolVersion {
+    /// Tool name (e.g. "clippy", "prettier")
+    pub tool: String,
+    /// "checker" or "formatter"
+    pub kind: &'static str,
+    /// Languages the tool is used for
+    pub languages: Vec<Language>,
+    /// Detected version, or `None` if the tool is missing
+    pub version: Option<String>,
+    /// Resolved executable path, or `None` if it isn't on `PATH`
+    pub path: Option<PathBuf>,
+}
+
+/// Built-in checkers, by the name used in a language's `checkers` list.
+const BUILTIN_CHECKERS: &[(&str, &[Language])] = &[
+    ("clippy", &[Language::Rust]),
+    ("ruff", &[Language::Python]),
+    ("bandit", &[Language::Python]),
+    ("eslint", &[Language::TypeScript, Language::JavaScript]),
+    ("golangci-lint", &[Language::Go]),
+    ("checkstyle", &[Language::Java]),
+    ("clang-tidy", &[Language::Cpp, Language::ObjectiveC]),
+    ("cpplint", &[Language::Cpp, Language::ObjectiveC]),
+];
+
+/// Built-in formatters and the languages they format.
+const BUILTIN_FORMATTERS: &[(&str, &[Language])] = &[
+    ("rustfmt", &[Language::Rust]),
+    ("ruff", &[Language::Python]),
+    ("prettier", &[Language::TypeScript, Language::JavaScript]),
+    ("gofmt", &[Language::Go]),
+    (
+        "clang-format",
+        &[Language::Cpp, Language::ObjectiveC, Language::Java],
+    ),
+];
+
+/// Detect the version and location of every built-in checker and formatter.
+///
+/// Missing tools are included with `version` set to `None`.
+pub fn tool_version_report() -> Vec<ToolVersion> {
+    let checkers = BUILTIN_CHECKERS.iter().filter_map(|(name, languages)| {
+        let checker = get_named_checker(languages[0], name, ToolEnv::default())?;
+        Some(ToolVersion {
+            tool: name.to_string(),
+            kind: "checker",
+            languages: languages.to_vec(),
+            version: checker.tool_version(),
+            path: utils::find_executable(&checker.binary()),
+        })
+    });
+    let formatters = BUILTIN_FORMATTERS.iter().filter_map(|(name, languages)| {
+        let formatter = get_formatter(languages[0])?;
+        Some(ToolVersion {
+            tool: name.to_string(),
+            kind: "formatter",
+            languages: languages.to_vec(),
+            version: formatter.tool_version(),
+            path: utils::find_executable(&formatter.binary()),
+        })
+    });
+    checkers.chain(formatters).collect()
+}
+
+/// Build metadata of this linthis binary (`linthis --version --json`).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VersionInfo {
+    /// linthis version (`CARGO_PKG_VERSION`)
+    pub version: &'static str,
+    /// Commit the binary was built from, if built from a git checkout
+    pub git_commit: Option<&'static str>,
+    /// Build date (UTC, YYYY-MM-DD)
+    pub build_date: &'static str,
+    /// Target triple (e.g. "x86_64-unknown-linux-gnu")
+    pub target: &'static str,
+}
+
+impl VersionInfo {
+    /// Metadata captured by the build script
+    pub fn current() -> Self {
+        let git_commit = env!("LINTHIS_GIT_COMMIT");
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: (!git_commit.is_empty()).then_some(git_commit),
+            build_date: env!("LINTHIS_BUILD_DATE"),
+            target: env!("LINTHIS_TARGET"),
+        }
+    }
+}
+
+/// Get installation instructions for a checker selected by name
+fn get_named_checker_install_hint(name: &str) -> Option<&'static str> {
+    match name {
+        "bandit" => Some("Install: pip install bandit"),
+        "cpplint" => Some("Install: pip install cpplint"),
+        "ruff" => Some("Install: pip install ruff"),
+        "eslint" => Some("Install: npm install -g eslint"),
+        "clippy" => Some("Install: rustup component add clippy"),
+        _ => None,
+    }
+}
+
 /// Get installation instructions for a language's linter (platform-specific)
 fn get_checker_install_hint(lang: Language) -> String {
     match lang {
@@ -431,6 +1050,179 @@ fn get_formatter_install_hint(lang: Language) -> String {
     }
 }
 
+/// Per-run cache of tool availability.
+///
+/// Tools are probed lazily, at most once per language and only for languages
+/// that actually have files to process, so a `--lang rust` run never spawns
+/// availability checks for other languages' tools.
+#[derive(Default)]
+struct ToolProbe {
+    /// Keyed by language and position in the language's checker chain
+    checkers: Mutex<HashMap<(Language, usize), bool>>,
+    formatters: Mutex<HashMap<Language, bool>>,
+    /// Custom tool availability, keyed by file extension
+    custom_checkers: Mutex<HashMap<String, bool>>,
+    custom_formatters: Mutex<HashMap<String, bool>>,
+    /// Tool versions for verbose per-file reports, keyed by tool name
+    versions: Mutex<HashMap<String, Option<String>>>,
+    /// Tools that were needed but not available
+    missing: Mutex<BTreeSet<String>>,
+    /// Records or replays linter runs (`--record` / `--replay`)
+    capture: Option<Arc<utils::capture::ToolCapture>>,
+    /// clang binaries, `-style=` arguments and warnings of the run
+    clang: Arc<utils::clang::ClangToolCache>,
+}
+
+impl ToolProbe {
+    /// Check (and cache) whether the checker at `index` in a language's chain is available.
+    fn checker_available(&self, lang: Language, index: usize, checker: &dyn Checker) -> bool {
+        let mut cache = self.checkers.lock().unwrap();
+        *cache
+            .entry((lang, index))
+            .or_insert_with(|| match self.capture.as_deref() {
+                // Replayed linters don't need to be installed, only recorded
+                Some(capture) if capture.is_replay() => capture.has_recording(checker.name()),
+                _ => checker.is_available(),
+            })
+    }
+
+    /// Check (and cache) whether the formatter for a language is available.
+    fn formatter_available(&self, lang: Language, formatter: &dyn Formatter) -> bool {
+        let mut cache = self.formatters.lock().unwrap();
+        *cache
+            .entry(lang)
+            .or_insert_with(|| formatter.is_available())
+    }
+
+    /// Record a tool that was needed but not available.
+    fn record_missing(&self, tool: String) {
+        self.missing.lock().unwrap().insert(tool);
+    }
+
+    /// Tools recorded as missing, sorted.
+    fn missing_tools(&self) -> Vec<String> {
+        self.missing.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Detect (and cache) a tool's version.
+    fn tool_version(&self, name: &str, detect: impl FnOnce() -> Option<String>) -> Option<String> {
+        let mut cache = self.versions.lock().unwrap();
+        cache.entry(name.to_string()).or_insert_with(detect).clone()
+    }
+
+    /// Check (and cache) whether a custom checker for the file's extension is available.
+    fn custom_checker_available(&self, path: &Path, checker: &dyn Checker) -> bool {
+        let ext = registry::path_extension(path).unwrap_or_default();
+        let mut cache = self.custom_checkers.lock().unwrap();
+        *cache.entry(ext).or_insert_with(|| checker.is_available())
+    }
+
+    /// Check (and cache) whether a custom formatter for the file's extension is available.
+    fn custom_formatter_available(&self, path: &Path, formatter: &dyn Formatter) -> bool {
+        let ext = registry::path_extension(path).unwrap_or_default();
+        let mut cache = self.custom_formatters.lock().unwrap();
+        *cache.entry(ext).or_insert_with(|| formatter.is_available())
+    }
+}
+
+/// Tracks the N slowest files of a run.
+///
+/// Only a min-heap of the current top N is kept, so memory stays bounded
+/// regardless of how many files are processed.
+struct SlowestFiles {
+    limit: usize,
+    heap: BinaryHeap<Reverse<(Duration, PathBuf)>>,
+}
+
+impl SlowestFiles {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    /// Record the total time spent on a file.
+    fn record(&mut self, path: &Path, elapsed: Duration) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.heap.len() == self.limit {
+            // Skip without allocating if it can't enter the top N
+            if let Some(Reverse((fastest, _))) = self.heap.peek() {
+                if elapsed <= *fastest {
+                    return;
+                }
+            }
+        }
+        self.heap.push(Reverse((elapsed, path.to_path_buf())));
+        if self.heap.len() > self.limit {
+            self.heap.pop();
+        }
+    }
+
+    /// Consume the tracker, returning timings sorted slowest first.
+    fn into_sorted(self) -> Vec<FileTiming> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((elapsed, path))| FileTiming {
+                path,
+                duration_ms: elapsed.as_millis() as u64,
+            })
+            .collect()
+    }
+}
+
+/// Which tools process a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileHandler {
+    /// Tools registered in [`registry`] for the file's extension
+    Custom,
+    /// Built-in tools for a language
+    Builtin(Language),
+}
+
+impl FileHandler {
+    /// Resolve the handler for a file. Registered custom tools take precedence.
+    fn for_path(path: &Path, extensions: &HashMap<Language, Vec<String>>) -> Option<Self> {
+        if registry::has_custom_tools(path) {
+            Some(FileHandler::Custom)
+        } else {
+            language_for_path(path, extensions).map(FileHandler::Builtin)
+        }
+    }
+
+    /// Handler for a file of a run: the run's language override, if any,
+    /// otherwise detected from the path
+    fn for_options(path: &Path, options: &RunOptions) -> Option<Self> {
+        match options.language_override {
+            Some(lang) => Some(FileHandler::Builtin(lang)),
+            None => Self::for_path(path, &options.extensions),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            FileHandler::Custom => "custom",
+            FileHandler::Builtin(lang) => lang.name(),
+        }
+    }
+}
+
+/// How a missing tool is listed at the end of a run, e.g. "ruff (python linter)"
+fn missing_tool_label(tool_type: &str, name: &str, file: &Path, handler: FileHandler) -> String {
+    match handler {
+        FileHandler::Custom => format!(
+            "{} (.{} {})",
+            name,
+            registry::path_extension(file).unwrap_or_default(),
+            tool_type
+        ),
+        FileHandler::Builtin(lang) => format!("{} ({} {})", name, lang.name(), tool_type),
+    }
+}
+
 /// Warn about missing tool (once per tool)
 fn warn_missing_tool(tool_type: &str, lang: Language, is_checker: bool) {
     let tool_key = format!("{}-{}", tool_type, lang.name());
@@ -451,305 +1243,3252 @@ fn warn_missing_tool(tool_type: &str, lang: Language, is_checker: bool) {
     }
 }
 
-/// Check if we've already warned about a tool
-fn should_warn_tool(tool_name: &str) -> bool {
-    let mut warned = WARNED_TOOLS.lock().unwrap();
-    if warned.is_none() {
-        *warned = Some(HashSet::new());
-    }
-    let set = warned.as_mut().unwrap();
-    if set.contains(tool_name) {
-        false
-    } else {
-        set.insert(tool_name.to_string());
-        true
-    }
-}
-
-/// Run checker on a file and return issues.
-fn run_checker_on_file(file: &Path, lang: Language, verbose: bool) -> Vec<utils::types::LintIssue> {
-    let mut issues = Vec::new();
-    if let Some(checker) = get_checker(lang) {
-        if checker.is_available() {
-            match checker.check(file) {
-                Ok(file_issues) => {
-                    // Set language for each issue
-                    for mut issue in file_issues {
-                        issue.language = Some(lang);
-                        issues.push(issue);
-                    }
-                }
-                Err(e) => {
-                    if verbose {
-                        eprintln!("Check error for {}: {}", file.display(), e);
-                    }
-                }
-            }
-        } else {
-            // Show warning once per tool (not per file)
-            warn_missing_tool("linter", lang, true);
+/// Warn about a missing checker from a configured chain (once per checker)
+fn warn_missing_checker(lang: Language, checker_name: &str) {
+    if should_warn_tool(&format!("linter-{}-{}", lang.name(), checker_name)) {
+        eprintln!(
+            "\x1b[33mWarning\x1b[0m: {} checker '{}' is not available",
+            lang.name(),
+            checker_name
+        );
+        if let Some(hint) = get_named_checker_install_hint(checker_name) {
+            eprintln!("  {}", hint);
         }
+        eprintln!();
     }
-    issues
 }
 
-/// Print progress message (respects quiet mode)
-fn print_progress(msg: &str, quiet: bool) {
-    if !quiet {
-        eprint!("\r\x1b[K{}", msg); // Clear line and print
-        use std::io::Write;
-        let _ = std::io::stderr().flush();
+/// Warn about a missing custom tool (once per extension)
+fn warn_missing_custom_tool(tool_type: &str, tool_name: &str, path: &Path) {
+    let ext = registry::path_extension(path).unwrap_or_default();
+    if should_warn_tool(&format!("{}-custom-{}", tool_type, ext)) {
+        eprintln!(
+            "\x1b[33mWarning\x1b[0m: Custom {} '{}' for .{} files is not available",
+            tool_type, tool_name, ext
+        );
+        eprintln!();
     }
 }
 
-/// Main entry point for running linthis.
-pub fn run(options: &RunOptions) -> Result<RunResult> {
-    use utils::types::RunModeKind;
-
-    let start = Instant::now();
-    let mut result = RunResult::new();
-
-    // Set run mode for appropriate output messages
-    result.run_mode = match options.mode {
-        RunMode::Both => RunModeKind::Both,
-        RunMode::CheckOnly => RunModeKind::CheckOnly,
-        RunMode::FormatOnly => RunModeKind::FormatOnly,
-    };
+/// Check if we've already warned about a tool.
+///
+/// Returns true exactly once per tool, however many threads ask.
+fn should_warn_tool(tool_name: &str) -> bool {
+    WARNED_TOOLS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(tool_name.to_string())
+}
 
-    // Print plugins in use
-    if !options.quiet && !options.plugins.is_empty() {
-        eprintln!("📦 Plugins: {}", options.plugins.join(", "));
+/// Forget which tool warnings have been shown.
+///
+/// Long-lived processes calling `run()` repeatedly can call this between runs
+/// so missing tools are reported again.
+pub fn reset_tool_warnings() {
+    if let Some(warned) = WARNED_TOOLS.get() {
+        warned.lock().unwrap().clear();
     }
+}
 
-    // Print starting message
-    if !options.quiet {
-        eprint!("⏳ Scanning files...");
-        use std::io::Write;
-        let _ = std::io::stderr().flush();
+/// Verbose report of the tool that handled a file and the config it used.
+fn tool_use_line(
+    file: &Path,
+    role: &str,
+    binary: &Path,
+    version: Option<&str>,
+    config: Option<&Path>,
+) -> String {
+    let mut line = format!("{}: {} {}", file.display(), role, binary.display());
+    if let Some(version) = version {
+        line.push_str(&format!(" ({})", version));
+    }
+    match config {
+        Some(config) => line.push_str(&format!(", config {}", config.display())),
+        None => line.push_str(", no config file"),
     }
+    line
+}
 
-    // Configure walker
-    let walker_config = WalkerConfig {
-        exclude_patterns: options.exclude_patterns.clone(),
-        languages: options.languages.clone(),
-        ..Default::default()
+/// Run the checker chain on a file and return the merged issues.
+fn run_checker_on_file(
+    file: &Path,
+    handler: FileHandler,
+    options: &RunOptions,
+    probe: &ToolProbe,
+) -> Vec<utils::types::LintIssue> {
+    let mut issues = Vec::new();
+    let checkers: Vec<(Box<dyn Checker>, bool)> = match handler {
+        FileHandler::Custom => registry::custom_checker(file)
+            .map(|checker| {
+                let available = probe.custom_checker_available(file, checker.as_ref());
+                (checker, available)
+            })
+            .into_iter()
+            .collect(),
+        FileHandler::Builtin(lang) => {
+            let names = options
+                .checkers
+                .get(&lang)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            get_checkers(
+                lang,
+                file,
+                names,
+                ToolEnv::of(options)
+                    .with_capture(probe.capture.as_ref())
+                    .with_clang_cache(&probe.clang),
+            )
+            .into_iter()
+            .enumerate()
+            .map(|(idx, checker)| {
+                let available = probe.checker_available(lang, idx, checker.as_ref());
+                (checker, available)
+            })
+            .collect()
+        }
     };
 
-    // Collect files to process
-    let (files, path_warnings) = walk_paths(&options.paths, &walker_config);
+    // With --staged, lint exactly what will be committed
+    let staged_copy = if options.staged && utils::has_unstaged_changes(file) {
+        utils::StagedCopy::create(file)
+    } else {
+        None
+    };
+    let check_path = staged_copy.as_ref().map_or(file, |copy| copy.path());
 
-    // Print warnings about paths (clear line first, then print warnings)
-    if !path_warnings.is_empty() && !options.quiet {
-        eprint!("\r\x1b[K"); // Clear "Scanning files..." line
-        for warning in &path_warnings {
-            eprintln!("\x1b[33mWarning\x1b[0m: {}", warning);
+    // Show warning once per tool (not per file)
+    let warn_missing = |name: &str| {
+        probe.record_missing(missing_tool_label("linter", name, file, handler));
+        match handler {
+            FileHandler::Custom => warn_missing_custom_tool("linter", name, file),
+            FileHandler::Builtin(lang) => match options.checkers.get(&lang) {
+                Some(names) if !names.is_empty() => warn_missing_checker(lang, name),
+                _ => warn_missing_tool("linter", lang, true),
+            },
         }
-        eprint!("⏳ Found {} files, checking...", files.len());
-        use std::io::Write;
-        let _ = std::io::stderr().flush();
-    } else if !options.quiet {
-        eprint!("\r\x1b[K⏳ Found {} files, checking...", files.len());
-        use std::io::Write;
-        let _ = std::io::stderr().flush();
-    }
-
-    if options.verbose {
-        eprintln!();
-        eprintln!("Found {} files to process", files.len());
-    }
-
-    // Build file-to-language map
-    let file_langs: Vec<_> = files
-        .iter()
-        .filter_map(|f| Language::from_path(f).map(|l| (f, l)))
-        .collect();
-
-    // Set total_files to actual processable files count
-    result.total_files = file_langs.len();
+    };
 
-    // For RunMode::Both: lint → format → lint (only files with issues)
-    if options.mode == RunMode::Both {
-        // Step 1: First lint pass (before formatting)
-        if options.verbose {
-            eprintln!("Step 1: Checking for issues...");
-        }
-        let mut issues_before = Vec::new();
-        let mut files_with_issues: HashSet<PathBuf> = HashSet::new();
-        let total_files = file_langs.len();
-        for (idx, (file, lang)) in file_langs.iter().enumerate() {
-            print_progress(
-                &format!("⏳ [1/3] Checking ({}/{})...", idx + 1, total_files),
-                options.quiet || options.verbose,
-            );
-            let file_issues = run_checker_on_file(file, *lang, options.verbose);
-            if !file_issues.is_empty() {
-                files_with_issues.insert((*file).clone());
-            }
-            issues_before.extend(file_issues);
+    for (checker, available) in checkers {
+        if !available {
+            warn_missing(checker.name());
+            continue;
         }
-        result.issues_before_format = issues_before.len();
 
-        // Step 2: Format files (only files with issues to save time)
+        // Resolving the config may spawn the tool, so only verbose runs do it
+        let config_path = options.verbose.then(|| checker.config_path(file)).flatten();
         if options.verbose {
+            let version = probe.tool_version(checker.name(), || checker.tool_version());
             eprintln!(
-                "Step 2: Formatting {} files with issues...",
-                files_with_issues.len()
+                "{}",
+                tool_use_line(
+                    file,
+                    "checker",
+                    &checker.binary(),
+                    version.as_deref(),
+                    config_path.as_deref()
+                )
             );
         }
-        let mut formatted_files: HashSet<PathBuf> = HashSet::new();
-        let files_to_format: Vec<_> = file_langs
-            .iter()
-            .filter(|(f, _)| files_with_issues.contains(*f))
-            .collect();
-        let format_total = files_to_format.len();
-        for (idx, (file, lang)) in files_to_format.iter().enumerate() {
-            print_progress(
-                &format!("⏳ [2/3] Formatting ({}/{})...", idx + 1, format_total),
-                options.quiet || options.verbose,
-            );
-            if let Some(formatter) = get_formatter(*lang) {
-                if formatter.is_available() {
-                    match formatter.format(file) {
-                        Ok(format_result) => {
-                            if format_result.changed {
-                                formatted_files.insert((*file).clone());
-                            }
-                            result.add_format_result(format_result);
-                        }
-                        Err(e) => {
-                            if options.verbose {
-                                eprintln!("Format error for {}: {}", file.display(), e);
-                            }
-                        }
+
+        match checker.check(check_path) {
+            Ok(file_issues) => {
+                for mut issue in file_issues {
+                    if let Some(ref copy) = staged_copy {
+                        copy.restore_path(&mut issue.file_path, file);
                     }
-                } else {
-                    warn_missing_tool("formatter", *lang, false);
+                    if issue.config_path.is_none() {
+                        issue.config_path = config_path.clone();
+                    }
+                    // Set language for each issue (custom checkers set their own)
+                    if let FileHandler::Builtin(lang) = handler {
+                        issue.language = Some(lang);
+                    }
+                    issues.push(issue);
+                }
+            }
+            Err(e) if e.is_tool_failure() => {
+                // Never treat a broken tool as a clean file; a tool stopped at
+                // the memory limit is a warning
+                let severity = match e {
+                    CheckerError::MemoryLimit { .. } => utils::types::Severity::Warning,
+                    _ => utils::types::Severity::Error,
+                };
+                issues.push(
+                    utils::types::LintIssue::new(file.to_path_buf(), 1, e.to_string(), severity)
+                        .with_source(checker.name().to_string())
+                        .with_code(utils::types::TOOL_FAILURE_CODE.to_string()),
+                );
+            }
+            Err(CheckerError::ToolNotFound { .. }) => warn_missing(checker.name()),
+            Err(e) => {
+                if options.verbose {
+                    eprintln!("Check error for {}: {}", file.display(), e);
                 }
             }
         }
+    }
 
-        // Step 3: Second lint pass (only re-check files that were formatted)
-        if options.verbose {
-            eprintln!(
-                "Step 3: Rechecking {} formatted files...",
-                formatted_files.len()
-            );
+    // Built-in checks run in addition to the language tools
+    if options.builtin_checks.any_enabled() {
+        let builtin = checkers::BuiltinChecker::new(options.builtin_checks.clone());
+        if let Ok(file_issues) = builtin.check(check_path) {
+            for mut issue in file_issues {
+                if let Some(ref copy) = staged_copy {
+                    copy.restore_path(&mut issue.file_path, file);
+                }
+                if let FileHandler::Builtin(lang) = handler {
+                    issue.language = Some(lang);
+                }
+                issues.push(issue);
+            }
         }
+    }
 
-        // Helper to normalize paths for comparison
-        // Convert to absolute path for reliable comparison
-        fn normalize_path(p: &Path) -> PathBuf {
-            // Try to canonicalize (absolute path), fall back to simple normalization
-            if let Ok(canonical) = p.canonicalize() {
-                canonical
-            } else {
-                // If canonicalize fails (e.g., file doesn't exist), try to make absolute
-                if p.is_absolute() {
-                    p.to_path_buf()
-                } else {
-                    // Make relative path absolute
-                    if let Ok(current_dir) = std::env::current_dir() {
-                        let joined = current_dir.join(p);
-                        // Try to canonicalize the joined path
-                        joined.canonicalize().unwrap_or(joined)
-                    } else {
-                        // Fall back to removing "./" prefix
-                        let s = p.to_string_lossy();
-                        let s = s.strip_prefix("./").unwrap_or(&s);
-                        PathBuf::from(s)
+    // Inline markers (`# noqa`, `// NOLINT`, ...) apply to every issue on the line
+    if !issues.is_empty() {
+        if let Ok(content) = std::fs::read_to_string(check_path) {
+            utils::suppress::filter_suppressed(&mut issues, &content);
+        }
+    }
+    issues
+}
+
+/// Get the formatter for a file if it is available (warns once otherwise).
+fn available_formatter(
+    file: &Path,
+    handler: FileHandler,
+    tools: ToolEnv,
+    probe: &ToolProbe,
+) -> Option<Box<dyn Formatter>> {
+    let (formatter, available) = match handler {
+        FileHandler::Custom => {
+            let formatter = registry::custom_formatter(file)?;
+            let available = probe.custom_formatter_available(file, formatter.as_ref());
+            (formatter, available)
+        }
+        FileHandler::Builtin(lang) => {
+            let formatter = get_configured_formatter(lang, tools.with_clang_cache(&probe.clang))?;
+            let available = probe.formatter_available(lang, formatter.as_ref());
+            (formatter, available)
+        }
+    };
+
+    if !available {
+        probe.record_missing(missing_tool_label(
+            "formatter",
+            formatter.name(),
+            file,
+            handler,
+        ));
+        match handler {
+            FileHandler::Custom => warn_missing_custom_tool("formatter", formatter.name(), file),
+            FileHandler::Builtin(lang) => warn_missing_tool("formatter", lang, false),
+        }
+        return None;
+    }
+    Some(formatter)
+}
+
+/// Run formatter on a file. Returns None if no formatter ran.
+fn run_formatter_on_file(
+    file: &Path,
+    handler: FileHandler,
+    options: &RunOptions,
+    probe: &ToolProbe,
+) -> Option<FormatResult> {
+    let formatter = available_formatter(file, handler, ToolEnv::of(options), probe)?;
+    if options.verbose {
+        let version = probe.tool_version(formatter.name(), || formatter.tool_version());
+        eprintln!(
+            "{}",
+            tool_use_line(
+                file,
+                "formatter",
+                &formatter.binary(),
+                version.as_deref(),
+                formatter.config_path(file).as_deref()
+            )
+        );
+    }
+    let had_bom = utils::bom::has_bom(file);
+    let before = options
+        .format_diff
+        .then(|| std::fs::read_to_string(file).ok())
+        .flatten();
+    match formatter.format(file) {
+        Ok(mut format_result) => {
+            // External tools may drop a BOM; apply the BOM policy instead
+            if had_bom
+                && format_result.error.is_none()
+                && utils::bom::restore_bom(file, true, options.bom).unwrap_or(false)
+                && options.bom == utils::bom::BomPolicy::Remove
+            {
+                format_result.changed = true;
+            }
+            if let Some(before) = before.filter(|_| format_result.changed) {
+                if format_result.diff.is_none() {
+                    if let Ok(after) = std::fs::read_to_string(file) {
+                        format_result.diff = Some(utils::diff::unified_diff(
+                            file,
+                            &before,
+                            &after,
+                            options.diff_context,
+                        ));
                     }
                 }
             }
+            Some(format_result)
+        }
+        Err(e) => {
+            if options.verbose {
+                eprintln!("Format error for {}: {}", file.display(), e);
+            }
+            // Only --fail-fast turns a formatter error into a failed run
+            options
+                .fail_fast
+                .then(|| FormatResult::error(file.to_path_buf(), e.to_string()))
         }
+    }
+}
 
-        let recheck_total = formatted_files.len();
-        let mut recheck_idx = 0;
-        for (file, lang) in &file_langs {
-            if formatted_files.contains(*file) {
-                recheck_idx += 1;
-                print_progress(
-                    &format!("⏳ [3/3] Rechecking ({}/{})...", recheck_idx, recheck_total),
-                    options.quiet || options.verbose,
-                );
-                // Re-check formatted files
-                for issue in run_checker_on_file(file, *lang, options.verbose) {
-                    result.add_issue(issue);
-                }
-            } else if files_with_issues.contains(*file) {
-                // Keep original issues for files that weren't formatted
-                let normalized_file = normalize_path(file);
-                for issue in &issues_before {
-                    let normalized_issue_path = normalize_path(&issue.file_path);
-                    if normalized_issue_path == normalized_file {
-                        result.add_issue(issue.clone());
-                    }
+/// Fix what the built-in `final-newline` check reports, after the main
+/// format. The change is folded into the file's format result.
+fn run_whitespace_pass(
+    file: &Path,
+    options: &RunOptions,
+    format_result: Option<FormatResult>,
+) -> Option<FormatResult> {
+    if !options.builtin_checks.final_newline_enabled()
+        || format_result.as_ref().is_some_and(|r| r.error.is_some())
+    {
+        return format_result;
+    }
+    let before = options
+        .format_diff
+        .then(|| std::fs::read_to_string(file).ok())
+        .flatten();
+    match fixers::WhitespaceFixer::new()
+        .with_bom_policy(options.bom)
+        .fix_file(file)
+    {
+        Ok(true) => {
+            let mut format_result =
+                format_result.unwrap_or_else(|| FormatResult::unchanged(file.to_path_buf()));
+            format_result.changed = true;
+            if let Some(before) = before.filter(|_| format_result.diff.is_none()) {
+                if let Ok(after) = std::fs::read_to_string(file) {
+                    format_result.diff = Some(utils::diff::unified_diff(
+                        file,
+                        &before,
+                        &after,
+                        options.diff_context,
+                    ));
                 }
             }
-            // Files without issues: no need to add anything
+            Some(format_result)
+        }
+        Ok(false) => format_result,
+        Err(e) => {
+            if options.verbose {
+                eprintln!("Whitespace fix error for {}: {}", file.display(), e);
+            }
+            format_result
+        }
+    }
+}
+
+/// Walk the run's paths with its excludes, languages and extension overrides.
+/// Returns (files, warnings) like [`utils::walker::walk_paths`].
+fn walk_run_paths(options: &RunOptions) -> (Vec<PathBuf>, Vec<String>) {
+    let walker_config = WalkerConfig {
+        exclude_patterns: options.exclude_patterns.clone(),
+        audit_patterns: options.audit_patterns.clone(),
+        languages: options.languages.clone(),
+        extension_overrides: options.extensions.clone(),
+        // Dropped below, to report them
+        skip_empty: false,
+        ..Default::default()
+    };
+    let scan_cache_dir = options.scan_cache.then(scan_cache_dir).flatten();
+    let (mut files, warnings) =
+        walk_paths_cached(&options.paths, &walker_config, scan_cache_dir.as_deref());
+    if options.skip_empty {
+        let skipped = drop_blank_files(&mut files);
+        if options.verbose && !skipped.is_empty() {
+            eprintln!("Skipping {} empty file(s):", skipped.len());
+            for file in &skipped {
+                eprintln!("  {}", file.display());
+            }
+        }
+    }
+    (files, warnings)
+}
+
+/// A file a run would process (`--list-files`)
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ListedFile {
+    pub path: PathBuf,
+    /// Built-in language, or None for files handled by registered custom tools
+    pub language: Option<Language>,
+}
+
+/// Resolve the files a run would process, without linting or formatting
+/// them: the walked files that a built-in language or registered tool handles.
+/// Returns (files, warnings about the given paths).
+pub fn list_files(options: &RunOptions) -> (Vec<ListedFile>, Vec<String>) {
+    let (files, warnings) = walk_run_paths(options);
+    let listed = files
+        .into_iter()
+        .filter_map(|path| {
+            let language = match FileHandler::for_options(&path, options)? {
+                FileHandler::Custom => None,
+                FileHandler::Builtin(lang) => Some(lang),
+            };
+            Some(ListedFile { path, language })
+        })
+        .collect();
+    (listed, warnings)
+}
+
+/// Sort and group a file's imports before the main format (`organize_imports`).
+/// Returns None if the pass is off or no import organizer ran.
+fn run_import_pass(
+    file: &Path,
+    handler: FileHandler,
+    options: &RunOptions,
+) -> Option<FormatResult> {
+    let FileHandler::Builtin(lang) = handler else {
+        return None;
+    };
+    if !options.organize_imports {
+        return None;
+    }
+    let organizer: Box<dyn Formatter> = match registry::import_organizer(lang, file) {
+        Some(organizer) => organizer,
+        None if matches!(lang, Language::Cpp | Language::ObjectiveC) => Box::new(
+            options
+                .builtin_checks
+                .include_order_fixer()
+                .with_bom_policy(options.bom),
+        ),
+        None => Box::new(formatters::ImportOrganizer::for_language(lang)?),
+    };
+    if !organizer.is_available() {
+        if options.verbose {
+            eprintln!(
+                "Skipping import pass for {} ({} not found)",
+                file.display(),
+                organizer.name()
+            );
+        }
+        return None;
+    }
+    match organizer.format(file) {
+        Ok(import_result) => Some(import_result),
+        Err(e) => {
+            if options.verbose {
+                eprintln!("Import pass error for {}: {}", file.display(), e);
+            }
+            Some(FormatResult::error(file.to_path_buf(), e.to_string()))
         }
+    }
+}
+
+/// Check a file the formatter just changed with a second, read-only pass
+/// (`--verify-idempotent`). Returns a warning if it would change again.
+fn verify_idempotent(
+    file: &Path,
+    handler: FileHandler,
+    options: &RunOptions,
+    probe: &ToolProbe,
+) -> Option<utils::types::LintIssue> {
+    let formatter = available_formatter(file, handler, ToolEnv::of(options), probe)?;
+    if !formatter.check(file).ok()? {
+        return None;
+    }
+    let mut issue = utils::types::LintIssue::new(
+        file.to_path_buf(),
+        1,
+        format!(
+            "formatter not idempotent: a second {} pass would change the file again",
+            formatter.name()
+        ),
+        utils::types::Severity::Warning,
+    )
+    .with_source("linthis".to_string())
+    .with_code(utils::types::NON_IDEMPOTENT_FORMAT_CODE.to_string());
+    if let FileHandler::Builtin(lang) = handler {
+        issue.language = Some(lang);
+    }
+    Some(issue)
+}
+
+/// Whether `--fail-fast` should stop the run after a file produced these results
+fn should_abort(
+    options: &RunOptions,
+    issues: &[utils::types::LintIssue],
+    format_result: Option<&FormatResult>,
+) -> bool {
+    options.fail_fast
+        && (issues.iter().any(|i| i.is_tool_failure())
+            || format_result.is_some_and(|r| r.error.is_some()))
+}
+
+/// Fill in the final stats of a run.
+fn finish_run(
+    result: &mut RunResult,
+    options: &RunOptions,
+    probe: &ToolProbe,
+    start: Instant,
+    slowest: SlowestFiles,
+    baseline: Option<&baseline::Baseline>,
+) {
+    result.apply_default_error_codes();
+    if let Some(baseline) = baseline {
+        result.baselined_issues = baseline.filter(&mut result.issues, &utils::get_project_root());
+    }
+    result.apply_severity_overrides(&options.severity_overrides);
+    result.count_files_with_issues();
+    result.calculate_exit_code_with_thresholds(options.fail_on_warnings, &options.fail_on);
+    result.apply_max_warnings(options.max_warnings);
+    result.missing_tools = probe.missing_tools();
+    if options.require_tools && !result.missing_tools.is_empty() {
+        result.exit_code = exit_code::TOOL_ERROR;
+    }
+    result.duration_ms = start.elapsed().as_millis() as u64;
+    result.slowest_files = slowest.into_sorted();
+    if let Some(base) = &options.relative_to {
+        rewrite_paths_relative_to(result, base);
+    }
+}
 
-        // Clear progress line
-        print_progress("", options.quiet || options.verbose);
+/// Rewrite every reported path relative to `base` (`--relative-to`).
+fn rewrite_paths_relative_to(result: &mut RunResult, base: &Path) {
+    for issue in result.issues.iter_mut().chain(&mut result.fixed_issues) {
+        issue.file_path = utils::relative_path(&issue.file_path, base);
+        for related in &mut issue.related {
+            if let Some(file) = &mut related.file {
+                *file = utils::relative_path(file, base);
+            }
+        }
+    }
+    for format_result in result
+        .format_results
+        .iter_mut()
+        .chain(&mut result.import_results)
+    {
+        format_result.file_path = utils::relative_path(&format_result.file_path, base);
+    }
+    for path in &mut result.unformatted_files {
+        *path = utils::relative_path(path, base);
+    }
+    for timing in &mut result.slowest_files {
+        timing.path = utils::relative_path(&timing.path, base);
+    }
+}
 
-        // Calculate fixed issues (only if some files were actually formatted)
-        if !formatted_files.is_empty() && result.issues_before_format > result.issues.len() {
-            result.issues_fixed = result.issues_before_format - result.issues.len();
+/// Check whether formatting would change a file, without writing it.
+/// Returns None if no formatter ran.
+fn run_format_check_on_file(
+    file: &Path,
+    handler: FileHandler,
+    options: &RunOptions,
+    probe: &ToolProbe,
+) -> Option<bool> {
+    let formatter = available_formatter(file, handler, ToolEnv::of(options), probe)?;
+    match formatter.check(file) {
+        Ok(needs_format) => Some(needs_format),
+        Err(e) => {
+            if options.verbose {
+                eprintln!("Format check error for {}: {}", file.display(), e);
+            }
+            None
         }
+    }
+}
+
+/// Lint the packages of all Go files up front (one linter run per module)
+/// when the built-in Go checker is part of the chain.
+fn prefetch_go_lint(
+    file_langs: &[(&PathBuf, FileHandler)],
+    options: &RunOptions,
+    probe: &ToolProbe,
+) {
+    if matches!(options.mode, RunMode::FormatOnly | RunMode::FormatCheck) {
+        return;
+    }
+
+    let names = options
+        .checkers
+        .get(&Language::Go)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let idx = if names.is_empty() {
+        0
     } else {
-        // FormatOnly or CheckOnly mode
-        let total_files = file_langs.len();
-        let mode_name = if options.mode == RunMode::FormatOnly {
-            "Formatting"
-        } else {
-            "Checking"
+        match names.iter().position(|n| n == "golangci-lint") {
+            Some(idx) if registry::named_checker("golangci-lint").is_none() => idx,
+            _ => return,
+        }
+    };
+
+    let go_files: Vec<PathBuf> = file_langs
+        .iter()
+        .filter(|(file, handler)| {
+            *handler == FileHandler::Builtin(Language::Go)
+                && registry::language_checker(Language::Go, file).is_none()
+        })
+        .map(|(file, _)| (*file).clone())
+        .collect();
+    let go_checker = GoChecker::new().with_capture(probe.capture.clone());
+    if go_files.is_empty() || !probe.checker_available(Language::Go, idx, &go_checker) {
+        return;
+    }
+
+    // Errors resurface from the per-file check, which lints the whole module
+    let invocation = options
+        .invocation
+        .get(&Language::Go)
+        .copied()
+        .unwrap_or(checkers::Invocation::PerDir);
+    if let Err(e) = checkers::go::prefetch(&go_files, invocation, probe.capture.as_deref()) {
+        if options.verbose {
+            eprintln!("Go lint prefetch failed: {}", e);
+        }
+    }
+}
+
+impl RunOptions {
+    /// Options for files governed by a directory-scoped config
+    fn with_config_overlay(&self, overlay: &config::Config) -> RunOptions {
+        let mut options = self.clone();
+        options
+            .checkers
+            .extend(overlay.language_overrides.checker_chains());
+        options.builtin_checks.merge(overlay.builtin_checks.clone());
+        if let Some(autofix) = overlay.autofix {
+            // The subdirectory's global switch beats the root's per-language ones
+            options.autofix = autofix;
+            options.autofix_overrides.clear();
+        }
+        options
+            .autofix_overrides
+            .extend(overlay.language_overrides.autofix_overrides());
+        if let Some(organize_imports) = overlay.organize_imports {
+            options.organize_imports = organize_imports;
+        }
+        options
+    }
+}
+
+/// Apply directory-scoped configs: drop the files they exclude or whose
+/// language they disable, and return the options for the remaining files
+/// they govern.
+fn apply_config_overlays(
+    file_langs: &mut Vec<(&PathBuf, FileHandler)>,
+    options: &RunOptions,
+) -> HashMap<PathBuf, Arc<RunOptions>> {
+    let mut file_options = HashMap::new();
+    let Some(root) = &options.config_overlay_root else {
+        return file_options;
+    };
+    let overlays = config::DirConfigs::new(root);
+    // Exclude globs and derived options, built once per overlay
+    let mut resolved: HashMap<_, (Option<globset::GlobSet>, Arc<RunOptions>)> = HashMap::new();
+
+    file_langs.retain(|(file, handler)| {
+        let Some(overlay) = file.parent().and_then(|dir| overlays.resolve(dir)) else {
+            return true;
         };
-        for (idx, (file, lang)) in file_langs.iter().enumerate() {
-            print_progress(
-                &format!("⏳ {} ({}/{})...", mode_name, idx + 1, total_files),
-                options.quiet || options.verbose,
-            );
-            if options.verbose {
-                eprintln!("Processing: {} ({})", file.display(), lang.name());
+        let (excludes, overlay_options) =
+            resolved.entry(Arc::as_ptr(&overlay)).or_insert_with(|| {
+                (
+                    utils::walker::build_glob_set(&overlay.excludes),
+                    Arc::new(options.with_config_overlay(&overlay)),
+                )
+            });
+
+        let excluded = excludes.as_ref().is_some_and(|gs| {
+            let abs = file.canonicalize().unwrap_or_else(|_| (*file).clone());
+            abs.strip_prefix(overlays.root())
+                .is_ok_and(|rel| gs.is_match(rel))
+        });
+        let disabled =
+            matches!(handler, FileHandler::Builtin(lang) if !overlay.language_enabled(*lang));
+        if excluded || disabled {
+            return false;
+        }
+        file_options.insert((*file).clone(), Arc::clone(overlay_options));
+        true
+    });
+    file_options
+}
+
+/// Whether formatters and fixers may modify files of this handler.
+fn autofix_enabled(handler: FileHandler, options: &RunOptions) -> bool {
+    match handler {
+        FileHandler::Builtin(lang) => options
+            .autofix_overrides
+            .get(&lang)
+            .copied()
+            .unwrap_or(options.autofix),
+        FileHandler::Custom => options.autofix,
+    }
+}
+
+/// In safe mode, check whether a file must be left untouched because it has
+/// unstaged changes (warns once per file of the run, tracked in `warned`).
+fn skip_in_safe_mode(file: &Path, options: &RunOptions, warned: &mut HashSet<PathBuf>) -> bool {
+    if !options.safe || !utils::has_unstaged_changes(file) {
+        return false;
+    }
+    if warned.insert(file.to_path_buf()) {
+        eprintln!(
+            "\x1b[33mWarning\x1b[0m: Skipping {} (unstaged changes, use --force to format anyway)",
+            file.display()
+        );
+    }
+    true
+}
+
+/// Select the files to re-lint after formatting.
+///
+/// `formatted_files` is used as is, so the default scope never scans the
+/// full file list.
+fn select_recheck_files<'a>(
+    file_langs: &[(&'a PathBuf, FileHandler)],
+    formatted_files: &[(&'a PathBuf, FileHandler)],
+    scope: RecheckScope,
+) -> Vec<(&'a PathBuf, FileHandler)> {
+    match scope {
+        RecheckScope::Formatted => formatted_files.to_vec(),
+        RecheckScope::None => Vec::new(),
+        RecheckScope::All => file_langs.to_vec(),
+    }
+}
+
+/// Minimum interval between plain progress lines when stderr is not a terminal
+const PLAIN_PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Minimum interval between redraws of the live progress line
+const LIVE_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Frames of the spinner shown while the amount of work is unknown
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner frame after `elapsed`, advancing every [`LIVE_PROGRESS_INTERVAL`]
+fn spinner_frame(elapsed: Duration) -> &'static str {
+    let ticks = elapsed.as_millis() / LIVE_PROGRESS_INTERVAL.as_millis();
+    SPINNER_FRAMES[(ticks % SPINNER_FRAMES.len() as u128) as usize]
+}
+
+/// Throughput (files/sec) and estimated time left, given the files
+/// processed so far out of `total` in `elapsed`. None until there is
+/// something to measure.
+fn progress_eta(processed: usize, total: usize, elapsed: Duration) -> Option<(f64, Duration)> {
+    let secs = elapsed.as_secs_f64();
+    if processed == 0 || secs <= 0.0 {
+        return None;
+    }
+    let rate = processed as f64 / secs;
+    let remaining = total.saturating_sub(processed) as f64 / rate;
+    Some((rate, Duration::from_secs_f64(remaining)))
+}
+
+/// Short duration for the progress line, e.g. "42s", "3m 05s", "1h 02m"
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs_f64().round() as u64;
+    match secs {
+        0 => "<1s".to_string(),
+        1..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Progress printer for stderr.
+///
+/// On a terminal progress is a live `\r` spinner line, redrawn at most every
+/// [`LIVE_PROGRESS_INTERVAL`], with throughput and ETA on file counters.
+/// Elsewhere (CI logs, pipes) it is printed as plain lines: once per step and
+/// then at most every [`PLAIN_PROGRESS_INTERVAL`].
+struct ProgressPrinter {
+    enabled: bool,
+    live: bool,
+    last_plain: Option<(String, Instant)>,
+    last_live: Option<(String, Instant)>,
+    /// Current counter step and when it started
+    step_start: Option<(String, Instant)>,
+}
+
+impl ProgressPrinter {
+    fn new(mode: ProgressMode, quiet: bool) -> Self {
+        use std::io::IsTerminal;
+        Self::with_terminal(mode, quiet, std::io::stderr().is_terminal())
+    }
+
+    fn with_terminal(mode: ProgressMode, quiet: bool, is_terminal: bool) -> Self {
+        Self {
+            enabled: !quiet && mode != ProgressMode::Never,
+            live: mode == ProgressMode::Always || is_terminal,
+            last_plain: None,
+            last_live: None,
+            step_start: None,
+        }
+    }
+
+    /// Counter message for file `current` of `total` in a step, e.g.
+    /// "⏳ [1/3] Checking (12/5000)...". The live line adds throughput and
+    /// ETA once the step has timing to go on.
+    fn counter_message(
+        &mut self,
+        step: &str,
+        current: usize,
+        total: usize,
+        now: Instant,
+    ) -> String {
+        let started = match &self.step_start {
+            Some((name, at)) if name == step => *at,
+            _ => {
+                self.step_start = Some((step.to_string(), now));
+                now
+            }
+        };
+        let mut msg = format!("{} ({}/{})...", step, current, total);
+        if self.live {
+            let processed = current.saturating_sub(1);
+            if let Some((rate, eta)) = progress_eta(processed, total, now - started) {
+                msg.push_str(&format!(" {:.1} files/s, ETA {}", rate, format_eta(eta)));
             }
+        }
+        msg
+    }
 
-            // Run formatter if needed
-            if options.mode == RunMode::FormatOnly {
-                if let Some(formatter) = get_formatter(*lang) {
-                    if formatter.is_available() {
-                        match formatter.format(file) {
-                            Ok(format_result) => {
-                                result.add_format_result(format_result);
-                            }
-                            Err(e) => {
-                                if options.verbose {
-                                    eprintln!("Format error for {}: {}", file.display(), e);
-                                }
-                            }
-                        }
-                    } else {
-                        warn_missing_tool("formatter", *lang, false);
-                    }
-                }
+    /// Text to write for a progress message (empty message clears the line).
+    fn render(&mut self, msg: &str, now: Instant) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+        // Step label, e.g. "⏳ [1/3] Checking" from "⏳ [1/3] Checking (3/10)..."
+        let step = msg.split(" (").next().unwrap_or(msg);
+        if self.live {
+            // Redraw a step's counter at a throttled rate
+            let throttled = !msg.is_empty()
+                && self.last_live.as_ref().is_some_and(|(last_step, at)| {
+                    last_step == step && now.duration_since(*at) < LIVE_PROGRESS_INTERVAL
+                });
+            if throttled {
+                return None;
+            }
+            self.last_live = Some((step.to_string(), now));
+            return Some(format!("\r\x1b[K{}", msg));
+        }
+        if msg.is_empty() {
+            return None;
+        }
+        let due = match &self.last_plain {
+            Some((last_step, at)) => {
+                last_step != step || now.duration_since(*at) >= PLAIN_PROGRESS_INTERVAL
             }
+            None => true,
+        };
+        if !due {
+            return None;
+        }
+        self.last_plain = Some((step.to_string(), now));
+        Some(format!("{}\n", msg))
+    }
 
-            // Run checker if needed
-            if options.mode == RunMode::CheckOnly {
-                for issue in run_checker_on_file(file, *lang, options.verbose) {
-                    result.add_issue(issue);
+    /// Print progress message (respects quiet mode and the progress mode)
+    fn print_progress(&mut self, msg: &str) {
+        if let Some(text) = self.render(msg, Instant::now()) {
+            eprint!("{}", text);
+            use std::io::Write;
+            let _ = std::io::stderr().flush();
+        }
+    }
+
+    /// Run `work`, with a spinner before `msg` on the live progress line
+    /// while it runs, for phases whose total is unknown (e.g. scanning).
+    /// Without a live line, `msg` is printed once as a plain line.
+    fn spin_while<T>(&mut self, msg: &str, work: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return work();
+        }
+        if !self.live {
+            self.print_progress(&format!("⏳ {}", msg));
+            return work();
+        }
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let result = std::thread::scope(|scope| {
+            scope.spawn(move || {
+                use std::io::Write;
+                let start = Instant::now();
+                loop {
+                    eprint!("\r\x1b[K{} {}", spinner_frame(start.elapsed()), msg);
+                    let _ = std::io::stderr().flush();
+                    if !matches!(
+                        stopped.recv_timeout(LIVE_PROGRESS_INTERVAL),
+                        Err(std::sync::mpsc::RecvTimeoutError::Timeout)
+                    ) {
+                        break;
+                    }
                 }
-            }
+            });
+            let result = work();
+            drop(stop);
+            result
+        });
+        self.last_live = None;
+        result
+    }
+
+    /// Print a step's file counter, see [`ProgressPrinter::counter_message`]
+    fn print_counter(&mut self, step: &str, current: usize, total: usize) {
+        if self.enabled {
+            let msg = self.counter_message(step, current, total, Instant::now());
+            self.print_progress(&msg);
         }
-        // Clear progress line
-        print_progress("", options.quiet || options.verbose);
     }
+}
 
-    // Calculate final stats
-    result.count_files_with_issues();
-    result.calculate_exit_code_with_warnings(options.fail_on_warnings);
-    result.duration_ms = start.elapsed().as_millis() as u64;
+/// Lint content read from stdin (`--stdin`).
+///
+/// The content is checked as `language` when given, otherwise as detected
+/// from `filename`; it is an error if neither resolves a language. It is
+/// written to a mirrored copy of `filename` (see [`utils::StagedCopy`]) so
+/// linters pick up the same configuration, and reported issues point at
+/// `filename` (or `<stdin>`).
+/// Nothing is formatted: the run is check-only.
+pub fn run_stdin(
+    content: &str,
+    filename: Option<&Path>,
+    language: Option<Language>,
+    options: &RunOptions,
+) -> Result<RunResult> {
+    let lang = language
+        .or_else(|| filename.and_then(Language::from_path))
+        .ok_or_else(|| {
+            LintisError::UnsupportedLanguage(match filename {
+                Some(name) => format!(
+                    "cannot tell the language of {}; pass --stdin-language",
+                    name.display()
+                ),
+                None => "stdin needs --stdin-filename or --stdin-language".to_string(),
+            })
+        })?;
 
+    // Without a filename, lint as an unnamed file of the working directory
+    let copy_target = match filename {
+        Some(name) => name.to_path_buf(),
+        None => {
+            let ext = match lang {
+                Language::Cpp => "cpp",
+                _ => lang.extensions()[0],
+            };
+            PathBuf::from(format!("stdin.{}", ext))
+        }
+    };
+    let copy = utils::StagedCopy::with_content(&copy_target, content).ok_or_else(|| {
+        LintisError::Io(std::io::Error::other(format!(
+            "cannot write stdin to {}",
+            copy_target.display()
+        )))
+    })?;
+
+    let mut result = run(&RunOptions {
+        paths: vec![copy.path().to_path_buf()],
+        mode: RunMode::CheckOnly,
+        staged: false,
+        language_override: Some(lang),
+        ..options.clone()
+    })?;
+    let reported = filename.map_or_else(|| PathBuf::from("<stdin>"), Path::to_path_buf);
+    for issue in &mut result.issues {
+        copy.restore_path(&mut issue.file_path, &reported);
+        for related in &mut issue.related {
+            if let Some(file) = related.file.as_mut() {
+                copy.restore_path(file, &reported);
+            }
+        }
+    }
     Ok(result)
 }
 
-// Re-export commonly used types
-pub use utils::types::{FormatResult, LintIssue, Severity};
+/// Main entry point for running linthis.
+pub fn run(options: &RunOptions) -> Result<RunResult> {
+    use utils::types::RunModeKind;
+
+    let start = Instant::now();
+    let mut result = RunResult::new();
+    let baseline = options
+        .baseline
+        .as_deref()
+        .map(baseline::Baseline::load)
+        .transpose()?;
+    let capture = match (&options.replay_dir, &options.record_dir) {
+        (Some(dir), _) => Some(utils::capture::ToolCapture::replay(
+            dir,
+            &utils::get_project_root(),
+        )?),
+        (None, Some(dir)) => Some(utils::capture::ToolCapture::record(
+            dir,
+            &utils::get_project_root(),
+        )?),
+        (None, None) => None,
+    };
+
+    // Set run mode for appropriate output messages
+    result.run_mode = match options.mode {
+        RunMode::Both => RunModeKind::Both,
+        RunMode::CheckOnly => RunModeKind::CheckOnly,
+        RunMode::FormatOnly => RunModeKind::FormatOnly,
+        RunMode::FormatCheck => RunModeKind::FormatCheck,
+    };
+
+    // Print plugins in use
+    if !options.quiet && !options.plugins.is_empty() {
+        eprintln!("📦 Plugins: {}", options.plugins.join(", "));
+    }
+
+    // Collect files to process, with a spinner while the total is unknown
+    let mut progress = ProgressPrinter::new(options.progress, options.quiet || options.verbose);
+    let (files, path_warnings) = if progress.enabled {
+        progress.spin_while("Scanning files...", || walk_run_paths(options))
+    } else {
+        if !options.quiet {
+            eprint!("⏳ Scanning files...");
+            use std::io::Write;
+            let _ = std::io::stderr().flush();
+        }
+        walk_run_paths(options)
+    };
+
+    // Guard against pointing linthis at a huge tree by accident
+    if let Some(limit) = options.max_files {
+        if files.len() > limit {
+            if !options.quiet {
+                eprint!("\r\x1b[K");
+            }
+            return Err(LintisError::TooManyFiles {
+                found: files.len(),
+                limit,
+            });
+        }
+    }
+
+    // Print warnings about paths (clear line first, then print warnings)
+    if !path_warnings.is_empty() && !options.quiet {
+        eprint!("\r\x1b[K"); // Clear "Scanning files..." line
+        for warning in &path_warnings {
+            eprintln!("\x1b[33mWarning\x1b[0m: {}", warning);
+        }
+        eprint!("⏳ Found {} files, checking...", files.len());
+        use std::io::Write;
+        let _ = std::io::stderr().flush();
+    } else if !options.quiet {
+        eprint!("\r\x1b[K⏳ Found {} files, checking...", files.len());
+        use std::io::Write;
+        let _ = std::io::stderr().flush();
+    }
+
+    if options.verbose {
+        eprintln!();
+        eprintln!("Found {} files to process", files.len());
+    }
+
+    // Build file-to-handler map (custom registered tools take precedence)
+    let mut file_langs: Vec<_> = files
+        .iter()
+        .filter_map(|f| FileHandler::for_options(f, options).map(|h| (f, h)))
+        .collect();
+
+    // Options per file, for files under directory-scoped configs
+    let file_options = apply_config_overlays(&mut file_langs, options);
+    let options_for = |file: &Path| file_options.get(file).map_or(options, |o| o.as_ref());
+
+    // Set total_files to actual processable files count
+    result.total_files = file_langs.len();
+
+    // Tool availability is probed lazily, only for languages with files
+    let probe = ToolProbe {
+        capture: capture.map(Arc::new),
+        ..Default::default()
+    };
+
+    prefetch_go_lint(&file_langs, options, &probe);
+    // Files skipped by --safe that were already reported
+    let mut safe_warned = HashSet::new();
+
+    let mut slowest = SlowestFiles::new(options.slowest);
+    // Time spent on files that go through later passes, reported (and
+    // removed) once a file's last pass is done
+    let mut pending_times: HashMap<PathBuf, Duration> = HashMap::new();
+
+    // For RunMode::Both: lint → format → lint (only files with issues)
+    // --fail-fast breaks out of 'passes; every path ends at the same finish_run
+    if options.mode == RunMode::Both {
+        'passes: {
+            // Step 1: First lint pass (before formatting)
+            if options.verbose {
+                eprintln!("Step 1: Checking for issues...");
+            }
+            let mut issues_before = Vec::new();
+            // First-pass issues keyed by the checked file (not the path the tool
+            // reported), so they can be carried over without matching paths
+            let mut issues_by_file: HashMap<PathBuf, Vec<utils::types::LintIssue>> = HashMap::new();
+            // Files with Error-severity issues, skipped by --no-format-on-error
+            let mut files_with_errors: HashSet<PathBuf> = HashSet::new();
+            let total_files = file_langs.len();
+            for (idx, (file, handler)) in file_langs.iter().enumerate() {
+                progress.print_counter("⏳ [1/3] Checking", idx + 1, total_files);
+                let file_start = Instant::now();
+                let file_issues = run_checker_on_file(file, *handler, options_for(file), &probe);
+                if should_abort(options, &file_issues, None) {
+                    slowest.record(file, file_start.elapsed());
+                    result.issues = issues_before;
+                    result.issues.extend(file_issues);
+                    result.aborted = true;
+                    break 'passes;
+                }
+                if file_issues.is_empty() {
+                    slowest.record(file, file_start.elapsed());
+                } else {
+                    if file_issues.iter().any(|i| i.severity == Severity::Error) {
+                        files_with_errors.insert((*file).clone());
+                    }
+                    if options.slowest > 0 {
+                        pending_times.insert((*file).clone(), file_start.elapsed());
+                    }
+                    issues_by_file.insert((*file).clone(), file_issues.clone());
+                }
+                issues_before.extend(file_issues);
+            }
+            result.issues_before_format = issues_before.len();
+
+            // Step 2: Format files (only files with issues to save time)
+            if options.verbose {
+                eprintln!(
+                    "Step 2: Formatting {} files with issues...",
+                    issues_by_file.len()
+                );
+            }
+            let mut formatted_files: Vec<(&PathBuf, FileHandler)> = Vec::new();
+            // Added after the before/after diff, which they aren't part of
+            let mut idempotency_issues = Vec::new();
+            let files_to_format: Vec<_> = file_langs
+                .iter()
+                .filter(|(f, _)| issues_by_file.contains_key(*f))
+                .collect();
+            let format_total = files_to_format.len();
+            for (idx, (file, handler)) in files_to_format.iter().enumerate() {
+                progress.print_counter("⏳ [2/3] Formatting", idx + 1, format_total);
+                if !autofix_enabled(*handler, options_for(file))
+                    || skip_in_safe_mode(file, options, &mut safe_warned)
+                {
+                    continue;
+                }
+                if options.no_format_on_error && files_with_errors.contains(*file) {
+                    if options.verbose {
+                        eprintln!("Skipping format of {} (has lint errors)", file.display());
+                    }
+                    continue;
+                }
+                let file_start = Instant::now();
+                let mut abort = false;
+                let mut changed = false;
+                if let Some(import_result) = run_import_pass(file, *handler, options_for(file)) {
+                    changed = import_result.changed;
+                    abort = should_abort(options, &[], Some(&import_result));
+                    result.add_import_result(import_result);
+                }
+                let format_result = if abort {
+                    None
+                } else {
+                    run_whitespace_pass(
+                        file,
+                        options_for(file),
+                        run_formatter_on_file(file, *handler, options, &probe),
+                    )
+                };
+                if let Some(format_result) = format_result {
+                    if format_result.changed {
+                        changed = true;
+                        if options.verify_idempotent {
+                            idempotency_issues
+                                .extend(verify_idempotent(file, *handler, options, &probe));
+                        }
+                    }
+                    abort = should_abort(options, &[], Some(&format_result));
+                    result.add_format_result(format_result);
+                }
+                if let Some(total) = pending_times.get_mut(*file) {
+                    *total += file_start.elapsed();
+                }
+                if abort {
+                    result.issues = issues_before;
+                    result.aborted = true;
+                    break 'passes;
+                }
+                if changed {
+                    formatted_files.push((*file, *handler));
+                }
+            }
+
+            // Step 3: Second lint pass (by default only re-check files that were formatted)
+            let recheck_files =
+                select_recheck_files(&file_langs, &formatted_files, options.recheck);
+            if options.verbose {
+                eprintln!("Step 3: Rechecking {} files...", recheck_files.len());
+            }
+
+            // Keep original issues for files that aren't rechecked
+            let rechecked: HashSet<&PathBuf> =
+                recheck_files.iter().map(|(file, _)| *file).collect();
+            for (file, _) in &file_langs {
+                if rechecked.contains(file) {
+                    continue;
+                }
+                if let Some(elapsed) = pending_times.remove(*file) {
+                    slowest.record(file, elapsed);
+                }
+                if let Some(file_issues) = issues_by_file.remove(*file) {
+                    for issue in file_issues {
+                        result.add_issue(issue);
+                    }
+                }
+            }
+
+            // Re-check in parallel; results are collected in file order
+            if !recheck_files.is_empty() {
+                progress.print_progress(&format!(
+                    "⏳ [3/3] Rechecking {} files...",
+                    recheck_files.len()
+                ));
+            }
+            let aborted = AtomicBool::new(false);
+            let rechecks: Vec<_> = recheck_files
+                .par_iter()
+                .map(|(file, handler)| {
+                    if aborted.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    let file_start = Instant::now();
+                    let file_issues =
+                        run_checker_on_file(file, *handler, options_for(file), &probe);
+                    if should_abort(options, &file_issues, None) {
+                        aborted.store(true, Ordering::Relaxed);
+                    }
+                    Some((*file, file_issues, file_start.elapsed()))
+                })
+                .collect();
+            for (file, file_issues, elapsed) in rechecks.into_iter().flatten() {
+                let abort = should_abort(options, &file_issues, None);
+                for issue in file_issues {
+                    result.add_issue(issue);
+                }
+                if let Some(total) = pending_times.remove(file) {
+                    slowest.record(file, total + elapsed);
+                }
+                if abort {
+                    result.aborted = true;
+                    break;
+                }
+            }
+
+            // Diff issues before and after formatting (fixed vs. newly introduced)
+            if !formatted_files.is_empty() {
+                result.count_issue_changes(&issues_before);
+            }
+            for issue in idempotency_issues {
+                result.add_issue(issue);
+            }
+        }
+    } else {
+        // FormatOnly, FormatCheck or CheckOnly mode
+        let total_files = file_langs.len();
+        let mode_name = match options.mode {
+            RunMode::FormatOnly => "Formatting",
+            RunMode::FormatCheck => "Checking format",
+            _ => "Checking",
+        };
+        for (idx, (file, handler)) in file_langs.iter().enumerate() {
+            progress.print_counter(&format!("⏳ {}", mode_name), idx + 1, total_files);
+            if options.verbose {
+                eprintln!("Processing: {} ({})", file.display(), handler.name());
+            }
+            let file_start = Instant::now();
+
+            // Run formatter if needed
+            let mut abort = false;
+            if options.mode == RunMode::FormatOnly
+                && autofix_enabled(*handler, options_for(file))
+                && !skip_in_safe_mode(file, options, &mut safe_warned)
+            {
+                if let Some(import_result) = run_import_pass(file, *handler, options_for(file)) {
+                    abort = should_abort(options, &[], Some(&import_result));
+                    result.add_import_result(import_result);
+                }
+                if !abort {
+                    if let Some(format_result) = run_whitespace_pass(
+                        file,
+                        options_for(file),
+                        run_formatter_on_file(file, *handler, options, &probe),
+                    ) {
+                        if format_result.changed && options.verify_idempotent {
+                            if let Some(issue) = verify_idempotent(file, *handler, options, &probe)
+                            {
+                                result.add_issue(issue);
+                            }
+                        }
+                        abort = should_abort(options, &[], Some(&format_result));
+                        result.add_format_result(format_result);
+                    }
+                }
+            }
+
+            // Check formatting without writing
+            if options.mode == RunMode::FormatCheck
+                && run_format_check_on_file(file, *handler, options, &probe) == Some(true)
+            {
+                result.unformatted_files.push((*file).clone());
+            }
+
+            // Run checker if needed
+            if options.mode == RunMode::CheckOnly {
+                let file_issues = run_checker_on_file(file, *handler, options_for(file), &probe);
+                abort = should_abort(options, &file_issues, None);
+                for issue in file_issues {
+                    result.add_issue(issue);
+                }
+            }
+            slowest.record(file, file_start.elapsed());
+            if abort {
+                result.aborted = true;
+                break;
+            }
+        }
+    }
+
+    // Files left pending by --fail-fast
+    for (file, elapsed) in &pending_times {
+        slowest.record(file, *elapsed);
+    }
+
+    // Clear progress line
+    progress.print_progress("");
+
+    // Calculate final stats
+    finish_run(
+        &mut result,
+        options,
+        &probe,
+        start,
+        slowest,
+        baseline.as_ref(),
+    );
+
+    Ok(result)
+}
+
+// Re-export commonly used types
+pub use registry::{
+    register_checker, register_formatter, register_language_checker, register_named_checker,
+};
+pub use utils::types::{FormatResult, LintIssue, Severity};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use checkers::CheckResult;
+    use std::sync::{Arc, Mutex};
+
+    /// Records which languages had their checker probed
+    struct ProbeRecorder(Language, Arc<Mutex<Vec<Language>>>);
+
+    impl Checker for ProbeRecorder {
+        fn name(&self) -> &str {
+            "probe-recorder"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, _path: &Path) -> CheckResult<Vec<LintIssue>> {
+            Ok(Vec::new())
+        }
+
+        fn is_available(&self) -> bool {
+            self.1.lock().unwrap().push(self.0);
+            true
+        }
+    }
+
+    #[test]
+    fn test_lang_restricted_run_only_probes_requested_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("app.py"), "x = 1\n").unwrap();
+        std::fs::write(root.join("index.ts"), "let x = 1;\n").unwrap();
+        std::fs::write(root.join("view.h"), "@interface V\n@end\n").unwrap();
+
+        let probed = Arc::new(Mutex::new(Vec::new()));
+        let languages = [
+            Language::Rust,
+            Language::Python,
+            Language::TypeScript,
+            Language::ObjectiveC,
+        ];
+        for lang in languages {
+            registry::register_language_checker_in(
+                root,
+                lang,
+                Box::new(ProbeRecorder(lang, probed.clone())),
+            );
+        }
+        let result = run(&RunOptions {
+            paths: vec![root.to_path_buf()],
+            mode: RunMode::CheckOnly,
+            languages: vec![Language::Rust],
+            quiet: true,
+            ..Default::default()
+        });
+        for lang in languages {
+            registry::unregister_language_checker_in(root, lang);
+        }
+
+        assert_eq!(result.unwrap().total_files, 1);
+        assert_eq!(*probed.lock().unwrap(), vec![Language::Rust]);
+    }
+
+    struct CustomExtChecker;
+
+    static CUSTOM_CHECK_CALLS: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
+
+    impl Checker for CustomExtChecker {
+        fn name(&self) -> &str {
+            "custom-ext-lint"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            CUSTOM_CHECK_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![LintIssue::new(
+                path.to_path_buf(),
+                1,
+                "custom issue".to_string(),
+                Severity::Warning,
+            )
+            .with_source("custom-ext-lint".to_string())])
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_registered_checker_is_invoked_for_custom_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("schema.customext");
+        std::fs::write(&file, "message A {}\n").unwrap();
+
+        register_checker("customext", Box::new(|| Box::new(CustomExtChecker)));
+
+        let options = RunOptions {
+            paths: vec![file.clone()],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            ..Default::default()
+        };
+        let result = run(&options).unwrap();
+        registry::unregister("customext");
+
+        assert_eq!(
+            CUSTOM_CHECK_CALLS.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        assert_eq!(result.total_files, 1);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].message, "custom issue");
+        assert_eq!(result.issues[0].language, None);
+    }
+
+    /// Checker shelling out to a fake tool binary
+    struct FakeToolChecker(PathBuf);
+
+    impl Checker for FakeToolChecker {
+        fn name(&self) -> &str {
+            "fake-tool"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            let output = std::process::Command::new(&self.0)
+                .arg(path)
+                .output()
+                .map_err(|e| CheckerError::spawn("fake-tool", e))?;
+            utils::check_tool_status("fake-tool", &output, &[1])?;
+            // The fake tool reports findings as a JSON array of messages
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if stdout.trim().is_empty() {
+                return Ok(Vec::new());
+            }
+            let messages: Vec<String> = serde_json::from_str(&stdout)
+                .map_err(|_| CheckerError::parse("fake-tool", &stdout))?;
+            Ok(messages
+                .into_iter()
+                .map(|m| LintIssue::new(path.to_path_buf(), 1, m, Severity::Warning))
+                .collect())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fake_tool_exits_map_to_checker_errors() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("input.txt");
+        std::fs::write(&file, "content\n").unwrap();
+        let check = |script: &str| {
+            let tool = dir.path().join("fake-tool");
+            std::fs::write(&tool, format!("#!/bin/sh\n{}\n", script)).unwrap();
+            std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+            FakeToolChecker(tool).check(&file)
+        };
+
+        assert!(check("exit 0").unwrap().is_empty());
+        assert_eq!(check("echo '[\"unused\"]'; exit 1").unwrap().len(), 1);
+        assert!(matches!(
+            check("exit 139"),
+            Err(CheckerError::ToolCrashed {
+                code: Some(139),
+                ..
+            })
+        ));
+        assert!(matches!(
+            check("kill -KILL $$"),
+            Err(CheckerError::ToolCrashed { code: None, .. })
+        ));
+        let err = check("echo 'Traceback (most recent call last)'; exit 1").unwrap_err();
+        assert!(matches!(err, CheckerError::ParseError { .. }));
+        assert!(err.is_tool_failure());
+
+        let missing = FakeToolChecker(dir.path().join("no-such-tool")).check(&file);
+        let err = missing.unwrap_err();
+        assert!(matches!(err, CheckerError::ToolNotFound { .. }));
+        assert!(!err.is_tool_failure());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_crashing_tool_is_reported_as_error_not_clean() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("schema.crashext");
+        std::fs::write(&file, "message A {}\n").unwrap();
+        let tool = dir.path().join("fake-tool");
+        std::fs::write(&tool, "#!/bin/sh\nexit 139\n").unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let factory_tool = tool.clone();
+        register_checker(
+            "crashext",
+            Box::new(move || Box::new(FakeToolChecker(factory_tool.clone()))),
+        );
+        let options = RunOptions {
+            paths: vec![file.clone()],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            ..Default::default()
+        };
+        let result = run(&options).unwrap();
+        registry::unregister("crashext");
+
+        assert_eq!(result.exit_code, 2);
+        assert_eq!(result.issues.len(), 1);
+        let issue = &result.issues[0];
+        assert!(issue.is_tool_failure());
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.source.as_deref(), Some("fake-tool"));
+        assert!(issue.message.contains("exit code 139"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fail_fast_stops_after_first_tool_error() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let tool = dir.path().join("fake-tool");
+        // Crash on the first file, succeed on the others
+        std::fs::write(
+            &tool,
+            "#!/bin/sh\necho \"$1\" >> \"$(dirname \"$0\")/calls\"\ncase \"$1\" in *a.fastext) exit 139;; esac\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let files: Vec<PathBuf> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let file = dir.path().join(format!("{}.fastext", name));
+                std::fs::write(&file, "x\n").unwrap();
+                file
+            })
+            .collect();
+
+        let factory_tool = tool.clone();
+        register_checker(
+            "fastext",
+            Box::new(move || Box::new(FakeToolChecker(factory_tool.clone()))),
+        );
+        let calls = |fail_fast: bool| {
+            let _ = std::fs::remove_file(dir.path().join("calls"));
+            let result = run(&RunOptions {
+                paths: files.clone(),
+                mode: RunMode::CheckOnly,
+                quiet: true,
+                fail_fast,
+                ..Default::default()
+            })
+            .unwrap();
+            let calls = std::fs::read_to_string(dir.path().join("calls")).unwrap();
+            (result, calls.lines().count())
+        };
+        let (collected, collected_calls) = calls(false);
+        let (stopped, stopped_calls) = calls(true);
+        registry::unregister("fastext");
+
+        assert_eq!(collected_calls, 3);
+        assert!(!collected.aborted);
+        assert_eq!(collected.exit_code, 2);
+
+        assert_eq!(stopped_calls, 1);
+        assert!(stopped.aborted);
+        assert_eq!(stopped.exit_code, 2);
+        assert_eq!(stopped.issues.len(), 1);
+        assert!(stopped.issues[0].is_tool_failure());
+    }
+
+    /// Formatter whose tool always fails
+    struct FailingFormatter;
+
+    impl Formatter for FailingFormatter {
+        fn name(&self) -> &str {
+            "failing-fmt"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn format(&self, _path: &Path) -> Result<FormatResult> {
+            Err(LintisError::Formatter("tool crashed".to_string()))
+        }
+
+        fn check(&self, _path: &Path) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_formatter_error_fails_run_only_with_fail_fast() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.failfmt");
+        std::fs::write(&file, "ok\n").unwrap();
+
+        register_formatter("failfmt", Box::new(|| Box::new(FailingFormatter)));
+        let run_with = |fail_fast: bool| {
+            run(&RunOptions {
+                paths: vec![file.clone()],
+                mode: RunMode::FormatOnly,
+                quiet: true,
+                fail_fast,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let collected = run_with(false);
+        let stopped = run_with(true);
+        registry::unregister("failfmt");
+
+        assert_eq!(collected.exit_code, 0);
+        assert!(collected.format_results.is_empty());
+        assert!(stopped.aborted);
+        assert_eq!(stopped.exit_code, 2);
+    }
+
+    struct RuleEngineChecker;
+
+    impl Checker for RuleEngineChecker {
+        fn name(&self) -> &str {
+            "rule-engine"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[Language::Go]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            Ok(vec![LintIssue::new(
+                path.to_path_buf(),
+                3,
+                "synthetic rule violation".to_string(),
+                Severity::Error,
+            )
+            .with_code("RE001".to_string())])
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_registered_language_checker_appears_in_run_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("main.go");
+        std::fs::write(&file, "package main\n").unwrap();
+
+        registry::register_language_checker_in(
+            dir.path(),
+            Language::Go,
+            Box::new(RuleEngineChecker),
+        );
+
+        let options = RunOptions {
+            paths: vec![file.clone()],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            ..Default::default()
+        };
+        let result = run(&options).unwrap();
+        registry::unregister_language_checker_in(dir.path(), Language::Go);
+
+        assert_eq!(result.issues.len(), 1);
+        let issue = &result.issues[0];
+        assert_eq!(issue.message, "synthetic rule violation");
+        assert_eq!(issue.code.as_deref(), Some("RE001"));
+        assert_eq!(issue.language, Some(Language::Go));
+        assert_eq!(result.exit_code, 1);
+    }
+
+    #[test]
+    fn test_stdin_language_overrides_header_detection() {
+        struct CppOnlyChecker;
+
+        impl Checker for CppOnlyChecker {
+            fn name(&self) -> &str {
+                "cpp-only"
+            }
+
+            fn supported_languages(&self) -> &[Language] {
+                &[Language::Cpp]
+            }
+
+            fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+                Ok(vec![LintIssue::new(
+                    path.to_path_buf(),
+                    1,
+                    "checked as C++".to_string(),
+                    Severity::Warning,
+                )])
+            }
+
+            fn is_available(&self) -> bool {
+                true
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        // Detected as Objective-C from its content
+        let filename = dir.path().join("view.h");
+        // The stdin copy lives outside `dir`, so pick the checker by a unique name
+        let options = RunOptions {
+            quiet: true,
+            checkers: HashMap::from([(Language::Cpp, vec!["stdin-cpp-only".to_string()])]),
+            ..Default::default()
+        };
+
+        register_named_checker("stdin-cpp-only", Box::new(|| Box::new(CppOnlyChecker)));
+        let result = run_stdin(
+            "@interface View\n@end\n",
+            Some(&filename),
+            Some(Language::Cpp),
+            &options,
+        );
+        registry::unregister_named_checker("stdin-cpp-only");
+
+        let result = result.unwrap();
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].message, "checked as C++");
+        assert_eq!(result.issues[0].language, Some(Language::Cpp));
+        assert_eq!(result.issues[0].file_path, filename);
+        // The copy is gone
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let unknown = run_stdin("x\n", Some(Path::new("notes.txt")), None, &options);
+        assert!(matches!(unknown, Err(LintisError::UnsupportedLanguage(_))));
+    }
+
+    #[test]
+    fn test_slowest_files_keeps_top_n_sorted() {
+        let mut slowest = SlowestFiles::new(3);
+        for (name, ms) in [
+            ("a.rs", 5),
+            ("b.rs", 50),
+            ("c.rs", 1),
+            ("d.rs", 120),
+            ("e.rs", 30),
+            ("f.rs", 50),
+        ] {
+            slowest.record(Path::new(name), Duration::from_millis(ms));
+        }
+
+        let sorted = slowest.into_sorted();
+        let durations: Vec<_> = sorted.iter().map(|t| t.duration_ms).collect();
+        assert_eq!(durations, vec![120, 50, 50]);
+        assert_eq!(sorted[0].path, PathBuf::from("d.rs"));
+        assert_eq!(sorted.len(), 3);
+    }
+
+    #[test]
+    fn test_slowest_files_disabled() {
+        let mut slowest = SlowestFiles::new(0);
+        slowest.record(Path::new("a.rs"), Duration::from_millis(10));
+        assert!(slowest.into_sorted().is_empty());
+    }
+
+    struct FixedIssueChecker(&'static str);
+
+    impl Checker for FixedIssueChecker {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[Language::Python]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            Ok(vec![LintIssue::new(
+                path.to_path_buf(),
+                1,
+                format!("issue from {}", self.0),
+                Severity::Warning,
+            )
+            .with_source(self.0.to_string())])
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_checker_chain_merges_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.py");
+        std::fs::write(&file, "x = 1\n").unwrap();
+
+        register_named_checker(
+            "chain-first",
+            Box::new(|| Box::new(FixedIssueChecker("chain-first"))),
+        );
+        register_named_checker(
+            "chain-second",
+            Box::new(|| Box::new(FixedIssueChecker("chain-second"))),
+        );
+
+        let options = RunOptions {
+            paths: vec![file.clone()],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            checkers: HashMap::from([(
+                Language::Python,
+                vec!["chain-first".to_string(), "chain-second".to_string()],
+            )]),
+            ..Default::default()
+        };
+        let result = run(&options).unwrap();
+        registry::unregister_named_checker("chain-first");
+        registry::unregister_named_checker("chain-second");
+
+        let sources: Vec<_> = result
+            .issues
+            .iter()
+            .filter_map(|i| i.source.as_deref())
+            .collect();
+        assert_eq!(sources, vec!["chain-first", "chain-second"]);
+    }
+
+    #[test]
+    fn test_default_checker_chain_is_single_tool() {
+        assert_eq!(
+            get_checkers(Language::Rust, Path::new("lib.rs"), &[], ToolEnv::default()).len(),
+            1
+        );
+        assert!(get_named_checker(Language::Cpp, "cpplint", ToolEnv::default()).is_some());
+        assert!(get_named_checker(Language::Rust, "cpplint", ToolEnv::default()).is_none());
+        assert!(get_named_checker(Language::Python, "bandit", ToolEnv::default()).is_some());
+    }
+
+    /// Checker that reports every file against a fixed config file
+    struct ConfiguredChecker(PathBuf);
+
+    impl Checker for ConfiguredChecker {
+        fn name(&self) -> &str {
+            "configured"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            Ok(vec![LintIssue::new(
+                path.to_path_buf(),
+                1,
+                "configured issue".to_string(),
+                Severity::Warning,
+            )])
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn config_path(&self, _path: &Path) -> Option<PathBuf> {
+            Some(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_verbose_reports_discovered_config_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let ruff_toml = dir.path().join("ruff.toml");
+        std::fs::write(&ruff_toml, "line-length = 100\n").unwrap();
+        let py = dir.path().join("app.py");
+        std::fs::write(&py, "x = 1\n").unwrap();
+
+        let checker = ruff_checker(ToolEnv::default());
+        let config = checker.config_path(&py);
+        assert_eq!(config.as_deref(), Some(ruff_toml.as_path()));
+        let line = tool_use_line(
+            &py,
+            "checker",
+            &checker.binary(),
+            Some("ruff 0.6.0"),
+            config.as_deref(),
+        );
+        assert_eq!(
+            line,
+            format!(
+                "{}: checker ruff (ruff 0.6.0), config {}",
+                py.display(),
+                ruff_toml.display()
+            )
+        );
+
+        // Issues carry the config of the checker that reported them
+        let file = dir.path().join("a.cfgext");
+        std::fs::write(&file, "x\n").unwrap();
+        let rc = dir.path().join(".configuredrc");
+        let factory_rc = rc.clone();
+        register_checker(
+            "cfgext",
+            Box::new(move || Box::new(ConfiguredChecker(factory_rc.clone()))),
+        );
+        let options = RunOptions {
+            paths: vec![file],
+            mode: RunMode::CheckOnly,
+            verbose: true,
+            ..Default::default()
+        };
+        let result = run(&options).unwrap();
+        let quiet_result = run(&RunOptions {
+            verbose: false,
+            quiet: true,
+            ..options
+        })
+        .unwrap();
+        registry::unregister("cfgext");
+
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].config_path.as_deref(), Some(rc.as_path()));
+        // Resolved only in verbose runs
+        assert_eq!(quiet_result.issues[0].config_path, None);
+    }
+
+    #[test]
+    fn test_config_free_passes_isolation_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".clang-format"), "BasedOnStyle: LLVM\n").unwrap();
+        std::fs::write(dir.path().join(".clang-tidy"), "Checks: '-*'\n").unwrap();
+        let file = |name: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "x\n").unwrap();
+            path
+        };
+        let (py, ts, cpp) = (file("app.py"), file("app.ts"), file("main.cpp"));
+
+        // Explicit plugin configs are dropped as well
+        let configs: ToolConfigs = [
+            (
+                (Language::Python, "ruff".to_string()),
+                dir.path().join("ruff.toml"),
+            ),
+            (
+                (Language::TypeScript, "prettier".to_string()),
+                dir.path().join(".prettierrc"),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let tools = ToolEnv {
+            configs: Some(&configs),
+            isolated: true,
+            ..Default::default()
+        };
+        let args = |cmd: std::process::Command| -> Vec<String> {
+            cmd.get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        let ruff_check = args(ruff_checker(tools).command(&py));
+        assert!(ruff_check.contains(&"--isolated".to_string()));
+        assert!(!ruff_check.contains(&"--config".to_string()));
+        let ruff_format = args(ruff_formatter(tools).command(&["--check"], &py));
+        assert!(ruff_format.contains(&"--isolated".to_string()));
+        assert!(!ruff_format.contains(&"--config".to_string()));
+
+        let eslint = args(eslint_checker(Language::TypeScript, tools).command(&ts));
+        assert!(eslint
+            .iter()
+            .any(|a| a == "--no-eslintrc" || a == "--no-config-lookup"));
+        let prettier =
+            args(prettier_formatter(Language::TypeScript, tools).command("--check", &ts));
+        assert!(prettier.contains(&"--no-config".to_string()));
+        assert!(!prettier.contains(&"--config".to_string()));
+
+        let clang_format = |isolated| {
+            args(
+                CppFormatter::new()
+                    .with_isolated(isolated)
+                    .clang_format_command(&cpp, "cpp"),
+            )
+        };
+        assert_eq!(clang_format(true), vec!["-style=Google"]);
+        assert!(clang_format(false)[0].starts_with("-style=file:"));
+
+        let clang_tidy = args(
+            cpp_checker(Language::Cpp, tools).clang_tidy_command(Path::new("clang-tidy"), &cpp),
+        );
+        assert!(clang_tidy.contains(&"--config={}".to_string()));
+        assert!(!clang_tidy.iter().any(|a| a.starts_with("--config-file")));
+    }
+
+    #[test]
+    fn test_formatter_capabilities_per_language() {
+        for lang in Language::ALL {
+            let formatter = get_formatter(lang).unwrap();
+            assert!(formatter.supports_check(), "{:?}", lang);
+            // The C/C++ formatter runs in-place fixers around clang-format
+            let stdout = !matches!(lang, Language::Cpp | Language::ObjectiveC);
+            assert_eq!(formatter.supports_stdout(), stdout, "{:?}", lang);
+        }
+
+        let imports = |lang| formatters::ImportOrganizer::for_language(lang).unwrap();
+        assert!(imports(Language::Go).supports_stdout());
+        assert!(!imports(Language::Python).supports_stdout());
+
+        let includes = fixers::IncludeOrderFixer::default();
+        assert!(includes.supports_check());
+        assert!(!includes.supports_stdout());
+    }
+
+    #[test]
+    fn test_recheck_scope_parse() {
+        assert_eq!(
+            RecheckScope::parse("formatted"),
+            Some(RecheckScope::Formatted)
+        );
+        assert_eq!(RecheckScope::parse("NONE"), Some(RecheckScope::None));
+        assert_eq!(RecheckScope::parse("all"), Some(RecheckScope::All));
+        assert_eq!(RecheckScope::parse("some"), None);
+        assert_eq!(RecheckScope::default(), RecheckScope::Formatted);
+    }
+
+    #[test]
+    fn test_select_recheck_files_per_scope() {
+        let files = [
+            PathBuf::from("a.rs"),
+            PathBuf::from("b.rs"),
+            PathBuf::from("c.rs"),
+        ];
+        let handler = FileHandler::Builtin(Language::Rust);
+        let file_langs: Vec<_> = files.iter().map(|f| (f, handler)).collect();
+        let formatted = vec![(&files[1], handler)];
+
+        let selected = |scope| select_recheck_files(&file_langs, &formatted, scope);
+
+        assert_eq!(selected(RecheckScope::Formatted), formatted);
+        assert!(selected(RecheckScope::None).is_empty());
+        assert_eq!(selected(RecheckScope::All), file_langs);
+    }
+
+    /// Treats a file as formatted when it has no trailing whitespace.
+    struct TrailingSpaceFormatter;
+
+    impl Formatter for TrailingSpaceFormatter {
+        fn name(&self) -> &str {
+            "trailing-space"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn format(&self, path: &Path) -> Result<FormatResult> {
+            let content = std::fs::read_to_string(path)?;
+            let formatted: String = content
+                .lines()
+                .map(|l| l.trim_end().to_string() + "\n")
+                .collect();
+            if formatted == content {
+                return Ok(FormatResult::unchanged(path.to_path_buf()));
+            }
+            std::fs::write(path, formatted)?;
+            Ok(FormatResult::changed(path.to_path_buf()))
+        }
+
+        fn check(&self, path: &Path) -> Result<bool> {
+            let content = std::fs::read_to_string(path)?;
+            Ok(content.lines().any(|l| l != l.trim_end()))
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    /// Formatter whose tool is never installed
+    struct MissingFormatter;
+
+    impl Formatter for MissingFormatter {
+        fn name(&self) -> &str {
+            "missing-fmt"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn format(&self, _path: &Path) -> Result<FormatResult> {
+            panic!("an unavailable formatter must not run");
+        }
+
+        fn check(&self, _path: &Path) -> Result<bool> {
+            panic!("an unavailable formatter must not run");
+        }
+
+        fn is_available(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_max_files_aborts_oversized_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.limitext", "b.limitext", "c.limitext"] {
+            std::fs::write(dir.path().join(name), "ok\n").unwrap();
+        }
+
+        register_checker("limitext", Box::new(|| Box::new(BadLineChecker)));
+        let run_with = |max_files: Option<usize>| {
+            run(&RunOptions {
+                paths: vec![dir.path().to_path_buf()],
+                mode: RunMode::CheckOnly,
+                quiet: true,
+                max_files,
+                ..Default::default()
+            })
+        };
+        let over = run_with(Some(2));
+        let at_limit = run_with(Some(3));
+        let unlimited = run_with(None);
+        registry::unregister("limitext");
+
+        assert!(matches!(
+            over,
+            Err(LintisError::TooManyFiles { found: 3, limit: 2 })
+        ));
+        assert_eq!(at_limit.unwrap().total_files, 3);
+        assert_eq!(unlimited.unwrap().total_files, 3);
+    }
+
+    #[test]
+    fn test_replay_run_uses_recordings_and_reports_unrecorded_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("bundle");
+        let py = dir.path().join("a.py");
+        let js = dir.path().join("b.js");
+        std::fs::write(&py, "import os\n").unwrap();
+        std::fs::write(&js, "let b;\n").unwrap();
+
+        // A bundle holding one ruff run on a.py, and no eslint run
+        std::fs::create_dir_all(&bundle).unwrap();
+        let recording = utils::capture::Recording {
+            tool: "ruff".to_string(),
+            target: utils::relative_path(&py, &utils::get_project_root())
+                .to_string_lossy()
+                .replace('\\', "/"),
+            command: vec!["ruff".to_string()],
+            code: Some(1),
+            stdout: serde_json::json!([{
+                "code": "F401",
+                "message": "`os` imported but unused",
+                "location": {"row": 1, "column": 8},
+                "end_location": {"row": 1, "column": 10},
+                "filename": py,
+            }])
+            .to_string(),
+            stderr: String::new(),
+        };
+        std::fs::write(
+            bundle.join("00000-ruff.json"),
+            serde_json::to_string(&recording).unwrap(),
+        )
+        .unwrap();
+
+        let result = run(&RunOptions {
+            paths: vec![py.clone(), js],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            replay_dir: Some(bundle),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].file_path, py);
+        assert_eq!(result.issues[0].code.as_deref(), Some("F401"));
+        // eslint has no recording: a missing tool, not a spawn error
+        assert_eq!(result.exit_code, exit_code::ISSUES);
+        assert_eq!(result.missing_tools.len(), 1);
+    }
+
+    #[test]
+    fn test_baseline_hides_accepted_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.baselined");
+        let baseline_file = dir.path().join("baseline.json");
+        std::fs::write(&file, "bad\nok\n").unwrap();
+
+        register_checker("baselined", Box::new(|| Box::new(BadLineChecker)));
+        let options = RunOptions {
+            paths: vec![file.clone()],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            ..Default::default()
+        };
+        let accepted = run(&options).unwrap();
+        baseline::update_baseline(&baseline_file, &accepted.issues, &utils::get_project_root())
+            .unwrap();
+        // The accepted issue moves down a line and a new one appears
+        std::fs::write(&file, "ok\nbad\nbad\n").unwrap();
+        let result = run(&RunOptions {
+            baseline: Some(baseline_file),
+            ..options
+        });
+        registry::unregister("baselined");
+
+        let result = result.unwrap();
+        assert_eq!(accepted.issues.len(), 1);
+        assert_eq!(result.baselined_issues, 1);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.exit_code, exit_code::ISSUES);
+    }
+
+    #[test]
+    fn test_exit_code_contract() {
+        let dir = tempfile::tempdir().unwrap();
+        let clean = dir.path().join("clean.exitcodes");
+        let issues = dir.path().join("issues.exitcodes");
+        let untooled = dir.path().join("a.exitfmt");
+        std::fs::write(&clean, "ok\n").unwrap();
+        std::fs::write(&issues, "bad\n").unwrap();
+        std::fs::write(&untooled, "ok\n").unwrap();
+
+        register_checker("exitcodes", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("exitfmt", Box::new(|| Box::new(MissingFormatter)));
+        let run_with = |path: &PathBuf, mode: RunMode, max_files: Option<usize>| {
+            run(&RunOptions {
+                paths: vec![path.clone()],
+                mode,
+                quiet: true,
+                require_tools: true,
+                max_files,
+                ..Default::default()
+            })
+        };
+        let passed = run_with(&clean, RunMode::CheckOnly, None);
+        let failed = run_with(&issues, RunMode::CheckOnly, None);
+        let tool_error = run_with(&untooled, RunMode::FormatOnly, None);
+        let config_error = run_with(&dir.path().to_path_buf(), RunMode::CheckOnly, Some(1));
+        registry::unregister("exitcodes");
+        registry::unregister("exitfmt");
+
+        assert_eq!(passed.unwrap().exit_code, exit_code::SUCCESS);
+        assert_eq!(failed.unwrap().exit_code, exit_code::ISSUES);
+        assert_eq!(tool_error.unwrap().exit_code, exit_code::TOOL_ERROR);
+        assert_eq!(
+            config_error.unwrap_err().exit_code(),
+            exit_code::CONFIG_ERROR
+        );
+        assert_eq!(
+            LintisError::NotAGitRepo("--staged".to_string()).exit_code(),
+            exit_code::CONFIG_ERROR
+        );
+        assert_eq!(
+            LintisError::Formatter("crashed".to_string()).exit_code(),
+            exit_code::TOOL_ERROR
+        );
+    }
+
+    #[test]
+    fn test_require_tools_fails_on_missing_formatter() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.missingfmt");
+        std::fs::write(&file, "value = 1\n").unwrap();
+
+        register_formatter("missingfmt", Box::new(|| Box::new(MissingFormatter)));
+        let run_with = |require_tools: bool| {
+            let options = RunOptions {
+                paths: vec![file.clone()],
+                mode: RunMode::FormatOnly,
+                quiet: true,
+                require_tools,
+                ..Default::default()
+            };
+            run(&options).unwrap()
+        };
+        let lenient = run_with(false);
+        let strict = run_with(true);
+        registry::unregister("missingfmt");
+
+        assert_eq!(lenient.exit_code, 0);
+        assert_eq!(strict.exit_code, 2);
+        assert_eq!(
+            strict.missing_tools,
+            vec!["missing-fmt (.missingfmt formatter)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_format_check_fails_unformatted_and_passes_formatted() {
+        let dir = tempfile::tempdir().unwrap();
+        let unformatted = dir.path().join("bad.fmtcheck");
+        let formatted = dir.path().join("good.fmtcheck");
+        std::fs::write(&unformatted, "value = 1   \n").unwrap();
+        std::fs::write(&formatted, "value = 1\n").unwrap();
+
+        register_formatter("fmtcheck", Box::new(|| Box::new(TrailingSpaceFormatter)));
+
+        let run_check = |path: &PathBuf| {
+            run(&RunOptions {
+                paths: vec![path.clone()],
+                mode: RunMode::FormatCheck,
+                quiet: true,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let bad = run_check(&unformatted);
+        let good = run_check(&formatted);
+        registry::unregister("fmtcheck");
+
+        assert_eq!(bad.unformatted_files, vec![unformatted.clone()]);
+        assert_eq!(bad.exit_code, 1);
+        assert!(bad.issues.is_empty());
+        // Nothing is written in format-check mode
+        assert_eq!(
+            std::fs::read_to_string(&unformatted).unwrap(),
+            "value = 1   \n"
+        );
+
+        assert!(good.unformatted_files.is_empty());
+        assert_eq!(good.exit_code, 0);
+    }
+
+    #[test]
+    fn test_format_check_json_lists_files_that_would_change() {
+        use utils::output::{format_result, OutputFormat};
+
+        let dir = tempfile::tempdir().unwrap();
+        let unformatted = dir.path().join("bad.fmtjson");
+        std::fs::write(&unformatted, "value = 1   \n").unwrap();
+        std::fs::write(dir.path().join("good.fmtjson"), "value = 1\n").unwrap();
+
+        register_formatter("fmtjson", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let result = run(&RunOptions {
+            paths: vec![dir.path().to_path_buf()],
+            mode: RunMode::FormatCheck,
+            quiet: true,
+            ..Default::default()
+        })
+        .unwrap();
+        registry::unregister("fmtjson");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&format_result(&result, OutputFormat::FormatCheckJson)).unwrap();
+        assert_eq!(report["files"], serde_json::json!([unformatted]));
+        assert_eq!(report["all_formatted"], false);
+        assert_ne!(result.exit_code, exit_code::SUCCESS);
+    }
+
+    #[test]
+    fn test_dumped_result_renders_like_the_run() {
+        use utils::output::{format_result, OutputFormat};
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.dump"), "ok\nbad   \n").unwrap();
+        std::fs::write(dir.path().join("b.dump"), "bad\n").unwrap();
+
+        register_checker("dump", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("dump", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let result = run(&RunOptions {
+            paths: vec![dir.path().to_path_buf()],
+            mode: RunMode::Both,
+            quiet: true,
+            ..Default::default()
+        })
+        .unwrap();
+        registry::unregister("dump");
+        assert!(!result.issues.is_empty() && result.files_formatted == 1);
+
+        let dump = dir.path().join("results.json");
+        result.dump(&dump).unwrap();
+        let loaded = RunResult::load(&dump).unwrap();
+        for format in [
+            OutputFormat::Human,
+            OutputFormat::Json,
+            OutputFormat::GithubActions,
+            OutputFormat::Csv,
+            OutputFormat::Sarif,
+            OutputFormat::Junit,
+        ] {
+            assert_eq!(
+                format_result(&loaded, format),
+                format_result(&result, format),
+                "{:?}",
+                format
+            );
+        }
+        assert_eq!(loaded.exit_code, result.exit_code);
+
+        std::fs::write(&dump, "[]").unwrap();
+        assert!(matches!(
+            RunResult::load(&dump),
+            Err(LintisError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_safe_mode_skips_file_with_unstaged_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .current_dir(root)
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q"]) {
+            // git not available
+            return;
+        }
+
+        let dirty = root.join("dirty.safefmt");
+        let clean = root.join("clean.safefmt");
+        std::fs::write(&dirty, "a = 1\n").unwrap();
+        std::fs::write(&clean, "b = 2   \n").unwrap();
+        assert!(git(&["add", "."]));
+        assert!(git(&[
+            "-c",
+            "user.name=test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "-m",
+            "init"
+        ]));
+        // Unstaged edit with trailing whitespace the formatter would strip
+        std::fs::write(&dirty, "a = 1   \n").unwrap();
+        // Untracked files have nothing staged to lose
+        let untracked = root.join("new.safefmt");
+        std::fs::write(&untracked, "c = 3   \n").unwrap();
+
+        register_formatter("safefmt", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let result = run(&RunOptions {
+            paths: vec![dirty.clone(), clean.clone(), untracked.clone()],
+            mode: RunMode::FormatOnly,
+            quiet: true,
+            safe: true,
+            ..Default::default()
+        })
+        .unwrap();
+        registry::unregister("safefmt");
+
+        assert_eq!(std::fs::read_to_string(&dirty).unwrap(), "a = 1   \n");
+        assert_eq!(std::fs::read_to_string(&clean).unwrap(), "b = 2\n");
+        assert_eq!(std::fs::read_to_string(&untracked).unwrap(), "c = 3\n");
+        assert_eq!(result.files_formatted, 2);
+    }
+
+    #[test]
+    fn test_autofix_disabled_leaves_files_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.nofix");
+        std::fs::write(&file, "bad   \n").unwrap();
+
+        register_checker("nofix", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("nofix", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let results: Vec<RunResult> = [RunMode::Both, RunMode::FormatOnly]
+            .into_iter()
+            .map(|mode| {
+                run(&RunOptions {
+                    paths: vec![file.clone()],
+                    mode,
+                    quiet: true,
+                    autofix: false,
+                    ..Default::default()
+                })
+                .unwrap()
+            })
+            .collect();
+        registry::unregister("nofix");
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "bad   \n");
+        assert!(results.iter().all(|r| r.files_formatted == 0));
+        // Report-only: the issue is still reported
+        assert_eq!(results[0].issues.len(), 1);
+    }
+
+    #[test]
+    fn test_parallel_recheck_attributes_issues_to_files() {
+        let dir = tempfile::tempdir().unwrap();
+        // File i has its bad line at line i + 1; all but the last get formatted
+        let files: Vec<PathBuf> = (0..12)
+            .map(|i| {
+                let path = dir.path().join(format!("f{:02}.recheck", i));
+                let trailing = if i < 11 { "   " } else { "" };
+                let content = "ok\n".repeat(i) + "bad" + trailing + "\n";
+                std::fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+
+        register_checker("recheck", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("recheck", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let result = run(&RunOptions {
+            paths: vec![dir.path().to_path_buf()],
+            mode: RunMode::Both,
+            quiet: true,
+            ..Default::default()
+        })
+        .unwrap();
+        registry::unregister("recheck");
+
+        assert_eq!(result.files_formatted, 11);
+        assert_eq!(result.issues.len(), files.len());
+        for (i, file) in files.iter().enumerate() {
+            let issues: Vec<_> = result
+                .issues
+                .iter()
+                .filter(|issue| issue.file_path.file_name() == file.file_name())
+                .collect();
+            assert_eq!(issues.len(), 1, "{}", file.display());
+            assert_eq!(issues[0].line, i + 1);
+        }
+    }
+
+    /// Flags every line containing "bad", reporting the canonical path
+    struct CanonicalPathChecker;
+
+    impl Checker for CanonicalPathChecker {
+        fn name(&self) -> &str {
+            "canonical-path"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            let canonical = path.canonicalize()?;
+            Ok(BadLineChecker
+                .check(path)?
+                .into_iter()
+                .map(|mut issue| {
+                    issue.file_path = canonical.clone();
+                    issue
+                })
+                .collect())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unformatted_file_keeps_issues_reported_with_absolute_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("real");
+        std::fs::create_dir(&real).unwrap();
+        std::fs::write(real.join("kept.canon"), "ok\nbad\n").unwrap();
+        std::fs::write(real.join("fixed.canon"), "bad   \n").unwrap();
+        // The walked path goes through a symlink; the tool reports the real one
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        register_checker("canon", Box::new(|| Box::new(CanonicalPathChecker)));
+        register_formatter("canon", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let result = run(&RunOptions {
+            paths: vec![link.join("kept.canon"), link.join("fixed.canon")],
+            mode: RunMode::Both,
+            quiet: true,
+            ..Default::default()
+        })
+        .unwrap();
+        registry::unregister("canon");
+
+        assert_eq!(result.files_formatted, 1);
+        let kept: Vec<_> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.file_path.ends_with("kept.canon"))
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].line, 2);
+        assert_eq!(
+            kept[0].file_path,
+            real.canonicalize().unwrap().join("kept.canon")
+        );
+        assert_eq!(result.issues.len(), 2);
+    }
+
+    #[test]
+    fn test_list_files_matches_walker_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "src/app.py",
+            "src/lib.rs",
+            "src/notes.txt",
+            "vendor/dep.py",
+            "src/gen/schema.ts",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "x\n").unwrap();
+        }
+
+        let (files, warnings) = list_files(&RunOptions {
+            paths: vec![dir.path().to_path_buf()],
+            exclude_patterns: vec!["**/vendor/**".to_string(), "**/gen/**".to_string()],
+            ..Default::default()
+        });
+        assert!(warnings.is_empty());
+        let mut listed: Vec<_> = files
+            .iter()
+            .map(|f| {
+                (
+                    f.path.strip_prefix(dir.path()).unwrap().to_path_buf(),
+                    f.language,
+                )
+            })
+            .collect();
+        listed.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            listed,
+            [
+                (PathBuf::from("src/app.py"), Some(Language::Python)),
+                (PathBuf::from("src/lib.rs"), Some(Language::Rust)),
+            ]
+        );
+
+        let (files, _) = list_files(&RunOptions {
+            paths: vec![dir.path().to_path_buf()],
+            languages: vec![Language::Python],
+            ..Default::default()
+        });
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| f.language == Some(Language::Python)));
+    }
+
+    /// Formatter that never converges: every pass appends a line
+    struct AppendingFormatter;
+
+    impl Formatter for AppendingFormatter {
+        fn name(&self) -> &str {
+            "appending"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn format(&self, path: &Path) -> Result<FormatResult> {
+            let content = std::fs::read_to_string(path)?;
+            std::fs::write(path, content + "// again\n")?;
+            Ok(FormatResult::changed(path.to_path_buf()))
+        }
+
+        fn check(&self, _path: &Path) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_verify_idempotent_flags_non_convergent_formatter() {
+        let dir = tempfile::tempdir().unwrap();
+        let unstable = dir.path().join("app.unstable");
+        let stable = dir.path().join("app.stable");
+
+        register_checker("unstable", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("unstable", Box::new(|| Box::new(AppendingFormatter)));
+        register_checker("stable", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("stable", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let run_with = |mode: RunMode, verify_idempotent: bool| {
+            std::fs::write(&unstable, "bad   \n").unwrap();
+            std::fs::write(&stable, "bad   \n").unwrap();
+            run(&RunOptions {
+                paths: vec![unstable.clone(), stable.clone()],
+                mode,
+                quiet: true,
+                verify_idempotent,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let non_idempotent = |result: &RunResult| -> Vec<PathBuf> {
+            result
+                .issues
+                .iter()
+                .filter(|i| i.code.as_deref() == Some(utils::types::NON_IDEMPOTENT_FORMAT_CODE))
+                .map(|i| i.file_path.clone())
+                .collect()
+        };
+        let both = run_with(RunMode::Both, true);
+        let format_only = run_with(RunMode::FormatOnly, true);
+        // The second pass only checks, so the file was formatted once
+        let formatted_once = std::fs::read_to_string(&unstable).unwrap();
+        let unverified = run_with(RunMode::Both, false);
+        registry::unregister("unstable");
+        registry::unregister("stable");
+
+        assert_eq!(non_idempotent(&both), vec![unstable.clone()]);
+        assert_eq!(non_idempotent(&format_only), vec![unstable.clone()]);
+        assert!(non_idempotent(&unverified).is_empty());
+        assert_eq!(formatted_once, "bad   \n// again\n");
+        let warning = both
+            .issues
+            .iter()
+            .find(|i| i.code.as_deref() == Some(utils::types::NON_IDEMPOTENT_FORMAT_CODE))
+            .unwrap();
+        assert_eq!(warning.severity, Severity::Warning);
+        assert!(warning.message.contains("appending"));
+        // The check is not counted as an issue introduced by formatting
+        assert_eq!(both.issues_introduced, unverified.issues_introduced);
+    }
+
+    /// Import organizer recording the files it was run on
+    struct RecordingOrganizer(Arc<Mutex<Vec<PathBuf>>>);
+
+    impl Formatter for RecordingOrganizer {
+        fn name(&self) -> &str {
+            "recording-imports"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[Language::Python]
+        }
+
+        fn format(&self, path: &Path) -> Result<FormatResult> {
+            self.0.lock().unwrap().push(path.to_path_buf());
+            Ok(FormatResult::changed(path.to_path_buf()))
+        }
+
+        fn check(&self, _path: &Path) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_version_info_json() {
+        let json = serde_json::to_value(VersionInfo::current()).unwrap();
+        let mut keys: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, ["build_date", "git_commit", "target", "version"]);
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            json["build_date"].as_str().unwrap().len(),
+            "2024-01-01".len()
+        );
+        assert!(!json["target"].as_str().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_pass_runs_only_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.py");
+        std::fs::write(&file, "import sys\nimport os\n").unwrap();
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorder = calls.clone();
+        registry::register_import_organizer_in(
+            dir.path(),
+            Language::Python,
+            Box::new(move || Box::new(RecordingOrganizer(recorder.clone()))),
+        );
+        let run_with = |organize_imports: bool| {
+            run(&RunOptions {
+                paths: vec![file.clone()],
+                mode: RunMode::FormatOnly,
+                quiet: true,
+                organize_imports,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let disabled = run_with(false);
+        let enabled = run_with(true);
+        registry::unregister_import_organizer_in(dir.path(), Language::Python);
+
+        assert_eq!(disabled.imports_organized, 0);
+        assert!(disabled.import_results.is_empty());
+        assert_eq!(enabled.imports_organized, 1);
+        assert_eq!(enabled.import_results.len(), 1);
+        assert_eq!(*calls.lock().unwrap(), vec![file]);
+    }
+
+    #[test]
+    fn test_no_format_on_error_skips_files_with_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.errfmt");
+
+        register_checker("errfmt", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("errfmt", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        let run_with = |no_format_on_error: bool| {
+            std::fs::write(&file, "bad   \n").unwrap();
+            run(&RunOptions {
+                paths: vec![file.clone()],
+                quiet: true,
+                no_format_on_error,
+                ..Default::default()
+            })
+            .unwrap();
+            std::fs::read_to_string(&file).unwrap()
+        };
+        let skipped = run_with(true);
+        let formatted = run_with(false);
+        registry::unregister("errfmt");
+
+        assert_eq!(skipped, "bad   \n");
+        assert_eq!(formatted, "bad\n");
+    }
+
+    #[test]
+    fn test_relative_to_rewrites_reported_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("pkg/src")).unwrap();
+        let file = root.join("pkg/src/app.relext");
+        std::fs::write(&file, "bad   \n").unwrap();
+
+        register_checker("relext", Box::new(|| Box::new(BadLineChecker)));
+        register_formatter("relext", Box::new(|| Box::new(TrailingSpaceFormatter)));
+        // Staged runs pass absolute paths
+        let result = run(&RunOptions {
+            paths: vec![file.clone()],
+            quiet: true,
+            staged: true,
+            relative_to: Some(root.join("pkg")),
+            ..Default::default()
+        })
+        .unwrap();
+        registry::unregister("relext");
+
+        assert!(!result.issues.is_empty());
+        for issue in &result.issues {
+            assert_eq!(issue.file_path, Path::new("src/app.relext"));
+        }
+        assert_eq!(result.format_results.len(), 1);
+        assert_eq!(
+            result.format_results[0].file_path,
+            Path::new("src/app.relext")
+        );
+    }
+
+    #[test]
+    fn test_autofix_language_override() {
+        let options = RunOptions {
+            autofix: false,
+            autofix_overrides: HashMap::from([(Language::Python, true)]),
+            ..Default::default()
+        };
+        assert!(autofix_enabled(
+            FileHandler::Builtin(Language::Python),
+            &options
+        ));
+        assert!(!autofix_enabled(
+            FileHandler::Builtin(Language::Go),
+            &options
+        ));
+        assert!(!autofix_enabled(FileHandler::Custom, &options));
+
+        let options = RunOptions {
+            autofix_overrides: HashMap::from([(Language::Java, false)]),
+            ..Default::default()
+        };
+        assert!(!autofix_enabled(
+            FileHandler::Builtin(Language::Java),
+            &options
+        ));
+        assert!(autofix_enabled(
+            FileHandler::Builtin(Language::Rust),
+            &options
+        ));
+    }
+
+    #[test]
+    fn test_directory_config_disables_language_in_subtree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("app.py", "x = 1\n");
+        write("legacy/old.py", "x = 1\n");
+        write("legacy/.linthis/config.toml", "[python]\nenabled = false\n");
+        write("tools/build.py", "x = 1\n");
+        write("tools/schema_gen.py", "x = 1\n");
+        write("tools/.linthis/config.toml", "excludes = [\"*_gen.py\"]\n");
+
+        let run_with = |config_overlay_root: Option<PathBuf>| {
+            run(&RunOptions {
+                paths: vec![root.to_path_buf()],
+                mode: RunMode::FormatCheck,
+                quiet: true,
+                config_overlay_root,
+                ..Default::default()
+            })
+            .unwrap()
+            .total_files
+        };
+        assert_eq!(run_with(None), 4);
+        // legacy/old.py is disabled, tools/schema_gen.py excluded
+        assert_eq!(run_with(Some(root.to_path_buf())), 2);
+    }
+
+    /// Flags every line containing "bad"
+    struct BadLineChecker;
+
+    impl Checker for BadLineChecker {
+        fn name(&self) -> &str {
+            "bad-line"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+            let content = std::fs::read_to_string(path)?;
+            Ok(content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.contains("bad"))
+                .map(|(idx, _)| {
+                    LintIssue::new(
+                        path.to_path_buf(),
+                        idx + 1,
+                        "bad line".to_string(),
+                        Severity::Error,
+                    )
+                })
+                .collect())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_staged_checks_partially_staged_file_as_staged_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .current_dir(root)
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q"]) {
+            // git not available
+            return;
+        }
+
+        // Staged content is bad, the unstaged worktree edit fixes it
+        let file = root.join("app.stagedext");
+        std::fs::write(&file, "good\nbad\n").unwrap();
+        assert!(git(&["add", "app.stagedext"]));
+        std::fs::write(&file, "good\nfixed\n").unwrap();
+
+        register_checker("stagedext", Box::new(|| Box::new(BadLineChecker)));
+        let options = RunOptions {
+            paths: vec![file.clone()],
+            mode: RunMode::CheckOnly,
+            quiet: true,
+            ..Default::default()
+        };
+        let worktree = run(&options).unwrap();
+        let staged = run(&RunOptions {
+            staged: true,
+            ..options
+        })
+        .unwrap();
+        registry::unregister("stagedext");
+
+        assert!(worktree.issues.is_empty());
+        assert_eq!(staged.issues.len(), 1);
+        assert_eq!(staged.issues[0].file_path, file);
+        assert_eq!(staged.issues[0].line, 2);
+        // The staged copy is cleaned up and the worktree is untouched
+        assert_eq!(std::fs::read_dir(root).unwrap().count(), 2);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "good\nfixed\n");
+    }
+
+    #[test]
+    fn test_tool_warning_shown_once_across_threads() {
+        let barrier = std::sync::Barrier::new(16);
+        let warned = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..16 {
+                scope.spawn(|| {
+                    barrier.wait();
+                    if should_warn_tool("linter-concurrency-test-tool") {
+                        warned.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+        assert_eq!(warned.into_inner(), 1);
+        assert!(!should_warn_tool("linter-concurrency-test-tool"));
+
+        // A reset lets the next run warn again
+        reset_tool_warnings();
+        assert!(should_warn_tool("linter-concurrency-test-tool"));
+    }
+
+    #[test]
+    fn test_progress_never_emits_nothing() {
+        let now = Instant::now();
+        for is_terminal in [true, false] {
+            let mut progress =
+                ProgressPrinter::with_terminal(ProgressMode::Never, false, is_terminal);
+            assert_eq!(progress.render("⏳ [1/3] Checking (1/2)...", now), None);
+            assert_eq!(progress.render("", now), None);
+        }
+    }
+
+    #[test]
+    fn test_spinner_frame_advances_with_elapsed_time() {
+        let frame = |ms: u64| spinner_frame(Duration::from_millis(ms));
+        let tick = LIVE_PROGRESS_INTERVAL.as_millis() as u64;
+        assert_eq!(frame(0), SPINNER_FRAMES[0]);
+        assert_eq!(frame(tick - 1), SPINNER_FRAMES[0]);
+        assert_eq!(frame(tick), SPINNER_FRAMES[1]);
+        assert_eq!(frame(3 * tick + tick / 2), SPINNER_FRAMES[3]);
+        // Wraps around after the last frame
+        let cycle = tick * SPINNER_FRAMES.len() as u64;
+        assert_eq!(frame(cycle), SPINNER_FRAMES[0]);
+        assert_eq!(frame(cycle + tick), SPINNER_FRAMES[1]);
+
+        // Without progress output the work just runs
+        let mut never = ProgressPrinter::with_terminal(ProgressMode::Never, false, true);
+        assert_eq!(never.spin_while("Scanning files...", || 42), 42);
+    }
+
+    #[test]
+    fn test_progress_live_on_terminal() {
+        let now = Instant::now();
+        let mut progress = ProgressPrinter::with_terminal(ProgressMode::Auto, false, true);
+        assert_eq!(
+            progress.render("⏳ Checking (1/2)...", now).as_deref(),
+            Some("\r\x1b[K⏳ Checking (1/2)...")
+        );
+
+        let mut quiet = ProgressPrinter::with_terminal(ProgressMode::Always, true, true);
+        assert_eq!(quiet.render("⏳ Checking (1/2)...", now), None);
+    }
+
+    #[test]
+    fn test_progress_plain_lines_without_terminal() {
+        let start = Instant::now();
+        let mut progress = ProgressPrinter::with_terminal(ProgressMode::Auto, false, false);
+        assert_eq!(
+            progress
+                .render("⏳ [1/3] Checking (1/9)...", start)
+                .as_deref(),
+            Some("⏳ [1/3] Checking (1/9)...\n")
+        );
+        // Throttled within the same step
+        assert_eq!(progress.render("⏳ [1/3] Checking (2/9)...", start), None);
+        // Printed again once the interval has passed
+        let later = start + PLAIN_PROGRESS_INTERVAL;
+        assert!(progress
+            .render("⏳ [1/3] Checking (3/9)...", later)
+            .is_some());
+        // New step prints immediately
+        assert!(progress
+            .render("⏳ [2/3] Formatting (1/1)...", later)
+            .is_some());
+        // Clearing the line is a no-op
+        assert_eq!(progress.render("", later), None);
+
+        // Always forces the live spinner even without a terminal
+        let mut always = ProgressPrinter::with_terminal(ProgressMode::Always, false, false);
+        assert!(always
+            .render("⏳ Checking (1/2)...", start)
+            .unwrap()
+            .starts_with('\r'));
+    }
+
+    #[test]
+    fn test_progress_eta() {
+        assert_eq!(progress_eta(0, 100, Duration::from_secs(5)), None);
+        assert_eq!(progress_eta(10, 100, Duration::ZERO), None);
+
+        // 10 files in 5s: 2 files/s, 90 left take 45s
+        let (rate, eta) = progress_eta(10, 100, Duration::from_secs(5)).unwrap();
+        assert!((rate - 2.0).abs() < 1e-9);
+        assert_eq!(eta.as_secs(), 45);
+        assert_eq!(format_eta(eta), "45s");
+        assert_eq!(
+            progress_eta(100, 100, Duration::from_secs(5)).unwrap().1,
+            Duration::ZERO
+        );
+
+        assert_eq!(format_eta(Duration::from_millis(300)), "<1s");
+        assert_eq!(format_eta(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_eta(Duration::from_secs(3720)), "1h 02m");
+    }
+
+    #[test]
+    fn test_progress_counter_shows_eta_on_live_line_only() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(5);
+        let mut live = ProgressPrinter::with_terminal(ProgressMode::Auto, false, true);
+        assert_eq!(
+            live.counter_message("⏳ Checking", 1, 100, start),
+            "⏳ Checking (1/100)..."
+        );
+        assert_eq!(
+            live.counter_message("⏳ Checking", 11, 100, later),
+            "⏳ Checking (11/100)... 2.0 files/s, ETA 45s"
+        );
+        // Redraws of the same step are throttled
+        assert!(live.render("⏳ Checking (12/100)...", later).is_some());
+        assert_eq!(live.render("⏳ Checking (13/100)...", later), None);
+        assert!(live.render("", later).is_some());
+
+        let mut plain = ProgressPrinter::with_terminal(ProgressMode::Auto, false, false);
+        plain.counter_message("⏳ Checking", 1, 100, start);
+        assert_eq!(
+            plain.counter_message("⏳ Checking", 11, 100, later),
+            "⏳ Checking (11/100)..."
+        );
+    }
+
+    #[test]
+    fn test_tool_version_report_covers_every_tool() {
+        let report = tool_version_report();
+        let tools: Vec<(&str, &str)> = report.iter().map(|t| (t.kind, t.tool.as_str())).collect();
+        assert_eq!(
+            tools,
+            vec![
+                ("checker", "clippy"),
+                ("checker", "ruff"),
+                ("checker", "bandit"),
+                ("checker", "eslint"),
+                ("checker", "golangci-lint"),
+                ("checker", "checkstyle"),
+                ("checker", "clang-tidy"),
+                ("checker", "cpplint"),
+                ("formatter", "rustfmt"),
+                ("formatter", "ruff"),
+                ("formatter", "prettier"),
+                ("formatter", "gofmt"),
+                ("formatter", "clang-format"),
+            ]
+        );
+        // Every language has both a checker and a formatter in the report
+        for lang in [
+            Language::Cpp,
+            Language::ObjectiveC,
+            Language::Java,
+            Language::Python,
+            Language::Rust,
+            Language::Go,
+            Language::JavaScript,
+            Language::TypeScript,
+        ] {
+            for kind in ["checker", "formatter"] {
+                assert!(report
+                    .iter()
+                    .any(|t| t.kind == kind && t.languages.contains(&lang)));
+            }
+        }
+    }
+}
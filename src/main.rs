@@ -12,17 +12,24 @@
 
 use clap::Parser;
 use colored::Colorize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
-use linthis::utils::output::{format_result, OutputFormat};
-use linthis::{run, Language, RunMode, RunOptions, Severity};
+use linthis::utils::output::{
+    detect_ci_output_format, render_with, CountReporter, FixFilter, HumanReporter, OutputFormat,
+    PathStyle, RenderOptions, Reporter,
+};
+use linthis::{
+    exit_code, run, Language, ProgressMode, RecheckScope, RunMode, RunOptions, Severity,
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "linthis")]
 #[command(
     author,
     version,
+    disable_version_flag = true,
     about = "A fast, cross-platform multi-language linter and formatter"
 )]
 struct Cli {
@@ -39,18 +46,55 @@ struct Cli {
     #[arg(short = 'f', long)]
     format_only: bool,
 
-    /// Check only staged files (git cached)
+    /// Check formatting without modifying files (fails if any file would change)
+    #[arg(long, conflicts_with_all = ["check_only", "format_only"])]
+    format_check: bool,
+
+    /// Skip formatting files that have unstaged changes
+    #[arg(long)]
+    safe: bool,
+
+    /// With --safe, format files even if they have unstaged changes;
+    /// with --init-configs, overwrite existing config files; run past the
+    /// `max_files` limit
+    #[arg(long)]
+    force: bool,
+
+    /// Check only staged files (git cached); files with unstaged edits
+    /// are checked as their staged content
     #[arg(short = 's', long)]
     staged: bool,
 
+    /// Lint content read from stdin instead of files (check only)
+    #[arg(long, conflicts_with_all = ["staged", "format_only", "format_check"])]
+    stdin: bool,
+
+    /// Path the stdin content belongs to: picks the language and tool
+    /// configs, and is the path issues are reported at
+    #[arg(long, value_name = "PATH", requires = "stdin")]
+    stdin_filename: Option<PathBuf>,
+
+    /// Language of the stdin content, overriding detection from
+    /// --stdin-filename (e.g. cpp for an ambiguous .h file)
+    #[arg(long, value_name = "LANG", requires = "stdin")]
+    stdin_language: Option<String>,
+
     /// Specify languages to check (comma-separated: rust,python,typescript)
     #[arg(short, long, value_delimiter = ',')]
     lang: Option<Vec<String>>,
 
+    /// Treat the configured `languages` as an allow-list that --lang can only narrow
+    #[arg(long)]
+    languages_from_config_only: bool,
+
     /// Exclude patterns (glob patterns)
     #[arg(short, long)]
     exclude: Option<Vec<String>>,
 
+    /// Lint paths matching this glob despite default, gitignore and config excludes
+    #[arg(long, value_name = "GLOB")]
+    audit: Option<Vec<String>>,
+
     /// Disable default exclusions (.git, node_modules, target, etc.)
     #[arg(long)]
     no_default_excludes: bool,
@@ -59,6 +103,10 @@ struct Cli {
     #[arg(long)]
     no_gitignore: bool,
 
+    /// Exclude test files by language convention (foo_test.go, test_foo.py, ...)
+    #[arg(long, visible_alias = "exclude-tests")]
+    no_tests: bool,
+
     /// Path to configuration file
     #[arg(long)]
     config: Option<std::path::PathBuf>,
@@ -71,13 +119,99 @@ struct Cli {
     #[arg(long)]
     init_configs: bool,
 
-    /// Format preset (google, standard, airbnb)
+    /// With --init-configs, only generate configs for these tools (e.g. ruff,prettier)
+    #[arg(long, value_delimiter = ',', requires = "init_configs")]
+    only: Option<Vec<String>>,
+
+    /// With --init-configs, write the configs into this directory (created if needed)
+    #[arg(long, value_name = "DIR", requires = "init_configs")]
+    init_configs_dir: Option<PathBuf>,
+
+    /// With --init-configs, only generate configs for languages present in the project
+    #[arg(long, requires = "init_configs")]
+    detected: bool,
+
+    /// Format preset (google, standard, airbnb, or none to ignore the config's preset)
     #[arg(long)]
     preset: Option<String>,
 
-    /// Output format: human, json, github-actions
-    #[arg(short, long, default_value = "human")]
-    output: String,
+    /// Output format: human, json, github-actions, csv, sarif, junit,
+    /// format-check-json (implies --format-check)
+    /// (default: human; in CI github-actions on GitHub Actions, json elsewhere)
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Print only issue and file counts (a JSON object with `-o json`)
+    #[arg(long)]
+    count: bool,
+
+    /// Print the files that would be processed, without linting them
+    /// (with their language as JSON with `-o json`)
+    #[arg(long)]
+    list_files: bool,
+
+    /// Print the config files found and loaded, in precedence order, and the
+    /// config file each tool gets from plugins or the preset, without linting
+    #[arg(long)]
+    config_path_resolution: bool,
+
+    /// Report paths relative to this directory (default: current directory; `git-root` for the repository root)
+    #[arg(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        default_missing_value = ".",
+        conflicts_with = "path_style"
+    )]
+    relative_to: Option<String>,
+
+    /// How file paths are printed: relative (to the project root, default), absolute
+    #[arg(long, value_name = "STYLE", value_parser = ["relative", "absolute"])]
+    path_style: Option<String>,
+
+    /// Only print issues at or above this severity (counts and exit code still include all issues)
+    #[arg(long, value_name = "LEVEL", value_parser = ["error", "warning", "info", "hint"])]
+    print_severity: Option<String>,
+
+    /// Only print the issues formatting fixed
+    #[arg(long, conflicts_with = "only_unfixed")]
+    only_fixed: bool,
+
+    /// Only print the issues that remain after formatting
+    #[arg(long)]
+    only_unfixed: bool,
+
+    /// Group human output by rule, with a count per rule, to fix one rule at a time
+    #[arg(long)]
+    group_errors_by_rule_first: bool,
+
+    /// Print the home directory as `~` in output paths, e.g. for sharing logs
+    #[arg(long)]
+    redact_home: bool,
+
+    /// Show the diff of each formatted file in human output
+    #[arg(long)]
+    show_diff: bool,
+
+    /// Context lines around each change in diffs (default: 3)
+    #[arg(long, value_name = "N")]
+    diff_context: Option<usize>,
+
+    /// Files re-linted after formatting: formatted (default), none, all
+    #[arg(long, value_name = "SCOPE", value_parser = ["formatted", "none", "all"])]
+    recheck: Option<String>,
+
+    /// Progress display: auto (spinner on a terminal, plain lines otherwise), always, never
+    #[arg(long, value_name = "WHEN", value_parser = ["auto", "always", "never"])]
+    progress: Option<String>,
+
+    /// Print the N slowest files after the run
+    #[arg(long, value_name = "N")]
+    slowest: Option<usize>,
+
+    /// Disable colored output (also honors the NO_COLOR environment variable)
+    #[arg(long)]
+    no_color: bool,
 
     /// Disable auto-saving results to .linthis/result/
     #[arg(long)]
@@ -87,6 +221,35 @@ struct Cli {
     #[arg(long, value_name = "FILE")]
     output_file: Option<PathBuf>,
 
+    /// Write the full run result as JSON, to re-render later with `linthis render`
+    #[arg(long, value_name = "FILE")]
+    dump_issues: Option<PathBuf>,
+
+    /// Run a full check and rewrite the baseline of accepted issues to its
+    /// issues (default file: .linthis/baseline.json); the check must cover
+    /// the whole project, so file selectors are rejected
+    #[arg(
+        long,
+        value_name = "FILE",
+        num_args = 0..=1,
+        default_missing_value = linthis::baseline::DEFAULT_BASELINE_FILE,
+        conflicts_with_all = ["paths", "staged", "stdin", "lang", "relative_to"]
+    )]
+    baseline_update: Option<PathBuf>,
+
+    /// Hide issues accepted in this baseline (default: .linthis/baseline.json
+    /// when it exists)
+    #[arg(long, value_name = "FILE", conflicts_with = "baseline_update")]
+    baseline: Option<PathBuf>,
+
+    /// Save each linter's command line, stdout and stderr to DIR for debugging
+    #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Parse linter output saved with --record instead of running the linters
+    #[arg(long, value_name = "DIR")]
+    replay: Option<PathBuf>,
+
     /// Maximum number of result files to keep (default: 10, 0 = unlimited)
     #[arg(long, default_value = "10")]
     keep_results: usize,
@@ -99,18 +262,74 @@ struct Cli {
     #[arg(short, long)]
     quiet: bool,
 
+    /// Print nothing when all checks pass, full output otherwise
+    #[arg(long, conflicts_with = "quiet")]
+    quiet_if_clean: bool,
+
     /// Fail on warnings (treat warnings as errors for exit code)
     #[arg(short = 'w', long)]
     fail_on_warnings: bool,
 
+    /// Fail when there are more than N warnings (-1 = unlimited)
+    #[arg(long, value_name = "N", allow_negative_numbers = true)]
+    max_warnings: Option<i64>,
+
+    /// Stop at the first tool error (crash or formatter failure) instead of
+    /// collecting all errors
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Fail with exit code 2 when a linter or formatter is missing instead
+    /// of warning and skipping it
+    #[arg(long)]
+    require_tools: bool,
+
+    /// Abort when more than this many files are found (guards against
+    /// pointing linthis at a huge tree by accident)
+    #[arg(long, value_name = "N")]
+    max_files: Option<usize>,
+
+    /// Ignore the max_files limit set in config
+    #[arg(long, conflicts_with = "max_files")]
+    no_max_files: bool,
+
+    /// Don't format files whose first lint pass found errors
+    #[arg(long)]
+    no_format_on_error: bool,
+
+    /// Check files the formatter changed with a second, read-only pass and
+    /// warn when it would change them again
+    #[arg(long, conflicts_with_all = ["check_only", "format_check"])]
+    verify_idempotent: bool,
+
     /// Run benchmark comparing ruff vs flake8+black for Python
     #[arg(long)]
     benchmark: bool,
 
+    /// Print the version and path of every linter/formatter linthis uses
+    #[arg(long)]
+    tool_version_report: bool,
+
     /// Skip loading plugins, use default configuration
     #[arg(long)]
     no_plugin: bool,
 
+    /// Ignore all project, plugin and tool config files; tools run with their defaults
+    #[arg(long, visible_alias = "no-project-config")]
+    config_free: bool,
+
+    /// Reuse the file list cached while no walked directory has changed
+    #[arg(long)]
+    scan_cache: bool,
+
+    /// Print version
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    /// With --version, print version and build metadata as JSON
+    #[arg(long, requires = "version")]
+    json: bool,
+
     /// Plugin subcommands (init, list, clean)
     #[command(subcommand)]
     command: Option<Commands>,
@@ -145,6 +364,27 @@ enum Commands {
         #[command(subcommand)]
         action: HookCommands,
     },
+    /// Remove the plugin cache, scan caches and update-check timestamps
+    Clean {
+        /// Only report what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Upgrade linthis to the latest version on PyPI
+    SelfUpdate {
+        /// Only report whether an update is available and the command that would run
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Render a result saved with --dump-issues without re-running tools
+    Render {
+        /// Result file written by --dump-issues
+        file: PathBuf,
+
+        /// Output format: human, json, github-actions, csv, sarif, junit
+        #[arg(short, long)]
+        output: Option<String>,
+    },
     /// Initialize configuration file
     Init {
         /// Create global configuration (~/.linthis/config.toml)
@@ -332,6 +572,15 @@ enum ConfigCommands {
         #[arg(short, long)]
         global: bool,
     },
+    /// Migrate a CodeCC .code.yml to linthis configuration
+    Migrate {
+        /// Path to the .code.yml to migrate
+        #[arg(default_value = ".code.yml")]
+        input: PathBuf,
+        /// Config file to write (existing files get the excludes appended)
+        #[arg(short, long, default_value = ".linthis/config.toml")]
+        output: PathBuf,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -2414,7 +2663,9 @@ MIT License - See LICENSE file for details.
 
 /// Perform self-update check and optionally upgrade linthis itself
 /// Returns true if update was performed, false otherwise
-fn perform_self_update(self_update_config: Option<&linthis::self_update::SelfUpdateConfig>) -> bool {
+fn perform_self_update(
+    self_update_config: Option<&linthis::self_update::SelfUpdateConfig>,
+) -> bool {
     use linthis::self_update::{SelfUpdateConfig, SelfUpdateManager};
 
     // Use default config if none provided
@@ -2423,7 +2674,11 @@ fn perform_self_update(self_update_config: Option<&linthis::self_update::SelfUpd
 
     // Validate config
     if let Err(e) = config.validate() {
-        eprintln!("{}: Invalid self_auto_update config: {}", "Warning".yellow(), e);
+        eprintln!(
+            "{}: Invalid self_auto_update config: {}",
+            "Warning".yellow(),
+            e
+        );
         return false;
     }
 
@@ -2447,19 +2702,21 @@ fn perform_self_update(self_update_config: Option<&linthis::self_update::SelfUpd
     }
 
     let current = manager.get_current_version();
-    let latest = manager.get_latest_version().unwrap_or_else(|| "unknown".to_string());
+    let latest = manager
+        .get_latest_version()
+        .unwrap_or_else(|| "unknown".to_string());
 
     // Prompt user if needed
-    if config.should_prompt() {
-        if !manager.prompt_user(&current, &latest) {
-            // User declined, update timestamp to avoid repeated prompts
-            let _ = manager.update_last_check_time();
-            return false;
-        }
+    if config.should_prompt() && !manager.prompt_user(&current, &latest) {
+        // User declined, update timestamp to avoid repeated prompts
+        let _ = manager.update_last_check_time();
+        return false;
     }
 
     // Perform upgrade
-    match manager.upgrade() {
+    // Without a known target version there is nothing to verify against
+    let verify = config.verify && latest != "unknown";
+    match manager.upgrade(&latest, verify) {
         Ok(success) => {
             if success {
                 let _ = manager.update_last_check_time();
@@ -2473,6 +2730,244 @@ fn perform_self_update(self_update_config: Option<&linthis::self_update::SelfUpd
     }
 }
 
+/// The `max_files` limit for a run: `--max-files` over config, none with
+/// `--no-max-files` or `--force`
+fn file_limit(
+    cli: Option<usize>,
+    config: Option<usize>,
+    no_max_files: bool,
+    force: bool,
+) -> Option<usize> {
+    if no_max_files || force {
+        None
+    } else {
+        cli.or(config)
+    }
+}
+
+/// Stderr redirected into a temp file while a `--quiet-if-clean` run is in
+/// progress, so it can be replayed if the run turns out not to be clean.
+struct StderrBuffer {
+    #[cfg(unix)]
+    saved: Option<std::os::unix::io::RawFd>,
+    #[cfg(unix)]
+    file: std::fs::File,
+}
+
+impl StderrBuffer {
+    /// Start buffering stderr; None when it can't be redirected.
+    #[cfg(unix)]
+    fn start() -> Option<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = tempfile::tempfile().ok()?;
+        let _ = std::io::Write::flush(&mut std::io::stderr());
+        // SAFETY: plain fd duplication; the saved fd is restored on finish/drop
+        unsafe {
+            let saved = libc::dup(libc::STDERR_FILENO);
+            if saved < 0 {
+                return None;
+            }
+            if libc::dup2(file.as_raw_fd(), libc::STDERR_FILENO) < 0 {
+                libc::close(saved);
+                return None;
+            }
+            Some(Self {
+                saved: Some(saved),
+                file,
+            })
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn start() -> Option<Self> {
+        None
+    }
+
+    /// Restore stderr and return what was written to it meanwhile.
+    #[cfg(unix)]
+    fn finish(mut self) -> Vec<u8> {
+        use std::io::{Read, Seek};
+
+        self.restore();
+        let mut output = Vec::new();
+        if self.file.rewind().is_ok() {
+            let _ = self.file.read_to_end(&mut output);
+        }
+        output
+    }
+
+    #[cfg(not(unix))]
+    fn finish(self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    #[cfg(unix)]
+    fn restore(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            let _ = std::io::Write::flush(&mut std::io::stderr());
+            // SAFETY: `saved` is the stderr fd duplicated in `start`
+            unsafe {
+                libc::dup2(saved, libc::STDERR_FILENO);
+                libc::close(saved);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for StderrBuffer {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// Remove caches and timestamps, or report what would be removed
+fn handle_clean_command(dry_run: bool) -> ExitCode {
+    use linthis::plugin::cache::format_size;
+
+    let cleaned = match linthis::clean::clean(&linthis::clean::clean_targets(), dry_run) {
+        Ok(cleaned) => cleaned,
+        Err(e) => {
+            eprintln!("{}: Failed to clean: {}", "Error".red(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    if cleaned.is_empty() {
+        println!("{} Nothing to clean", "✓".green());
+        return ExitCode::SUCCESS;
+    }
+
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    for item in &cleaned {
+        println!(
+            "{} {} {} ({})",
+            verb,
+            item.kind.label(),
+            item.path.display(),
+            format_size(item.size)
+        );
+    }
+    let total: u64 = cleaned.iter().map(|item| item.size).sum();
+    if dry_run {
+        println!("Would reclaim {}", format_size(total));
+    } else {
+        println!("{} Reclaimed {}", "✓".green(), format_size(total));
+    }
+    ExitCode::SUCCESS
+}
+
+/// Rewrite the baseline to the issues of a finished check and report how it
+/// changed. A check where linters failed or were missing leaves it alone.
+fn update_baseline(
+    path: &std::path::Path,
+    result: &linthis::utils::types::RunResult,
+    project_root: &std::path::Path,
+) -> ExitCode {
+    if result.exit_code == exit_code::TOOL_ERROR || !result.missing_tools.is_empty() {
+        eprintln!(
+            "{}: Not updating {}: some linters failed or are missing",
+            "Error".red(),
+            path.display()
+        );
+        return ExitCode::from(exit_code::TOOL_ERROR as u8);
+    }
+    match linthis::baseline::update_baseline(path, &result.issues, project_root) {
+        Ok(update) => {
+            println!(
+                "{} Updated {}: {} removed (fixed), {} added (accepted), {} total",
+                "✓".green(),
+                path.display(),
+                update.removed,
+                update.added,
+                update.total
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!(
+                "{}: Failed to update {}: {}",
+                "Error".red(),
+                path.display(),
+                e
+            );
+            ExitCode::from(exit_code::CONFIG_ERROR as u8)
+        }
+    }
+}
+
+/// Re-render a result saved with `--dump-issues`
+fn handle_render_command(file: &std::path::Path, output: Option<&str>) -> ExitCode {
+    let result = match linthis::utils::types::RunResult::load(file) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red(), e);
+            return ExitCode::from(e.exit_code() as u8);
+        }
+    };
+    let output_format = match output {
+        Some(name) => match OutputFormat::from_name(name) {
+            Some(format) => Some(format),
+            None => {
+                eprintln!("{}: Unknown output format '{}'", "Error".red(), name);
+                return ExitCode::from(exit_code::CONFIG_ERROR as u8);
+            }
+        },
+        None => detect_ci_output_format(),
+    };
+    let reporter = output_format.map_or_else(
+        || Box::new(HumanReporter) as Box<dyn Reporter>,
+        OutputFormat::reporter,
+    );
+    let rendered = render_with(&result, reporter.as_ref(), &RenderOptions::default());
+    if !rendered.is_empty() {
+        println!("{}", rendered);
+    }
+    ExitCode::from(result.exit_code as u8)
+}
+
+/// Upgrade linthis on request, or report what an upgrade would do
+fn handle_self_update_command(dry_run: bool) -> ExitCode {
+    use linthis::self_update::{SelfUpdateManager, SelfUpdateOutcome, UPGRADE_COMMAND};
+
+    let config = linthis::config::Config::load_merged(&linthis::utils::get_project_root());
+    let verify = config.self_auto_update.is_none_or(|c| c.verify);
+
+    let manager = SelfUpdateManager::new();
+    match manager.self_update(dry_run, verify) {
+        Ok(SelfUpdateOutcome::Unknown { current }) => {
+            eprintln!(
+                "{}: Could not determine the latest version (current: {})",
+                "Error".red(),
+                current
+            );
+            ExitCode::from(1)
+        }
+        Ok(SelfUpdateOutcome::UpToDate { current }) => {
+            println!("{} linthis {} is up to date", "✓".green(), current);
+            ExitCode::SUCCESS
+        }
+        Ok(SelfUpdateOutcome::WouldUpdate { current, latest }) => {
+            println!("Would upgrade linthis {} → {}", current, latest);
+            println!("  command: {}", UPGRADE_COMMAND);
+            ExitCode::SUCCESS
+        }
+        Ok(SelfUpdateOutcome::Updated { success, .. }) => {
+            if success {
+                let _ = manager.update_last_check_time();
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            }
+        }
+        Err(e) => {
+            eprintln!("{}: Failed to upgrade linthis: {}", "Error".red(), e);
+            ExitCode::from(2)
+        }
+    }
+}
+
 /// Perform auto-sync check and optionally sync plugins
 /// Returns true if sync was performed, false otherwise
 fn perform_auto_sync(auto_sync_config: Option<&linthis::plugin::AutoSyncConfig>) -> bool {
@@ -2484,7 +2979,11 @@ fn perform_auto_sync(auto_sync_config: Option<&linthis::plugin::AutoSyncConfig>)
 
     // Validate config
     if let Err(e) = config.validate() {
-        eprintln!("{}: Invalid plugin_auto_sync config: {}", "Warning".yellow(), e);
+        eprintln!(
+            "{}: Invalid plugin_auto_sync config: {}",
+            "Warning".yellow(),
+            e
+        );
         return false;
     }
 
@@ -2497,7 +2996,11 @@ fn perform_auto_sync(auto_sync_config: Option<&linthis::plugin::AutoSyncConfig>)
     let manager = match AutoSyncManager::new() {
         Ok(m) => m,
         Err(e) => {
-            eprintln!("{}: Failed to create auto-sync manager: {}", "Warning".yellow(), e);
+            eprintln!(
+                "{}: Failed to create auto-sync manager: {}",
+                "Warning".yellow(),
+                e
+            );
             return false;
         }
     };
@@ -2596,7 +3099,11 @@ fn perform_auto_sync(auto_sync_config: Option<&linthis::plugin::AutoSyncConfig>)
     // Update last sync timestamp
     if synced {
         if let Err(e) = manager.update_last_sync_time() {
-            eprintln!("{}: Failed to update sync timestamp: {}", "Warning".yellow(), e);
+            eprintln!(
+                "{}: Failed to update sync timestamp: {}",
+                "Warning".yellow(),
+                e
+            );
         }
     }
 
@@ -2675,10 +3182,7 @@ fn sync_plugins(plugins: &[(String, String, Option<String>)]) -> Result<(), ()>
 
 /// Handle plugin subcommands
 fn handle_plugin_command(action: PluginCommands) -> ExitCode {
-    use linthis::plugin::{
-        cache::PluginCache,
-        manifest::PluginManifest,
-    };
+    use linthis::plugin::{cache::PluginCache, manifest::PluginManifest};
 
     match action {
         PluginCommands::Init { name } => {
@@ -2877,7 +3381,10 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
                                     plugin.url
                                 );
                                 println!("    Path: {}", plugin.cache_path.display());
-                                println!("    Cached: {}", plugin.cached_at.format("%Y-%m-%d %H:%M"));
+                                println!(
+                                    "    Cached: {}",
+                                    plugin.cached_at.format("%Y-%m-%d %H:%M")
+                                );
                                 println!(
                                     "    Updated: {}",
                                     plugin.last_updated.format("%Y-%m-%d %H:%M")
@@ -2933,11 +3440,7 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
                         return ExitCode::SUCCESS;
                     }
 
-                    println!(
-                        "{} ({}):",
-                        "Configured plugins".bold(),
-                        config_type
-                    );
+                    println!("{} ({}):", "Configured plugins".bold(), config_type);
                     for (name, url, git_ref) in &plugins {
                         if verbose {
                             if let Some(r) = git_ref {
@@ -3103,13 +3606,12 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
                             _ => false,
                         };
 
-                        let hash_info = new_hash
-                            .map(|h| &h[..7.min(h.len())])
-                            .unwrap_or("unknown");
+                        let hash_info = new_hash.map(|h| &h[..7.min(h.len())]).unwrap_or("unknown");
 
                         if was_updated {
                             if old_hash.is_some() {
-                                let old_short = old_hash.as_ref()
+                                let old_short = old_hash
+                                    .as_ref()
                                     .map(|h| &h[..7.min(h.len())])
                                     .unwrap_or("unknown");
                                 println!("{} {} -> {}", "✓".green(), old_short, hash_info);
@@ -3140,11 +3642,7 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
                         updated_count
                     );
                 } else {
-                    println!(
-                        "{} All {} plugin(s) up to date",
-                        "✓".green(),
-                        success_count
-                    );
+                    println!("{} All {} plugin(s) up to date", "✓".green(), success_count);
                 }
             } else {
                 println!(
@@ -3315,7 +3813,11 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
             }
         }
 
-        PluginCommands::Apply { alias, global, language } => {
+        PluginCommands::Apply {
+            alias,
+            global,
+            language,
+        } => {
             use linthis::plugin::{loader::PluginLoader, PluginConfigManager, PluginSource};
 
             let manager = if global {
@@ -3349,14 +3851,22 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
 
             // Filter by alias if specified
             let plugins: Vec<_> = if let Some(ref alias_filter) = alias {
-                plugins.into_iter().filter(|(name, _, _)| name == alias_filter).collect()
+                plugins
+                    .into_iter()
+                    .filter(|(name, _, _)| name == alias_filter)
+                    .collect()
             } else {
                 plugins
             };
 
             if plugins.is_empty() {
                 if let Some(ref a) = alias {
-                    eprintln!("{}: Plugin '{}' not found in {} config", "Error".red(), a, config_type);
+                    eprintln!(
+                        "{}: Plugin '{}' not found in {} config",
+                        "Error".red(),
+                        a,
+                        config_type
+                    );
                 } else {
                     println!("No plugins configured in {} config.", config_type);
                 }
@@ -3386,7 +3896,10 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
                     Ok(configs) => {
                         // Filter by language if specified
                         let configs: Vec<_> = if let Some(ref langs) = language {
-                            configs.into_iter().filter(|c| langs.contains(&c.language)).collect()
+                            configs
+                                .into_iter()
+                                .filter(|c| langs.contains(&c.language))
+                                .collect()
                         } else {
                             configs
                         };
@@ -3433,7 +3946,12 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
                         }
                     }
                     Err(e) => {
-                        eprintln!("{}: Failed to load plugin '{}': {}", "Warning".yellow(), name, e);
+                        eprintln!(
+                            "{}: Failed to load plugin '{}': {}",
+                            "Warning".yellow(),
+                            name,
+                            e
+                        );
                     }
                 }
             }
@@ -3441,7 +3959,10 @@ fn handle_plugin_command(action: PluginCommands) -> ExitCode {
             println!();
             if applied_count > 0 {
                 println!("{} Applied {} config file(s)", "✓".green(), applied_count);
-                println!("\n{}: Add these to .gitignore if you don't want to commit them", "Tip".cyan());
+                println!(
+                    "\n{}: Add these to .gitignore if you don't want to commit them",
+                    "Tip".cyan()
+                );
             } else {
                 println!("{} No new configs applied (all already exist)", "ℹ".blue());
             }
@@ -3474,15 +3995,12 @@ fn handle_config_command(action: ConfigCommands) -> ExitCode {
         ConfigCommands::Unset { field, global } => cli::handle_config_unset(&field, global),
         ConfigCommands::Get { field, global } => cli::handle_config_get(&field, global),
         ConfigCommands::List { verbose, global } => cli::handle_config_list(verbose, global),
+        ConfigCommands::Migrate { input, output } => cli::handle_config_migrate(&input, &output),
     }
 }
 
 /// Handle init subcommand
-fn handle_init_command(
-    global: bool,
-    with_hook: bool,
-    force: bool
-) -> ExitCode {
+fn handle_init_command(global: bool, with_hook: bool, force: bool) -> ExitCode {
     use colored::Colorize;
     use linthis::config::Config;
 
@@ -3544,18 +4062,19 @@ fn handle_init_command(
                 "Warning".yellow()
             );
             eprintln!("  Global hook template feature has been removed");
-            eprintln!("  Use {} in each project instead",
+            eprintln!(
+                "  Use {} in each project instead",
                 "linthis hook install".cyan()
             );
         } else {
             // Install hook for project
             println!();
             let exit_code = handle_hook_install(
-                None,        // Use default hook type (Git)
-                false,       // Not check-only
-                false,       // Not format-only
-                force,       // Use force flag from init
-                true         // Non-interactive mode
+                None,  // Use default hook type (Git)
+                false, // Not check-only
+                false, // Not format-only
+                force, // Use force flag from init
+                true,  // Non-interactive mode
             );
             if exit_code != ExitCode::SUCCESS {
                 return exit_code;
@@ -3569,18 +4088,16 @@ fn handle_init_command(
 /// Handle hook subcommands
 fn handle_hook_command(action: HookCommands) -> ExitCode {
     match action {
-        HookCommands::Install { hook_type, check_only, format_only, force, yes } => {
-            handle_hook_install(hook_type, check_only, format_only, force, yes)
-        }
-        HookCommands::Uninstall { yes } => {
-            handle_hook_uninstall(yes)
-        }
-        HookCommands::Status => {
-            handle_hook_status()
-        }
-        HookCommands::Check => {
-            handle_hook_check()
-        }
+        HookCommands::Install {
+            hook_type,
+            check_only,
+            format_only,
+            force,
+            yes,
+        } => handle_hook_install(hook_type, check_only, format_only, force, yes),
+        HookCommands::Uninstall { yes } => handle_hook_uninstall(yes),
+        HookCommands::Status => handle_hook_status(),
+        HookCommands::Check => handle_hook_check(),
     }
 }
 
@@ -3609,12 +4126,17 @@ fn handle_hook_install(
 
     // Check for existing hook
     if hook_path.exists() && !force {
-        println!("{}: {} already exists", "Warning".yellow(), hook_path.display());
+        println!(
+            "{}: {} already exists",
+            "Warning".yellow(),
+            hook_path.display()
+        );
 
         // Read and analyze existing hook
         if let Ok(existing_content) = std::fs::read_to_string(&hook_path) {
             let has_linthis = existing_content.contains("linthis");
-            let has_prek = existing_content.contains("prek") || std::path::Path::new(".pre-commit-config.yaml").exists();
+            let has_prek = existing_content.contains("prek")
+                || std::path::Path::new(".pre-commit-config.yaml").exists();
             let has_precommit = existing_content.contains("pre-commit");
             let has_husky = existing_content.contains("husky");
 
@@ -3634,7 +4156,10 @@ fn handle_hook_install(
 
             if !yes {
                 println!("\nOptions:");
-                println!("  1. {} - Replace existing hook with linthis", "Replace".cyan());
+                println!(
+                    "  1. {} - Replace existing hook with linthis",
+                    "Replace".cyan()
+                );
                 println!("  2. {} - Append linthis to existing hook", "Append".cyan());
                 println!("  3. {} - Create backup and replace", "Backup".cyan());
                 println!("  4. {} - Cancel", "Cancel".cyan());
@@ -3648,11 +4173,23 @@ fn handle_hook_install(
                 match choice.trim() {
                     "1" => {
                         // Replace: use force flag internally
-                        return handle_hook_install_impl(hook_type, check_only, format_only, true, false);
+                        return handle_hook_install_impl(
+                            hook_type,
+                            check_only,
+                            format_only,
+                            true,
+                            false,
+                        );
                     }
                     "2" => {
                         // Append
-                        return handle_hook_install_impl(hook_type, check_only, format_only, false, true);
+                        return handle_hook_install_impl(
+                            hook_type,
+                            check_only,
+                            format_only,
+                            false,
+                            true,
+                        );
                     }
                     "3" => {
                         // Backup and replace
@@ -3661,10 +4198,20 @@ fn handle_hook_install(
                             eprintln!("{}: Failed to create backup: {}", "Error".red(), e);
                             return ExitCode::from(2);
                         }
-                        println!("{} Created backup at {}", "✓".green(), backup_path.display());
-                        return handle_hook_install_impl(hook_type, check_only, format_only, true, false);
+                        println!(
+                            "{} Created backup at {}",
+                            "✓".green(),
+                            backup_path.display()
+                        );
+                        return handle_hook_install_impl(
+                            hook_type,
+                            check_only,
+                            format_only,
+                            true,
+                            false,
+                        );
                     }
-                    "4" | _ => {
+                    _ => {
                         println!("Installation cancelled");
                         return ExitCode::SUCCESS;
                     }
@@ -3675,7 +4222,11 @@ fn handle_hook_install(
             }
         }
 
-        println!("  Use {} to overwrite, or {} to append", "--force".yellow(), "choose option 2".cyan());
+        println!(
+            "  Use {} to overwrite, or {} to append",
+            "--force".yellow(),
+            "choose option 2".cyan()
+        );
         return ExitCode::from(1);
     }
 
@@ -3778,7 +4329,10 @@ fn handle_hook_status() -> ExitCode {
         println!("  Run {} to install hook", "linthis hook install".cyan());
     } else if let Ok(content) = std::fs::read_to_string(&hook_path) {
         if !content.contains("linthis") {
-            println!("  Run {} to add linthis to existing hook", "linthis hook install".cyan());
+            println!(
+                "  Run {} to add linthis to existing hook",
+                "linthis hook install".cyan()
+            );
         }
     }
 
@@ -3816,14 +4370,13 @@ fn handle_hook_uninstall(yes: bool) -> ExitCode {
     };
 
     let has_linthis = existing_content.contains("linthis");
-    let has_other_content = existing_content.lines()
-        .any(|line| {
-            let trimmed = line.trim();
-            !trimmed.is_empty()
-                && !trimmed.starts_with('#')
-                && !trimmed.starts_with("#!/")
-                && !trimmed.contains("linthis")
-        });
+    let has_other_content = existing_content.lines().any(|line| {
+        let trimmed = line.trim();
+        !trimmed.is_empty()
+            && !trimmed.starts_with('#')
+            && !trimmed.starts_with("#!/")
+            && !trimmed.contains("linthis")
+    });
 
     if !has_linthis {
         println!("{}: Hook does not contain linthis", "Info".cyan());
@@ -3842,7 +4395,10 @@ fn handle_hook_uninstall(yes: bool) -> ExitCode {
 
         println!("\nOptions:");
         if has_other_content {
-            println!("  1. {} - Remove only linthis lines", "Remove linthis".cyan());
+            println!(
+                "  1. {} - Remove only linthis lines",
+                "Remove linthis".cyan()
+            );
             println!("  2. {} - Delete entire hook file", "Delete all".cyan());
         } else {
             println!("  1. {} - Delete hook file", "Delete".cyan());
@@ -3861,7 +4417,9 @@ fn handle_hook_uninstall(yes: bool) -> ExitCode {
                     // Remove only linthis lines
                     let new_content: String = existing_content
                         .lines()
-                        .filter(|line| !line.contains("linthis") && !line.contains("# linthis hook"))
+                        .filter(|line| {
+                            !line.contains("linthis") && !line.contains("# linthis hook")
+                        })
                         .collect::<Vec<_>>()
                         .join("\n");
 
@@ -3869,7 +4427,11 @@ fn handle_hook_uninstall(yes: bool) -> ExitCode {
                         eprintln!("{}: Failed to update hook: {}", "Error".red(), e);
                         return ExitCode::from(2);
                     }
-                    println!("{} Removed linthis from {}", "✓".green(), hook_path.display());
+                    println!(
+                        "{} Removed linthis from {}",
+                        "✓".green(),
+                        hook_path.display()
+                    );
                 } else {
                     // Delete entire file
                     if let Err(e) = std::fs::remove_file(&hook_path) {
@@ -3887,7 +4449,7 @@ fn handle_hook_uninstall(yes: bool) -> ExitCode {
                 }
                 println!("{} Deleted {}", "✓".green(), hook_path.display());
             }
-            "3" | _ => {
+            _ => {
                 println!("Uninstall cancelled");
                 return ExitCode::SUCCESS;
             }
@@ -3956,7 +4518,11 @@ fn handle_hook_check() -> ExitCode {
 
             if tool_count > 1 {
                 has_conflicts = true;
-                println!("{} Multiple hook tools detected in {}", "⚠".yellow(), hook_path.display());
+                println!(
+                    "{} Multiple hook tools detected in {}",
+                    "⚠".yellow(),
+                    hook_path.display()
+                );
                 if has_linthis {
                     println!("  {} linthis", "✓".green());
                 }
@@ -3977,12 +4543,14 @@ fn handle_hook_check() -> ExitCode {
     // Check for prek/pre-commit config without hook
     if prek_config.exists() {
         if let Ok(content) = std::fs::read_to_string(prek_config) {
-            if content.contains("linthis") {
-                if !hook_path.exists() {
-                    has_conflicts = true;
-                    println!("{} {} exists but no hook installed", "⚠".yellow(), prek_config.display());
-                    warnings.push("Run 'prek install' or 'pre-commit install' to activate hooks");
-                }
+            if content.contains("linthis") && !hook_path.exists() {
+                has_conflicts = true;
+                println!(
+                    "{} {} exists but no hook installed",
+                    "⚠".yellow(),
+                    prek_config.display()
+                );
+                warnings.push("Run 'prek install' or 'pre-commit install' to activate hooks");
             }
         }
     }
@@ -3991,7 +4559,11 @@ fn handle_hook_check() -> ExitCode {
     if husky_dir.exists() {
         let husky_pre_commit = husky_dir.join("pre-commit");
         if husky_pre_commit.exists() {
-            println!("{} Husky detected: {}", "ℹ".cyan(), husky_pre_commit.display());
+            println!(
+                "{} Husky detected: {}",
+                "ℹ".cyan(),
+                husky_pre_commit.display()
+            );
             warnings.push("Husky manages its own hooks in .husky/ directory");
             warnings.push("To use linthis with husky, add linthis command to .husky/pre-commit");
         }
@@ -4005,7 +4577,10 @@ fn handle_hook_check() -> ExitCode {
         }
         println!();
         println!("{}", "Recommendations:".bold());
-        println!("  • Use {} to see current hook setup", "linthis hook status".cyan());
+        println!(
+            "  • Use {} to see current hook setup",
+            "linthis hook status".cyan()
+        );
         println!("  • Choose one hook tool and stick with it");
         println!("  • For teams, document hook setup in README");
     } else {
@@ -4067,7 +4642,12 @@ fn find_git_root() -> Option<PathBuf> {
     }
 }
 /// Create hook configuration file based on the selected tool
-fn create_hook_config(tool: &HookTool, hook_check_only: bool, hook_format_only: bool, force: bool) -> Result<(), ExitCode> {
+fn create_hook_config(
+    tool: &HookTool,
+    hook_check_only: bool,
+    hook_format_only: bool,
+    force: bool,
+) -> Result<(), ExitCode> {
     use colored::Colorize;
     use std::fs;
     #[cfg(unix)]
@@ -4096,7 +4676,8 @@ fn create_hook_config(tool: &HookTool, hook_check_only: bool, hook_format_only:
                 "linthis -s -c -f -w"
             };
 
-            let content = format!(r#"repos:
+            let content = format!(
+                r#"repos:
   - repo: local
     hooks:
       - id: linthis
@@ -4104,7 +4685,9 @@ fn create_hook_config(tool: &HookTool, hook_check_only: bool, hook_format_only:
         entry: {}
         language: system
         pass_filenames: false
-"#, hook_cmd);
+"#,
+                hook_cmd
+            );
 
             match fs::write(&config_path, content) {
                 Ok(_) => {
@@ -4131,12 +4714,18 @@ fn create_hook_config(tool: &HookTool, hook_check_only: bool, hook_format_only:
                             Ok(_) => {
                                 println!("{}", "✓".green());
                                 println!("\n{} Pre-commit hooks are ready!", "✓".green().bold());
-                                println!("  Hooks will run automatically on {}", "git commit".cyan());
+                                println!(
+                                    "  Hooks will run automatically on {}",
+                                    "git commit".cyan()
+                                );
                             }
                             Err(e) => {
                                 println!("{}", "✗".red());
                                 eprintln!("{}: {}", "Warning".yellow(), e);
-                                println!("\nPlease run manually: {}", format!("{} install", tool_name).cyan());
+                                println!(
+                                    "\nPlease run manually: {}",
+                                    format!("{} install", tool_name).cyan()
+                                );
                             }
                         }
                     } else {
@@ -4147,7 +4736,10 @@ fn create_hook_config(tool: &HookTool, hook_check_only: bool, hook_format_only:
                             println!("  1. Install prek: {}", "pip install prek".cyan());
                             println!("  2. Set up hooks: {}", "prek install".cyan());
                         } else {
-                            println!("  1. Install pre-commit: {}", "pip install pre-commit".cyan());
+                            println!(
+                                "  1. Install pre-commit: {}",
+                                "pip install pre-commit".cyan()
+                            );
                             println!("  2. Set up hooks: {}", "pre-commit install".cyan());
                         }
                     }
@@ -4267,7 +4859,11 @@ fn create_hook_config(tool: &HookTool, hook_check_only: bool, hook_format_only:
                         {
                             let mut perms = fs::metadata(&hook_path)
                                 .map_err(|e| {
-                                    eprintln!("{}: Failed to get file metadata: {}", "Error".red(), e);
+                                    eprintln!(
+                                        "{}: Failed to get file metadata: {}",
+                                        "Error".red(),
+                                        e
+                                    );
                                     ExitCode::from(2)
                                 })?
                                 .permissions();
@@ -4313,11 +4909,12 @@ mod dirs {
     }
 }
 
-/// Default config file contents for each linter/formatter
-fn get_default_configs() -> Vec<(&'static str, &'static str)> {
+/// Default config files as (tool, filename, contents) for each linter/formatter
+fn get_default_configs() -> Vec<(&'static str, &'static str, &'static str)> {
     vec![
         // Python - ruff (standalone config)
         (
+            "ruff",
             "ruff.toml",
             r#"# Linthis default ruff config
 # Ruff is an extremely fast Python linter and formatter, written in Rust
@@ -4345,6 +4942,7 @@ indent-style = "space"
         ),
         // Python - ruff (in pyproject.toml for projects that prefer it)
         (
+            "ruff",
             "pyproject.toml",
             r#"[tool.ruff]
 line-length = 120
@@ -4365,6 +4963,7 @@ indent-style = "space"
         ),
         // C/C++ - clang-format
         (
+            "clang-format",
             ".clang-format",
             r#"# Lintis default clang-format config
 BasedOnStyle: Google
@@ -4376,10 +4975,20 @@ AllowShortLoopsOnASingleLine: false
 BreakBeforeBraces: Attach
 PointerAlignment: Left
 SpaceAfterCStyleCast: false
-"#,
+---
+# Objective-C overrides
+Language: ObjC
+ColumnLimit: 120
+ObjCBlockIndentWidth: 4
+ObjCSpaceAfterProperty: true
+ObjCSpaceBeforeProtocolList: true
+ObjCBreakBeforeNestedBlockParam: false
+PointerAlignment: Right
+"#,
         ),
         // C/C++ - cpplint
         (
+            "cpplint",
             "CPPLINT.cfg",
             r#"# Lintis default cpplint config
 set noparent
@@ -4388,6 +4997,7 @@ linelength=120
         ),
         // TypeScript/JavaScript - prettier
         (
+            "prettier",
             ".prettierrc",
             r#"{
   "semi": true,
@@ -4402,6 +5012,7 @@ linelength=120
         ),
         // TypeScript/JavaScript - eslint
         (
+            "eslint",
             ".eslintrc.json",
             r#"{
   "env": {
@@ -4425,64 +5036,442 @@ linelength=120
         ),
         // Rust - rustfmt
         (
+            "rustfmt",
             "rustfmt.toml",
             r#"# Lintis default rustfmt config
 max_width = 120
 tab_spaces = 4
 edition = "2021"
 use_small_heuristics = "Default"
+"#,
+        ),
+        // Go - golangci-lint
+        (
+            "golangci-lint",
+            ".golangci.yml",
+            r#"# Linthis default golangci-lint config
+run:
+  timeout: 5m
+
+linters:
+  enable:
+    - errcheck
+    - gosimple
+    - govet
+    - ineffassign
+    - staticcheck
+    - unused
+    - gofmt
+    - goimports
+    - misspell
+    - revive
+
+linters-settings:
+  revive:
+    rules:
+      - name: exported
+        disabled: true
+
+issues:
+  max-issues-per-linter: 0
+  max-same-issues: 0
+"#,
+        ),
+        // Java - checkstyle
+        (
+            "checkstyle",
+            "checkstyle.xml",
+            r#"<?xml version="1.0"?>
+<!DOCTYPE module PUBLIC
+    "-//Checkstyle//DTD Checkstyle Configuration 1.3//EN"
+    "https://checkstyle.org/dtds/configuration_1_3.dtd">
+<!-- Linthis default checkstyle config (Google style, 120 columns) -->
+<module name="Checker">
+  <property name="charset" value="UTF-8"/>
+  <property name="severity" value="warning"/>
+  <property name="fileExtensions" value="java"/>
+
+  <module name="FileTabCharacter"/>
+  <module name="NewlineAtEndOfFile"/>
+  <module name="LineLength">
+    <property name="max" value="120"/>
+    <property name="ignorePattern" value="^package.*|^import.*|a href|href|http://|https://"/>
+  </module>
+
+  <module name="TreeWalker">
+    <module name="AvoidStarImport"/>
+    <module name="UnusedImports"/>
+    <module name="RedundantImport"/>
+    <module name="NeedBraces"/>
+    <module name="LeftCurly"/>
+    <module name="RightCurly"/>
+    <module name="WhitespaceAround"/>
+    <module name="OneStatementPerLine"/>
+    <module name="MultipleVariableDeclarations"/>
+    <module name="EmptyBlock"/>
+    <module name="EqualsHashCode"/>
+    <module name="MissingSwitchDefault"/>
+    <module name="TypeName"/>
+    <module name="MethodName"/>
+    <module name="ConstantName"/>
+    <module name="LocalVariableName"/>
+    <module name="MemberName"/>
+    <module name="ParameterName"/>
+    <module name="Indentation">
+      <property name="basicOffset" value="4"/>
+      <property name="caseIndent" value="4"/>
+    </module>
+  </module>
+</module>
 "#,
         ),
     ]
 }
 
-/// Initialize default config files for all linters/formatters
-fn init_linter_configs() -> ExitCode {
-    use std::fs;
-    use std::path::Path;
-
+/// Select default configs for the given tools (empty = all).
+fn select_default_configs(
+    only: &[String],
+    languages: Option<&[Language]>,
+) -> Result<Vec<(&'static str, &'static str, &'static str)>, String> {
     let configs = get_default_configs();
-    let mut created = 0;
-    let mut skipped = 0;
+    let unknown: Vec<_> = only
+        .iter()
+        .filter(|tool| !configs.iter().any(|(t, _, _)| t == tool))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        let mut known: Vec<_> = configs.iter().map(|(t, _, _)| *t).collect();
+        known.dedup();
+        return Err(format!(
+            "Unknown tool(s) for --only: {} (available: {})",
+            unknown.join(", "),
+            known.join(", ")
+        ));
+    }
+
+    Ok(configs
+        .into_iter()
+        .filter(|(tool, _, _)| only.is_empty() || only.iter().any(|t| t == tool))
+        .filter(|(tool, _, _)| {
+            languages.is_none_or(|langs| {
+                config_tool_languages(tool)
+                    .iter()
+                    .any(|lang| langs.contains(lang))
+            })
+        })
+        .collect())
+}
+
+/// Languages a default config file's tool is used for
+fn config_tool_languages(tool: &str) -> &'static [Language] {
+    match tool {
+        "ruff" => &[Language::Python],
+        "clang-format" => &[Language::Cpp, Language::ObjectiveC, Language::Java],
+        "cpplint" => &[Language::Cpp, Language::ObjectiveC],
+        "prettier" | "eslint" => &[Language::TypeScript, Language::JavaScript],
+        "rustfmt" => &[Language::Rust],
+        "golangci-lint" => &[Language::Go],
+        "checkstyle" => &[Language::Java],
+        _ => &[],
+    }
+}
+
+/// Languages of the files under `root`, honoring the default excludes
+fn detected_languages(root: &std::path::Path) -> Vec<Language> {
+    use linthis::utils::walker::{walk_paths, WalkerConfig};
+
+    let walker_config = WalkerConfig {
+        exclude_patterns: linthis::utils::default_excludes_without(&[]),
+        ..Default::default()
+    };
+    let (files, _) = walk_paths(&[root.to_path_buf()], &walker_config);
+    let mut languages: Vec<Language> = files
+        .iter()
+        .filter_map(|f| Language::from_path(f))
+        .collect();
+    languages.sort_by_key(|lang| lang.name());
+    languages.dedup();
+    languages
+}
+
+/// Counts reported by `--init-configs`
+#[derive(Debug, Default, PartialEq, Eq)]
+struct InitConfigsReport {
+    created: usize,
+    overwritten: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+/// Write config files into `dir`, overwriting existing ones only with `force`.
+fn write_default_configs(
+    dir: &std::path::Path,
+    configs: &[(&str, &str, &str)],
+    force: bool,
+) -> InitConfigsReport {
+    let mut report = InitConfigsReport::default();
+
+    for (_, filename, content) in configs {
+        let path = dir.join(filename);
+        let exists = path.exists();
+        if exists && !force {
+            println!("  {} {} (already exists)", "⊘".yellow(), filename);
+            report.skipped += 1;
+            continue;
+        }
+
+        // pyproject.toml holds more than ruff settings: only replace [tool.ruff]
+        let written = if exists && *filename == "pyproject.toml" {
+            merge_tool_ruff(&path, content)
+        } else {
+            std::fs::write(&path, content).map_err(|e| e.to_string())
+        };
+        match written {
+            Ok(_) if exists && *filename == "pyproject.toml" => {
+                println!("  {} {} ([tool.ruff] replaced)", "✓".green(), filename);
+                report.overwritten += 1;
+            }
+            Ok(_) if exists => {
+                println!("  {} {} (overwritten)", "✓".green(), filename);
+                report.overwritten += 1;
+            }
+            Ok(_) => {
+                println!("  {} {}", "✓".green(), filename);
+                report.created += 1;
+            }
+            Err(e) => {
+                eprintln!("  {} {} ({})", "✗".red(), filename, e);
+                report.failed += 1;
+            }
+        }
+    }
+
+    report
+}
+
+/// Replace the `[tool.ruff]` table of the pyproject.toml at `path` with the
+/// one in `content`, keeping everything else in the file as it is.
+fn merge_tool_ruff(path: &std::path::Path, content: &str) -> Result<(), String> {
+    use toml_edit::{DocumentMut, Item, Table};
+
+    let existing = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut doc: DocumentMut = existing.parse().map_err(|e| format!("{}", e))?;
+    let defaults: DocumentMut = content.parse().map_err(|e| format!("{}", e))?;
+    let Some(ruff) = defaults.get("tool").and_then(|tool| tool.get("ruff")) else {
+        return Err("no [tool.ruff] table in the default config".to_string());
+    };
+
+    let tool = doc.entry("tool").or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    let Some(tool) = tool.as_table_mut() else {
+        return Err("`tool` is not a table".to_string());
+    };
+    tool.insert("ruff", ruff.clone());
+    std::fs::write(path, doc.to_string()).map_err(|e| e.to_string())
+}
+
+/// Initialize default config files for linters/formatters in `dir`,
+/// optionally only for tools used by `languages`
+fn init_linter_configs(
+    dir: &std::path::Path,
+    only: &[String],
+    languages: Option<&[Language]>,
+    force: bool,
+) -> ExitCode {
+    let configs = match select_default_configs(only, languages) {
+        Ok(configs) => configs,
+        Err(e) => {
+            eprintln!("{}: {}", "Error".red(), e);
+            return ExitCode::from(exit_code::CONFIG_ERROR as u8);
+        }
+    };
+
+    if let Some(languages) = languages {
+        let names: Vec<_> = languages.iter().map(|lang| lang.name()).collect();
+        println!("Detected languages: {}", names.join(", "));
+    }
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        eprintln!(
+            "{}: Failed to create {}: {}",
+            "Error".red(),
+            dir.display(),
+            e
+        );
+        return ExitCode::from(1);
+    }
 
     println!(
         "{}",
         "Generating default linter/formatter configs...".cyan()
     );
 
-    for (filename, content) in configs {
-        let path = Path::new(filename);
-        if path.exists() {
-            println!("  {} {} (already exists)", "⊘".yellow(), filename);
-            skipped += 1;
-        } else {
-            match fs::write(path, content) {
-                Ok(_) => {
-                    println!("  {} {}", "✓".green(), filename);
-                    created += 1;
-                }
-                Err(e) => {
-                    eprintln!("  {} {} ({})", "✗".red(), filename, e);
-                }
+    let report = write_default_configs(dir, &configs, force);
+
+    println!();
+    println!(
+        "Created {} config file{}, overwritten {}, skipped {} existing",
+        report.created,
+        if report.created == 1 { "" } else { "s" },
+        report.overwritten,
+        report.skipped
+    );
+
+    if report.failed > 0 {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Print the files a run would process (`--list-files`).
+fn print_file_list(options: &RunOptions, json: bool) -> ExitCode {
+    let (mut files, warnings) = linthis::list_files(options);
+    for warning in &warnings {
+        eprintln!("{}: {}", "Warning".yellow(), warning);
+    }
+    if let Some(base) = &options.relative_to {
+        for file in &mut files {
+            file.path = linthis::utils::relative_path(&file.path, base);
+        }
+    }
+
+    if json {
+        match serde_json::to_string_pretty(&files) {
+            Ok(out) => println!("{}", out),
+            Err(e) => {
+                eprintln!("{}: Failed to serialize file list: {}", "Error".red(), e);
+                return ExitCode::from(2);
             }
         }
+    } else {
+        for file in &files {
+            println!("{}", file.path.display());
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Print where the configuration comes from (`--config-path-resolution`).
+fn print_config_resolution(
+    sources: &[linthis::config::ConfigSource],
+    tool_configs: &HashMap<(Language, String), PathBuf>,
+    origins: &HashMap<(Language, String), String>,
+) -> ExitCode {
+    use linthis::config::ConfigStatus;
+
+    println!("{}", "Config sources (lowest precedence first):".bold());
+    if sources.is_empty() {
+        println!("  none (--config-free)");
+    }
+    for (idx, source) in sources.iter().enumerate() {
+        let path = source
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "defaults".to_string());
+        let status = match &source.status {
+            ConfigStatus::Loaded => "loaded".green().to_string(),
+            ConfigStatus::NotFound => "not found".dimmed().to_string(),
+            ConfigStatus::Failed(e) => format!("{}: {}", "ignored".red(), e),
+        };
+        println!(
+            "  {}. {:<8} {} ({})",
+            idx + 1,
+            source.layer.label(),
+            path,
+            status
+        );
     }
+    println!("  command-line flags override all of the above");
 
     println!();
+    println!("{}", "Tool configs:".bold());
+    if tool_configs.is_empty() {
+        println!("  none (tools find their own config files)");
+    }
+    let mut entries: Vec<_> = tool_configs.iter().collect();
+    entries.sort_by(|((a_lang, a_tool), _), ((b_lang, b_tool), _)| {
+        (a_lang.name(), a_tool).cmp(&(b_lang.name(), b_tool))
+    });
+    for ((lang, tool), path) in entries {
+        let origin = origins
+            .get(&(*lang, tool.clone()))
+            .map(String::as_str)
+            .unwrap_or("unknown");
+        println!(
+            "  {}/{}: {} ({})",
+            lang.name(),
+            tool,
+            path.display(),
+            origin
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// Print the linthis version, or its build metadata as JSON.
+fn print_version(json: bool) -> ExitCode {
+    let info = linthis::VersionInfo::current();
+    if !json {
+        println!("linthis {}", info.version);
+        return ExitCode::SUCCESS;
+    }
+    match serde_json::to_string_pretty(&info) {
+        Ok(out) => println!("{}", out),
+        Err(e) => {
+            eprintln!("{}: Failed to serialize version: {}", "Error".red(), e);
+            return ExitCode::from(2);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Print the detected version and path of every built-in tool
+fn print_tool_version_report(json: bool) -> ExitCode {
+    let report = linthis::tool_version_report();
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(out) => println!("{}", out),
+            Err(e) => {
+                eprintln!("{}: Failed to serialize report: {}", "Error".red(), e);
+                return ExitCode::from(2);
+            }
+        }
+        return ExitCode::SUCCESS;
+    }
+
     println!(
-        "Created {} config file{}, skipped {} existing",
-        created,
-        if created == 1 { "" } else { "s" },
-        skipped
+        "{:<15} {:<10} {:<12} {}",
+        "Tool".bold(),
+        "Kind".bold(),
+        "Version".bold(),
+        "Path".bold()
     );
-
+    for tool in &report {
+        let version = match &tool.version {
+            Some(version) => version.green(),
+            None => "missing".red(),
+        };
+        let path = tool
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<15} {:<10} {:<12} {}",
+            tool.tool, tool.kind, version, path
+        );
+    }
     ExitCode::SUCCESS
 }
 
 /// Run benchmark comparing ruff vs flake8+black for Python
 fn run_benchmark(cli: &Cli) -> ExitCode {
     use linthis::benchmark::{format_benchmark_table, run_python_benchmark};
-    use linthis::utils::walker::{walk_paths, WalkerConfig};
+    use linthis::utils::walker::{scan_cache_dir, walk_paths_cached, WalkerConfig};
 
     println!(
         "{}",
@@ -4505,7 +5494,8 @@ fn run_benchmark(cli: &Cli) -> ExitCode {
     };
 
     // Collect Python files
-    let (files, _) = walk_paths(&paths, &walker_config);
+    let cache_dir = cli.scan_cache.then(scan_cache_dir).flatten();
+    let (files, _) = walk_paths_cached(&paths, &walker_config, cache_dir.as_deref());
 
     if files.is_empty() {
         println!("{}", "No Python files found to benchmark.".yellow());
@@ -4535,7 +5525,21 @@ fn strip_ansi_codes(s: &str) -> String {
 fn main() -> ExitCode {
     env_logger::init();
 
-    let cli = Cli::parse();
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            return ExitCode::from(parse_error_exit_code(&e));
+        }
+    };
+
+    if cli.no_color {
+        colored::control::set_override(false);
+    }
+
+    if cli.version {
+        return print_version(cli.json);
+    }
 
     // Handle plugin subcommands first
     if let Some(Commands::Plugin { action }) = cli.command {
@@ -4553,10 +5557,30 @@ fn main() -> ExitCode {
     }
 
     // Handle init subcommand
-    if let Some(Commands::Init { global, with_hook, force }) = cli.command {
+    if let Some(Commands::Init {
+        global,
+        with_hook,
+        force,
+    }) = cli.command
+    {
         return handle_init_command(global, with_hook, force);
     }
 
+    // Handle clean subcommand
+    if let Some(Commands::Clean { dry_run }) = cli.command {
+        return handle_clean_command(dry_run);
+    }
+
+    // Handle render subcommand
+    if let Some(Commands::Render { file, output }) = &cli.command {
+        return handle_render_command(file, output.as_deref());
+    }
+
+    // Handle self-update subcommand (before the automatic update check)
+    if let Some(Commands::SelfUpdate { dry_run }) = cli.command {
+        return handle_self_update_command(dry_run);
+    }
+
     // Perform self-update and auto-sync checks (before loading plugins)
     // Load config to get self_auto_update and plugin_auto_sync settings
     {
@@ -4574,9 +5598,15 @@ fn main() -> ExitCode {
 
     // Track loaded plugins for display
     let mut loaded_plugins: Vec<String> = Vec::new();
+    // Plugin config files, passed to their tools explicitly
+    let mut tool_configs: HashMap<(Language, String), PathBuf> = HashMap::new();
+    // Where each tool config came from, for --config-path-resolution
+    let mut tool_config_origins: HashMap<(Language, String), String> = HashMap::new();
+    // House style preset declared by a plugin (the last one wins)
+    let mut plugin_preset: Option<linthis::presets::Preset> = None;
 
     // Load plugins from config files (project first, then global)
-    if !cli.no_plugin {
+    if !cli.no_plugin && !cli.config_free {
         use linthis::plugin::{PluginConfigManager, PluginLoader, PluginSource};
 
         let mut plugins_to_load: Vec<(String, PluginSource)> = Vec::new();
@@ -4620,14 +5650,24 @@ fn main() -> ExitCode {
                         "Error".red(),
                         e
                     );
-                    return ExitCode::from(1);
+                    return ExitCode::from(exit_code::CONFIG_ERROR as u8);
                 }
             };
 
             for (plugin_name, source) in plugins_to_load {
-                match loader.load_configs(&[source], false) {
-                    Ok(configs) => {
+                let sources = [source];
+                match loader.load_configs_and_preset(&sources, false) {
+                    Ok((configs, preset)) => {
                         loaded_plugins.push(plugin_name.clone());
+                        if let Some(preset) = preset {
+                            if cli.verbose {
+                                eprintln!(
+                                    "Loaded preset '{}' from plugin '{}'",
+                                    preset.name, plugin_name
+                                );
+                            }
+                            plugin_preset = Some(preset);
+                        }
                         if cli.verbose {
                             eprintln!(
                                 "Loaded {} config(s) from plugin '{}'",
@@ -4638,29 +5678,38 @@ fn main() -> ExitCode {
                         // Auto-apply plugin configs to .linthis/configs/{language}/
                         // Each language gets its own subdirectory to avoid conflicts
                         // (e.g., cpp/.clang-format vs oc/.clang-format)
-                        let linthis_dir = std::env::current_dir()
-                            .unwrap_or_default()
-                            .join(".linthis");
+                        let linthis_dir =
+                            std::env::current_dir().unwrap_or_default().join(".linthis");
                         let config_dir = linthis_dir.join("configs");
 
                         for config in &configs {
+                            if let Some(lang) = Language::from_name(&config.language) {
+                                tool_configs.insert(
+                                    (lang, config.tool.clone()),
+                                    config.config_path.clone(),
+                                );
+                                tool_config_origins.insert(
+                                    (lang, config.tool.clone()),
+                                    format!("plugin '{}'", config.plugin_name),
+                                );
+                            }
                             if let Some(filename) = config.config_path.file_name() {
                                 // Create language-specific subdirectory
                                 let lang_dir = config_dir.join(&config.language);
                                 if std::fs::create_dir_all(&lang_dir).is_ok() {
                                     let target = lang_dir.join(filename);
                                     // Always update to latest plugin config
-                                    if std::fs::copy(&config.config_path, &target).is_ok() {
-                                        if cli.verbose {
-                                            eprintln!(
-                                                "  - {}/{}: {} -> .linthis/configs/{}/{}",
-                                                config.language,
-                                                config.tool,
-                                                filename.to_string_lossy(),
-                                                config.language,
-                                                filename.to_string_lossy()
-                                            );
-                                        }
+                                    if std::fs::copy(&config.config_path, &target).is_ok()
+                                        && cli.verbose
+                                    {
+                                        eprintln!(
+                                            "  - {}/{}: {} -> .linthis/configs/{}/{}",
+                                            config.language,
+                                            config.tool,
+                                            filename.to_string_lossy(),
+                                            config.language,
+                                            filename.to_string_lossy()
+                                        );
                                     }
                                 }
                             }
@@ -4696,7 +5745,7 @@ fn main() -> ExitCode {
                 "Warning".yellow(),
                 config_path.display()
             );
-            return ExitCode::from(1);
+            return ExitCode::from(exit_code::CONFIG_ERROR as u8);
         }
 
         let content = linthis::config::Config::generate_default_toml();
@@ -4714,7 +5763,17 @@ fn main() -> ExitCode {
 
     // Handle --init-configs flag
     if cli.init_configs {
-        return init_linter_configs();
+        let languages = cli
+            .detected
+            .then(|| detected_languages(&std::env::current_dir().unwrap_or_default()));
+        return init_linter_configs(
+            cli.init_configs_dir
+                .as_deref()
+                .unwrap_or(std::path::Path::new(".")),
+            &cli.only.clone().unwrap_or_default(),
+            languages.as_deref(),
+            cli.force,
+        );
     }
 
     // Handle --benchmark flag
@@ -4722,8 +5781,18 @@ fn main() -> ExitCode {
         return run_benchmark(&cli);
     }
 
-    // Determine run mode
-    let mode = if cli.check_only {
+    // Handle --tool-version-report flag
+    if cli.tool_version_report {
+        return print_tool_version_report(cli.output.as_deref() == Some("json"));
+    }
+
+    // Determine run mode (the format-check report never modifies files)
+    let mode = if cli.format_check
+        || cli.output.as_deref().and_then(OutputFormat::from_name)
+            == Some(OutputFormat::FormatCheckJson)
+    {
+        RunMode::FormatCheck
+    } else if cli.check_only || cli.baseline_update.is_some() {
         RunMode::CheckOnly
     } else if cli.format_only {
         RunMode::FormatOnly
@@ -4731,28 +5800,82 @@ fn main() -> ExitCode {
         RunMode::Both
     };
 
+    // With --quiet-if-clean, early exits (which are all clean) stay silent;
+    // the run itself buffers its stderr and decides afterwards
+    let quiet = cli.quiet || cli.quiet_if_clean;
+
     // Parse languages
-    let languages: Vec<Language> = cli
+    let cli_languages: Vec<Language> = cli
         .lang
         .unwrap_or_default()
         .iter()
         .filter_map(|s| Language::from_name(s))
         .collect();
+    // The merged config is loaded once and shared by everything below
+    let project_root = linthis::utils::get_project_root();
+    let (merged_config, config_sources) = if cli.config_free {
+        (linthis::config::Config::default(), Vec::new())
+    } else if cli.config_path_resolution {
+        // Failed sources are listed by the resolution report instead
+        linthis::config::Config::load_merged_with_sources(&project_root)
+    } else {
+        match linthis::config::Config::try_load_merged(&project_root) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red(), e);
+                return ExitCode::from(e.exit_code() as u8);
+            }
+        }
+    };
+    let languages =
+        match merged_config.run_languages(&cli_languages, cli.languages_from_config_only) {
+            Ok(languages) => languages,
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red(), e);
+                return ExitCode::from(e.exit_code() as u8);
+            }
+        };
+    if cli.languages_from_config_only && !merged_config.languages.is_empty() {
+        if languages.is_empty() {
+            if !quiet {
+                eprintln!(
+                    "{}",
+                    "No configured languages selected by --lang, nothing to check".yellow()
+                );
+            }
+            return ExitCode::SUCCESS;
+        }
+        if cli.verbose {
+            let names: Vec<&str> = languages.iter().map(|l| l.name()).collect();
+            eprintln!(
+                "Only checking configured languages ({}), other files are ignored",
+                names.join(", ")
+            );
+        }
+    }
 
     // Build exclusion patterns FIRST (defaults + gitignore + user-specified)
     // This must be done before getting staged files so we can filter them
     let mut exclude_patterns: Vec<String> = if cli.no_default_excludes {
         Vec::new()
+    } else if cli.config_free {
+        linthis::utils::default_excludes_without(&[])
     } else {
-        linthis::utils::DEFAULT_EXCLUDES
-            .iter()
-            .map(|s| s.to_string())
-            .collect()
+        let remove = &merged_config.default_excludes_remove;
+        for pattern in remove {
+            if !linthis::utils::DEFAULT_EXCLUDES.contains(&pattern.as_str()) {
+                eprintln!(
+                    "{}: default_excludes_remove entry '{}' is not a default exclude",
+                    "Warning".yellow(),
+                    pattern
+                );
+            }
+        }
+        linthis::utils::default_excludes_without(remove)
     };
 
     // Add .gitignore patterns if in a git repo and not disabled
     if !cli.no_gitignore && linthis::utils::is_git_repo() {
-        let project_root = linthis::utils::get_project_root();
         let gitignore_patterns = linthis::utils::get_gitignore_patterns(&project_root);
         if cli.verbose && !gitignore_patterns.is_empty() {
             eprintln!(
@@ -4764,10 +5887,15 @@ fn main() -> ExitCode {
     }
 
     exclude_patterns.extend(cli.exclude.unwrap_or_default());
+    let audit_patterns = cli.audit.clone().unwrap_or_default();
 
     // Add excludes from project config file
-    let project_root = linthis::utils::get_project_root();
-    if let Some(project_config) = linthis::config::Config::load_project_config(&project_root) {
+    let project_config = if cli.config_free {
+        None
+    } else {
+        linthis::config::Config::load_project_config(&project_root)
+    };
+    if let Some(project_config) = project_config {
         if !project_config.excludes.is_empty() {
             if cli.verbose {
                 eprintln!(
@@ -4779,12 +5907,18 @@ fn main() -> ExitCode {
         }
     }
 
+    // Exclude test files by language convention (--no-tests / skip_tests)
+    let skip_tests = cli.no_tests || merged_config.skip_tests == Some(true);
+    if skip_tests {
+        exclude_patterns.extend(linthis::utils::test_excludes(&languages));
+    }
+
     // Get paths (handle staged files) and apply exclusion filters
     let paths = if cli.staged {
         match linthis::utils::get_staged_files() {
             Ok(files) => {
                 if files.is_empty() {
-                    if !cli.quiet {
+                    if !quiet {
                         println!("{}", "No staged files to check".yellow());
                     }
                     return ExitCode::SUCCESS;
@@ -4793,9 +5927,13 @@ fn main() -> ExitCode {
                 // Filter staged files using exclusion patterns
                 use linthis::utils::walker::build_glob_set;
                 let glob_set = build_glob_set(&exclude_patterns);
+                let audit_set = build_glob_set(&audit_patterns);
                 let filtered_files: Vec<PathBuf> = files
                     .into_iter()
                     .filter(|path| {
+                        if linthis::utils::walker::is_audited(path, &audit_set) {
+                            return true;
+                        }
                         // Check if file should be excluded
                         if let Some(ref gs) = glob_set {
                             // Check relative path from git root
@@ -4814,7 +5952,11 @@ fn main() -> ExitCode {
                                     let subpath: PathBuf = components[i..].iter().collect();
                                     if gs.is_match(&subpath) {
                                         if cli.verbose {
-                                            eprintln!("Excluding: {} (matches from subpath {})", relative.display(), subpath.display());
+                                            eprintln!(
+                                                "Excluding: {} (matches from subpath {})",
+                                                relative.display(),
+                                                subpath.display()
+                                            );
                                         }
                                         return false;
                                     }
@@ -4826,21 +5968,24 @@ fn main() -> ExitCode {
                     .collect();
 
                 if filtered_files.is_empty() {
-                    if !cli.quiet {
+                    if !quiet {
                         println!("{}", "No staged files to check after exclusions".yellow());
                     }
                     return ExitCode::SUCCESS;
                 }
 
                 if cli.verbose {
-                    eprintln!("Checking {} staged file(s) after exclusions", filtered_files.len());
+                    eprintln!(
+                        "Checking {} staged file(s) after exclusions",
+                        filtered_files.len()
+                    );
                 }
 
                 filtered_files
             }
             Err(e) => {
                 eprintln!("{}: {}", "Error getting staged files".red(), e);
-                return ExitCode::from(2);
+                return ExitCode::from(e.exit_code() as u8);
             }
         }
     } else if cli.paths.is_empty() {
@@ -4851,19 +5996,175 @@ fn main() -> ExitCode {
     };
 
     // Build options
+    let recheck = cli
+        .recheck
+        .as_deref()
+        .and_then(RecheckScope::parse)
+        .or(merged_config.recheck)
+        .unwrap_or_default();
+    // Preset-generated ruff/prettier configs, from a built-in preset or a
+    // plugin's (plugin configs take precedence).
+    // Bound here so the temp files live until the run finishes.
+    let preset_configs = linthis::presets::Preset::resolve(
+        cli.preset.as_deref(),
+        merged_config.preset.as_deref(),
+        plugin_preset,
+    )
+    .and_then(|preset| match preset.write_tool_configs() {
+        Ok(configs) => Some(configs),
+        Err(e) => {
+            if cli.verbose {
+                eprintln!("Failed to write preset tool configs: {}", e);
+            }
+            None
+        }
+    });
+    if let Some(configs) = &preset_configs {
+        for (language, tool, path) in configs.files() {
+            // The project's own tool config wins over the preset's
+            if linthis::presets::project_has_tool_config(&project_root, tool) {
+                if cli.verbose {
+                    eprintln!(
+                        "Using the project's {} config instead of the preset's",
+                        tool
+                    );
+                }
+                continue;
+            }
+            if let Some(lang) = Language::from_name(language) {
+                tool_configs
+                    .entry((lang, tool.to_string()))
+                    .or_insert_with(|| path.to_path_buf());
+                tool_config_origins
+                    .entry((lang, tool.to_string()))
+                    .or_insert_with(|| "preset".to_string());
+            }
+        }
+    }
+    if cli.config_path_resolution {
+        return print_config_resolution(&config_sources, &tool_configs, &tool_config_origins);
+    }
+    let relative_to = cli.relative_to.as_deref().map(|dir| match dir {
+        "git-root" => project_root.clone(),
+        dir => PathBuf::from(dir),
+    });
+    let stderr_buffer = if cli.quiet_if_clean {
+        StderrBuffer::start()
+    } else {
+        None
+    };
     let options = RunOptions {
         paths,
         mode,
         languages,
         exclude_patterns,
+        audit_patterns,
         verbose: cli.verbose,
-        quiet: cli.quiet,
+        // Without a buffer to replay, --quiet-if-clean can only run quietly
+        quiet: cli.quiet || (cli.quiet_if_clean && stderr_buffer.is_none()),
         plugins: loaded_plugins,
+        tool_configs,
+        config_free: cli.config_free,
         fail_on_warnings: cli.fail_on_warnings,
+        max_warnings: cli.max_warnings.and_then(|n| usize::try_from(n).ok()),
+        slowest: cli.slowest.unwrap_or(0),
+        checkers: merged_config.language_overrides.checker_chains(),
+        extensions: merged_config.language_overrides.extension_overrides(),
+        recheck,
+        safe: cli.safe && !cli.force,
+        staged: cli.staged,
+        builtin_checks: merged_config.builtin_checks.clone(),
+        fail_on: merged_config.language_overrides.fail_on_thresholds(),
+        fail_fast: cli.fail_fast,
+        autofix: merged_config.autofix.unwrap_or(true),
+        no_format_on_error: cli.no_format_on_error,
+        verify_idempotent: cli.verify_idempotent,
+        autofix_overrides: merged_config.language_overrides.autofix_overrides(),
+        invocation: merged_config.language_overrides.invocations(),
+        organize_imports: merged_config.organize_imports.unwrap_or(false),
+        skip_empty: merged_config.skip_empty.unwrap_or(true),
+        require_tools: cli.require_tools || merged_config.require_tools.unwrap_or(false),
+        format_diff: cli.show_diff,
+        diff_context: cli
+            .diff_context
+            .or(merged_config.diff_context)
+            .unwrap_or(linthis::utils::diff::DEFAULT_CONTEXT_LINES),
+        max_files: file_limit(
+            cli.max_files,
+            merged_config.max_files,
+            cli.no_max_files,
+            cli.force,
+        ),
+        bom: merged_config.bom.unwrap_or_default(),
+        tool_memory_limit_mb: merged_config.tool_memory_limit_mb,
+        clang_pin: merged_config.language_overrides.clang_pin(&project_root),
+        record_dir: cli.record.clone(),
+        replay_dir: cli.replay.clone(),
+        // A baseline update must see every issue
+        baseline: match &cli.baseline {
+            Some(path) => Some(path.clone()),
+            None => Some(project_root.join(linthis::baseline::DEFAULT_BASELINE_FILE))
+                .filter(|path| cli.baseline_update.is_none() && path.is_file()),
+        },
+        severity_overrides: merged_config.severity_overrides.clone(),
+        scan_cache: cli.scan_cache,
+        config_overlay_root: (!cli.config_free).then(|| project_root.clone()),
+        progress: cli
+            .progress
+            .as_deref()
+            .and_then(ProgressMode::parse)
+            .unwrap_or_default(),
+        relative_to: relative_to.clone(),
+        // Set by run_stdin for --stdin-language
+        language_override: None,
     };
 
-    // Parse output format
-    let output_format = OutputFormat::parse(&cli.output).unwrap_or(OutputFormat::Human);
+    if cli.list_files {
+        let json =
+            cli.output.as_deref().and_then(OutputFormat::from_name) == Some(OutputFormat::Json);
+        return print_file_list(&options, json);
+    }
+
+    // Parse output format; without --output, CI picks its own default
+    let output_format = match cli.output.as_deref() {
+        Some(name) => OutputFormat::from_name(name),
+        None => detect_ci_output_format(),
+    };
+    let reporter: Box<dyn Reporter> = if cli.count {
+        Box::new(CountReporter {
+            json: output_format == Some(OutputFormat::Json),
+        })
+    } else {
+        output_format.map_or_else(
+            || Box::new(HumanReporter) as Box<dyn Reporter>,
+            OutputFormat::reporter,
+        )
+    };
+    let render_options = RenderOptions {
+        print_severity: cli.print_severity.as_deref().and_then(Severity::parse),
+        quiet_if_clean: cli.quiet_if_clean,
+        path_style: cli
+            .path_style
+            .as_deref()
+            .and_then(PathStyle::parse)
+            .unwrap_or_default(),
+        // Paths are already relative to --relative-to
+        project_root: relative_to.is_none().then(|| project_root.clone()),
+        fix_filter: if cli.only_fixed {
+            Some(FixFilter::Fixed)
+        } else if cli.only_unfixed {
+            Some(FixFilter::Unfixed)
+        } else {
+            None
+        },
+        group_by_rule: cli.group_errors_by_rule_first,
+        redact_home: if cli.redact_home {
+            dirs::home_dir()
+        } else {
+            None
+        },
+        show_diff: cli.show_diff,
+    };
 
     if cli.verbose {
         eprintln!(
@@ -4875,15 +6176,67 @@ fn main() -> ExitCode {
     }
 
     // Run linthis
-    match run(&options) {
+    let run_result = if cli.stdin {
+        let language = match cli.stdin_language.as_deref() {
+            Some(name) => match Language::from_name(name) {
+                Some(lang) => Some(lang),
+                None => {
+                    eprintln!("{}: Unknown --stdin-language '{}'", "Error".red(), name);
+                    return ExitCode::from(exit_code::CONFIG_ERROR as u8);
+                }
+            },
+            None => None,
+        };
+        let mut content = String::new();
+        if let Err(e) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut content) {
+            eprintln!("{}: Failed to read stdin: {}", "Error".red(), e);
+            return ExitCode::from(exit_code::TOOL_ERROR as u8);
+        }
+        linthis::run_stdin(&content, cli.stdin_filename.as_deref(), language, &options)
+    } else {
+        run(&options)
+    };
+    // Replay the run's progress and warnings unless it was clean
+    if let Some(buffer) = stderr_buffer {
+        let output = buffer.finish();
+        let clean = run_result
+            .as_ref()
+            .is_ok_and(|result| render_options.suppress_output(result));
+        if !clean {
+            let _ = std::io::Write::write_all(&mut std::io::stderr(), &output);
+        }
+    }
+    match run_result {
         Ok(result) => {
+            let quiet = cli.quiet || render_options.suppress_output(&result);
+
+            if let Some(path) = &cli.dump_issues {
+                if let Err(e) = result.dump(path) {
+                    eprintln!(
+                        "{}: Failed to dump issues to {}: {}",
+                        "Warning".yellow(),
+                        path.display(),
+                        e
+                    );
+                }
+            }
+
+            if let Some(path) = &cli.baseline_update {
+                return update_baseline(path, &result, &project_root);
+            }
+
             // Output results
-            let output = format_result(&result, output_format);
+            let output = render_with(&result, reporter.as_ref(), &render_options);
 
             // Print to console
-            if !cli.quiet || result.exit_code != 0 {
-                if !output.is_empty() {
-                    println!("{}", output);
+            if (!quiet || result.exit_code != 0) && !output.is_empty() {
+                println!("{}", output);
+            }
+
+            if options.require_tools && !result.missing_tools.is_empty() {
+                eprintln!("{}: Required tools are not available:", "Error".red());
+                for tool in &result.missing_tools {
+                    eprintln!("  - {}", tool);
                 }
             }
 
@@ -4930,12 +6283,8 @@ fn main() -> ExitCode {
                                 output_file.display(),
                                 e
                             );
-                        } else if !cli.quiet {
-                            eprintln!(
-                                "{} Results saved to {}",
-                                "✓".green(),
-                                output_file.display()
-                            );
+                        } else if !quiet {
+                            eprintln!("{} Results saved to {}", "✓".green(), output_file.display());
                         }
                     }
                     Err(e) => {
@@ -4955,10 +6304,8 @@ fn main() -> ExitCode {
                         let mut result_files: Vec<_> = entries
                             .filter_map(|e| e.ok())
                             .filter(|e| {
-                                e.file_name()
-                                    .to_string_lossy()
-                                    .starts_with("result-")
-                                    && e.path().extension().map_or(false, |ext| ext == "txt")
+                                e.file_name().to_string_lossy().starts_with("result-")
+                                    && e.path().extension().is_some_and(|ext| ext == "txt")
                             })
                             .collect();
 
@@ -4989,29 +6336,71 @@ fn main() -> ExitCode {
             }
 
             // Show failure message if exit code is non-zero
-            if result.exit_code != 0 && !cli.quiet {
+            if result.exit_code != 0 && !quiet {
                 eprintln!();
-                if result.exit_code == 1 {
+                if result.exit_code == exit_code::ISSUES && !result.unformatted_files.is_empty() {
+                    eprintln!(
+                        "{} {} {}",
+                        "✗".red().bold(),
+                        "Format check failed.".red().bold(),
+                        "Run linthis -f to format the files above.".red()
+                    );
+                } else if result.exit_code == exit_code::ISSUES {
                     let has_errors = result.issues.iter().any(|i| i.severity == Severity::Error);
-                    let has_warnings = result.issues.iter().any(|i| i.severity == Severity::Warning);
+                    let has_warnings = result
+                        .issues
+                        .iter()
+                        .any(|i| i.severity == Severity::Warning);
 
                     if has_errors {
-                        eprintln!("{} {} {}",
+                        eprintln!(
+                            "{} {} {}",
                             "✗".red().bold(),
                             "Linting failed due to errors.".red().bold(),
                             "Fix the issues above before committing.".red()
                         );
                     } else if has_warnings && cli.fail_on_warnings {
-                        eprintln!("{} {} {}",
+                        eprintln!(
+                            "{} {} {}",
                             "✗".red().bold(),
-                            "Linting failed due to warnings (--fail-on-warnings is enabled).".red().bold(),
+                            "Linting failed due to warnings (--fail-on-warnings is enabled)."
+                                .red()
+                                .bold(),
                             "Fix the warnings above before committing.".red()
                         );
+                    } else if let Some(max) = options
+                        .max_warnings
+                        .filter(|max| result.warning_count() > *max)
+                    {
+                        eprintln!(
+                            "{} {} {}",
+                            "✗".red().bold(),
+                            format!(
+                                "Linting failed: {} warnings, more than --max-warnings {}.",
+                                result.warning_count(),
+                                max
+                            )
+                            .red()
+                            .bold(),
+                            "Fix some of the warnings above before committing.".red()
+                        );
+                    } else {
+                        eprintln!(
+                            "{} {} {}",
+                            "✗".red().bold(),
+                            "Linting failed due to a per-language fail_on threshold."
+                                .red()
+                                .bold(),
+                            "Fix the issues above before committing.".red()
+                        );
                     }
-                } else if result.exit_code == 2 {
-                    eprintln!("{} {}",
+                } else if result.exit_code == exit_code::TOOL_ERROR {
+                    eprintln!(
+                        "{} {}",
                         "✗".red().bold(),
-                        "Linting failed due to formatting errors.".red().bold()
+                        "Linting failed due to formatting errors or tool failures."
+                            .red()
+                            .bold()
                     );
                 }
             }
@@ -5020,7 +6409,184 @@ fn main() -> ExitCode {
         }
         Err(e) => {
             eprintln!("{}: {}", "Error".red().bold(), e);
-            ExitCode::from(2)
+            ExitCode::from(e.exit_code() as u8)
         }
     }
 }
+
+/// Exit code for a command line clap rejected: usage errors get
+/// [`exit_code::CONFIG_ERROR`], `--help` succeeds.
+fn parse_error_exit_code(error: &clap::Error) -> u8 {
+    if error.use_stderr() {
+        exit_code::CONFIG_ERROR as u8
+    } else {
+        exit_code::SUCCESS as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_errors_exit_with_config_error() {
+        let unknown = Cli::try_parse_from(["linthis", "--no-such-flag"]).unwrap_err();
+        assert_eq!(parse_error_exit_code(&unknown), 3);
+        let bad_value = Cli::try_parse_from(["linthis", "--max-files", "many"]).unwrap_err();
+        assert_eq!(parse_error_exit_code(&bad_value), 3);
+        let help = Cli::try_parse_from(["linthis", "--help"]).unwrap_err();
+        assert_eq!(parse_error_exit_code(&help), 0);
+    }
+
+    fn only(tools: &[&str]) -> Vec<String> {
+        tools.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_max_files_and_force_lift_limit() {
+        let limit = |args: &[&str], config: Option<usize>| {
+            let cli = Cli::try_parse_from(["linthis"].iter().chain(args)).unwrap();
+            file_limit(cli.max_files, config, cli.no_max_files, cli.force)
+        };
+        assert_eq!(limit(&[], Some(100)), Some(100));
+        assert_eq!(limit(&["--max-files", "10"], Some(100)), Some(10));
+        assert_eq!(limit(&["--no-max-files"], Some(100)), None);
+        assert_eq!(limit(&["--max-files", "10", "--force"], Some(100)), None);
+        assert_eq!(limit(&[], None), None);
+    }
+
+    #[test]
+    fn test_init_configs_only_generates_requested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let configs = select_default_configs(&only(&["ruff", "prettier"]), None).unwrap();
+
+        let report = write_default_configs(dir.path(), &configs, false);
+        assert_eq!(report.created, configs.len());
+
+        let mut files: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec![".prettierrc", "pyproject.toml", "ruff.toml"]);
+
+        assert!(select_default_configs(&only(&["ruff", "black"]), None).is_err());
+        assert_eq!(
+            select_default_configs(&[], None).unwrap().len(),
+            get_default_configs().len()
+        );
+    }
+
+    #[test]
+    fn test_init_configs_dir_and_detected_languages() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("app.py"), "x = 1\n").unwrap();
+        std::fs::write(project.path().join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::create_dir_all(project.path().join("node_modules/pkg")).unwrap();
+        std::fs::write(project.path().join("node_modules/pkg/index.js"), "").unwrap();
+
+        let languages = detected_languages(project.path());
+        assert_eq!(languages, vec![Language::Python, Language::Rust]);
+
+        let dir = project.path().join("configs/lint");
+        let code = init_linter_configs(&dir, &[], Some(&languages), false);
+        assert_eq!(code, ExitCode::SUCCESS);
+
+        let mut files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["pyproject.toml", "ruff.toml", "rustfmt.toml"]);
+    }
+
+    #[test]
+    fn test_init_configs_force_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rustfmt.toml");
+        std::fs::write(&path, "max_width = 80\n").unwrap();
+        let configs = select_default_configs(&only(&["rustfmt"]), None).unwrap();
+
+        let report = write_default_configs(dir.path(), &configs, false);
+        assert_eq!(
+            report,
+            InitConfigsReport {
+                skipped: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "max_width = 80\n");
+
+        let report = write_default_configs(dir.path(), &configs, true);
+        assert_eq!(
+            report,
+            InitConfigsReport {
+                overwritten: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), configs[0].2);
+    }
+
+    #[test]
+    fn test_init_configs_force_keeps_pyproject_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pyproject.toml");
+        let project = "[project]\nname = \"app\"\n\n[tool.ruff]\nline-length = 80\n\n[tool.black]\nline-length = 80\n";
+        std::fs::write(&path, project).unwrap();
+        let configs: Vec<_> = select_default_configs(&only(&["ruff"]), None)
+            .unwrap()
+            .into_iter()
+            .filter(|(_, filename, _)| *filename == "pyproject.toml")
+            .collect();
+
+        let report = write_default_configs(dir.path(), &configs, true);
+        assert_eq!(report.overwritten, 1);
+        let merged: toml::Value = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(merged["project"]["name"].as_str(), Some("app"));
+        assert_eq!(
+            merged["tool"]["black"]["line-length"].as_integer(),
+            Some(80)
+        );
+        assert_eq!(
+            merged["tool"]["ruff"]["line-length"].as_integer(),
+            Some(120)
+        );
+        assert!(merged["tool"]["ruff"]["lint"]["select"].is_array());
+    }
+
+    #[test]
+    fn test_default_configs_cover_go_java_objc() {
+        let configs = get_default_configs();
+        let content = |filename: &str| {
+            configs
+                .iter()
+                .find(|(_, f, _)| *f == filename)
+                .map(|(_, _, c)| *c)
+                .unwrap_or_else(|| panic!("missing default config {}", filename))
+        };
+
+        let golangci: serde_yaml::Value = serde_yaml::from_str(content(".golangci.yml")).unwrap();
+        assert!(golangci["linters"]["enable"].is_sequence());
+
+        let checkstyle = roxmltree::Document::parse_with_options(
+            content("checkstyle.xml"),
+            roxmltree::ParsingOptions {
+                allow_dtd: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(checkstyle.root_element().attribute("name"), Some("Checker"));
+
+        // .clang-format holds a default document plus an Objective-C section
+        let documents: Vec<serde_yaml::Value> =
+            serde_yaml::Deserializer::from_str(content(".clang-format"))
+                .map(|doc| serde::Deserialize::deserialize(doc).unwrap())
+                .collect();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[1]["Language"].as_str(), Some("ObjC"));
+
+        assert!(select_default_configs(&only(&["golangci-lint", "checkstyle"]), None).is_ok());
+    }
+}
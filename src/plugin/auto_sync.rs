@@ -314,6 +314,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_should_sync_disabled() {
         let (manager, _temp) = create_temp_manager();
         let mut config = AutoSyncConfig::default();
@@ -23,6 +23,7 @@ use super::fetcher::PluginFetcher;
 use super::manifest::PluginManifest;
 use super::registry::PluginRegistry;
 use super::{log_plugin_operation, PluginError, PluginSource, Result};
+use crate::presets::Preset;
 
 /// Loaded configuration from a plugin
 #[derive(Debug, Clone)]
@@ -85,7 +86,21 @@ impl PluginLoader {
         sources: &[PluginSource],
         force_update: bool,
     ) -> Result<Vec<LoadedConfig>> {
+        Ok(self.load_configs_and_preset(sources, force_update)?.0)
+    }
+
+    /// Load configurations and the preset declared by plugins, reading each
+    /// plugin's manifest once
+    ///
+    /// Configs merge as in [`PluginLoader::load_configs`]; when several plugins
+    /// declare a preset, the last enabled plugin wins.
+    pub fn load_configs_and_preset(
+        &self,
+        sources: &[PluginSource],
+        force_update: bool,
+    ) -> Result<(Vec<LoadedConfig>, Option<Preset>)> {
         let mut all_configs: HashMap<(String, String), LoadedConfig> = HashMap::new();
+        let mut preset = None;
 
         for source in sources {
             // Skip disabled plugins
@@ -100,7 +115,11 @@ impl PluginLoader {
 
             // Try to load plugin configs
             match self.load_plugin_configs(source, force_update) {
-                Ok(configs) => {
+                Ok((configs, plugin_preset)) => {
+                    if let Some(plugin_preset) = plugin_preset {
+                        self.log_preset(&plugin_preset, source);
+                        preset = Some(plugin_preset);
+                    }
                     for config in configs {
                         // Later plugins override earlier ones (same language + tool)
                         let key = (config.language.clone(), config.tool.clone());
@@ -124,12 +143,16 @@ impl PluginLoader {
                     );
 
                     // Try to use cached version if available
-                    if let Ok(configs) = self.load_from_cache_only(source) {
+                    if let Ok((configs, plugin_preset)) = self.load_from_cache_only(source) {
                         log_plugin_operation(
                             "fallback",
                             &format!("Using cached version of '{}'", source.name),
                             self.verbose,
                         );
+                        if let Some(plugin_preset) = plugin_preset {
+                            self.log_preset(&plugin_preset, source);
+                            preset = Some(plugin_preset);
+                        }
                         for config in configs {
                             let key = (config.language.clone(), config.tool.clone());
                             all_configs.insert(key, config);
@@ -139,7 +162,15 @@ impl PluginLoader {
             }
         }
 
-        Ok(all_configs.into_values().collect())
+        Ok((all_configs.into_values().collect(), preset))
+    }
+
+    fn log_preset(&self, preset: &Preset, source: &PluginSource) {
+        log_plugin_operation(
+            "load",
+            &format!("Loaded preset '{}' from {}", preset.name, source.name),
+            self.verbose,
+        );
     }
 
     /// Resolve alias to URL by looking up in project and global configurations
@@ -188,12 +219,25 @@ impl PluginLoader {
         Ok(source.clone())
     }
 
-    /// Load configurations from a single plugin source
+    /// Load configurations (and the preset, if any) from a single plugin source
     fn load_plugin_configs(
         &self,
         source: &PluginSource,
         force_update: bool,
-    ) -> Result<Vec<LoadedConfig>> {
+    ) -> Result<(Vec<LoadedConfig>, Option<Preset>)> {
+        let (cache_path, manifest) = self.load_plugin_manifest(source, force_update)?;
+
+        // Extract all configs
+        let configs = self.extract_configs(&manifest, &cache_path)?;
+        Ok((configs, manifest.preset))
+    }
+
+    /// Fetch a plugin and load its manifest
+    fn load_plugin_manifest(
+        &self,
+        source: &PluginSource,
+        force_update: bool,
+    ) -> Result<(PathBuf, PluginManifest)> {
         // First, try to resolve alias from configuration files
         let resolved_from_alias = self.resolve_alias(source)?;
 
@@ -207,21 +251,28 @@ impl PluginLoader {
 
         // Load manifest
         let manifest = PluginManifest::load(&cached.cache_path)?;
+        Ok((cached.cache_path, manifest))
+    }
 
-        // Extract all configs
-        self.extract_configs(&manifest, &cached.cache_path)
+    /// Load configs (and the preset, if any) from cache only (no network)
+    fn load_from_cache_only(
+        &self,
+        source: &PluginSource,
+    ) -> Result<(Vec<LoadedConfig>, Option<Preset>)> {
+        let (cache_path, manifest) = self.load_cached_manifest(source)?;
+
+        // Extract configs
+        let configs = self.extract_configs(&manifest, &cache_path)?;
+        Ok((configs, manifest.preset))
     }
 
-    /// Load configs from cache only (no network)
-    fn load_from_cache_only(&self, source: &PluginSource) -> Result<Vec<LoadedConfig>> {
+    /// Load a plugin's manifest from cache only (no network)
+    fn load_cached_manifest(&self, source: &PluginSource) -> Result<(PathBuf, PluginManifest)> {
         // Resolve registry name to URL if needed
         let resolved_source = self.registry.resolve(source)?;
 
         // Load from cache
-        let (cache_path, manifest) = self.cache.load_cached_plugin(&resolved_source)?;
-
-        // Extract configs
-        self.extract_configs(&manifest, &cache_path)
+        self.cache.load_cached_plugin(&resolved_source)
     }
 
     /// Extract configurations from a loaded manifest
@@ -336,6 +387,60 @@ clippy = "rust/clippy.toml"
         plugin_dir
     }
 
+    #[test]
+    fn test_load_preset_from_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = PluginCache::with_dir(temp_dir.path().to_path_buf());
+        let url = "https://example.com/acme/style.git";
+        let plugin_dir = cache.url_to_cache_path(url);
+        fs::create_dir_all(plugin_dir.join("python")).unwrap();
+        fs::write(plugin_dir.join("python/ruff.toml"), "line-length = 100\n").unwrap();
+        fs::write(
+            plugin_dir.join("linthis-plugin.toml"),
+            r#"
+[plugin]
+name = "acme"
+version = "1.0.0"
+
+[configs.python]
+ruff = "python/ruff.toml"
+
+[preset]
+description = "Acme house style"
+
+[preset.languages.python]
+max_line_length = 100
+"#,
+        )
+        .unwrap();
+
+        let loader = PluginLoader::with_components(cache, PluginRegistry::new(), false);
+        let (configs, preset) = loader
+            .load_configs_and_preset(&[PluginSource::new(url)], false)
+            .unwrap();
+        assert_eq!(configs.len(), 1);
+        let preset = preset.unwrap();
+        assert_eq!(preset.name, "acme");
+        assert_eq!(
+            preset.get_language_rules("python").unwrap().max_line_length,
+            Some(100)
+        );
+        assert!(preset
+            .write_tool_configs()
+            .unwrap()
+            .files()
+            .any(|(language, tool, _)| { language == "python" && tool == "ruff" }));
+
+        // A plugin without a preset, or a disabled one, has none
+        let plugin_path = create_test_plugin(temp_dir.path(), "plain");
+        let plain = PluginManifest::load(&plugin_path).unwrap();
+        assert!(plain.preset.is_none());
+        let mut disabled = PluginSource::new(url);
+        disabled.enabled = false;
+        let (configs, preset) = loader.load_configs_and_preset(&[disabled], false).unwrap();
+        assert!(configs.is_empty() && preset.is_none());
+    }
+
     #[test]
     fn test_extract_configs() {
         let temp_dir = TempDir::new().unwrap();
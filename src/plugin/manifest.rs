@@ -19,6 +19,7 @@ use std::fs;
 use std::path::Path;
 
 use super::{PluginError, Result};
+use crate::presets::Preset;
 
 /// Plugin manifest file name
 pub const MANIFEST_FILENAME: &str = "linthis-plugin.toml";
@@ -31,6 +32,9 @@ pub struct PluginManifest {
     /// Configuration mappings by language
     #[serde(default)]
     pub configs: HashMap<String, HashMap<String, String>>,
+    /// House style preset (`[preset]` section), used like a built-in preset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<Preset>,
 }
 
 /// Plugin metadata
@@ -86,7 +90,7 @@ impl PluginManifest {
         // First try standard format
         if let Ok(manifest) = toml::from_str::<PluginManifest>(content) {
             if !manifest.configs.is_empty() {
-                return Ok(manifest);
+                return Ok(manifest.with_default_preset_name());
             }
         }
 
@@ -151,7 +155,34 @@ impl PluginManifest {
             }
         }
 
-        Ok(Self { plugin, configs })
+        let preset = value
+            .get("preset")
+            .map(|section| {
+                section.clone().try_into().map_err(|e: toml::de::Error| {
+                    PluginError::InvalidManifest {
+                        path: path.to_path_buf(),
+                        message: format!("Invalid preset: {}", e),
+                    }
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            plugin,
+            configs,
+            preset,
+        }
+        .with_default_preset_name())
+    }
+
+    /// Name an unnamed preset after the plugin
+    fn with_default_preset_name(mut self) -> Self {
+        if let Some(preset) = &mut self.preset {
+            if preset.name.is_empty() {
+                preset.name = self.plugin.name.clone();
+            }
+        }
+        self
     }
 
     /// Validate manifest contents
@@ -225,6 +256,7 @@ impl PluginManifest {
                 }],
             },
             configs: HashMap::new(),
+            preset: None,
         }
     }
 
@@ -240,6 +272,7 @@ impl PluginManifest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::presets::{IndentStyle, QuoteStyle};
 
     #[test]
     fn test_parse_minimal_manifest() {
@@ -284,6 +317,60 @@ ruff = "python/ruff.toml"
         );
     }
 
+    #[test]
+    fn test_parse_preset_section() {
+        let content = r#"
+[plugin]
+name = "acme"
+version = "1.0.0"
+
+[configs.python]
+ruff = "python/ruff.toml"
+
+[preset]
+description = "Acme house style"
+
+[preset.languages.python]
+max_line_length = 100
+quotes = "single"
+disable_rules = ["E501"]
+
+[preset.languages.typescript]
+indent = { spaces = 2 }
+semicolons = false
+"#;
+        let manifest = PluginManifest::parse(content, Path::new("test")).unwrap();
+        let preset = manifest.preset.unwrap();
+        assert_eq!(preset.name, "acme");
+        assert_eq!(preset.description, "Acme house style");
+
+        let python = preset.get_language_rules("python").unwrap();
+        assert_eq!(python.max_line_length, Some(100));
+        assert_eq!(python.quotes, Some(QuoteStyle::Single));
+        assert_eq!(python.disable_rules, vec!["E501"]);
+        let typescript = preset.get_language_rules("typescript").unwrap();
+        assert_eq!(typescript.indent, Some(IndentStyle::Spaces(2)));
+        assert_eq!(typescript.semicolons, Some(false));
+
+        // Extended format, and an invalid preset is an error
+        let extended = r#"
+[plugin]
+name = "acme"
+version = "1.0.0"
+
+["language.python".tools.ruff]
+files = ["ruff.toml"]
+
+[preset.languages.python]
+max_line_length = 88
+"#;
+        let manifest = PluginManifest::parse(extended, Path::new("test")).unwrap();
+        let rules = manifest.preset.unwrap().languages.remove("python").unwrap();
+        assert_eq!(rules.max_line_length, Some(88));
+        let invalid = extended.replace("88", "\"wide\"");
+        assert!(PluginManifest::parse(&invalid, Path::new("test")).is_err());
+    }
+
     #[test]
     fn test_scaffold_manifest() {
         let manifest = PluginManifest::scaffold("my-config");
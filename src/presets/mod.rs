@@ -17,6 +17,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 
 /// Available format presets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -28,6 +31,8 @@ pub enum PresetName {
     Standard,
     /// Airbnb style guide
     Airbnb,
+    /// No preset: tools keep their own defaults
+    None,
 }
 
 impl PresetName {
@@ -37,20 +42,30 @@ impl PresetName {
             "google" => Some(PresetName::Google),
             "standard" => Some(PresetName::Standard),
             "airbnb" => Some(PresetName::Airbnb),
+            "none" => Some(PresetName::None),
             _ => None,
         }
     }
 
+    /// The preset in effect: `--preset` over the config's `preset`.
+    /// Returns None when neither names a preset, or either says "none".
+    pub fn resolve(cli: Option<&str>, config: Option<&str>) -> Option<Self> {
+        cli.or(config)
+            .and_then(Self::parse)
+            .filter(|name| *name != PresetName::None)
+    }
+
     /// Get the preset name as a string
     pub fn as_str(&self) -> &'static str {
         match self {
             PresetName::Google => "google",
             PresetName::Standard => "standard",
             PresetName::Airbnb => "airbnb",
+            PresetName::None => "none",
         }
     }
 
-    /// List all available presets
+    /// List all available presets (not counting `none`)
     pub fn all() -> &'static [PresetName] {
         &[PresetName::Google, PresetName::Standard, PresetName::Airbnb]
     }
@@ -82,6 +97,86 @@ pub struct LanguageRules {
     pub disable_rules: Vec<String>,
 }
 
+impl LanguageRules {
+    /// ruff config (`ruff.toml`) applying these rules
+    pub fn ruff_config(&self) -> String {
+        let mut config = String::new();
+        if let Some(max_line_length) = self.max_line_length {
+            config.push_str(&format!("line-length = {}\n", max_line_length));
+        }
+        if let Some(IndentStyle::Spaces(width)) = self.indent {
+            config.push_str(&format!("indent-width = {}\n", width));
+        }
+
+        let mut format = Vec::new();
+        if let Some(indent) = self.indent {
+            let style = match indent {
+                IndentStyle::Tabs => "tab",
+                IndentStyle::Spaces(_) => "space",
+            };
+            format.push(format!("indent-style = \"{}\"", style));
+        }
+        if let Some(quotes) = self.quotes {
+            let style = match quotes {
+                QuoteStyle::Single => "single",
+                QuoteStyle::Double => "double",
+            };
+            format.push(format!("quote-style = \"{}\"", style));
+        }
+        if !format.is_empty() {
+            config.push_str(&format!("\n[format]\n{}\n", format.join("\n")));
+        }
+
+        let mut lint = Vec::new();
+        if !self.enable_rules.is_empty() {
+            lint.push(format!("extend-select = {:?}", self.enable_rules));
+        }
+        if !self.disable_rules.is_empty() {
+            lint.push(format!("ignore = {:?}", self.disable_rules));
+        }
+        if !lint.is_empty() {
+            config.push_str(&format!("\n[lint]\n{}\n", lint.join("\n")));
+        }
+        config
+    }
+
+    /// prettier config (`.prettierrc.json`) applying these rules
+    pub fn prettier_config(&self) -> String {
+        let mut config = serde_json::Map::new();
+        match self.indent {
+            Some(IndentStyle::Tabs) => {
+                config.insert("useTabs".to_string(), true.into());
+            }
+            Some(IndentStyle::Spaces(width)) => {
+                config.insert("useTabs".to_string(), false.into());
+                config.insert("tabWidth".to_string(), width.into());
+            }
+            None => {}
+        }
+        if let Some(max_line_length) = self.max_line_length {
+            config.insert("printWidth".to_string(), max_line_length.into());
+        }
+        if let Some(semicolons) = self.semicolons {
+            config.insert("semi".to_string(), semicolons.into());
+        }
+        if let Some(quotes) = self.quotes {
+            config.insert(
+                "singleQuote".to_string(),
+                (quotes == QuoteStyle::Single).into(),
+            );
+        }
+        if let Some(trailing_commas) = self.trailing_commas {
+            let style = match trailing_commas {
+                TrailingCommaStyle::None => "none",
+                TrailingCommaStyle::Es5 => "es5",
+                TrailingCommaStyle::All => "all",
+            };
+            config.insert("trailingComma".to_string(), style.into());
+        }
+        serde_json::to_string_pretty(&config).unwrap_or_default()
+    }
+}
+
 /// Indentation style
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -119,8 +214,10 @@ pub enum TrailingCommaStyle {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Preset {
     /// Preset name
+    #[serde(default)]
     pub name: String,
     /// Description
+    #[serde(default)]
     pub description: String,
     /// Per-language rules
     #[serde(default)]
@@ -362,13 +459,115 @@ impl Preset {
             PresetName::Google => Self::google(),
             PresetName::Standard => Self::standard(),
             PresetName::Airbnb => Self::airbnb(),
+            PresetName::None => Preset {
+                name: "none".to_string(),
+                description: "No preset; tools use their own defaults".to_string(),
+                languages: HashMap::new(),
+            },
+        }
+    }
+
+    /// The preset in effect: a built-in preset named by `--preset` or the
+    /// config's `preset`, else the preset of a plugin. `none` disables both.
+    pub fn resolve(
+        cli: Option<&str>,
+        config: Option<&str>,
+        plugin: Option<Preset>,
+    ) -> Option<Self> {
+        if let Some(name) = PresetName::resolve(cli, config) {
+            return Some(Self::load(name));
         }
+        let disabled = cli.or(config).and_then(PresetName::parse) == Some(PresetName::None);
+        plugin.filter(|_| !disabled)
     }
 
     /// Get rules for a specific language
     pub fn get_language_rules(&self, language: &str) -> Option<&LanguageRules> {
         self.languages.get(language)
     }
+
+    /// Write ruff and prettier configs for this preset to a temporary
+    /// directory, so the tools follow the preset without project config files.
+    pub fn write_tool_configs(&self) -> io::Result<PresetToolConfigs> {
+        let dir = std::env::temp_dir().join(format!(
+            "linthis-preset-{}-{}",
+            self.name,
+            std::process::id()
+        ));
+        let mut configs = PresetToolConfigs {
+            dir,
+            files: Vec::new(),
+        };
+
+        let mut languages: Vec<_> = self.languages.iter().collect();
+        languages.sort_by_key(|(language, _)| language.as_str());
+        for (language, rules) in languages {
+            let (tool, filename, content) = match language.as_str() {
+                "python" => ("ruff", "ruff.toml", rules.ruff_config()),
+                "javascript" | "typescript" => {
+                    ("prettier", ".prettierrc.json", rules.prettier_config())
+                }
+                _ => continue,
+            };
+            // One directory per language, like .linthis/configs/{language}/
+            let lang_dir = configs.dir.join(language);
+            fs::create_dir_all(&lang_dir)?;
+            let path = lang_dir.join(filename);
+            fs::write(&path, content)?;
+            configs.files.push((language.clone(), tool, path));
+        }
+        Ok(configs)
+    }
+}
+
+/// Tool config files generated from a preset. The files are removed when
+/// this is dropped.
+#[derive(Debug)]
+pub struct PresetToolConfigs {
+    dir: PathBuf,
+    files: Vec<(String, &'static str, PathBuf)>,
+}
+
+impl PresetToolConfigs {
+    /// Generated configs as (language, tool, config path)
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str, &Path)> {
+        self.files
+            .iter()
+            .map(|(language, tool, path)| (language.as_str(), *tool, path.as_path()))
+    }
+}
+
+/// Whether the project at `root` configures `tool` itself, in which case its
+/// own config wins over a preset-generated one.
+pub fn project_has_tool_config(root: &Path, tool: &str) -> bool {
+    let (files, manifest, section): (&[&str], &str, &str) = match tool {
+        "ruff" => (&["ruff.toml", ".ruff.toml"], "pyproject.toml", "[tool.ruff"),
+        "prettier" => (
+            &[
+                ".prettierrc",
+                ".prettierrc.json",
+                ".prettierrc.yaml",
+                ".prettierrc.yml",
+                ".prettierrc.js",
+                ".prettierrc.cjs",
+                ".prettierrc.toml",
+                "prettier.config.js",
+                "prettier.config.cjs",
+                "prettier.config.mjs",
+            ],
+            "package.json",
+            "\"prettier\"",
+        ),
+        _ => return false,
+    };
+    files.iter().any(|name| root.join(name).is_file())
+        || fs::read_to_string(root.join(manifest)).is_ok_and(|content| content.contains(section))
+}
+
+impl Drop for PresetToolConfigs {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
 }
 
 #[cfg(test)]
@@ -382,6 +581,45 @@ mod tests {
         assert_eq!(PresetName::parse("standard"), Some(PresetName::Standard));
         assert_eq!(PresetName::parse("airbnb"), Some(PresetName::Airbnb));
         assert_eq!(PresetName::parse("unknown"), None);
+        assert_eq!(PresetName::parse("none"), Some(PresetName::None));
+    }
+
+    #[test]
+    fn test_preset_none_overrides_config_preset() {
+        assert_eq!(
+            PresetName::resolve(None, Some("google")),
+            Some(PresetName::Google)
+        );
+        assert_eq!(
+            PresetName::resolve(Some("airbnb"), Some("google")),
+            Some(PresetName::Airbnb)
+        );
+        assert_eq!(PresetName::resolve(Some("none"), Some("google")), None);
+        assert_eq!(PresetName::resolve(None, Some("none")), None);
+
+        let configs = Preset::load(PresetName::None).write_tool_configs().unwrap();
+        assert_eq!(configs.files().count(), 0);
+    }
+
+    #[test]
+    fn test_plugin_preset_applies_without_builtin_preset() {
+        let plugin = || {
+            Some(Preset {
+                name: "acme".to_string(),
+                ..Default::default()
+            })
+        };
+        let name = |preset: Option<Preset>| preset.map(|p| p.name);
+        assert_eq!(
+            name(Preset::resolve(None, None, plugin())).as_deref(),
+            Some("acme")
+        );
+        assert_eq!(
+            name(Preset::resolve(None, Some("google"), plugin())).as_deref(),
+            Some("google")
+        );
+        assert_eq!(name(Preset::resolve(Some("none"), None, plugin())), None);
+        assert_eq!(name(Preset::resolve(None, None, None)), None);
     }
 
     #[test]
@@ -411,4 +649,93 @@ mod tests {
         let js_rules = preset.get_language_rules("javascript").unwrap();
         assert_eq!(js_rules.trailing_commas, Some(TrailingCommaStyle::All));
     }
+
+    #[test]
+    fn test_ruff_config_from_preset() {
+        let preset = Preset::standard();
+        let rules = preset.get_language_rules("python").unwrap();
+        assert_eq!(
+            rules.ruff_config(),
+            "line-length = 79\nindent-width = 4\n\n[format]\nindent-style = \"space\"\n"
+        );
+
+        let rules = LanguageRules {
+            quotes: Some(QuoteStyle::Single),
+            enable_rules: vec!["I".to_string()],
+            disable_rules: vec!["E501".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.ruff_config(),
+            "\n[format]\nquote-style = \"single\"\n\n[lint]\nextend-select = [\"I\"]\nignore = [\"E501\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_prettier_config_from_preset() {
+        let preset = Preset::airbnb();
+        let rules = preset.get_language_rules("typescript").unwrap();
+        let config: serde_json::Value = serde_json::from_str(&rules.prettier_config()).unwrap();
+        assert_eq!(
+            config,
+            serde_json::json!({
+                "useTabs": false,
+                "tabWidth": 2,
+                "printWidth": 100,
+                "semi": true,
+                "singleQuote": true,
+                "trailingComma": "all",
+            })
+        );
+    }
+
+    #[test]
+    fn test_tool_configs_are_written_and_cleaned_up() {
+        let configs = Preset::standard().write_tool_configs().unwrap();
+        let files: Vec<_> = configs
+            .files()
+            .map(|(language, tool, path)| {
+                (language.to_string(), tool.to_string(), path.to_path_buf())
+            })
+            .collect();
+        let tools: Vec<_> = files
+            .iter()
+            .map(|(l, t, _)| (l.as_str(), t.as_str()))
+            .collect();
+        assert_eq!(
+            tools,
+            [
+                ("javascript", "prettier"),
+                ("python", "ruff"),
+                ("typescript", "prettier")
+            ]
+        );
+        let semi = fs::read_to_string(&files[0].2).unwrap();
+        assert!(semi.contains("\"semi\": false"));
+
+        drop(configs);
+        assert!(files.iter().all(|(_, _, path)| !path.exists()));
+    }
+
+    #[test]
+    fn test_project_tool_config_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        assert!(!project_has_tool_config(root, "ruff"));
+        assert!(!project_has_tool_config(root, "prettier"));
+
+        // A pyproject.toml only counts with a ruff section
+        fs::write(root.join("pyproject.toml"), "[project]\nname = \"app\"\n").unwrap();
+        assert!(!project_has_tool_config(root, "ruff"));
+        fs::write(
+            root.join("pyproject.toml"),
+            "[tool.ruff]\nline-length = 100\n",
+        )
+        .unwrap();
+        assert!(project_has_tool_config(root, "ruff"));
+
+        fs::write(root.join(".prettierrc.json"), "{}").unwrap();
+        assert!(project_has_tool_config(root, "prettier"));
+        assert!(!project_has_tool_config(root, "clang-format"));
+    }
 }
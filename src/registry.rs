@@ -0,0 +1,312 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Registry of custom checkers and formatters.
+//!
+//! Library consumers can add tools for languages linthis doesn't support (or
+//! replace a built-in tool for an extension), and register in-process checkers
+//! for built-in languages. `run()` consults this registry before falling back
+//! to the built-in language tools.
+//!
+//! ```ignore
+//! linthis::register_checker("proto", Box::new(|| Box::new(MyProtoChecker::new())));
+//! linthis::register_language_checker(Language::Python, Box::new(RuleEngineChecker));
+//! ```
+
+use crate::checkers::{CheckResult, Checker};
+use crate::formatters::Formatter;
+use crate::utils::types::LintIssue;
+use crate::Language;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Factory creating a checker instance
+pub type CheckerFactory = Box<dyn Fn() -> Box<dyn Checker> + Send + Sync>;
+
+/// Factory creating a formatter instance
+pub type FormatterFactory = Box<dyn Fn() -> Box<dyn Formatter> + Send + Sync>;
+
+static CHECKERS: RwLock<Option<HashMap<String, Arc<CheckerFactory>>>> = RwLock::new(None);
+static FORMATTERS: RwLock<Option<HashMap<String, Arc<FormatterFactory>>>> = RwLock::new(None);
+static NAMED_CHECKERS: RwLock<Option<HashMap<String, Arc<CheckerFactory>>>> = RwLock::new(None);
+static LANGUAGE_CHECKERS: RwLock<Option<Scoped<Arc<dyn Checker>>>> = RwLock::new(None);
+static IMPORT_ORGANIZERS: RwLock<Option<Scoped<Arc<FormatterFactory>>>> = RwLock::new(None);
+
+/// Per-language registrations, each either global (`None`) or limited to the
+/// files under a root directory.
+type Scoped<T> = HashMap<(Language, Option<PathBuf>), T>;
+
+/// Find the registration for a file of the given language. A scoped one beats
+/// the global one, and a deeper root beats a shallower one.
+fn scoped_lookup<T: Clone>(map: &Scoped<T>, lang: Language, file: &Path) -> Option<T> {
+    map.iter()
+        .filter(|((l, root), _)| *l == lang && root.as_ref().is_none_or(|r| file.starts_with(r)))
+        .max_by_key(|((_, root), _)| root.as_ref().map_or(0, |r| r.components().count() + 1))
+        .map(|(_, value)| value.clone())
+}
+
+/// Shared checkers can be handed out as `Box<dyn Checker>`.
+impl Checker for Arc<dyn Checker> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    fn supported_languages(&self) -> &[Language] {
+        self.as_ref().supported_languages()
+    }
+
+    fn check(&self, path: &Path) -> CheckResult<Vec<LintIssue>> {
+        self.as_ref().check(path)
+    }
+
+    fn is_available(&self) -> bool {
+        self.as_ref().is_available()
+    }
+
+    fn binary(&self) -> PathBuf {
+        self.as_ref().binary()
+    }
+
+    fn tool_version(&self) -> Option<String> {
+        self.as_ref().tool_version()
+    }
+}
+
+/// Normalize an extension: strip a leading dot and lowercase.
+fn normalize_extension(ext: &str) -> String {
+    ext.trim_start_matches('.').to_lowercase()
+}
+
+/// Get the normalized extension of a path.
+pub(crate) fn path_extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(normalize_extension)
+}
+
+/// Register a checker for files with the given extension (e.g. "proto" or ".proto").
+///
+/// Registering the same extension again replaces the previous factory.
+pub fn register_checker(ext: &str, factory: CheckerFactory) {
+    let mut checkers = CHECKERS.write().unwrap();
+    checkers
+        .get_or_insert_with(HashMap::new)
+        .insert(normalize_extension(ext), Arc::new(factory));
+}
+
+/// Register a formatter for files with the given extension (e.g. "proto" or ".proto").
+///
+/// Registering the same extension again replaces the previous factory.
+pub fn register_formatter(ext: &str, factory: FormatterFactory) {
+    let mut formatters = FORMATTERS.write().unwrap();
+    formatters
+        .get_or_insert_with(HashMap::new)
+        .insert(normalize_extension(ext), Arc::new(factory));
+}
+
+/// Register an in-process checker for a built-in language.
+///
+/// It replaces the built-in checker for that language (e.g. a company-internal
+/// rule engine instead of shelling out to ruff).
+pub fn register_language_checker(lang: Language, checker: Box<dyn Checker>) {
+    let mut checkers = LANGUAGE_CHECKERS.write().unwrap();
+    checkers
+        .get_or_insert_with(HashMap::new)
+        .insert((lang, None), Arc::from(checker));
+}
+
+/// Register an in-process checker for a built-in language, used only for the
+/// files under `root`. It takes precedence over a global registration.
+pub fn register_language_checker_in(root: &Path, lang: Language, checker: Box<dyn Checker>) {
+    let mut checkers = LANGUAGE_CHECKERS.write().unwrap();
+    checkers
+        .get_or_insert_with(HashMap::new)
+        .insert((lang, Some(root.to_path_buf())), Arc::from(checker));
+}
+
+/// Remove the checker registered for a built-in language.
+pub fn unregister_language_checker(lang: Language) {
+    if let Some(map) = LANGUAGE_CHECKERS.write().unwrap().as_mut() {
+        map.remove(&(lang, None));
+    }
+}
+
+/// Remove the checker registered for a built-in language under `root`.
+pub fn unregister_language_checker_in(root: &Path, lang: Language) {
+    if let Some(map) = LANGUAGE_CHECKERS.write().unwrap().as_mut() {
+        map.remove(&(lang, Some(root.to_path_buf())));
+    }
+}
+
+/// Get the checker registered for a file of a built-in language, if any.
+pub fn language_checker(lang: Language, file: &Path) -> Option<Box<dyn Checker>> {
+    let checker = scoped_lookup(LANGUAGE_CHECKERS.read().unwrap().as_ref()?, lang, file)?;
+    Some(Box::new(checker))
+}
+
+/// Register an import organizer for a built-in language.
+///
+/// It replaces the built-in organizer (ruff's isort rules, goimports) used
+/// by the `organize_imports` pass.
+pub fn register_import_organizer(lang: Language, factory: FormatterFactory) {
+    let mut organizers = IMPORT_ORGANIZERS.write().unwrap();
+    organizers
+        .get_or_insert_with(HashMap::new)
+        .insert((lang, None), Arc::new(factory));
+}
+
+/// Register an import organizer for a built-in language, used only for the
+/// files under `root`. It takes precedence over a global registration.
+pub fn register_import_organizer_in(root: &Path, lang: Language, factory: FormatterFactory) {
+    let mut organizers = IMPORT_ORGANIZERS.write().unwrap();
+    organizers
+        .get_or_insert_with(HashMap::new)
+        .insert((lang, Some(root.to_path_buf())), Arc::new(factory));
+}
+
+/// Remove the import organizer registered for a built-in language.
+pub fn unregister_import_organizer(lang: Language) {
+    if let Some(map) = IMPORT_ORGANIZERS.write().unwrap().as_mut() {
+        map.remove(&(lang, None));
+    }
+}
+
+/// Remove the import organizer registered for a built-in language under `root`.
+pub fn unregister_import_organizer_in(root: &Path, lang: Language) {
+    if let Some(map) = IMPORT_ORGANIZERS.write().unwrap().as_mut() {
+        map.remove(&(lang, Some(root.to_path_buf())));
+    }
+}
+
+/// Create the import organizer registered for a file of a built-in language, if any.
+pub fn import_organizer(lang: Language, file: &Path) -> Option<Box<dyn Formatter>> {
+    let factory = scoped_lookup(IMPORT_ORGANIZERS.read().unwrap().as_ref()?, lang, file)?;
+    Some(factory())
+}
+
+/// Register a checker that can be referenced by name in a language's
+/// `checkers` chain (e.g. `checkers = ["ruff", "my-rules"]`).
+pub fn register_named_checker(name: &str, factory: CheckerFactory) {
+    let mut checkers = NAMED_CHECKERS.write().unwrap();
+    checkers
+        .get_or_insert_with(HashMap::new)
+        .insert(name.to_string(), Arc::new(factory));
+}
+
+/// Remove a checker registered by name.
+pub fn unregister_named_checker(name: &str) {
+    if let Some(map) = NAMED_CHECKERS.write().unwrap().as_mut() {
+        map.remove(name);
+    }
+}
+
+/// Create the checker registered under the given name, if any.
+pub fn named_checker(name: &str) -> Option<Box<dyn Checker>> {
+    let factory = NAMED_CHECKERS.read().unwrap().as_ref()?.get(name)?.clone();
+    Some(factory())
+}
+
+/// Remove any checker and formatter registered for the extension.
+pub fn unregister(ext: &str) {
+    let ext = normalize_extension(ext);
+    if let Some(map) = CHECKERS.write().unwrap().as_mut() {
+        map.remove(&ext);
+    }
+    if let Some(map) = FORMATTERS.write().unwrap().as_mut() {
+        map.remove(&ext);
+    }
+}
+
+/// Check if a custom checker or formatter is registered for the file's extension.
+pub fn has_custom_tools(path: &Path) -> bool {
+    let Some(ext) = path_extension(path) else {
+        return false;
+    };
+    let has_checker = CHECKERS
+        .read()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|m| m.contains_key(&ext));
+    let has_formatter = FORMATTERS
+        .read()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|m| m.contains_key(&ext));
+    has_checker || has_formatter
+}
+
+/// Create the custom checker registered for the file's extension, if any.
+pub fn custom_checker(path: &Path) -> Option<Box<dyn Checker>> {
+    let ext = path_extension(path)?;
+    // Clone the factory out so the lock isn't held while it runs
+    let factory = CHECKERS.read().unwrap().as_ref()?.get(&ext)?.clone();
+    Some(factory())
+}
+
+/// Create the custom formatter registered for the file's extension, if any.
+pub fn custom_formatter(path: &Path) -> Option<Box<dyn Formatter>> {
+    let ext = path_extension(path)?;
+    let factory = FORMATTERS.read().unwrap().as_ref()?.get(&ext)?.clone();
+    Some(factory())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoopChecker;
+
+    impl Checker for NoopChecker {
+        fn name(&self) -> &str {
+            "noop"
+        }
+
+        fn supported_languages(&self) -> &[Language] {
+            &[]
+        }
+
+        fn check(&self, _path: &Path) -> CheckResult<Vec<LintIssue>> {
+            Ok(Vec::new())
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_register_and_lookup_checker() {
+        register_checker(".RegTestExt", Box::new(|| Box::new(NoopChecker)));
+
+        assert!(has_custom_tools(Path::new("a/b.regtestext")));
+        assert!(custom_checker(Path::new("b.REGTESTEXT")).is_some());
+        assert!(custom_formatter(Path::new("b.regtestext")).is_none());
+        assert!(custom_checker(Path::new("b.other")).is_none());
+
+        unregister("regtestext");
+        assert!(!has_custom_tools(Path::new("b.regtestext")));
+    }
+
+    #[test]
+    fn test_register_language_checker() {
+        let root = Path::new("/registry-test/project");
+        let inside = root.join("src/Main.java");
+        register_language_checker_in(root, Language::Java, Box::new(NoopChecker));
+        let checker = language_checker(Language::Java, &inside).unwrap();
+        assert_eq!(checker.name(), "noop");
+        assert!(checker.is_available());
+        assert!(language_checker(Language::Java, Path::new("/elsewhere/Main.java")).is_none());
+        assert!(language_checker(Language::Go, &inside).is_none());
+
+        unregister_language_checker_in(root, Language::Java);
+        assert!(language_checker(Language::Java, &inside).is_none());
+    }
+}
@@ -16,7 +16,7 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -34,6 +34,11 @@ pub struct SelfUpdateConfig {
     /// Check for updates every N days
     #[serde(default = "default_interval_days")]
     pub interval_days: u64,
+
+    /// After upgrading, check that `pip show linthis` reports the new
+    /// version and roll back if it doesn't
+    #[serde(default = "default_verify")]
+    pub verify: bool,
 }
 
 fn default_enabled() -> bool {
@@ -48,12 +53,17 @@ fn default_interval_days() -> u64 {
     7
 }
 
+fn default_verify() -> bool {
+    true
+}
+
 impl Default for SelfUpdateConfig {
     fn default() -> Self {
         Self {
             enabled: default_enabled(),
             mode: default_mode(),
             interval_days: default_interval_days(),
+            verify: default_verify(),
         }
     }
 }
@@ -86,6 +96,71 @@ impl SelfUpdateConfig {
     }
 }
 
+/// Outcome of checking the installed version after an upgrade
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeCheck {
+    /// The installed version is the expected one
+    Verified,
+    /// A different version is installed
+    Mismatch { found: String },
+    /// The installed version could not be determined
+    Unknown,
+}
+
+/// Compare the version reported by `query` with the expected one.
+pub fn verify_upgrade(expected: &str, query: impl FnOnce() -> Option<String>) -> UpgradeCheck {
+    match query() {
+        Some(found) if found == expected => UpgradeCheck::Verified,
+        Some(found) => UpgradeCheck::Mismatch { found },
+        None => UpgradeCheck::Unknown,
+    }
+}
+
+/// Extract the version from `pip show linthis` output ("Version: 0.1.2").
+fn parse_pip_show(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("Version:"))
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+/// `ERROR:` lines pip printed, which it can do even when exiting 0
+/// (e.g. dependency conflicts after a partial install).
+fn pip_errors(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("ERROR:"))
+        .collect()
+}
+
+/// The pip command that upgrades linthis
+pub const UPGRADE_COMMAND: &str = "pip install --upgrade linthis";
+
+/// The pip of [`UPGRADE_COMMAND`]; version checks and rollbacks use the same
+/// one, so they see the installation the upgrade changed.
+fn pip_program() -> &'static str {
+    UPGRADE_COMMAND.split_whitespace().next().unwrap_or("pip")
+}
+
+/// What `linthis self-update` did or, with `--dry-run`, would do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfUpdateOutcome {
+    /// The latest version could not be determined
+    Unknown { current: String },
+    /// The running version is the latest
+    UpToDate { current: String },
+    /// A newer version is available and was not installed (dry run)
+    WouldUpdate { current: String, latest: String },
+    /// An upgrade was attempted
+    Updated {
+        current: String,
+        latest: String,
+        success: bool,
+    },
+}
+
 /// Manages self-update timing and execution
 #[derive(Debug)]
 pub struct SelfUpdateManager {
@@ -101,11 +176,20 @@ impl Default for SelfUpdateManager {
 impl SelfUpdateManager {
     /// Create a new self-update manager
     pub fn new() -> Self {
-        let home_dir = Self::get_home_dir().expect("Failed to get home directory");
-        let linthis_dir = home_dir.join(".linthis");
+        Self::try_new().expect("Failed to get home directory")
+    }
+
+    /// Create a self-update manager, or None without a home directory
+    pub fn try_new() -> Option<Self> {
+        let linthis_dir = Self::get_home_dir()?.join(".linthis");
         let timestamp_file = linthis_dir.join(".self_update_last_check");
 
-        Self { timestamp_file }
+        Some(Self { timestamp_file })
+    }
+
+    /// Get the path to the timestamp file
+    pub fn timestamp_file_path(&self) -> &Path {
+        &self.timestamp_file
     }
 
     /// Get home directory
@@ -161,7 +245,7 @@ impl SelfUpdateManager {
     /// Check PyPI for the latest version
     pub fn get_latest_version(&self) -> Option<String> {
         // Use pip to check the latest version
-        let output = Command::new("pip")
+        let output = Command::new(pip_program())
             .args(["index", "versions", "linthis"])
             .output()
             .ok()?;
@@ -222,6 +306,42 @@ impl SelfUpdateManager {
         0
     }
 
+    /// Check for a newer version and upgrade to it, or only report the
+    /// decision with `dry_run`.
+    pub fn self_update(&self, dry_run: bool, verify: bool) -> io::Result<SelfUpdateOutcome> {
+        self.run_update(
+            dry_run,
+            || self.get_latest_version(),
+            |latest| self.upgrade(latest, verify),
+        )
+    }
+
+    /// Decide on an update with the given latest-version query, calling
+    /// `install` only when upgrading for real.
+    fn run_update(
+        &self,
+        dry_run: bool,
+        latest_version: impl FnOnce() -> Option<String>,
+        install: impl FnOnce(&str) -> io::Result<bool>,
+    ) -> io::Result<SelfUpdateOutcome> {
+        let current = self.get_current_version();
+        let Some(latest) = latest_version() else {
+            return Ok(SelfUpdateOutcome::Unknown { current });
+        };
+        if self.compare_versions(&current, &latest) >= 0 {
+            return Ok(SelfUpdateOutcome::UpToDate { current });
+        }
+        if dry_run {
+            return Ok(SelfUpdateOutcome::WouldUpdate { current, latest });
+        }
+        let success = install(&latest)?;
+        Ok(SelfUpdateOutcome::Updated {
+            current,
+            latest,
+            success,
+        })
+    }
+
     /// Prompt user for confirmation
     pub fn prompt_user(&self, current: &str, latest: &str) -> bool {
         print!(
@@ -237,23 +357,83 @@ impl SelfUpdateManager {
         response.is_empty() || response == "y" || response == "yes"
     }
 
-    /// Execute pip upgrade
-    pub fn upgrade(&self) -> io::Result<bool> {
+    /// Query the linthis version installed by the upgrading pip, which may
+    /// differ from the first `linthis` on PATH
+    pub fn installed_version(&self) -> Option<String> {
+        let output = Command::new(pip_program())
+            .args(["show", "linthis"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_pip_show(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Execute pip upgrade.
+    ///
+    /// With `verify`, the installed version must then match `expected`;
+    /// otherwise the previous version is reinstalled.
+    pub fn upgrade(&self, expected: &str, verify: bool) -> io::Result<bool> {
         println!("↓ Upgrading linthis via pip...");
 
-        let output = Command::new("pip")
-            .args(["install", "--upgrade", "linthis"])
+        let output = Command::new(pip_program())
+            .args(UPGRADE_COMMAND.split_whitespace().skip(1))
             .output()?;
 
-        if output.status.success() {
-            println!("✓ linthis upgraded successfully");
-            Ok(true)
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
             eprintln!("✗ Failed to upgrade linthis: {}", stderr);
-            Ok(false)
+            return Ok(false);
+        }
+        for line in pip_errors(&stdout).into_iter().chain(pip_errors(&stderr)) {
+            eprintln!("⚠ pip reported: {}", line);
+        }
+
+        if !verify {
+            println!("✓ linthis upgraded successfully");
+            return Ok(true);
+        }
+        match verify_upgrade(expected, || self.installed_version()) {
+            UpgradeCheck::Verified => {
+                println!("✓ linthis upgraded to {}", expected);
+                Ok(true)
+            }
+            UpgradeCheck::Mismatch { found } => {
+                eprintln!(
+                    "✗ Upgrade verification failed: expected linthis {}, found {}",
+                    expected, found
+                );
+                self.rollback()?;
+                Ok(false)
+            }
+            UpgradeCheck::Unknown => {
+                eprintln!(
+                    "⚠ Could not verify the upgrade: `{} show linthis` did not report a version",
+                    pip_program()
+                );
+                Ok(true)
+            }
         }
     }
+
+    /// Reinstall the running version after a failed upgrade
+    fn rollback(&self) -> io::Result<()> {
+        let current = self.get_current_version();
+        println!("↺ Rolling back to linthis {}...", current);
+        let status = Command::new(pip_program())
+            .args(["install", &format!("linthis=={}", current)])
+            .output()?
+            .status;
+        if !status.success() {
+            eprintln!(
+                "✗ Rollback failed; reinstall with `pip install linthis=={}`",
+                current
+            );
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -307,6 +487,86 @@ mod tests {
         assert!(bad_config2.validate().is_err());
     }
 
+    #[test]
+    fn test_self_update_config_verify_default() {
+        assert!(SelfUpdateConfig::default().verify);
+        let config: SelfUpdateConfig = toml::from_str("verify = false").unwrap();
+        assert!(!config.verify);
+    }
+
+    #[test]
+    fn test_verify_upgrade_with_stubbed_version_query() {
+        assert_eq!(
+            verify_upgrade("0.2.0", || Some("0.2.0".to_string())),
+            UpgradeCheck::Verified
+        );
+        // pip left the old version on PATH (e.g. another environment)
+        assert_eq!(
+            verify_upgrade("0.2.0", || Some("0.1.9".to_string())),
+            UpgradeCheck::Mismatch {
+                found: "0.1.9".to_string()
+            }
+        );
+        assert_eq!(verify_upgrade("0.2.0", || None), UpgradeCheck::Unknown);
+    }
+
+    #[test]
+    fn test_parse_pip_show() {
+        assert_eq!(
+            parse_pip_show("Name: linthis\nVersion: 0.2.0\nSummary: A linter\n"),
+            Some("0.2.0".to_string())
+        );
+        assert_eq!(
+            parse_pip_show("WARNING: Package(s) not found: linthis\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pip_errors_detects_partial_failure() {
+        let output = "Successfully installed linthis-0.2.0\n\
+                      ERROR: pip's dependency resolver does not currently take into account all the packages\n";
+        assert_eq!(pip_errors(output).len(), 1);
+        assert!(pip_errors("Successfully installed linthis-0.2.0\n").is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_installing() {
+        let manager = SelfUpdateManager::new();
+        let current = manager.get_current_version();
+        let never_install = |_: &str| -> io::Result<bool> { panic!("dry run ran pip install") };
+
+        let outcome = manager
+            .run_update(true, || Some("999.0.0".to_string()), never_install)
+            .unwrap();
+        assert_eq!(
+            outcome,
+            SelfUpdateOutcome::WouldUpdate {
+                current: current.clone(),
+                latest: "999.0.0".to_string()
+            }
+        );
+
+        let outcome = manager
+            .run_update(true, || Some(current.clone()), never_install)
+            .unwrap();
+        assert_eq!(outcome, SelfUpdateOutcome::UpToDate { current });
+
+        // A real run installs the newer version
+        let mut installed = None;
+        manager
+            .run_update(
+                false,
+                || Some("999.0.0".to_string()),
+                |latest| {
+                    installed = Some(latest.to_string());
+                    Ok(true)
+                },
+            )
+            .unwrap();
+        assert_eq!(installed.as_deref(), Some("999.0.0"));
+    }
+
     #[test]
     fn test_version_comparison() {
         let manager = SelfUpdateManager::new();
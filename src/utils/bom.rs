@@ -0,0 +1,142 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! UTF-8 byte order mark (BOM) handling.
+//!
+//! Line-based fixers read files with the BOM stripped, so it doesn't become
+//! part of the first line, and re-attach it on write unless the caller's
+//! [`BomPolicy`] removes it.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The UTF-8 byte order mark
+pub const UTF8_BOM: char = '\u{feff}';
+
+/// What happens to a BOM when linthis rewrites a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BomPolicy {
+    /// Keep the BOM of files that had one (default)
+    #[default]
+    Preserve,
+    /// Drop the BOM from files linthis rewrites
+    Remove,
+}
+
+/// Split a leading BOM off `content`. Returns the rest and whether it had one.
+pub fn strip_bom(content: &str) -> (&str, bool) {
+    match content.strip_prefix(UTF8_BOM) {
+        Some(rest) => (rest, true),
+        None => (content, false),
+    }
+}
+
+/// Read a file with any BOM stripped. Returns the content and whether it had one.
+pub fn read_source(path: &Path) -> io::Result<(String, bool)> {
+    let mut content = fs::read_to_string(path)?;
+    let had_bom = content.starts_with(UTF8_BOM);
+    if had_bom {
+        content.drain(..UTF8_BOM.len_utf8());
+    }
+    Ok((content, had_bom))
+}
+
+/// Write BOM-stripped content, re-attaching the BOM if the file had one and
+/// `policy` preserves it.
+pub fn write_source(
+    path: &Path,
+    content: &str,
+    had_bom: bool,
+    policy: BomPolicy,
+) -> io::Result<()> {
+    if had_bom && policy == BomPolicy::Preserve {
+        fs::write(path, format!("{}{}", UTF8_BOM, content))
+    } else {
+        fs::write(path, content)
+    }
+}
+
+/// Restore the BOM policy on a file an external tool rewrote: put back a BOM
+/// the tool dropped, or drop it under [`BomPolicy::Remove`].
+///
+/// Returns whether the file was rewritten.
+pub fn restore_bom(path: &Path, had_bom: bool, policy: BomPolicy) -> io::Result<bool> {
+    if !had_bom {
+        return Ok(false);
+    }
+    let (content, has_bom) = read_source(path)?;
+    let keep = policy == BomPolicy::Preserve;
+    if has_bom == keep {
+        return Ok(false);
+    }
+    write_source(path, &content, true, policy)?;
+    Ok(true)
+}
+
+/// Whether a file starts with a UTF-8 BOM.
+pub fn has_bom(path: &Path) -> bool {
+    use std::io::Read;
+    let mut buf = [0u8; 3];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut buf))
+        .is_ok_and(|_| buf == [0xEF, 0xBB, 0xBF])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!(strip_bom("\u{feff}// hi\n"), ("// hi\n", true));
+        assert_eq!(strip_bom("// hi\n"), ("// hi\n", false));
+    }
+
+    #[test]
+    fn test_read_and_write_source_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.cpp");
+        fs::write(&path, "\u{feff}int a;\n").unwrap();
+        assert!(has_bom(&path));
+
+        let (content, had_bom) = read_source(&path).unwrap();
+        assert_eq!(content, "int a;\n");
+        assert!(had_bom);
+
+        write_source(&path, "int b;\n", had_bom, BomPolicy::Preserve).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\u{feff}int b;\n");
+
+        // A tool that dropped the BOM gets it back
+        fs::write(&path, "int c;\n").unwrap();
+        assert!(restore_bom(&path, true, BomPolicy::Preserve).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "\u{feff}int c;\n");
+        assert!(!restore_bom(&path, true, BomPolicy::Preserve).unwrap());
+    }
+
+    #[test]
+    fn test_remove_policy_drops_the_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.cpp");
+        fs::write(&path, "\u{feff}int a;\n").unwrap();
+
+        let (_, had_bom) = read_source(&path).unwrap();
+        write_source(&path, "int b;\n", had_bom, BomPolicy::Remove).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "int b;\n");
+
+        // A BOM an external tool kept is dropped as well
+        fs::write(&path, "\u{feff}int c;\n").unwrap();
+        assert!(restore_bom(&path, true, BomPolicy::Remove).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "int c;\n");
+        assert!(!restore_bom(&path, true, BomPolicy::Remove).unwrap());
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Recording and replaying linter runs (`--record` / `--replay`).
+//!
+//! With `--record <dir>`, every linter invocation is saved to
+//! `<dir>/<seq>-<tool>.json` with the file or directory it ran on (relative to
+//! the project root), its command line, exit code, stdout and stderr. With
+//! `--replay <dir>`, linters are not run: each invocation gets the next saved
+//! output of the same tool on the same target, so a user's parsing issue can
+//! be reproduced from their bundle on another machine without their toolchain.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{LintisError, Result};
+
+/// One recorded tool invocation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recording {
+    /// Tool name, as in [`crate::utils::TOOL_DIAGNOSTIC_STREAMS`]
+    pub tool: String,
+    /// File or directory the tool ran on, relative to the project root
+    pub target: String,
+    /// Command line as run, for reading the bundle (not used for matching)
+    pub command: Vec<String>,
+    /// Exit code (None when killed by a signal)
+    pub code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Recording {
+    /// The process output this recording stands for
+    pub fn output(&self) -> Output {
+        Output {
+            status: exit_status(self.code),
+            stdout: self.stdout.clone().into_bytes(),
+            stderr: self.stderr.clone().into_bytes(),
+        }
+    }
+}
+
+/// Program and arguments of a command
+fn command_line(cmd: &Command) -> Vec<String> {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(unix)]
+fn exit_status(code: Option<i32>) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    // Without a code the tool was killed; report SIGKILL
+    ExitStatus::from_raw(code.map_or(9, |c| (c & 0xff) << 8))
+}
+
+#[cfg(windows)]
+fn exit_status(code: Option<i32>) -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(code.unwrap_or(1) as u32)
+}
+
+/// Recordings left to replay, keyed by tool and target
+type ReplayQueue = HashMap<(String, String), VecDeque<Recording>>;
+
+/// Records tool invocations to, or replays them from, a directory
+#[derive(Debug)]
+pub struct ToolCapture {
+    dir: PathBuf,
+    /// Project root that recorded targets are relative to
+    root: PathBuf,
+    /// Recordings to replay; None when recording
+    replay: Option<Mutex<ReplayQueue>>,
+    next: AtomicUsize,
+}
+
+impl ToolCapture {
+    /// Record tool invocations of the project at `root` into `dir`,
+    /// creating it if needed.
+    pub fn record(dir: &Path, root: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| LintisError::Config(format!("cannot create {}: {}", dir.display(), e)))?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            root: root.to_path_buf(),
+            replay: None,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Replay the invocations recorded in `dir` for the project at `root`.
+    pub fn replay(dir: &Path, root: &Path) -> Result<Self> {
+        let read_error = |e: &dyn std::fmt::Display| {
+            LintisError::Config(format!("cannot replay {}: {}", dir.display(), e))
+        };
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| read_error(&e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+
+        let mut recordings = ReplayQueue::new();
+        for file in files {
+            let content = std::fs::read_to_string(&file).map_err(|e| read_error(&e))?;
+            let recording: Recording = serde_json::from_str(&content)
+                .map_err(|e| read_error(&format!("{}: {}", file.display(), e)))?;
+            recordings
+                .entry((recording.tool.clone(), recording.target.clone()))
+                .or_default()
+                .push_back(recording);
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            root: root.to_path_buf(),
+            replay: Some(Mutex::new(recordings)),
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Whether tools are replayed rather than run
+    pub fn is_replay(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Whether the bundle being replayed has any recording of `tool`
+    pub fn has_recording(&self, tool: &str) -> bool {
+        self.replay
+            .as_ref()
+            .is_some_and(|replay| replay.lock().unwrap().keys().any(|(t, _)| t == tool))
+    }
+
+    /// `target` relative to the project root, with `/` separators
+    fn target_key(&self, target: &Path) -> String {
+        crate::utils::relative_path(target, &self.root)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// Run `cmd` as `tool` on `target`, recording its output, or return the
+    /// next recorded output of `tool` on `target` without running it.
+    pub fn output(&self, tool: &str, target: &Path, cmd: &mut Command) -> std::io::Result<Output> {
+        let target = self.target_key(target);
+        if let Some(replay) = &self.replay {
+            return replay
+                .lock()
+                .unwrap()
+                .get_mut(&(tool.to_string(), target.clone()))
+                .and_then(VecDeque::pop_front)
+                .map(|recording| recording.output())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!(
+                            "no recording of {} on {} left in {}",
+                            tool,
+                            target,
+                            self.dir.display()
+                        ),
+                    )
+                });
+        }
+
+        let output = cmd.output()?;
+        let seq = self.next.fetch_add(1, Ordering::Relaxed);
+        let file = self
+            .dir
+            .join(format!("{:05}-{}.json", seq, tool.replace(' ', "-")));
+        let recording = Recording {
+            tool: tool.to_string(),
+            target,
+            command: command_line(cmd),
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        };
+        if let Ok(json) = serde_json::to_string_pretty(&recording) {
+            let _ = std::fs::write(file, json);
+        }
+        Ok(output)
+    }
+}
+
+/// Run a linter on `target`, going through `capture` when there is one.
+pub fn tool_output(
+    capture: Option<&ToolCapture>,
+    tool: &str,
+    target: &Path,
+    cmd: &mut Command,
+) -> std::io::Result<Output> {
+    match capture {
+        Some(capture) => capture.output(tool, target, cmd),
+        None => cmd.output(),
+    }
+}
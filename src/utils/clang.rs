@@ -0,0 +1,616 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Clang tool (clang-format/clang-tidy) binary selection and version detection.
+//!
+//! Projects can pin the LLVM version they format with, either through a
+//! `.clang-format-version` file (searched upwards from the source file) or the
+//! `clang_format_version` key in the `[cpp]`/`[oc]` config sections. A pin is
+//! either a version (`17`, `17.0.6`) or a path to a clang-format binary.
+
+use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Name of the per-project version pin file
+pub const CLANG_VERSION_FILE: &str = ".clang-format-version";
+
+/// Cache key: tool name plus the pin in effect
+type ToolKey = (String, Option<ClangPin>);
+
+/// What the clang tools of a run have probed and reported.
+///
+/// One cache is shared by the C/C++ checkers and formatters of a run, so each
+/// binary is probed and each warning printed once per run.
+#[derive(Debug, Default)]
+pub struct ClangToolCache {
+    /// Resolved binaries per (tool, pin)
+    resolved: Mutex<HashMap<ToolKey, PathBuf>>,
+    /// Version mismatches and style fallbacks that have already been reported
+    warned: Mutex<HashSet<String>>,
+    /// `-style=` arguments per (binary, generated config)
+    style_args: Mutex<HashMap<(PathBuf, PathBuf), String>>,
+}
+
+/// Parsed clang tool version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClangVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl std::fmt::Display for ClangVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Project expectation for which clang tools to use
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClangPin {
+    /// Expected LLVM major version (prefer e.g. `clang-format-17`)
+    Version(u32),
+    /// Explicit path to a clang-format binary (clang-tidy is looked up next to it)
+    Path(PathBuf),
+}
+
+impl ClangPin {
+    /// Parse a pin value: a version like "17" / "17.0.6" / "v17", or a binary path.
+    /// Relative paths are resolved against `base_dir`.
+    pub fn parse(value: &str, base_dir: &Path) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+
+        let version_str = value.strip_prefix('v').unwrap_or(value);
+        if version_str
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        {
+            let major = version_str.split('.').next()?.parse().ok()?;
+            return Some(ClangPin::Version(major));
+        }
+
+        let path = PathBuf::from(value);
+        if path.is_absolute() {
+            Some(ClangPin::Path(path))
+        } else {
+            Some(ClangPin::Path(base_dir.join(path)))
+        }
+    }
+}
+
+/// Parse the version from `clang-format --version` / `clang-tidy --version` output.
+///
+/// Handles outputs such as:
+/// - `clang-format version 17.0.6 (https://github.com/llvm/llvm-project ...)`
+/// - `Ubuntu clang-format version 14.0.0-1ubuntu1`
+/// - `LLVM (http://llvm.org/):\n  LLVM version 15.0.7` (clang-tidy)
+pub fn parse_clang_version(output: &str) -> Option<ClangVersion> {
+    for line in output.lines() {
+        let Some(idx) = line.find("version ") else {
+            continue;
+        };
+        let token = line[idx + "version ".len()..].split_whitespace().next()?;
+
+        let mut numbers = token.split('.').map(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse::<u32>().ok()
+        });
+
+        let major = match numbers.next() {
+            Some(Some(m)) => m,
+            _ => continue,
+        };
+        let minor = numbers.next().flatten().unwrap_or(0);
+        let patch = numbers.next().flatten().unwrap_or(0);
+
+        return Some(ClangVersion {
+            major,
+            minor,
+            patch,
+        });
+    }
+    None
+}
+
+/// Select which binary to run for a clang tool given the project pin.
+///
+/// `is_available` reports whether a candidate binary can be executed.
+pub fn select_clang_binary<F>(tool: &str, pin: Option<&ClangPin>, is_available: F) -> PathBuf
+where
+    F: Fn(&Path) -> bool,
+{
+    match pin {
+        Some(ClangPin::Path(path)) => {
+            if tool == "clang-format" {
+                return path.clone();
+            }
+            // Look for the sibling tool from the same LLVM installation
+            if let Some(sibling) = path.parent().map(|dir| dir.join(tool)) {
+                if is_available(&sibling) {
+                    return sibling;
+                }
+            }
+            PathBuf::from(tool)
+        }
+        Some(ClangPin::Version(major)) => {
+            let versioned = PathBuf::from(format!("{}-{}", tool, major));
+            if is_available(&versioned) {
+                versioned
+            } else {
+                PathBuf::from(tool)
+            }
+        }
+        None => PathBuf::from(tool),
+    }
+}
+
+/// Find the `.clang-format-version` pin by walking up from the given path.
+pub fn find_version_file_pin(start_path: &Path) -> Option<ClangPin> {
+    let mut current = if start_path.is_file() {
+        start_path.parent()?.to_path_buf()
+    } else {
+        start_path.to_path_buf()
+    };
+
+    loop {
+        let pin_file = current.join(CLANG_VERSION_FILE);
+        if pin_file.is_file() {
+            let content = std::fs::read_to_string(&pin_file).ok()?;
+            return ClangPin::parse(&content, &current);
+        }
+        if !current.pop() {
+            break;
+        }
+    }
+    None
+}
+
+/// Check if a binary can be executed.
+fn binary_runs(binary: &Path) -> bool {
+    Command::new(binary)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Get the version of a clang tool binary.
+pub fn detect_clang_version(binary: &Path) -> Option<ClangVersion> {
+    let output = Command::new(binary).arg("--version").output().ok()?;
+    parse_clang_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// clang-format major version that first accepts `-style=file:<path>`
+pub const STYLE_FILE_PATH_MIN_VERSION: u32 = 14;
+
+/// Style keys used by linthis configs that older clang-format releases
+/// reject, with the major version that introduced them
+pub const STYLE_KEY_MIN_VERSIONS: &[(&str, u32)] = &[
+    ("IndentPPDirectives", 6),
+    ("AlignConsecutiveMacros", 9),
+    ("SpaceAfterLogicalNot", 9),
+    ("IndentGotoLabels", 10),
+    ("AllowShortEnumsOnASingleLine", 11),
+    ("IndentCaseBlocks", 11),
+    ("IndentExternBlock", 11),
+    ("InsertTrailingCommas", 11),
+    ("ObjCBreakBeforeNestedBlockParam", 11),
+    ("WhitespaceSensitiveMacros", 11),
+    ("BitFieldColonSpacing", 12),
+    ("EmptyLineBeforeAccessModifier", 12),
+    ("SpaceAroundPointerQualifiers", 12),
+    ("EmptyLineAfterAccessModifier", 13),
+    ("IndentAccessModifiers", 13),
+    ("ReferenceAlignment", 13),
+    ("ShortNamespaceLines", 13),
+    ("SpacesInLineCommentPrefix", 13),
+    ("PackConstructorInitializers", 14),
+    ("QualifierAlignment", 14),
+    ("SeparateDefinitionBlocks", 14),
+    ("InsertBraces", 15),
+    ("RequiresClausePosition", 15),
+    ("BreakAfterAttributes", 16),
+    ("InsertNewlineAtEOF", 16),
+    ("LineEnding", 16),
+    ("RemoveSemicolon", 16),
+    ("BracedInitializerIndentWidth", 17),
+    ("RemoveParentheses", 17),
+    ("KeepEmptyLines", 19),
+];
+
+/// How a linthis-generated `.clang-format` can be used with the installed
+/// clang-format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleSupport {
+    /// Every key is understood
+    Full,
+    /// These keys are too new and are dropped from the config
+    Subset(Vec<String>),
+    /// The config file cannot be passed at all; only its `BasedOnStyle` is used
+    BaseStyleOnly(String),
+}
+
+/// Top-level `key: value` entries of a `.clang-format` document set
+fn style_entries(config: &str) -> impl Iterator<Item = (&str, &str)> {
+    config.lines().filter_map(style_entry)
+}
+
+/// The `key: value` of a top-level line; comments, document markers and
+/// nested lines have none
+fn style_entry(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with([' ', '\t', '#', '-']) {
+        return None;
+    }
+    line.split_once(':')
+        .map(|(key, value)| (key.trim(), value.trim()))
+}
+
+/// Decide how clang-format `version` can use the generated `config`.
+pub fn style_support(config: &str, version: ClangVersion) -> StyleSupport {
+    if version.major < STYLE_FILE_PATH_MIN_VERSION {
+        let base = style_entries(config)
+            .find(|(key, _)| *key == "BasedOnStyle")
+            .map(|(_, value)| value.to_string())
+            .unwrap_or_else(|| "Google".to_string());
+        return StyleSupport::BaseStyleOnly(base);
+    }
+
+    let mut unsupported: Vec<String> = Vec::new();
+    for (key, _) in style_entries(config) {
+        let too_new = STYLE_KEY_MIN_VERSIONS
+            .iter()
+            .any(|(k, min)| *k == key && version.major < *min);
+        if too_new && !unsupported.iter().any(|k| k == key) {
+            unsupported.push(key.to_string());
+        }
+    }
+    if unsupported.is_empty() {
+        StyleSupport::Full
+    } else {
+        StyleSupport::Subset(unsupported)
+    }
+}
+
+/// `config` without the given top-level keys (and their nested values)
+pub fn drop_style_keys(config: &str, keys: &[String]) -> String {
+    let mut kept = Vec::new();
+    let mut skipping = false;
+    for line in config.lines() {
+        if line.starts_with([' ', '\t']) && skipping {
+            continue;
+        }
+        skipping = style_entry(line).is_some_and(|(key, _)| keys.iter().any(|k| k == key));
+        if !skipping {
+            kept.push(line);
+        }
+    }
+    let mut result = kept.join("\n");
+    result.push('\n');
+    result
+}
+
+/// Warn that a generated clang-format config was reduced for `version`.
+pub fn warn_style_fallback(config: &Path, version: ClangVersion, support: &StyleSupport) {
+    match support {
+        StyleSupport::Full => return,
+        StyleSupport::Subset(keys) => eprintln!(
+            "{}: clang-format {} does not support {} in {}; ignoring them",
+            "Warning".yellow(),
+            version,
+            keys.join(", "),
+            config.display()
+        ),
+        StyleSupport::BaseStyleOnly(base) => eprintln!(
+            "{}: clang-format {} cannot load {}; formatting with {} style",
+            "Warning".yellow(),
+            version,
+            config.display(),
+            base
+        ),
+    }
+    eprintln!("  Install a newer clang-format to use the full config");
+    eprintln!();
+}
+
+/// Write a reduced config to `<temp>/linthis/clang-format/<version>/<hash>/`
+fn write_style_subset(config: &Path, version: ClangVersion, content: &str) -> Option<PathBuf> {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    config.hash(&mut hasher);
+    let dir = std::env::temp_dir()
+        .join("linthis")
+        .join("clang-format")
+        .join(version.major.to_string())
+        .join(format!("{:016x}", hasher.finish()));
+    std::fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(".clang-format");
+    std::fs::write(&path, content).ok()?;
+    Some(path)
+}
+
+impl ClangToolCache {
+    /// Resolve the binary to run for a clang tool when processing `start_path`.
+    ///
+    /// The config `pin` takes precedence over `.clang-format-version`. When the
+    /// project expects a version and the selected binary reports a different
+    /// major version, a warning is printed once per tool.
+    pub fn resolve(&self, tool: &str, pin: Option<&ClangPin>, start_path: &Path) -> PathBuf {
+        let pin = pin.cloned().or_else(|| find_version_file_pin(start_path));
+        let key = (tool.to_string(), pin.clone());
+
+        let mut resolved = self.resolved.lock().unwrap();
+        if let Some(binary) = resolved.get(&key) {
+            return binary.clone();
+        }
+
+        let binary = select_clang_binary(tool, pin.as_ref(), binary_runs);
+
+        if let Some(ClangPin::Version(expected)) = pin {
+            if let Some(found) = detect_clang_version(&binary) {
+                if found.major != expected && self.first_warning(tool.to_string()) {
+                    warn_version_mismatch(tool, found, expected);
+                }
+            }
+        }
+
+        resolved.insert(key, binary.clone());
+        binary
+    }
+
+    /// The `-style=` argument for running `binary` with a linthis-generated
+    /// `config`, computed once per binary and config. Keys the installed
+    /// version does not know are dropped into a copy under the temp
+    /// directory, and a warning is printed once per config.
+    pub fn generated_style_arg(&self, binary: &Path, config: &Path) -> String {
+        self.style_args
+            .lock()
+            .unwrap()
+            .entry((binary.to_path_buf(), config.to_path_buf()))
+            .or_insert_with(|| self.compute_style_arg(binary, config))
+            .clone()
+    }
+
+    fn compute_style_arg(&self, binary: &Path, config: &Path) -> String {
+        let full = format!("-style=file:{}", config.display());
+        match (
+            detect_clang_version(binary),
+            std::fs::read_to_string(config),
+        ) {
+            (Some(version), Ok(content)) => {
+                let support = style_support(&content, version);
+                if self.first_warning(format!("style:{}", config.display())) {
+                    warn_style_fallback(config, version, &support);
+                }
+                match support {
+                    StyleSupport::Full => full,
+                    StyleSupport::BaseStyleOnly(base) => format!("-style={}", base),
+                    StyleSupport::Subset(keys) => {
+                        write_style_subset(config, version, &drop_style_keys(&content, &keys))
+                            .map(|path| format!("-style=file:{}", path.display()))
+                            .unwrap_or(full)
+                    }
+                }
+            }
+            _ => full,
+        }
+    }
+
+    /// Whether the warning identified by `key` has not been printed yet
+    fn first_warning(&self, key: String) -> bool {
+        self.warned.lock().unwrap().insert(key)
+    }
+}
+
+/// Warn that the available version differs from the pinned one.
+fn warn_version_mismatch(tool: &str, found: ClangVersion, expected: u32) {
+    eprintln!(
+        "{}: {} {} found, but project expects version {}",
+        "Warning".yellow(),
+        tool,
+        found,
+        expected
+    );
+    eprintln!(
+        "  Install {}-{} or update {} to avoid formatting churn",
+        tool, expected, CLANG_VERSION_FILE
+    );
+    eprintln!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ==================== parse_clang_version tests ====================
+
+    #[test]
+    fn test_parse_clang_version_upstream() {
+        let output = "clang-format version 17.0.6 (https://github.com/llvm/llvm-project 6009708b)";
+        let version = parse_clang_version(output).unwrap();
+        assert_eq!(
+            version,
+            ClangVersion {
+                major: 17,
+                minor: 0,
+                patch: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_clang_version_distro_suffix() {
+        let version = parse_clang_version("Ubuntu clang-format version 14.0.0-1ubuntu1.1").unwrap();
+        assert_eq!(version.major, 14);
+        assert_eq!(version.minor, 0);
+        assert_eq!(version.patch, 0);
+    }
+
+    #[test]
+    fn test_parse_clang_version_clang_tidy_multiline() {
+        let output = "LLVM (http://llvm.org/):\n  LLVM version 15.0.7\n  Optimized build.";
+        assert_eq!(parse_clang_version(output).unwrap().major, 15);
+    }
+
+    #[test]
+    fn test_parse_clang_version_invalid() {
+        assert!(parse_clang_version("command not found").is_none());
+        assert!(parse_clang_version("").is_none());
+    }
+
+    // ==================== StyleSupport tests ====================
+
+    #[test]
+    fn test_style_support_by_version() {
+        let config = "\
+BasedOnStyle: LLVM
+IndentWidth: 4
+AlignConsecutiveMacros: true
+SpaceAroundPointerQualifiers: Default
+InsertBraces: true
+BraceWrapping:
+  AfterClass: false
+---
+Language: ObjC
+ObjCBreakBeforeNestedBlockParam: false
+";
+        let support = |output: &str| style_support(config, parse_clang_version(output).unwrap());
+
+        assert_eq!(support("clang-format version 18.1.3"), StyleSupport::Full);
+        assert_eq!(
+            support("Ubuntu clang-format version 14.0.0-1ubuntu1.1"),
+            StyleSupport::Subset(vec!["InsertBraces".to_string()])
+        );
+        assert_eq!(
+            support("clang-format version 10.0.0"),
+            StyleSupport::BaseStyleOnly("LLVM".to_string())
+        );
+
+        let reduced = drop_style_keys(
+            config,
+            &["InsertBraces".to_string(), "BraceWrapping".to_string()],
+        );
+        assert!(!reduced.contains("InsertBraces") && !reduced.contains("AfterClass"));
+        assert!(reduced.contains("---\nLanguage: ObjC\n"));
+        assert_eq!(
+            style_support(
+                &reduced,
+                parse_clang_version("clang-format version 14.0.6").unwrap()
+            ),
+            StyleSupport::Full
+        );
+    }
+
+    // ==================== ClangPin tests ====================
+
+    #[test]
+    fn test_pin_parse_version() {
+        let base = Path::new("/project");
+        assert_eq!(ClangPin::parse("17\n", base), Some(ClangPin::Version(17)));
+        assert_eq!(ClangPin::parse("17.0.6", base), Some(ClangPin::Version(17)));
+        assert_eq!(ClangPin::parse("v16", base), Some(ClangPin::Version(16)));
+        assert_eq!(ClangPin::parse("  ", base), None);
+    }
+
+    #[test]
+    fn test_pin_parse_path() {
+        let base = Path::new("/project");
+        assert_eq!(
+            ClangPin::parse("/opt/llvm/bin/clang-format", base),
+            Some(ClangPin::Path(PathBuf::from("/opt/llvm/bin/clang-format")))
+        );
+        assert_eq!(
+            ClangPin::parse("tools/clang-format", base),
+            Some(ClangPin::Path(PathBuf::from("/project/tools/clang-format")))
+        );
+    }
+
+    #[test]
+    fn test_find_version_file_pin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        std::fs::write(dir.path().join(CLANG_VERSION_FILE), "18\n").unwrap();
+        let file = dir.path().join("src/nested/a.cpp");
+        std::fs::write(&file, "int x;\n").unwrap();
+
+        assert_eq!(find_version_file_pin(&file), Some(ClangPin::Version(18)));
+    }
+
+    // ==================== select_clang_binary tests ====================
+
+    #[test]
+    fn test_select_binary_no_pin() {
+        let binary = select_clang_binary("clang-format", None, |_| true);
+        assert_eq!(binary, PathBuf::from("clang-format"));
+    }
+
+    #[test]
+    fn test_select_binary_prefers_versioned() {
+        let pin = ClangPin::Version(17);
+        let binary = select_clang_binary("clang-format", Some(&pin), |p| {
+            p == Path::new("clang-format-17")
+        });
+        assert_eq!(binary, PathBuf::from("clang-format-17"));
+    }
+
+    #[test]
+    fn test_select_binary_falls_back_when_versioned_missing() {
+        let pin = ClangPin::Version(17);
+        let binary = select_clang_binary("clang-tidy", Some(&pin), |_| false);
+        assert_eq!(binary, PathBuf::from("clang-tidy"));
+    }
+
+    #[test]
+    fn test_select_binary_path_pin() {
+        let pin = ClangPin::Path(PathBuf::from("/opt/llvm-17/bin/clang-format"));
+
+        let format = select_clang_binary("clang-format", Some(&pin), |_| false);
+        assert_eq!(format, PathBuf::from("/opt/llvm-17/bin/clang-format"));
+
+        let tidy = select_clang_binary("clang-tidy", Some(&pin), |p| {
+            p == Path::new("/opt/llvm-17/bin/clang-tidy")
+        });
+        assert_eq!(tidy, PathBuf::from("/opt/llvm-17/bin/clang-tidy"));
+
+        let tidy_missing = select_clang_binary("clang-tidy", Some(&pin), |_| false);
+        assert_eq!(tidy_missing, PathBuf::from("clang-tidy"));
+    }
+
+    // ==================== ClangToolCache tests ====================
+
+    #[test]
+    fn test_resolve_config_pin_beats_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(CLANG_VERSION_FILE),
+            "/opt/llvm-16/bin/clang-format\n",
+        )
+        .unwrap();
+        let file = dir.path().join("a.cpp");
+        std::fs::write(&file, "int x;\n").unwrap();
+        let config_pin = ClangPin::Path(PathBuf::from("/opt/llvm-17/bin/clang-format"));
+
+        let cache = ClangToolCache::default();
+        assert_eq!(
+            cache.resolve("clang-format", Some(&config_pin), &file),
+            PathBuf::from("/opt/llvm-17/bin/clang-format")
+        );
+        assert_eq!(
+            cache.resolve("clang-format", None, &file),
+            PathBuf::from("/opt/llvm-16/bin/clang-format")
+        );
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Unified diffs of formatting changes.
+
+use similar::TextDiff;
+use std::path::Path;
+
+/// Context lines around each change, unless `--diff-context` says otherwise
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Unified diff (git style, `a/` and `b/` headers) between a file's
+/// content before and after formatting, with `context` lines around each
+/// change.
+pub fn unified_diff(path: &Path, before: &str, after: &str, context: usize) -> String {
+    let name = path.display().to_string();
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(context)
+        .header(&format!("a/{}", name), &format!("b/{}", name))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff(
+            Path::new("src/a.py"),
+            "x=1\ny = 2\n",
+            "x = 1\ny = 2\n",
+            DEFAULT_CONTEXT_LINES,
+        );
+        assert_eq!(
+            diff,
+            "--- a/src/a.py\n+++ b/src/a.py\n@@ -1,2 +1,2 @@\n-x=1\n+x = 1\n y = 2\n"
+        );
+        assert!(unified_diff(Path::new("a.py"), "x\n", "x\n", DEFAULT_CONTEXT_LINES).is_empty());
+    }
+
+    #[test]
+    fn test_unified_diff_context_lines() {
+        let before: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let after = before.replace("line 10\n", "line ten\n");
+        let context_of = |n| {
+            unified_diff(Path::new("a.txt"), &before, &after, n)
+                .lines()
+                .filter(|l| l.starts_with(' '))
+                .count()
+        };
+        assert_eq!(context_of(0), 0);
+        assert_eq!(context_of(1), 2);
+        assert_eq!(context_of(DEFAULT_CONTEXT_LINES), 6);
+        assert_eq!(context_of(5), 10);
+        // Context stops at the start and end of the file
+        assert_eq!(context_of(50), 19);
+    }
+}
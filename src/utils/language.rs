@@ -81,7 +81,7 @@ pub fn detect_languages(root: &Path) -> Vec<Language> {
 
     // Sort by count (descending) and return languages
     let mut langs: Vec<_> = counts.into_iter().collect();
-    langs.sort_by(|a, b| b.1.cmp(&a.1));
+    langs.sort_by_key(|b| std::cmp::Reverse(b.1));
     langs.into_iter().map(|(lang, _)| lang).collect()
 }
 
@@ -10,8 +10,13 @@
 
 //! Utility modules for linthis.
 
+pub mod bom;
+pub mod capture;
+pub mod clang;
+pub mod diff;
 pub mod language;
 pub mod output;
+pub mod suppress;
 pub mod types;
 pub mod unicode;
 pub mod walker;
@@ -32,6 +37,10 @@ pub const DEFAULT_EXCLUDES: &[&str] = &[
     "venv/**",
     ".venv/**",
     "__pycache__/**",
+    // Cargo home (registry and git checkouts) and the Go module cache
+    ".cargo/**",
+    "**/.cargo/**",
+    "**/pkg/mod/**",
     // Third-party libraries
     "third_party/**",
     "thirdparty/**",
@@ -43,8 +52,9 @@ pub const DEFAULT_EXCLUDES: &[&str] = &[
     "external/**",
     "externals/**",
     "deps/**",
-    // Build outputs
+    // Build outputs (nested `target` for workspace members)
     "target/**",
+    "**/target/**",
     "build/**",
     "dist/**",
     "out/**",
@@ -59,6 +69,9 @@ pub const DEFAULT_EXCLUDES: &[&str] = &[
     "*.generated.*",
     "*.min.js",
     "*.min.css",
+    // Go test fixtures, never compiled
+    "testdata/**",
+    "**/testdata/**",
     // Package managers (iOS)
     "Pods/**",
     "**/Pods/**",
@@ -66,9 +79,46 @@ pub const DEFAULT_EXCLUDES: &[&str] = &[
     "**/Carthage/**",
 ];
 
+/// Default exclusion patterns minus the entries listed in `remove`.
+///
+/// Entries must match a default exactly, e.g. removing `vendor/**` lints
+/// vendored code while the other defaults still apply.
+pub fn default_excludes_without(remove: &[String]) -> Vec<String> {
+    DEFAULT_EXCLUDES
+        .iter()
+        .filter(|pattern| !remove.iter().any(|r| r == *pattern))
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// Exclusion patterns for test files of `languages` (all when empty),
+/// used by `--no-tests` and `skip_tests`.
+pub fn test_excludes(languages: &[crate::Language]) -> Vec<String> {
+    let languages = if languages.is_empty() {
+        &crate::Language::ALL[..]
+    } else {
+        languages
+    };
+    languages
+        .iter()
+        .flat_map(|lang| lang.test_file_patterns())
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
 /// Get list of staged files from git.
+///
+/// Deleted files are dropped and renamed files are reported under their new name.
+/// Fails with [`crate::LintisError::GitNotFound`] when git is missing and
+/// [`crate::LintisError::NotAGitRepo`] outside a repository.
 pub fn get_staged_files() -> crate::Result<Vec<std::path::PathBuf>> {
-    let output = Command::new("git")
+    get_staged_files_with(Path::new("git"))
+}
+
+/// Same as [`get_staged_files`], using the given git executable.
+pub fn get_staged_files_with(git: &Path) -> crate::Result<Vec<std::path::PathBuf>> {
+    require_git_with(git, "--staged")?;
+    let output = Command::new(git)
         .args(["diff", "--cached", "--name-only", "--diff-filter=d"])
         .output()
         .map_err(crate::LintisError::Io)?;
@@ -130,6 +180,47 @@ pub fn get_project_root() -> std::path::PathBuf {
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
 }
 
+/// Express `path` relative to `base`, with `..` where it lies outside `base`.
+///
+/// Relative inputs are resolved against the working directory; symlinks are
+/// resolved for paths that exist.
+pub fn relative_path(path: &Path, base: &Path) -> std::path::PathBuf {
+    use std::path::{Component, PathBuf};
+
+    let absolute = |p: &Path| -> PathBuf {
+        if let Ok(canonical) = p.canonicalize() {
+            return canonical;
+        }
+        let joined = std::env::current_dir().unwrap_or_default().join(p);
+        let mut normalized = PathBuf::new();
+        for component in joined.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                other => normalized.push(other),
+            }
+        }
+        normalized
+    };
+    let (path, base) = (absolute(path), absolute(base));
+
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    for component in path.components().skip(common) {
+        relative.push(component);
+    }
+    relative
+}
+
 /// Check if we're in a git repository.
 pub fn is_git_repo() -> bool {
     Command::new("git")
@@ -139,6 +230,419 @@ pub fn is_git_repo() -> bool {
         .unwrap_or(false)
 }
 
+/// Fail with a clear error when the git-dependent `mode` can't work: `git`
+/// is not installed, or the working directory is not inside a repository.
+fn require_git_with(git: &Path, mode: &str) -> crate::Result<()> {
+    let output = match Command::new(git).args(["rev-parse", "--git-dir"]).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(crate::LintisError::GitNotFound(mode.to_string()))
+        }
+        Err(e) => return Err(crate::LintisError::Io(e)),
+    };
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(crate::LintisError::NotAGitRepo(mode.to_string()))
+    }
+}
+
+/// Check if a tracked file has unstaged changes (modified in the worktree).
+///
+/// Untracked files count as clean: nothing of them is staged that a
+/// formatter could clobber.
+///
+/// Returns false when git is unavailable or the file is not inside a repository.
+pub fn has_unstaged_changes(path: &Path) -> bool {
+    has_unstaged_changes_with(Path::new("git"), path)
+}
+
+/// Same as [`has_unstaged_changes`], using the given git executable.
+pub fn has_unstaged_changes_with(git: &Path, path: &Path) -> bool {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let Some(file_name) = path.file_name() else {
+        return false;
+    };
+
+    Command::new(git)
+        .current_dir(dir)
+        .args(["status", "--porcelain", "--"])
+        .arg(file_name)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| porcelain_has_unstaged(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or(false)
+}
+
+/// Read the staged (index) content of a file.
+///
+/// Returns None when git is unavailable or the file is not in the index.
+pub fn staged_content(path: &Path) -> Option<Vec<u8>> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path.file_name()?.to_str()?;
+
+    Command::new("git")
+        .current_dir(dir)
+        .arg("show")
+        .arg(format!(":./{}", file_name))
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| o.stdout)
+}
+
+/// Copy of a file's staged content, for linting what will be committed.
+///
+/// The copy is written to a private temp dir at the file's path relative to
+/// its worktree root. Every other entry of the file's ancestor directories is
+/// linked into the mirror, so linters still find the project's configuration,
+/// sibling sources and module layout, and the user's tree is never written to.
+/// The temp dir is removed on drop.
+pub struct StagedCopy {
+    /// Temp dir holding the mirror, removed on drop
+    _dir: tempfile::TempDir,
+    /// Canonical path of the temp dir, standing for `root`
+    mirror: std::path::PathBuf,
+    /// Real directory mirrored at the top of the temp dir
+    root: std::path::PathBuf,
+    /// Path of the copy
+    path: std::path::PathBuf,
+}
+
+impl StagedCopy {
+    /// Write the staged content of `path` to a mirrored copy.
+    ///
+    /// Returns None when the file has no staged content or the copy can't be written.
+    pub fn create(path: &Path) -> Option<Self> {
+        let content = staged_content(path)?;
+        Self::with_content(path, &content)
+    }
+
+    /// Write `content` to a mirrored copy of `path` (which need not exist).
+    ///
+    /// Without an existing parent directory, nothing is mirrored. Returns None
+    /// when the copy can't be written.
+    pub fn with_content(path: &Path, content: impl AsRef<[u8]>) -> Option<Self> {
+        let file_name = path.file_name()?;
+        let parent = match path.parent() {
+            Some(p) if p.as_os_str().is_empty() => Path::new("."),
+            Some(p) => p,
+            None => Path::new("."),
+        };
+        let dir = tempfile::Builder::new()
+            .prefix("linthis-staged-")
+            .tempdir()
+            .ok()?;
+        // Canonical, so paths reported by tools can be mapped back
+        let mirror = dir.path().canonicalize().ok()?;
+
+        let (root, rel) = match parent.canonicalize() {
+            Ok(parent) => {
+                let root = parent
+                    .ancestors()
+                    .find(|dir| dir.join(".git").exists())
+                    .unwrap_or(&parent)
+                    .to_path_buf();
+                let rel = parent.strip_prefix(&root).ok()?.join(file_name);
+                mirror_ancestors(&root, &rel, &mirror).ok()?;
+                (root, rel)
+            }
+            Err(_) => (parent.to_path_buf(), std::path::PathBuf::from(file_name)),
+        };
+
+        let copy = mirror.join(&rel);
+        fs::write(&copy, content).ok()?;
+        Some(Self {
+            _dir: dir,
+            mirror,
+            root,
+            path: copy,
+        })
+    }
+
+    /// Path of the staged copy
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Point an issue path reported inside the mirror back at the real tree:
+    /// the copy itself becomes `original`, other files their real path.
+    pub fn restore_path(&self, issue_path: &mut std::path::PathBuf, original: &Path) {
+        if *issue_path == self.path {
+            *issue_path = original.to_path_buf();
+            return;
+        }
+        if let Ok(rest) = issue_path.strip_prefix(&self.mirror) {
+            *issue_path = self.root.join(rest);
+        }
+    }
+}
+
+/// Recreate the directories of `rel` under `mirror`, linking in every other
+/// entry of the corresponding directories under `root`.
+fn mirror_ancestors(root: &Path, rel: &Path, mirror: &Path) -> std::io::Result<()> {
+    let mut real = root.to_path_buf();
+    let mut shadow = mirror.to_path_buf();
+    for component in rel.components() {
+        let name = component.as_os_str();
+        for entry in fs::read_dir(&real)? {
+            let entry = entry?;
+            if entry.file_name() != name {
+                link_entry(&entry.path(), &shadow.join(entry.file_name()));
+            }
+        }
+        real.push(name);
+        shadow.push(name);
+        if real.is_dir() {
+            fs::create_dir(&shadow)?;
+        }
+    }
+    Ok(())
+}
+
+/// Make `real` visible at `shadow`; best effort, since a missing sibling only
+/// costs the linter some context.
+#[cfg(unix)]
+fn link_entry(real: &Path, shadow: &Path) {
+    let _ = std::os::unix::fs::symlink(real, shadow);
+}
+
+/// Make `real` visible at `shadow` (files are copied, directories skipped,
+/// as symlinks need extra privileges on Windows).
+#[cfg(not(unix))]
+fn link_entry(real: &Path, shadow: &Path) {
+    if real.is_file() {
+        let _ = fs::copy(real, shadow);
+    }
+}
+
+/// Check `git status --porcelain` output for worktree (unstaged) changes.
+///
+/// The second status column is the worktree state; untracked (`?`) and
+/// ignored (`!`) files have none.
+fn porcelain_has_unstaged(output: &str) -> bool {
+    output
+        .lines()
+        .any(|line| matches!(line.chars().nth(1), Some(y) if !matches!(y, ' ' | '?' | '!')))
+}
+
+/// Stderr fragments that indicate a tool crashed rather than reported findings
+const CRASH_MARKERS: &[&str] = &[
+    "Stack dump:",
+    "PLEASE submit a bug report",
+    "Segmentation fault",
+    "panicked at",
+    "Traceback (most recent call last)",
+];
+
+/// Stderr fragments of a tool that failed to allocate memory
+const OUT_OF_MEMORY_MARKERS: &[&str] = &[
+    "bad_alloc",
+    "out of memory",
+    "Out of memory",
+    "Cannot allocate memory",
+    "MemoryError",
+    "memory allocation of",
+    "memory exhausted",
+];
+
+/// Cap the address space of the process `cmd` spawns at `limit_mb`, if any
+/// (`tool_memory_limit_mb`). See [`limit_memory`].
+pub fn apply_memory_limit(cmd: &mut Command, limit_mb: Option<u64>) -> &mut Command {
+    match limit_mb {
+        Some(limit_mb) => limit_memory(cmd, limit_mb),
+        None => cmd,
+    }
+}
+
+/// Cap the address space of the process `cmd` spawns at `limit_mb`, so
+/// allocations beyond it fail and the tool stops.
+///
+/// Only on Unix (`setrlimit(RLIMIT_AS)`); elsewhere the command is unchanged.
+pub fn limit_memory(cmd: &mut Command, limit_mb: u64) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let bytes = limit_mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+        // SAFETY: setrlimit is async-signal-safe and touches no parent state
+        unsafe {
+            cmd.pre_exec(move || {
+                let limit = libc::rlimit {
+                    rlim_cur: bytes,
+                    rlim_max: bytes,
+                };
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    cmd
+}
+
+/// Output stream an external tool writes its diagnostics to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticStream {
+    Stdout,
+    Stderr,
+    /// Both streams, stdout first
+    Both,
+}
+
+/// Where each built-in tool writes its diagnostics. Reading the wrong one
+/// silently reports a file as clean; tools not listed use stdout.
+pub const TOOL_DIAGNOSTIC_STREAMS: &[(&str, DiagnosticStream)] = &[
+    ("bandit", DiagnosticStream::Stdout),
+    ("checkstyle", DiagnosticStream::Both),
+    ("clang-tidy", DiagnosticStream::Stdout),
+    ("clippy", DiagnosticStream::Stdout),
+    ("cpplint", DiagnosticStream::Stderr),
+    ("eslint", DiagnosticStream::Stdout),
+    ("go vet", DiagnosticStream::Stderr),
+    ("golangci-lint", DiagnosticStream::Stdout),
+    ("ruff", DiagnosticStream::Stdout),
+];
+
+impl DiagnosticStream {
+    /// Stream carrying a tool's diagnostics (see [`TOOL_DIAGNOSTIC_STREAMS`])
+    pub fn for_tool(tool: &str) -> Self {
+        TOOL_DIAGNOSTIC_STREAMS
+            .iter()
+            .find(|(name, _)| *name == tool)
+            .map_or(DiagnosticStream::Stdout, |(_, stream)| *stream)
+    }
+
+    /// Text of this stream in a finished process's output
+    pub fn read(self, output: &std::process::Output) -> String {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match self {
+            DiagnosticStream::Stdout => stdout.into_owned(),
+            DiagnosticStream::Stderr => stderr.into_owned(),
+            DiagnosticStream::Both if stdout.is_empty() || stdout.ends_with('\n') => {
+                format!("{}{}", stdout, stderr)
+            }
+            DiagnosticStream::Both => format!("{}\n{}", stdout, stderr),
+        }
+    }
+}
+
+/// The diagnostics `tool` wrote, read from the stream it uses.
+pub fn tool_diagnostics(tool: &str, output: &std::process::Output) -> String {
+    DiagnosticStream::for_tool(tool).read(output)
+}
+
+/// Interpret the exit status of an external tool.
+///
+/// `finding_codes` are the nonzero exit codes the tool uses to signal that it
+/// ran and found issues. Termination by a signal, exit codes of 128 and above,
+/// crash markers on stderr, or any other unexpected exit code are reported as
+/// [`crate::checkers::CheckerError::ToolCrashed`] so a broken tool is never
+/// mistaken for a clean file.
+pub fn check_tool_status(
+    tool: &str,
+    output: &std::process::Output,
+    finding_codes: &[i32],
+) -> crate::checkers::CheckResult<()> {
+    check_tool_status_with_limit(tool, output, finding_codes, None)
+}
+
+/// [`check_tool_status`] for a tool run under `memory_limit_mb`. A failed
+/// tool that reports an allocation failure on stderr is reported as
+/// [`crate::checkers::CheckerError::MemoryLimit`]; a signal alone (e.g. a
+/// SIGSEGV) is still a crash.
+pub fn check_tool_status_with_limit(
+    tool: &str,
+    output: &std::process::Output,
+    finding_codes: &[i32],
+    memory_limit_mb: Option<u64>,
+) -> crate::checkers::CheckResult<()> {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if let Some(limit_mb) = memory_limit_mb {
+        let out_of_memory =
+            !output.status.success() && OUT_OF_MEMORY_MARKERS.iter().any(|m| stderr.contains(m));
+        if out_of_memory {
+            return Err(crate::checkers::CheckerError::MemoryLimit {
+                tool: tool.to_string(),
+                limit_mb,
+            });
+        }
+    }
+    let detail = stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| format!(": {}", line))
+        .unwrap_or_default();
+
+    let reason = match output.status.code() {
+        None => Some(format!("{} was terminated by a signal", tool)),
+        Some(code) if code >= 128 => Some(format!("{} crashed (exit code {})", tool, code)),
+        Some(code) if CRASH_MARKERS.iter().any(|m| stderr.contains(m)) => {
+            Some(format!("{} crashed (exit code {})", tool, code))
+        }
+        Some(0) => None,
+        Some(code) if finding_codes.contains(&code) => None,
+        Some(code) => Some(format!("{} failed (exit code {})", tool, code)),
+    };
+
+    match reason {
+        Some(reason) => Err(crate::checkers::CheckerError::ToolCrashed {
+            code: output.status.code(),
+            message: reason + &detail,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Run `binary args` and extract the tool version from its output.
+///
+/// Uses the first non-empty line of stdout (or stderr, for tools that print
+/// their version there) and returns the first dotted version number in it,
+/// falling back to the whole line. Returns `None` if the tool can't be run.
+pub fn tool_version(binary: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(binary).args(args).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stdout
+        .lines()
+        .chain(stderr.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+
+    let version_re = regex::Regex::new(r"\d+(?:\.\d+)+").unwrap();
+    Some(
+        version_re
+            .find(line)
+            .map_or(line, |m| m.as_str())
+            .to_string(),
+    )
+}
+
+/// Find an executable, searching `PATH` for bare command names.
+pub fn find_executable(binary: &Path) -> Option<std::path::PathBuf> {
+    if binary.components().count() > 1 {
+        return binary.is_file().then(|| binary.to_path_buf());
+    }
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var).find_map(|dir| {
+        let candidate = dir.join(binary);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = candidate.with_extension("exe");
+        (cfg!(windows) && exe.is_file()).then_some(exe)
+    })
+}
+
 /// Parse .gitignore file and return glob patterns.
 /// Converts gitignore patterns to glob patterns for use with our walker.
 pub fn parse_gitignore(gitignore_path: &Path) -> Vec<String> {
@@ -205,6 +709,31 @@ fn convert_gitignore_to_glob(pattern: &str) -> String {
     pattern
 }
 
+/// Check if a file is excluded by the nearest tool ignore file (e.g.
+/// `.prettierignore`), searching upward from the file's directory.
+///
+/// Patterns are gitignore-style and relative to the ignore file's directory.
+/// Tools invoked on a single file may not apply their own ignore file, so
+/// callers use this to skip such files.
+pub fn is_ignored_by(path: &Path, ignore_file_name: &str) -> bool {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut current = path.parent();
+
+    while let Some(dir) = current {
+        let ignore_file = dir.join(ignore_file_name);
+        if ignore_file.is_file() {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                return false;
+            };
+            return walker::build_glob_set(&parse_gitignore(&ignore_file))
+                .is_some_and(|set| set.is_match(relative));
+        }
+        current = dir.parent();
+    }
+
+    false
+}
+
 /// Get all gitignore patterns from the project.
 /// Reads .gitignore from project root and any nested .gitignore files.
 pub fn get_gitignore_patterns(project_root: &Path) -> Vec<String> {
@@ -226,3 +755,385 @@ pub fn get_gitignore_patterns(project_root: &Path) -> Vec<String> {
 
     patterns
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::write(root.join("src/nested/a.rs"), "").unwrap();
+
+        let file = root.join("src/nested/a.rs");
+        assert_eq!(relative_path(&file, root), Path::new("src/nested/a.rs"));
+        assert_eq!(
+            relative_path(&file, &root.join("src/other")),
+            Path::new("../nested/a.rs")
+        );
+        assert_eq!(
+            relative_path(&root.join("src/./gone/../nested/a.rs"), &root.join("src")),
+            Path::new("nested/a.rs")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_memory_hungry_tool_is_stopped_at_limit() {
+        // tail buffers its whole input line, which never ends
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "head -c 300000000 /dev/zero | tail"]);
+        let output = limit_memory(&mut cmd, 64).output().unwrap();
+        assert!(!output.status.success());
+
+        let err = check_tool_status_with_limit("tail", &output, &[], Some(64)).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::checkers::CheckerError::MemoryLimit { limit_mb: 64, .. }
+        ));
+        assert!(err.is_tool_failure());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_signal_without_allocation_failure_is_a_crash() {
+        let run = |script: &str| Command::new("sh").args(["-c", script]).output().unwrap();
+
+        let err = check_tool_status_with_limit("tool", &run("kill -SEGV $$"), &[1], Some(64))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::checkers::CheckerError::ToolCrashed { .. }
+        ));
+
+        let oom = run("echo 'memory allocation of 8 bytes failed' >&2; kill -ABRT $$");
+        let err = check_tool_status_with_limit("tool", &oom, &[1], Some(64)).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::checkers::CheckerError::MemoryLimit { limit_mb: 64, .. }
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tool_diagnostics_read_from_the_tools_stream() {
+        let output = Command::new("sh")
+            .args(["-c", "printf 'out'; echo 'err: diagnostic' >&2; exit 1"])
+            .output()
+            .unwrap();
+
+        assert_eq!(tool_diagnostics("cpplint", &output), "err: diagnostic\n");
+        assert_eq!(tool_diagnostics("go vet", &output), "err: diagnostic\n");
+        assert_eq!(tool_diagnostics("ruff", &output), "out");
+        assert_eq!(tool_diagnostics("unknown-tool", &output), "out");
+        assert_eq!(
+            tool_diagnostics("checkstyle", &output),
+            "out\nerr: diagnostic\n"
+        );
+    }
+
+    #[test]
+    fn test_default_excludes_cover_nested_build_and_module_dirs() {
+        use crate::utils::walker::{walk_relative, write_test_tree, WalkerConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        write_test_tree(
+            dir.path(),
+            &[
+                "crates/foo/src/lib.rs",
+                "crates/foo/target/x.rs",
+                "home/.cargo/registry/src/dep/lib.rs",
+                "go/pkg/mod/golang.org/x/text/doc.go",
+                "go/parser/testdata/fixture.go",
+            ],
+        );
+        let config = WalkerConfig {
+            exclude_patterns: default_excludes_without(&[]),
+            ..Default::default()
+        };
+        assert_eq!(
+            walk_relative(dir.path(), &config),
+            vec![Path::new("crates/foo/src/lib.rs")]
+        );
+    }
+
+    #[test]
+    fn test_test_excludes_skip_go_and_python_tests() {
+        use crate::utils::walker::{walk_relative, write_test_tree, WalkerConfig};
+        use crate::Language;
+
+        let dir = tempfile::tempdir().unwrap();
+        write_test_tree(
+            dir.path(),
+            &[
+                "pkg/foo.go",
+                "pkg/foo_test.go",
+                "app/foo.py",
+                "app/test_foo.py",
+            ],
+        );
+        let walk = |exclude_patterns: Vec<String>| {
+            let config = WalkerConfig {
+                exclude_patterns,
+                ..Default::default()
+            };
+            walk_relative(dir.path(), &config)
+        };
+        assert_eq!(walk(Vec::new()).len(), 4);
+        assert_eq!(
+            walk(test_excludes(&[])),
+            vec![Path::new("app/foo.py"), Path::new("pkg/foo.go")]
+        );
+        // Only the conventions of the requested languages apply
+        assert_eq!(walk(test_excludes(&[Language::Go])).len(), 3);
+    }
+
+    #[test]
+    fn test_test_excludes_follow_each_language_convention() {
+        use crate::utils::walker::{walk_relative, write_test_tree, WalkerConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let sources = [
+            "web/app.ts",
+            "src/lib.rs",
+            "pkg/main.py",
+            "src/main/java/App.java",
+        ];
+        let tests = [
+            "web/app.test.ts",
+            "web/__tests__/app.ts",
+            "tests/integration.rs",
+            "pkg/tests/helpers.py",
+            "src/test/java/AppTest.java",
+        ];
+        write_test_tree(dir.path(), &sources);
+        write_test_tree(dir.path(), &tests);
+
+        let config = WalkerConfig {
+            exclude_patterns: test_excludes(&[]),
+            ..Default::default()
+        };
+        let files = walk_relative(dir.path(), &config);
+        let mut expected: Vec<_> = sources.iter().map(std::path::PathBuf::from).collect();
+        expected.sort();
+        assert_eq!(files, expected);
+
+        let config: crate::config::Config = toml::from_str("exclude_tests = true").unwrap();
+        assert_eq!(config.skip_tests, Some(true));
+    }
+
+    #[test]
+    fn test_default_excludes_without_reincludes_vendor() {
+        use crate::utils::walker::{walk_relative, write_test_tree, WalkerConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        write_test_tree(
+            dir.path(),
+            &["src/app.py", "vendor/lib.py", "node_modules/pkg/index.js"],
+        );
+        let walk = |remove: &[String]| {
+            let config = WalkerConfig {
+                exclude_patterns: default_excludes_without(remove),
+                ..Default::default()
+            };
+            walk_relative(dir.path(), &config)
+        };
+
+        assert_eq!(walk(&[]), vec![Path::new("src/app.py")]);
+        assert_eq!(
+            walk(&["vendor/**".to_string()]),
+            vec![Path::new("src/app.py"), Path::new("vendor/lib.py")]
+        );
+        assert_eq!(
+            default_excludes_without(&["vendor/**".to_string()]).len(),
+            DEFAULT_EXCLUDES.len() - 1
+        );
+    }
+
+    #[test]
+    fn test_porcelain_has_unstaged() {
+        assert!(!porcelain_has_unstaged(""));
+        // Staged only
+        assert!(!porcelain_has_unstaged("M  src/lib.rs\n"));
+        assert!(!porcelain_has_unstaged("A  new.rs\n"));
+        // Unstaged modifications
+        assert!(porcelain_has_unstaged(" M src/lib.rs\n"));
+        assert!(porcelain_has_unstaged("MM src/lib.rs\n"));
+        // Untracked
+        assert!(!porcelain_has_unstaged("?? scratch.rs\n"));
+        // Ignored
+        assert!(!porcelain_has_unstaged("!! target/\n"));
+    }
+
+    #[test]
+    fn test_is_ignored_by() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/legacy")).unwrap();
+        fs::write(
+            root.join(".prettierignore"),
+            "# generated\nlegacy/\n*.min.js\n",
+        )
+        .unwrap();
+
+        let file = |rel: &str| {
+            let path = root.join(rel);
+            fs::write(&path, "x\n").unwrap();
+            path
+        };
+
+        assert!(is_ignored_by(&file("src/app.min.js"), ".prettierignore"));
+        assert!(!is_ignored_by(&file("src/app.js"), ".prettierignore"));
+        assert!(!is_ignored_by(&file("src/app.js"), ".eslintignore"));
+
+        // Nearest ignore file wins
+        fs::write(root.join("src/.prettierignore"), "legacy/\n").unwrap();
+        assert!(is_ignored_by(&file("src/legacy/old.js"), ".prettierignore"));
+        assert!(!is_ignored_by(&file("src/app.min.js"), ".prettierignore"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_has_unstaged_changes_with_stub_git() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("dirty.py");
+        fs::write(&file, "x = 1\n").unwrap();
+
+        let write_stub = |name: &str, script: &str| {
+            let stub = dir.path().join(name);
+            fs::write(&stub, script).unwrap();
+            fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+            stub
+        };
+        let dirty_git = write_stub("git-dirty", "#!/bin/sh\necho ' M dirty.py'\n");
+        let clean_git = write_stub("git-clean", "#!/bin/sh\nexit 0\n");
+        let failing_git = write_stub("git-fail", "#!/bin/sh\nexit 128\n");
+
+        assert!(has_unstaged_changes_with(&dirty_git, &file));
+        assert!(!has_unstaged_changes_with(&clean_git, &file));
+        assert!(!has_unstaged_changes_with(&failing_git, &file));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_staged_copy_mirrors_tree_outside_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("pkg/sub")).unwrap();
+        std::fs::write(root.join("pyproject.toml"), "[tool.ruff]\n").unwrap();
+        std::fs::write(root.join("pkg/a.py"), "a = 1\n").unwrap();
+        std::fs::write(root.join("pkg/b.py"), "b = 2\n").unwrap();
+
+        let file = root.join("pkg/a.py");
+        let copy = StagedCopy::with_content(&file, "a = 2\n").unwrap();
+        let path = copy.path().to_path_buf();
+        assert!(!path.starts_with(&root));
+        assert!(path.ends_with("pkg/a.py"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a = 2\n");
+        // Configuration and siblings are visible from the copy
+        let mirror = path.parent().unwrap().parent().unwrap();
+        assert!(mirror.join("pyproject.toml").is_file());
+        assert!(mirror.join("pkg/b.py").is_file());
+        assert!(mirror.join("pkg/sub").is_dir());
+
+        let mut reported = path.clone();
+        copy.restore_path(&mut reported, &file);
+        assert_eq!(reported, file);
+        let mut sibling = mirror.join("pkg/b.py");
+        copy.restore_path(&mut sibling, &file);
+        assert_eq!(sibling, root.join("pkg/b.py"));
+
+        // The worktree is untouched and the mirror goes away on drop
+        drop(copy);
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "a = 1\n");
+        assert_eq!(std::fs::read_dir(root.join("pkg")).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_staged_without_git() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // No git on PATH
+        let dir = tempfile::tempdir().unwrap();
+        let err = get_staged_files_with(&dir.path().join("git")).unwrap_err();
+        assert!(matches!(err, crate::LintisError::GitNotFound(_)));
+        assert_eq!(
+            err.to_string(),
+            "git not found: --staged requires git on PATH"
+        );
+
+        // git present, but not inside a repository
+        let stub = dir.path().join("git-outside-repo");
+        fs::write(
+            &stub,
+            "#!/bin/sh
+echo 'fatal: not a git repository' >&2
+exit 128
+",
+        )
+        .unwrap();
+        fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+        let err = get_staged_files_with(&stub).unwrap_err();
+        assert!(matches!(err, crate::LintisError::NotAGitRepo(_)));
+        assert!(err.to_string().starts_with("not a git repository"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_tool_status() {
+        let run = |script: &str| Command::new("sh").args(["-c", script]).output().unwrap();
+
+        assert!(check_tool_status("tool", &run("exit 0"), &[1]).is_ok());
+        assert!(check_tool_status("tool", &run("exit 1"), &[1]).is_ok());
+
+        let err = check_tool_status("tool", &run("echo boom >&2; exit 139"), &[1]).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::checkers::CheckerError::ToolCrashed {
+                code: Some(139),
+                ..
+            }
+        ));
+        assert_eq!(err.to_string(), "tool crashed (exit code 139): boom");
+
+        let err = check_tool_status("tool", &run("kill -SEGV $$"), &[1]).unwrap_err();
+        assert!(err.to_string().contains("terminated by a signal"));
+
+        let crash = run("echo 'Stack dump:' >&2; exit 1");
+        assert!(check_tool_status("tool", &crash, &[1]).is_err());
+        assert!(check_tool_status("tool", &run("exit 2"), &[1]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tool_version_and_find_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let write_stub = |name: &str, script: &str| {
+            let stub = dir.path().join(name);
+            fs::write(&stub, script).unwrap();
+            fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+            stub
+        };
+        let stdout_tool = write_stub("fmt", "#!/bin/sh\necho\necho 'fmt v1.2.3 (abc)'\n");
+        let stderr_tool = write_stub("old", "#!/bin/sh\necho 'old 0.9' >&2\n");
+        let plain_tool = write_stub("dev", "#!/bin/sh\necho 'dev build'\n");
+        let version = |tool: &Path| tool_version(tool, &["--version"]);
+
+        assert_eq!(version(&stdout_tool).as_deref(), Some("1.2.3"));
+        assert_eq!(version(&stderr_tool).as_deref(), Some("0.9"));
+        assert_eq!(version(&plain_tool).as_deref(), Some("dev build"));
+        assert_eq!(version(&dir.path().join("missing")), None);
+
+        assert_eq!(find_executable(&stdout_tool), Some(stdout_tool.clone()));
+        assert_eq!(find_executable(&dir.path().join("missing")), None);
+        assert!(find_executable(Path::new("sh")).is_some());
+    }
+}
@@ -12,6 +12,8 @@
 
 use crate::utils::types::{LintIssue, RunResult, Severity};
 use colored::Colorize;
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
 
 /// Output format enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,32 +21,392 @@ pub enum OutputFormat {
     Human,
     Json,
     GithubActions,
+    Csv,
+    Sarif,
+    Junit,
+    /// Files that would change, for pre-commit hooks (implies `--format-check`)
+    FormatCheckJson,
 }
 
 impl OutputFormat {
-    pub fn parse(s: &str) -> Option<Self> {
+    /// Look up a built-in format by name.
+    pub fn from_name(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "human" => Some(OutputFormat::Human),
             "json" => Some(OutputFormat::Json),
             "github-actions" | "github" | "ga" => Some(OutputFormat::GithubActions),
+            "csv" => Some(OutputFormat::Csv),
+            "sarif" => Some(OutputFormat::Sarif),
+            "junit" => Some(OutputFormat::Junit),
+            "format-check-json" => Some(OutputFormat::FormatCheckJson),
             _ => None,
         }
     }
+
+    /// Reporter for a built-in format name.
+    pub fn parse(s: &str) -> Option<Box<dyn Reporter>> {
+        Self::from_name(s).map(Self::reporter)
+    }
+
+    /// Reporter implementing this format.
+    pub fn reporter(self) -> Box<dyn Reporter> {
+        match self {
+            OutputFormat::Human => Box::new(HumanReporter),
+            OutputFormat::Json => Box::new(JsonReporter),
+            OutputFormat::GithubActions => Box::new(GithubActionsReporter),
+            OutputFormat::Csv => Box::new(CsvReporter),
+            OutputFormat::Sarif => Box::new(SarifReporter),
+            OutputFormat::Junit => Box::new(JunitReporter),
+            OutputFormat::FormatCheckJson => Box::new(FormatCheckJsonReporter),
+        }
+    }
 }
 
-/// Format a single lint issue for human-readable output.
-pub fn format_issue_human(issue: &LintIssue) -> String {
-    let severity_str = match issue.severity {
-        Severity::Error => "error".red().bold(),
-        Severity::Warning => "warning".yellow().bold(),
-        Severity::Info => "info".blue().bold(),
+/// Default output format when running in CI: github-actions on GitHub
+/// Actions (`GITHUB_ACTIONS`), json on other CI systems (`GITLAB_CI`, `CI`).
+/// None outside CI.
+pub fn detect_ci_output_format() -> Option<OutputFormat> {
+    ci_output_format(|name| std::env::var(name).ok())
+}
+
+/// [`detect_ci_output_format`] with environment lookup by `env`
+fn ci_output_format(env: impl Fn(&str) -> Option<String>) -> Option<OutputFormat> {
+    let is_set = |name: &str| {
+        env(name).is_some_and(|v| {
+            let v = v.trim();
+            !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false")
+        })
     };
+    if is_set("GITHUB_ACTIONS") {
+        Some(OutputFormat::GithubActions)
+    } else if is_set("GITLAB_CI") || is_set("CI") {
+        Some(OutputFormat::Json)
+    } else {
+        None
+    }
+}
+
+/// Renders a run result in some output format.
+///
+/// Implement this to add a custom output format and pass it to
+/// [`render_with`]. Only `render` is required: the default
+/// `render_with_options` keeps only the printed issues (see
+/// [`RenderOptions::printed_issues`]) before rendering, leaving all counters
+/// untouched.
+pub trait Reporter: Send + Sync {
+    /// Render the full result.
+    fn render(&self, result: &RunResult) -> String;
+
+    /// Render the result, applying render options.
+    fn render_with_options(&self, result: &RunResult, options: &RenderOptions) -> String {
+        match options.filter_result(result) {
+            Some(filtered) => self.render(&filtered),
+            None => self.render(result),
+        }
+    }
+}
+
+/// Human-readable output (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HumanReporter;
+
+impl Reporter for HumanReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_human(result)
+    }
+
+    fn render_with_options(&self, result: &RunResult, options: &RenderOptions) -> String {
+        format_result_human_with_options(result, options)
+    }
+}
+
+/// The serialized `RunResult` as pretty-printed JSON
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_json(result)
+    }
+
+    fn render_with_options(&self, result: &RunResult, options: &RenderOptions) -> String {
+        format_result_json_with_options(result, options)
+    }
+}
+
+/// One CSV row per issue: file,line,column,severity,code,source,message
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvReporter;
+
+impl Reporter for CsvReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_csv(result)
+    }
+}
+
+/// SARIF 2.1.0 log, for code scanning dashboards
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SarifReporter;
+
+impl Reporter for SarifReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_sarif(result)
+    }
+}
+
+/// JUnit XML, one test suite per file and one failing test case per issue
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JunitReporter;
+
+impl Reporter for JunitReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_junit(result)
+    }
+}
+
+/// `{"files": [...], "all_formatted": bool}`: only the files format-check
+/// found unformatted
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatCheckJsonReporter;
+
+impl Reporter for FormatCheckJsonReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_format_check_json(result)
+    }
+}
+
+/// Only the issue counts, as `key: value` lines or a JSON object
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountReporter {
+    pub json: bool,
+}
+
+impl Reporter for CountReporter {
+    fn render(&self, result: &RunResult) -> String {
+        if self.json {
+            format_counts_json(result)
+        } else {
+            format_counts_human(result)
+        }
+    }
+
+    /// Counts always cover the full run
+    fn render_with_options(&self, result: &RunResult, _options: &RenderOptions) -> String {
+        self.render(result)
+    }
+}
+
+/// GitHub Actions workflow commands (`::error file=...::`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GithubActionsReporter;
+
+impl Reporter for GithubActionsReporter {
+    fn render(&self, result: &RunResult) -> String {
+        format_result_github_actions(result)
+    }
+
+    fn render_with_options(&self, result: &RunResult, options: &RenderOptions) -> String {
+        format_result_github_actions_with_options(result, options)
+    }
+}
+
+/// How file paths are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    /// Relative to the project root (paths outside it stay absolute)
+    #[default]
+    Relative,
+    /// Absolute paths
+    Absolute,
+}
+
+impl PathStyle {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "relative" => Some(PathStyle::Relative),
+            "absolute" => Some(PathStyle::Absolute),
+            _ => None,
+        }
+    }
+}
+
+/// Which side of a format run's issues to render
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixFilter {
+    /// Only the issues formatting fixed (`--only-fixed`)
+    Fixed,
+    /// Only the issues still remaining (`--only-unfixed`)
+    Unfixed,
+}
+
+/// Render-time options that change what is printed without touching the result.
+///
+/// Counts, summaries and exit codes are always computed from the full
+/// `RunResult`; these options only control which issues are rendered.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Only print issues at or above this severity (None = print all)
+    pub print_severity: Option<Severity>,
+    /// Print nothing when the run is clean (exit code 0)
+    pub quiet_if_clean: bool,
+    /// How file paths are rendered
+    pub path_style: PathStyle,
+    /// Root that paths are resolved against (None = print paths as reported)
+    pub project_root: Option<PathBuf>,
+    /// Render only fixed or only remaining issues (None = remaining issues)
+    pub fix_filter: Option<FixFilter>,
+    /// Group human output by rule (code) with a count per rule, instead of by file
+    pub group_by_rule: bool,
+    /// Home directory to print as `~` in paths (None = print paths unredacted)
+    pub redact_home: Option<PathBuf>,
+    /// Print the diff of each formatted file (human output)
+    pub show_diff: bool,
+}
+
+impl RenderOptions {
+    /// Check if an issue should be rendered under these options.
+    pub fn should_print(&self, issue: &LintIssue) -> bool {
+        self.print_severity
+            .map(|threshold| issue.severity.is_at_least(threshold))
+            .unwrap_or(true)
+    }
+
+    /// Render a path in the configured style, with the home directory
+    /// redacted if requested.
+    ///
+    /// Relative paths are taken as relative to the working directory.
+    pub fn style_path(&self, path: &Path) -> PathBuf {
+        let styled = self.style_path_unredacted(path);
+        match &self.redact_home {
+            Some(home) => match styled.strip_prefix(home) {
+                Ok(rest) => Path::new("~").join(rest),
+                Err(_) => styled,
+            },
+            None => styled,
+        }
+    }
+
+    fn style_path_unredacted(&self, path: &Path) -> PathBuf {
+        let Some(root) = &self.project_root else {
+            return path.to_path_buf();
+        };
+
+        let absolute: PathBuf = if path.is_absolute() {
+            path.components().collect()
+        } else {
+            std::env::current_dir()
+                .unwrap_or_else(|_| root.clone())
+                .join(path)
+                .components()
+                .collect()
+        };
+
+        match self.path_style {
+            PathStyle::Absolute => absolute,
+            PathStyle::Relative => absolute
+                .strip_prefix(root)
+                .map(Path::to_path_buf)
+                .unwrap_or(absolute),
+        }
+    }
+
+    /// Copy of the result with all file paths rendered in the configured
+    /// style, or None when paths are printed as reported.
+    pub fn apply_path_style(&self, result: &RunResult) -> Option<RunResult> {
+        if self.project_root.is_none() && self.redact_home.is_none() {
+            return None;
+        }
+
+        let mut styled = result.clone();
+        for issue in styled.issues.iter_mut().chain(&mut styled.fixed_issues) {
+            issue.file_path = self.style_path(&issue.file_path);
+            if let Some(config) = &mut issue.config_path {
+                *config = self.style_path(config);
+            }
+            for related in &mut issue.related {
+                if let Some(file) = &mut related.file {
+                    *file = self.style_path(file);
+                }
+            }
+        }
+        for format_result in styled
+            .format_results
+            .iter_mut()
+            .chain(&mut styled.import_results)
+        {
+            format_result.file_path = self.style_path(&format_result.file_path);
+        }
+        for path in &mut styled.unformatted_files {
+            *path = self.style_path(path);
+        }
+        for timing in &mut styled.slowest_files {
+            timing.path = self.style_path(&timing.path);
+        }
+        Some(styled)
+    }
+
+    /// Issues to render: the fixed or remaining ones, at or above the
+    /// print severity.
+    pub fn printed_issues<'a>(
+        &'a self,
+        result: &'a RunResult,
+    ) -> impl Iterator<Item = &'a LintIssue> + 'a {
+        let selected = match self.fix_filter {
+            Some(FixFilter::Fixed) => &result.fixed_issues,
+            Some(FixFilter::Unfixed) | None => &result.issues,
+        };
+        selected.iter().filter(|i| self.should_print(i))
+    }
+
+    /// Copy of the result whose `issues` are the printed issues, or None
+    /// when nothing is filtered. Counters are left untouched.
+    pub fn filter_result(&self, result: &RunResult) -> Option<RunResult> {
+        if self.print_severity.is_none() && self.fix_filter.is_none() {
+            return None;
+        }
+        let mut filtered = result.clone();
+        filtered.issues = self.printed_issues(result).cloned().collect();
+        if self.fix_filter.is_some() {
+            filtered.fixed_issues.clear();
+        }
+        Some(filtered)
+    }
+
+    /// Check if all console output should be suppressed for this result:
+    /// with `quiet_if_clean`, a run that passed without any issues.
+    pub fn suppress_output(&self, result: &RunResult) -> bool {
+        self.quiet_if_clean && result.exit_code == 0 && result.issues.is_empty()
+    }
+}
 
+/// Format a single lint issue for human-readable output.
+pub fn format_issue_human(issue: &LintIssue) -> String {
     let location = if let Some(col) = issue.column {
         format!("{}:{}:{}", issue.file_path.display(), issue.line, col)
     } else {
         format!("{}:{}", issue.file_path.display(), issue.line)
     };
+    format_issue_at(issue, &location, "")
+}
+
+/// Format a lint issue listed under its file header (location without the path).
+fn format_issue_in_file(issue: &LintIssue) -> String {
+    let location = if let Some(col) = issue.column {
+        format!("{}:{}", issue.line, col)
+    } else {
+        issue.line.to_string()
+    };
+    format_issue_at(issue, &location, "  ")
+}
+
+/// Format a lint issue at the given location; continuation lines get `indent`.
+fn format_issue_at(issue: &LintIssue, location: &str, indent: &str) -> String {
+    let severity_str = match issue.severity {
+        Severity::Error => "error".red().bold(),
+        Severity::Warning => "warning".yellow().bold(),
+        Severity::Info => "info".blue().bold(),
+        Severity::Hint => "hint".cyan().bold(),
+    };
 
     let code_str = issue
         .code
@@ -63,17 +425,31 @@ pub fn format_issue_human(issue: &LintIssue) -> String {
     // Show the source code line if available
     if let Some(code_line) = &issue.code_line {
         let line_num = format!("{:>5}", issue.line);
-        output.push_str(&format!("\n{} | {}", line_num.cyan(), code_line));
+        output.push_str(&format!("\n{}{} | {}", indent, line_num.cyan(), code_line));
 
         // Show column indicator if available
         if let Some(col) = issue.column {
             let spaces = " ".repeat(line_num.len() + 3 + col.saturating_sub(1));
-            output.push_str(&format!("\n{}^", spaces));
+            output.push_str(&format!("\n{}{}^", indent, spaces));
         }
     }
 
     if let Some(suggestion) = &issue.suggestion {
-        output.push_str(&format!("\n  --> {}", suggestion.cyan()));
+        output.push_str(&format!("\n{}  --> {}", indent, suggestion.cyan()));
+    }
+
+    for related in &issue.related {
+        let location = match (&related.file, related.line) {
+            (Some(file), Some(line)) => format!("{}:{}: ", file.display(), line),
+            _ => String::new(),
+        };
+        output.push_str(&format!(
+            "\n{}  = {}: {}{}",
+            indent,
+            related.kind.to_string().cyan().bold(),
+            location,
+            related.message
+        ));
     }
 
     output
@@ -84,7 +460,7 @@ pub fn format_issue_github_actions(issue: &LintIssue) -> String {
     let severity = match issue.severity {
         Severity::Error => "error",
         Severity::Warning => "warning",
-        Severity::Info => "notice",
+        Severity::Info | Severity::Hint => "notice",
     };
 
     let col_str = issue
@@ -113,6 +489,10 @@ pub fn format_issue_github_actions(issue: &LintIssue) -> String {
 pub fn format_summary_human(result: &RunResult) -> String {
     use crate::utils::types::RunModeKind;
 
+    if result.run_mode == RunModeKind::FormatCheck {
+        return format_format_check_summary_human(result);
+    }
+
     let issue_count = result.issues.len();
     let error_count = result
         .issues
@@ -125,11 +505,16 @@ pub fn format_summary_human(result: &RunResult) -> String {
         .filter(|i| i.severity == Severity::Warning)
         .count();
 
-    if issue_count == 0 && result.files_formatted == 0 && result.issues_fixed == 0 {
+    if issue_count == 0
+        && result.files_formatted == 0
+        && result.issues_fixed == 0
+        && result.imports_organized == 0
+    {
         let msg = match result.run_mode {
             RunModeKind::FormatOnly => "All formats passed",
             RunModeKind::CheckOnly => "All checks passed",
             RunModeKind::Both => "All checks and formats passed",
+            RunModeKind::FormatCheck => "All files formatted",
         };
 
         // Add file statistics
@@ -154,8 +539,24 @@ pub fn format_summary_human(result: &RunResult) -> String {
 
     let mut summary = String::new();
 
-    // Show formatting stats first
+    // Show import and formatting stats first
+    if result.imports_organized > 0 {
+        summary.push_str(&format!(
+            "{} Organized imports in {} file{}",
+            "✓".green(),
+            result.imports_organized,
+            if result.imports_organized == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ));
+    }
+
     if result.files_formatted > 0 {
+        if !summary.is_empty() {
+            summary.push('\n');
+        }
         summary.push_str(&format!(
             "{} Formatted {} file{}",
             "✓".green(),
@@ -177,6 +578,51 @@ pub fn format_summary_human(result: &RunResult) -> String {
         ));
     }
 
+    // Flag lint regressions caused by formatting
+    if result.issues_introduced > 0 {
+        if !summary.is_empty() {
+            summary.push('\n');
+        }
+        summary.push_str(&format!(
+            "{} Formatting introduced {} new issue{}",
+            "⚠".yellow(),
+            result.issues_introduced,
+            if result.issues_introduced == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ));
+    }
+
+    // Issues accepted by the baseline are not listed
+    if result.baselined_issues > 0 {
+        if !summary.is_empty() {
+            summary.push('\n');
+        }
+        summary.push_str(&format!(
+            "{} {} issue{} accepted by the baseline",
+            "ℹ".blue(),
+            result.baselined_issues,
+            if result.baselined_issues == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ));
+    }
+
+    // Note that later files were not processed
+    if result.aborted {
+        if !summary.is_empty() {
+            summary.push('\n');
+        }
+        summary.push_str(&format!(
+            "{} Stopped at the first tool error (--fail-fast)",
+            "✗".red()
+        ));
+    }
+
     // Show remaining issues
     if issue_count > 0 {
         if !summary.is_empty() {
@@ -195,7 +641,8 @@ pub fn format_summary_human(result: &RunResult) -> String {
             result.total_files,
             if result.total_files == 1 { "" } else { "s" }
         ));
-    } else if result.files_formatted > 0 || result.issues_fixed > 0 {
+    } else if result.files_formatted > 0 || result.issues_fixed > 0 || result.imports_organized > 0
+    {
         // All issues were fixed
         if !summary.is_empty() {
             summary.push('\n');
@@ -204,6 +651,7 @@ pub fn format_summary_human(result: &RunResult) -> String {
             RunModeKind::FormatOnly => "All formats passed",
             RunModeKind::CheckOnly => "All checks passed",
             RunModeKind::Both => "All checks and formats passed",
+            RunModeKind::FormatCheck => "All files formatted",
         };
 
         // Add file statistics
@@ -226,38 +674,274 @@ pub fn format_summary_human(result: &RunResult) -> String {
         ));
     }
 
-    // Show duration
-    if !summary.is_empty() {
-        summary.push('\n');
+    summary
+}
+
+/// Narrate the lint → format → lint passes of a check-and-format run:
+/// `Before: 5 issues; Formatted 2 files; Fixed 3 issues; Remaining 2 issues`.
+///
+/// Returns None for other run modes.
+pub fn render_three_pass_summary(result: &RunResult) -> Option<String> {
+    let summary = result.three_pass_summary()?;
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    Some(format!(
+        "{} {} issue{}; {} {} file{}; {} {} issue{}; {} {} issue{}",
+        "Before:".bold(),
+        summary.before,
+        plural(summary.before),
+        "Formatted".bold(),
+        summary.formatted,
+        plural(summary.formatted),
+        "Fixed".bold(),
+        summary.fixed,
+        plural(summary.fixed),
+        "Remaining".bold(),
+        summary.remaining,
+        plural(summary.remaining)
+    ))
+}
+
+/// Format the summary for format-check mode, kept separate from lint issue counts.
+fn format_format_check_summary_human(result: &RunResult) -> String {
+    let unformatted = result.unformatted_files.len();
+    if unformatted == 0 {
+        format!(
+            "{} {} ({} file{} checked)",
+            "✓".green(),
+            "All files formatted".green().bold(),
+            result.total_files,
+            if result.total_files == 1 { "" } else { "s" }
+        )
+    } else {
+        format!(
+            "{} {} of {} file{} not formatted (run without --format-check to fix)",
+            "✗".red(),
+            unformatted,
+            result.total_files,
+            if result.total_files == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// Format a duration in milliseconds as "120ms" or "1.50s".
+fn format_duration(duration_ms: u64) -> String {
+    if duration_ms >= 1000 {
+        format!("{:.2}s", duration_ms as f64 / 1000.0)
+    } else {
+        format!("{}ms", duration_ms)
+    }
+}
+
+/// Format the slowest files section for human-readable output.
+pub fn format_slowest_files_human(result: &RunResult) -> String {
+    let mut output = format!("{}\n", "Slowest files:".bold());
+    for timing in &result.slowest_files {
+        output.push_str(&format!(
+            "  {}  {}\n",
+            format!("{:>8}", format_duration(timing.duration_ms)).cyan(),
+            timing.path.display()
+        ));
     }
-    let duration_str = if result.duration_ms >= 1000 {
-        format!("{:.2}s", result.duration_ms as f64 / 1000.0)
+    output
+}
+
+/// Format the severity counts footer for human-readable output.
+///
+/// Example: `✖ 3 errors, 5 warnings, 2 info across 4 files (120ms)`.
+/// Counts are computed from all issues in the result; hints are listed only
+/// when there are any. The file count is the number of files with issues, or
+/// the number of files checked when clean.
+pub fn format_footer_human(result: &RunResult) -> String {
+    let count = |severity: Severity| {
+        result
+            .issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    };
+    let error_count = count(Severity::Error);
+    let warning_count = count(Severity::Warning);
+    let info_count = count(Severity::Info);
+    let hint_count = count(Severity::Hint);
+
+    let unformatted_count = result.unformatted_files.len();
+
+    let file_count = if result.issues.is_empty() && unformatted_count == 0 {
+        result.total_files
     } else {
-        format!("{}ms", result.duration_ms)
+        result
+            .issues
+            .iter()
+            .map(|i| &i.file_path)
+            .chain(result.unformatted_files.iter())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
     };
-    summary.push_str(&format!("Done in {}", duration_str.cyan()));
 
-    summary
+    let icon = if error_count > 0 || unformatted_count > 0 {
+        "✖".red().bold()
+    } else if warning_count > 0 {
+        "⚠".yellow().bold()
+    } else {
+        "✔".green().bold()
+    };
+
+    let errors = format!(
+        "{} error{}",
+        error_count,
+        if error_count == 1 { "" } else { "s" }
+    );
+    let warnings = format!(
+        "{} warning{}",
+        warning_count,
+        if warning_count == 1 { "" } else { "s" }
+    );
+    let infos = format!("{} info", info_count);
+    let hints = if hint_count > 0 {
+        format!(
+            ", {}",
+            format!(
+                "{} hint{}",
+                hint_count,
+                if hint_count == 1 { "" } else { "s" }
+            )
+            .cyan()
+        )
+    } else {
+        String::new()
+    };
+    let unformatted = if unformatted_count > 0 {
+        format!(", {}", format!("{} unformatted", unformatted_count).red())
+    } else {
+        String::new()
+    };
+
+    format!(
+        "{} {}, {}, {}{}{} across {} file{} ({})",
+        icon,
+        if error_count > 0 {
+            errors.red()
+        } else {
+            errors.normal()
+        },
+        if warning_count > 0 {
+            warnings.yellow()
+        } else {
+            warnings.normal()
+        },
+        if info_count > 0 {
+            infos.blue()
+        } else {
+            infos.normal()
+        },
+        hints,
+        unformatted,
+        file_count,
+        if file_count == 1 { "" } else { "s" },
+        format_duration(result.duration_ms)
+    )
+}
+
+/// Diff lines shown per file with `--show-diff` before truncating
+const MAX_DIFF_LINES: usize = 200;
+
+/// Colorize a unified diff like git, keeping at most `max_lines` lines.
+fn format_diff_human(diff: &str, max_lines: usize) -> String {
+    let mut output = String::new();
+    let lines: Vec<&str> = diff.lines().collect();
+    for line in lines.iter().take(max_lines) {
+        let styled = if line.starts_with("+++") || line.starts_with("---") {
+            line.bold()
+        } else if line.starts_with("@@") {
+            line.cyan()
+        } else if line.starts_with('+') {
+            line.green()
+        } else if line.starts_with('-') {
+            line.red()
+        } else {
+            line.normal()
+        };
+        output.push_str(&format!("{}\n", styled));
+    }
+    if lines.len() > max_lines {
+        output.push_str(&format!(
+            "{}\n",
+            format!("... +{} more lines", lines.len() - max_lines).dimmed()
+        ));
+    }
+    output
 }
 
 /// Format the entire run result for human-readable output.
 pub fn format_result_human(result: &RunResult) -> String {
+    format_result_human_with_options(result, &RenderOptions::default())
+}
+
+/// Sort key grouping issues by rule (code); issues without a code go last.
+fn rule_order(issue: &LintIssue, group_by_rule: bool) -> (bool, Option<&str>) {
+    if group_by_rule {
+        (issue.code.is_none(), issue.code.as_deref())
+    } else {
+        (false, None)
+    }
+}
+
+/// Format the run result for human-readable output, applying render options.
+pub fn format_result_human_with_options(result: &RunResult, options: &RenderOptions) -> String {
     let mut output = String::new();
 
-    // Separate errors and warnings for numbered output
-    let errors: Vec<_> = result
-        .issues
-        .iter()
-        .filter(|i| i.severity == Severity::Error)
-        .collect();
-    let warnings: Vec<_> = result
-        .issues
-        .iter()
-        .filter(|i| i.severity == Severity::Warning)
+    // Errors and warnings, grouped by file (or by rule) in a stable order
+    let mut printed: Vec<_> = options
+        .printed_issues(result)
+        .filter(|i| matches!(i.severity, Severity::Error | Severity::Warning))
         .collect();
+    printed.sort_by(|a, b| {
+        let rule = |i| rule_order(i, options.group_by_rule);
+        (
+            rule(a),
+            &a.file_path,
+            a.line,
+            a.column,
+            Reverse(a.severity.rank()),
+            &a.message,
+        )
+            .cmp(&(
+                rule(b),
+                &b.file_path,
+                b.line,
+                b.column,
+                Reverse(b.severity.rank()),
+                &b.message,
+            ))
+    });
+
+    // Number errors [E1][lang][tool], [E2]... and warnings [W1], [W2]... in print order
+    let (mut error_idx, mut warning_idx) = (0, 0);
+    let mut current_file = None;
+    let mut current_rule = None;
+    for (idx, issue) in printed.iter().enumerate() {
+        if options.group_by_rule {
+            if idx == 0 || current_rule != Some(&issue.code) {
+                current_rule = Some(&issue.code);
+                let count = printed[idx..]
+                    .iter()
+                    .take_while(|i| i.code == issue.code)
+                    .count();
+                output.push_str(&format!(
+                    "{} ({} issue{})\n",
+                    issue.code.as_deref().unwrap_or("(no code)").bold(),
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+        } else if current_file != Some(&issue.file_path) {
+            current_file = Some(&issue.file_path);
+            output.push_str(&format!(
+                "{}\n",
+                issue.file_path.display().to_string().bold()
+            ));
+        }
 
-    // Output errors with [E1][lang][tool], [E2][lang][tool], etc.
-    for (idx, issue) in errors.iter().enumerate() {
         let lang_tag = issue
             .language
             .map(|l| format!("[{}]", l.name()))
@@ -267,42 +951,66 @@ pub fn format_result_human(result: &RunResult) -> String {
             .as_ref()
             .map(|s| format!("[{}]", s))
             .unwrap_or_default();
-        output.push_str(&format!(
-            "{}{}{} {}",
-            format!("[E{}]", idx + 1).red().bold(),
-            lang_tag.red(),
-            tool_tag.red(),
+        let tags = if issue.severity == Severity::Error {
+            error_idx += 1;
+            format!(
+                "{}{}{}",
+                format!("[E{}]", error_idx).red().bold(),
+                lang_tag.red(),
+                tool_tag.red()
+            )
+        } else {
+            warning_idx += 1;
+            format!(
+                "{}{}{}",
+                format!("[W{}]", warning_idx).yellow().bold(),
+                lang_tag.yellow(),
+                tool_tag.yellow()
+            )
+        };
+        let issue_str = if options.group_by_rule {
             format_issue_human(issue)
-        ));
-        output.push('\n');
+        } else {
+            format_issue_in_file(issue)
+        };
+        output.push_str(&format!("  {} {}\n", tags, issue_str));
     }
 
-    // Output warnings with [W1][lang][tool], [W2][lang][tool], etc.
-    for (idx, issue) in warnings.iter().enumerate() {
-        let lang_tag = issue
-            .language
-            .map(|l| format!("[{}]", l.name()))
-            .unwrap_or_default();
-        let tool_tag = issue
-            .source
-            .as_ref()
-            .map(|s| format!("[{}]", s))
-            .unwrap_or_default();
+    // Output files failing format-check with [F1], [F2], etc.
+    for (idx, path) in result.unformatted_files.iter().enumerate() {
         output.push_str(&format!(
-            "{}{}{} {}",
-            format!("[W{}]", idx + 1).yellow().bold(),
-            lang_tag.yellow(),
-            tool_tag.yellow(),
-            format_issue_human(issue)
+            "{} {} {}\n",
+            format!("[F{}]", idx + 1).red().bold(),
+            path.display(),
+            "not formatted".red()
         ));
+    }
+
+    if !printed.is_empty() || !result.unformatted_files.is_empty() {
         output.push('\n');
     }
 
-    if !result.issues.is_empty() {
+    if options.show_diff {
+        for format_result in result.format_results.iter().chain(&result.import_results) {
+            if let Some(diff) = format_result.diff.as_deref().filter(|d| !d.is_empty()) {
+                output.push_str(&format_diff_human(diff, MAX_DIFF_LINES));
+                output.push('\n');
+            }
+        }
+    }
+
+    if !result.slowest_files.is_empty() {
+        output.push_str(&format_slowest_files_human(result));
         output.push('\n');
     }
 
+    if let Some(three_pass) = render_three_pass_summary(result) {
+        output.push_str(&three_pass);
+        output.push('\n');
+    }
     output.push_str(&format_summary_human(result));
+    output.push('\n');
+    output.push_str(&format_footer_human(result));
 
     output
 }
@@ -312,28 +1020,308 @@ pub fn format_result_json(result: &RunResult) -> String {
     serde_json::to_string_pretty(result).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// Format the run result as JSON, applying render options.
+///
+/// Filtered issues are dropped from the `issues` array only; all counters
+/// in the serialized result still reflect the full run.
+pub fn format_result_json_with_options(result: &RunResult, options: &RenderOptions) -> String {
+    match options.filter_result(result) {
+        Some(filtered) => format_result_json(&filtered),
+        None => format_result_json(result),
+    }
+}
+
+/// Format the files that would change (format-check mode) as JSON.
+pub fn format_result_format_check_json(result: &RunResult) -> String {
+    let report = serde_json::json!({
+        "files": result.unformatted_files,
+        "all_formatted": result.unformatted_files.is_empty(),
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
+}
+
 /// Format the entire run result for GitHub Actions.
 pub fn format_result_github_actions(result: &RunResult) -> String {
-    result
-        .issues
-        .iter()
+    format_result_github_actions_with_options(result, &RenderOptions::default())
+}
+
+/// Format the run result for GitHub Actions, applying render options.
+pub fn format_result_github_actions_with_options(
+    result: &RunResult,
+    options: &RenderOptions,
+) -> String {
+    options
+        .printed_issues(result)
         .map(format_issue_github_actions)
+        .chain(
+            result
+                .unformatted_files
+                .iter()
+                .map(|path| format!("::error file={}::File is not formatted", path.display())),
+        )
         .collect::<Vec<_>>()
         .join("\n")
 }
 
+/// Issue and file counts of a run (`--count`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IssueCounts {
+    pub issues: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    pub hints: usize,
+    pub files_affected: usize,
+    pub files_formatted: usize,
+}
+
+impl IssueCounts {
+    pub fn from_result(result: &RunResult) -> Self {
+        let count = |severity| {
+            result
+                .issues
+                .iter()
+                .filter(|i| i.severity == severity)
+                .count()
+        };
+        let files_affected = result
+            .issues
+            .iter()
+            .map(|i| &i.file_path)
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        Self {
+            issues: result.issues.len(),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            hints: count(Severity::Hint),
+            files_affected,
+            files_formatted: result.files_formatted,
+        }
+    }
+}
+
+/// Format only the counts of a run, one `key: value` per line.
+pub fn format_counts_human(result: &RunResult) -> String {
+    let counts = IssueCounts::from_result(result);
+    format!(
+        "issues: {}\nerrors: {}\nwarnings: {}\ninfo: {}\nhints: {}\nfiles_affected: {}\nfiles_formatted: {}",
+        counts.issues,
+        counts.errors,
+        counts.warnings,
+        counts.info,
+        counts.hints,
+        counts.files_affected,
+        counts.files_formatted
+    )
+}
+
+/// Format only the counts of a run as a JSON object.
+pub fn format_counts_json(result: &RunResult) -> String {
+    serde_json::to_string(&IssueCounts::from_result(result)).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Quote a CSV field if it contains a comma, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Format the run result as CSV, one row per issue.
+pub fn format_result_csv(result: &RunResult) -> String {
+    let mut output = String::from("file,line,column,severity,code,source,message\n");
+    for issue in &result.issues {
+        let fields = [
+            issue.file_path.display().to_string(),
+            issue.line.to_string(),
+            issue.column.map(|c| c.to_string()).unwrap_or_default(),
+            issue.severity.to_string(),
+            issue.code.clone().unwrap_or_default(),
+            issue.source.clone().unwrap_or_default(),
+            issue.message.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        output.push_str(&row.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+/// SARIF rule id of an issue: its code, else the tool that reported it.
+fn sarif_rule_id(issue: &LintIssue) -> String {
+    issue
+        .code
+        .clone()
+        .or_else(|| issue.source.clone())
+        .unwrap_or_else(|| "linthis".to_string())
+}
+
+/// Format the run result as a SARIF 2.1.0 log.
+pub fn format_result_sarif(result: &RunResult) -> String {
+    let mut rule_ids: Vec<String> = result.issues.iter().map(sarif_rule_id).collect();
+    rule_ids.sort();
+    rule_ids.dedup();
+    let rules: Vec<_> = rule_ids
+        .iter()
+        .map(|id| serde_json::json!({ "id": id }))
+        .collect();
+
+    let results: Vec<_> = result
+        .issues
+        .iter()
+        .map(|issue| {
+            let level = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info | Severity::Hint => "note",
+            };
+            let mut region = serde_json::json!({ "startLine": issue.line.max(1) });
+            if let Some(column) = issue.column {
+                region["startColumn"] = column.max(1).into();
+            }
+            let mut sarif_result = serde_json::json!({
+                "ruleId": sarif_rule_id(issue),
+                "level": level,
+                "message": { "text": issue.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": issue.file_path.to_string_lossy().replace('\\', "/"),
+                        },
+                        "region": region,
+                    }
+                }],
+            });
+            if let Some(source) = &issue.source {
+                sarif_result["properties"] = serde_json::json!({ "source": source });
+            }
+            sarif_result
+        })
+        .collect();
+
+    let log = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "linthis",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/zhlinh/linthis",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Escape text for an XML attribute or element.
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Format the run result as JUnit XML: a test suite per file with issues and
+/// a failing test case per issue.
+pub fn format_result_junit(result: &RunResult) -> String {
+    let mut by_file: Vec<(&Path, Vec<&LintIssue>)> = Vec::new();
+    for issue in &result.issues {
+        match by_file
+            .iter_mut()
+            .find(|(file, _)| *file == issue.file_path)
+        {
+            Some((_, issues)) => issues.push(issue),
+            None => by_file.push((&issue.file_path, vec![issue])),
+        }
+    }
+
+    let total = result.issues.len();
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(&format!(
+        "<testsuites name=\"linthis\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+        total,
+        total,
+        result.duration_ms as f64 / 1000.0
+    ));
+    for (file, issues) in by_file {
+        let file = xml_escape(&file.display().to_string());
+        output.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+            file,
+            issues.len(),
+            issues.len()
+        ));
+        for issue in issues {
+            let location = match issue.column {
+                Some(column) => format!("{}:{}:{}", file, issue.line, column),
+                None => format!("{}:{}", file, issue.line),
+            };
+            let name = issue
+                .code
+                .as_deref()
+                .or(issue.source.as_deref())
+                .unwrap_or("linthis");
+            output.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\">\n",
+                xml_escape(name),
+                location
+            ));
+            output.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}: {}</failure>\n",
+                xml_escape(&issue.message),
+                issue.severity,
+                location,
+                xml_escape(&issue.message)
+            ));
+            output.push_str("    </testcase>\n");
+        }
+        output.push_str("  </testsuite>\n");
+    }
+    output.push_str("</testsuites>\n");
+    output
+}
+
 /// Format result according to the specified output format.
 pub fn format_result(result: &RunResult, format: OutputFormat) -> String {
-    match format {
-        OutputFormat::Human => format_result_human(result),
-        OutputFormat::Json => format_result_json(result),
-        OutputFormat::GithubActions => format_result_github_actions(result),
-    }
+    format_result_with_options(result, format, &RenderOptions::default())
+}
+
+/// Format result according to the specified output format and render options.
+pub fn format_result_with_options(
+    result: &RunResult,
+    format: OutputFormat,
+    options: &RenderOptions,
+) -> String {
+    render_with(result, format.reporter().as_ref(), options)
+}
+
+/// Render a result with any reporter, applying render options.
+pub fn render_with(result: &RunResult, reporter: &dyn Reporter, options: &RenderOptions) -> String {
+    let styled = options.apply_path_style(result);
+    let result = styled.as_ref().unwrap_or(result);
+    reporter.render_with_options(result, options)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::types::FormatResult;
     use std::path::PathBuf;
 
     #[test]
@@ -353,6 +1341,116 @@ mod tests {
         assert!(output.contains("W0001"));
     }
 
+    #[test]
+    fn test_related_info_rendered_under_issue() {
+        use crate::utils::types::{RelatedInfo, RelatedKind};
+        colored::control::set_override(false);
+
+        let issue = LintIssue::new(
+            PathBuf::from("src/main.rs"),
+            4,
+            "mismatched types".to_string(),
+            Severity::Error,
+        )
+        .with_related(
+            RelatedInfo::new(RelatedKind::Note, "expected because of this".to_string())
+                .with_location(PathBuf::from("src/lib.rs"), 2),
+        )
+        .with_related(RelatedInfo::new(
+            RelatedKind::Help,
+            "try a conversion".to_string(),
+        ));
+
+        let output = format_issue_human(&issue);
+        assert!(output.ends_with(
+            "\n  = note: src/lib.rs:2: expected because of this\n  = help: try a conversion"
+        ));
+
+        let mut result = RunResult::new();
+        result.add_issue(issue);
+        let json: serde_json::Value = serde_json::from_str(&format_result_json(&result)).unwrap();
+        assert_eq!(
+            json["issues"][0]["related"],
+            serde_json::json!([
+                {"kind": "note", "file": "src/lib.rs", "line": 2, "message": "expected because of this"},
+                {"kind": "help", "message": "try a conversion"},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_human_output_groups_issues_by_file() {
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        for (file, line, severity, message) in [
+            ("src/b.rs", 7, Severity::Warning, "unused import"),
+            ("src/a.rs", 9, Severity::Error, "type mismatch"),
+            ("src/b.rs", 2, Severity::Error, "missing semicolon"),
+            ("src/a.rs", 3, Severity::Warning, "dead code"),
+        ] {
+            result.add_issue(
+                LintIssue::new(PathBuf::from(file), line, message.to_string(), severity)
+                    .with_source("clippy".to_string()),
+            );
+        }
+
+        let output = format_result_human(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            &lines[..6],
+            &[
+                "src/a.rs",
+                "  [W1][clippy] 3: warning: dead code",
+                "  [E1][clippy] 9: error: type mismatch",
+                "src/b.rs",
+                "  [E2][clippy] 2: error: missing semicolon",
+                "  [W2][clippy] 7: warning: unused import",
+            ]
+        );
+        assert_eq!(output.matches("src/a.rs").count(), 1);
+    }
+
+    #[test]
+    fn test_human_output_groups_issues_by_rule() {
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        for (file, line, code, severity) in [
+            ("src/b.py", 7, Some("F401"), Severity::Warning),
+            ("src/a.py", 9, Some("E501"), Severity::Warning),
+            ("src/b.py", 2, Some("E501"), Severity::Warning),
+            ("src/a.py", 3, None, Severity::Error),
+            ("src/a.py", 1, Some("F401"), Severity::Warning),
+            ("src/a.py", 4, Some("E501"), Severity::Warning),
+        ] {
+            let mut issue = LintIssue::new(PathBuf::from(file), line, "msg".to_string(), severity);
+            issue.code = code.map(str::to_string);
+            result.add_issue(issue);
+        }
+
+        let options = RenderOptions {
+            group_by_rule: true,
+            ..Default::default()
+        };
+        let output = format_result_human_with_options(&result, &options);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            &lines[..9],
+            &[
+                "E501 (3 issues)",
+                "  [W1] src/a.py:4: warning: msg (E501)",
+                "  [W2] src/a.py:9: warning: msg (E501)",
+                "  [W3] src/b.py:2: warning: msg (E501)",
+                "F401 (2 issues)",
+                "  [W4] src/a.py:1: warning: msg (F401)",
+                "  [W5] src/b.py:7: warning: msg (F401)",
+                "(no code) (1 issue)",
+                "  [E1] src/a.py:3: error: msg",
+            ]
+        );
+    }
+
     #[test]
     fn test_format_issue_github_actions() {
         let issue = LintIssue::new(
@@ -369,4 +1467,659 @@ mod tests {
         assert!(output.contains("line=42"));
         assert!(output.contains("col=10"));
     }
+
+    #[test]
+    fn test_print_severity_filters_rendering_not_counts() {
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        result.add_issue(LintIssue::new(
+            PathBuf::from("src/a.rs"),
+            1,
+            "an error here".to_string(),
+            Severity::Error,
+        ));
+        result.add_issue(LintIssue::new(
+            PathBuf::from("src/b.rs"),
+            2,
+            "a warning here".to_string(),
+            Severity::Warning,
+        ));
+        result.total_files = 2;
+        result.count_files_with_issues();
+
+        let options = RenderOptions {
+            print_severity: Some(Severity::Error),
+            ..Default::default()
+        };
+        let output = format_result_with_options(&result, OutputFormat::Human, &options);
+
+        assert!(output.contains("an error here"));
+        assert!(!output.contains("a warning here"));
+        // Summary counts still include the unprinted warning
+        assert!(output.contains("2 remaining issues"));
+        assert!(output.contains("1 error, 1 warning, 0 info across 2 files"));
+        assert!(output.contains("1 warning"));
+        assert_eq!(result.issues.len(), 2);
+
+        let ga = format_result_with_options(&result, OutputFormat::GithubActions, &options);
+        assert_eq!(ga.lines().count(), 1);
+        assert!(ga.starts_with("::error"));
+    }
+
+    #[test]
+    fn test_footer_counts_by_severity() {
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        for (file, severity) in [
+            ("src/a.rs", Severity::Error),
+            ("src/a.rs", Severity::Error),
+            ("src/b.rs", Severity::Error),
+            ("src/b.rs", Severity::Warning),
+            ("src/c.rs", Severity::Warning),
+            ("src/d.rs", Severity::Info),
+            ("src/e.rs", Severity::Hint),
+        ] {
+            result.add_issue(LintIssue::new(
+                PathBuf::from(file),
+                1,
+                "msg".to_string(),
+                severity,
+            ));
+        }
+        result.total_files = 10;
+        result.duration_ms = 120;
+
+        let footer = format_footer_human(&result);
+        assert_eq!(
+            footer,
+            "✖ 3 errors, 2 warnings, 1 info, 1 hint across 5 files (120ms)"
+        );
+
+        let output = format_result_human(&result);
+        assert!(output.trim_end().ends_with(&footer));
+    }
+
+    #[test]
+    fn test_footer_clean_run() {
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        result.total_files = 1;
+        result.duration_ms = 1500;
+
+        assert_eq!(
+            format_footer_human(&result),
+            "✔ 0 errors, 0 warnings, 0 info across 1 file (1.50s)"
+        );
+    }
+
+    #[test]
+    fn test_three_pass_summary() {
+        use crate::utils::types::RunModeKind;
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        result.total_files = 4;
+        result.issues_before_format = 5;
+        result.files_formatted = 2;
+        result.issues_fixed = 4;
+        result.add_issue(LintIssue::new(
+            PathBuf::from("src/a.rs"),
+            3,
+            "msg".to_string(),
+            Severity::Warning,
+        ));
+
+        let summary = result.three_pass_summary().unwrap();
+        assert_eq!((summary.before, summary.formatted), (5, 2));
+        assert_eq!((summary.fixed, summary.remaining), (4, 1));
+        let line = "Before: 5 issues; Formatted 2 files; Fixed 4 issues; Remaining 1 issue";
+        assert_eq!(render_three_pass_summary(&result).unwrap(), line);
+        assert!(format_result_human(&result).contains(line));
+
+        // Only runs that both check and format tell the story
+        result.run_mode = RunModeKind::CheckOnly;
+        assert!(render_three_pass_summary(&result).is_none());
+        assert!(!format_result_human(&result).contains("Before:"));
+    }
+
+    #[test]
+    fn test_format_check_output_distinct_from_lint_issues() {
+        use crate::utils::types::RunModeKind;
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        result.run_mode = RunModeKind::FormatCheck;
+        result.total_files = 3;
+        result.unformatted_files = vec![PathBuf::from("src/a.rs"), PathBuf::from("src/b.rs")];
+
+        let output = format_result_human(&result);
+        assert!(output.contains("[F1] src/a.rs not formatted"));
+        assert!(output.contains("2 of 3 files not formatted"));
+        assert!(output.contains("0 errors, 0 warnings, 0 info, 2 unformatted across 2 files"));
+
+        let github = format_result_github_actions(&result);
+        assert!(github.contains("::error file=src/b.rs::File is not formatted"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_result(&result, OutputFormat::FormatCheckJson)).unwrap();
+        assert_eq!(json["files"], serde_json::json!(["src/a.rs", "src/b.rs"]));
+        assert_eq!(json["all_formatted"], false);
+    }
+
+    #[test]
+    fn test_quiet_if_clean_suppresses_only_clean_runs() {
+        let options = RenderOptions {
+            quiet_if_clean: true,
+            ..Default::default()
+        };
+
+        let mut clean = RunResult::new();
+        clean.total_files = 3;
+        clean.calculate_exit_code();
+        assert!(options.suppress_output(&clean));
+
+        let mut dirty = RunResult::new();
+        dirty.total_files = 3;
+        dirty.add_issue(LintIssue::new(
+            PathBuf::from("a.rs"),
+            1,
+            "bad".to_string(),
+            Severity::Error,
+        ));
+        dirty.calculate_exit_code();
+        assert!(!options.suppress_output(&dirty));
+
+        // Issues that don't fail the run are still shown
+        let mut warned = RunResult::new();
+        warned.add_issue(LintIssue::new(
+            PathBuf::from("a.rs"),
+            1,
+            "meh".to_string(),
+            Severity::Warning,
+        ));
+        warned.calculate_exit_code();
+        assert_eq!(warned.exit_code, 0);
+        assert!(!options.suppress_output(&warned));
+
+        // Without the flag nothing is suppressed
+        assert!(!RenderOptions::default().suppress_output(&clean));
+    }
+
+    #[test]
+    fn test_path_style_relative_and_absolute() {
+        let root = std::env::temp_dir().join("linthis-project");
+        let mut result = RunResult::new();
+        result.add_issue(LintIssue::new(
+            root.join("src/./main.rs"),
+            3,
+            "unused import".to_string(),
+            Severity::Error,
+        ));
+        result.unformatted_files.push(root.join("lib/util.py"));
+        // Paths outside the project stay absolute
+        let outside = std::env::temp_dir().join("elsewhere/gen.rs");
+        result.add_issue(LintIssue::new(
+            outside.clone(),
+            1,
+            "generated".to_string(),
+            Severity::Error,
+        ));
+
+        let relative = RenderOptions {
+            project_root: Some(root.clone()),
+            ..Default::default()
+        };
+        let styled = relative.apply_path_style(&result).unwrap();
+        assert_eq!(styled.issues[0].file_path, PathBuf::from("src/main.rs"));
+        assert_eq!(styled.unformatted_files[0], PathBuf::from("lib/util.py"));
+        assert_eq!(styled.issues[1].file_path, outside);
+
+        let github = format_result_with_options(&result, OutputFormat::GithubActions, &relative);
+        assert!(github.contains("file=src/main.rs,line=3"));
+
+        let absolute = RenderOptions {
+            path_style: PathStyle::Absolute,
+            project_root: Some(root.clone()),
+            ..Default::default()
+        };
+        let styled = absolute.apply_path_style(&result).unwrap();
+        assert_eq!(styled.issues[0].file_path, root.join("src/main.rs"));
+        // Relative paths are resolved from the working directory
+        assert_eq!(
+            absolute.style_path(Path::new("./a.rs")),
+            std::env::current_dir().unwrap().join("a.rs")
+        );
+
+        let json = format_result_with_options(&result, OutputFormat::Json, &absolute);
+        assert!(json.contains(&root.join("lib/util.py").display().to_string()));
+
+        // Without a root, paths are printed as reported
+        assert!(RenderOptions::default().apply_path_style(&result).is_none());
+        assert_eq!(PathStyle::parse("ABSOLUTE"), Some(PathStyle::Absolute));
+        assert_eq!(PathStyle::parse("short"), None);
+    }
+
+    #[test]
+    fn test_show_diff_renders_format_diffs() {
+        colored::control::set_override(false);
+
+        let mut result = RunResult::new();
+        result.add_format_result(FormatResult::changed(PathBuf::from("src/a.py")).with_diff(
+            "--- a/src/a.py\n+++ b/src/a.py\n@@ -1,2 +1,2 @@\n-x=1\n+x = 1\n y = 2\n".to_string(),
+        ));
+
+        let hidden = format_result_human(&result);
+        assert!(!hidden.contains("+x = 1"));
+
+        let options = RenderOptions {
+            show_diff: true,
+            ..Default::default()
+        };
+        let output = format_result_human_with_options(&result, &options);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            &lines[..6],
+            &[
+                "--- a/src/a.py",
+                "+++ b/src/a.py",
+                "@@ -1,2 +1,2 @@",
+                "-x=1",
+                "+x = 1",
+                " y = 2",
+            ]
+        );
+
+        let long: String = (0..10).map(|i| format!("+line {}\n", i)).collect();
+        let truncated = format_diff_human(&long, 4);
+        assert_eq!(truncated.lines().count(), 5);
+        assert!(truncated.ends_with("... +6 more lines\n"));
+    }
+
+    #[test]
+    fn test_redact_home_in_output_paths() {
+        let home = std::env::temp_dir().join("home/alice");
+        let mut result = RunResult::new();
+        result.add_issue(
+            LintIssue::new(
+                home.join("src/main.rs"),
+                3,
+                "unused import".to_string(),
+                Severity::Error,
+            )
+            .with_config_path(home.join("ruff.toml")),
+        );
+        let elsewhere = std::env::temp_dir().join("srv/app.rs");
+        result.unformatted_files.push(elsewhere.clone());
+
+        let options = RenderOptions {
+            redact_home: Some(home.clone()),
+            ..Default::default()
+        };
+        let styled = options.apply_path_style(&result).unwrap();
+        assert_eq!(styled.issues[0].file_path, PathBuf::from("~/src/main.rs"));
+        assert_eq!(
+            styled.issues[0].config_path,
+            Some(PathBuf::from("~/ruff.toml"))
+        );
+        assert_eq!(styled.unformatted_files[0], elsewhere);
+
+        let json = format_result_with_options(&result, OutputFormat::Json, &options);
+        assert!(json.contains("~/src/main.rs"));
+        assert!(!json.contains(&home.display().to_string()));
+    }
+
+    /// Custom reporter emitting one unescaped CSV row per issue
+    struct PlainCsvReporter;
+
+    impl Reporter for PlainCsvReporter {
+        fn render(&self, result: &RunResult) -> String {
+            let mut out = String::from("file,line,severity,message\n");
+            for issue in &result.issues {
+                out.push_str(&format!(
+                    "{},{},{},{}\n",
+                    issue.file_path.display(),
+                    issue.line,
+                    issue.severity,
+                    issue.message
+                ));
+            }
+            out
+        }
+    }
+
+    #[test]
+    fn test_custom_reporter_csv() {
+        let root = std::env::temp_dir().join("linthis-project");
+        let mut result = RunResult::new();
+        result.add_issue(LintIssue::new(
+            root.join("src/main.rs"),
+            3,
+            "unused import".to_string(),
+            Severity::Error,
+        ));
+        result.add_issue(LintIssue::new(
+            root.join("src/lib.rs"),
+            7,
+            "missing docs".to_string(),
+            Severity::Info,
+        ));
+
+        let options = RenderOptions {
+            print_severity: Some(Severity::Warning),
+            project_root: Some(root),
+            ..Default::default()
+        };
+        let csv = render_with(&result, &PlainCsvReporter, &options);
+        assert_eq!(
+            csv,
+            "file,line,severity,message\nsrc/main.rs,3,error,unused import\n"
+        );
+
+        // Built-in formats are reporters too
+        assert!(OutputFormat::parse("xml").is_none());
+        let json = OutputFormat::parse("json").unwrap();
+        assert_eq!(
+            render_with(&result, json.as_ref(), &RenderOptions::default()),
+            format_result(&result, OutputFormat::Json)
+        );
+    }
+
+    #[test]
+    fn test_format_result_csv_escapes_fields() {
+        let mut result = RunResult::new();
+        result.add_issue(
+            LintIssue::new(
+                PathBuf::from("src/app.py"),
+                4,
+                "expected \"self\", got cls, or none".to_string(),
+                Severity::Warning,
+            )
+            .with_code("N805".to_string())
+            .with_source("ruff".to_string())
+            .with_column(9),
+        );
+        result.add_issue(LintIssue::new(
+            PathBuf::from("src/lib.rs"),
+            1,
+            "missing docs".to_string(),
+            Severity::Info,
+        ));
+
+        let csv = format_result(&result, OutputFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "file,line,column,severity,code,source,message");
+        assert_eq!(
+            lines[1],
+            "src/app.py,4,9,warning,N805,ruff,\"expected \"\"self\"\", got cls, or none\""
+        );
+        assert_eq!(lines[2], "src/lib.rs,1,,info,,,missing docs");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert!(OutputFormat::parse("CSV").is_some());
+    }
+
+    fn sample_result() -> RunResult {
+        let mut result = RunResult::new();
+        result.add_issue(
+            LintIssue::new(
+                PathBuf::from("src/app.py"),
+                4,
+                "use \"self\" & <cls>".to_string(),
+                Severity::Warning,
+            )
+            .with_code("N805".to_string())
+            .with_source("ruff".to_string())
+            .with_column(9),
+        );
+        result.add_issue(
+            LintIssue::new(
+                PathBuf::from("src/app.py"),
+                10,
+                "unused import".to_string(),
+                Severity::Error,
+            )
+            .with_source("ruff".to_string()),
+        );
+        result.add_issue(LintIssue::new(
+            PathBuf::from("src/lib.rs"),
+            0,
+            "missing docs".to_string(),
+            Severity::Info,
+        ));
+        result
+    }
+
+    #[test]
+    fn test_ci_output_format() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            ci_output_format(env(&[("CI", "true"), ("GITHUB_ACTIONS", "true")])),
+            Some(OutputFormat::GithubActions)
+        );
+        assert_eq!(
+            ci_output_format(env(&[("GITLAB_CI", "true")])),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(
+            ci_output_format(env(&[("CI", "1")])),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(ci_output_format(env(&[("CI", "false")])), None);
+        assert_eq!(ci_output_format(env(&[])), None);
+    }
+
+    #[test]
+    fn test_every_reporter_renders_sample_result() {
+        colored::control::set_override(false);
+        let result = sample_result();
+        for format in [
+            OutputFormat::Human,
+            OutputFormat::Json,
+            OutputFormat::GithubActions,
+            OutputFormat::Csv,
+            OutputFormat::Sarif,
+            OutputFormat::Junit,
+        ] {
+            let output = format_result(&result, format);
+            assert!(!output.trim().is_empty(), "{:?} rendered nothing", format);
+            assert!(output.contains("src/app.py"), "{:?}", format);
+            if matches!(format, OutputFormat::Json | OutputFormat::Sarif) {
+                serde_json::from_str::<serde_json::Value>(&output).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_result_sarif() {
+        let sarif: serde_json::Value =
+            serde_json::from_str(&format_result(&sample_result(), OutputFormat::Sarif)).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "linthis");
+        let rule_ids: Vec<_> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rule_ids, ["N805", "linthis", "ruff"]);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[0]["ruleId"], "N805");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/app.py");
+        assert_eq!(location["region"]["startLine"], 4);
+        assert_eq!(location["region"]["startColumn"], 9);
+        assert_eq!(results[1]["ruleId"], "ruff");
+        // SARIF lines start at 1
+        assert_eq!(results[2]["level"], "note");
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            1
+        );
+    }
+
+    #[test]
+    fn test_format_result_junit() {
+        let xml = format_result(&sample_result(), OutputFormat::Junit);
+        let lines: Vec<&str> = xml.lines().collect();
+        assert_eq!(lines[0], "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        assert_eq!(
+            lines[1],
+            "<testsuites name=\"linthis\" tests=\"3\" failures=\"3\" time=\"0.000\">"
+        );
+        assert_eq!(
+            lines[2],
+            "  <testsuite name=\"src/app.py\" tests=\"2\" failures=\"2\" errors=\"0\">"
+        );
+        assert_eq!(
+            lines[3],
+            "    <testcase name=\"N805\" classname=\"src/app.py:4:9\">"
+        );
+        assert_eq!(
+            lines[4],
+            "      <failure message=\"use &quot;self&quot; &amp; &lt;cls&gt;\" type=\"warning\">\
+             src/app.py:4:9: use &quot;self&quot; &amp; &lt;cls&gt;</failure>"
+        );
+        assert_eq!(xml.matches("<testsuite ").count(), 2);
+        assert_eq!(lines.last(), Some(&"</testsuites>"));
+
+        let empty = format_result(&RunResult::new(), OutputFormat::Junit);
+        assert!(empty.contains("tests=\"0\" failures=\"0\""));
+    }
+
+    fn counted_result() -> RunResult {
+        let mut result = RunResult::new();
+        for (file, severity) in [
+            ("a.rs", Severity::Error),
+            ("a.rs", Severity::Warning),
+            ("b.rs", Severity::Warning),
+            ("c.rs", Severity::Info),
+            ("c.rs", Severity::Hint),
+        ] {
+            result.add_issue(LintIssue::new(
+                PathBuf::from(file),
+                1,
+                "msg".to_string(),
+                severity,
+            ));
+        }
+        result.add_format_result(crate::utils::types::FormatResult::changed(PathBuf::from(
+            "d.rs",
+        )));
+        result
+    }
+
+    #[test]
+    fn test_format_counts_human() {
+        let output = format_counts_human(&counted_result());
+        assert_eq!(
+            output,
+            "issues: 5\nerrors: 1\nwarnings: 2\ninfo: 1\nhints: 1\nfiles_affected: 3\nfiles_formatted: 1"
+        );
+    }
+
+    #[test]
+    fn test_format_counts_json() {
+        let result = counted_result();
+        let json: serde_json::Value = serde_json::from_str(&format_counts_json(&result)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "issues": 5,
+                "errors": 1,
+                "warnings": 2,
+                "info": 1,
+                "hints": 1,
+                "files_affected": 3,
+                "files_formatted": 1
+            })
+        );
+
+        // Print filters don't change the counts
+        let options = RenderOptions {
+            print_severity: Some(Severity::Error),
+            ..Default::default()
+        };
+        let reporter = CountReporter { json: true };
+        assert_eq!(
+            render_with(&result, &reporter, &options),
+            format_counts_json(&result)
+        );
+    }
+
+    fn fix_run_result() -> RunResult {
+        let mut result = RunResult::new();
+        result.add_issue(
+            LintIssue::new(
+                PathBuf::from("a.py"),
+                2,
+                "line too long".to_string(),
+                Severity::Error,
+            )
+            .with_code("E501".to_string()),
+        );
+        result.fixed_issues.push(
+            LintIssue::new(
+                PathBuf::from("a.py"),
+                3,
+                "trailing whitespace".to_string(),
+                Severity::Warning,
+            )
+            .with_code("W291".to_string()),
+        );
+        result.issues_fixed = 1;
+        result
+    }
+
+    #[test]
+    fn test_only_fixed_renders_fixed_issues() {
+        let result = fix_run_result();
+        let options = RenderOptions {
+            fix_filter: Some(FixFilter::Fixed),
+            ..Default::default()
+        };
+        let human = render_with(&result, &HumanReporter, &options);
+        assert!(human.contains("trailing whitespace"));
+        assert!(!human.contains("line too long"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_with(&result, &JsonReporter, &options)).unwrap();
+        assert_eq!(json["issues"].as_array().unwrap().len(), 1);
+        assert_eq!(json["issues"][0]["code"], "W291");
+        assert!(json.get("fixed_issues").is_none());
+        assert_eq!(json["issues_fixed"], 1);
+    }
+
+    #[test]
+    fn test_only_unfixed_renders_remaining_issues() {
+        let result = fix_run_result();
+        let options = RenderOptions {
+            fix_filter: Some(FixFilter::Unfixed),
+            ..Default::default()
+        };
+        let github = render_with(&result, &GithubActionsReporter, &options);
+        assert!(github.contains("line too long"));
+        assert!(!github.contains("trailing whitespace"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_with(&result, &JsonReporter, &options)).unwrap();
+        assert_eq!(json["issues"][0]["code"], "E501");
+        assert!(json.get("fixed_issues").is_none());
+
+        // Without a filter both lists are serialized
+        let json: serde_json::Value =
+            serde_json::from_str(&format_result(&result, OutputFormat::Json)).unwrap();
+        assert_eq!(json["fixed_issues"][0]["code"], "W291");
+    }
 }
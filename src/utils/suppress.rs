@@ -0,0 +1,242 @@
+// Copyright 2024 zhlinh and linthis Project Authors. All rights reserved.
+// Use of this source code is governed by a MIT-style
+// license that can be found at
+//
+// https://opensource.org/license/MIT
+//
+// The above copyright notice and this permission
+// notice shall be included in all copies or
+// substantial portions of the Software.
+
+//! Inline suppression markers (`# noqa`, `// NOLINT`, `// eslint-disable-line`).
+//!
+//! Each tool honors its own markers; this filter applies the same markers to
+//! every issue on the line, including linthis's built-in checks.
+
+use crate::utils::types::LintIssue;
+use crate::Language;
+
+/// An inline suppression marker
+struct Marker {
+    text: &'static str,
+    /// Applies to the following line instead of its own
+    next_line: bool,
+    /// Codes follow after whitespace (eslint) rather than `:` or `(...)`
+    spaced_codes: bool,
+}
+
+const PYTHON_MARKERS: &[Marker] = &[Marker {
+    text: "# noqa",
+    next_line: false,
+    spaced_codes: false,
+}];
+
+const CPP_MARKERS: &[Marker] = &[
+    Marker {
+        text: "NOLINTNEXTLINE",
+        next_line: true,
+        spaced_codes: false,
+    },
+    Marker {
+        text: "NOLINT",
+        next_line: false,
+        spaced_codes: false,
+    },
+];
+
+const ESLINT_MARKERS: &[Marker] = &[
+    Marker {
+        text: "eslint-disable-next-line",
+        next_line: true,
+        spaced_codes: true,
+    },
+    Marker {
+        text: "eslint-disable-line",
+        next_line: false,
+        spaced_codes: true,
+    },
+];
+
+const GO_MARKERS: &[Marker] = &[Marker {
+    text: "//nolint",
+    next_line: false,
+    spaced_codes: false,
+}];
+
+/// Suppression markers recognized for a language.
+fn markers(lang: Language) -> &'static [Marker] {
+    match lang {
+        Language::Python => PYTHON_MARKERS,
+        Language::Cpp | Language::ObjectiveC => CPP_MARKERS,
+        Language::TypeScript | Language::JavaScript => ESLINT_MARKERS,
+        Language::Go => GO_MARKERS,
+        Language::Rust | Language::Java => &[],
+    }
+}
+
+/// Codes listed after a marker; empty means every code is suppressed.
+///
+/// Handles `# noqa: E501,W291`, `NOLINT(check-a, check-b)`,
+/// `//nolint:errcheck` and `eslint-disable-line no-console, semi`.
+fn marker_codes(rest: &str, spaced: bool) -> Vec<String> {
+    let list = if let Some(rest) = rest.strip_prefix(':') {
+        rest.split_whitespace().next().unwrap_or_default()
+    } else if let Some(rest) = rest.strip_prefix('(') {
+        rest.split(')').next().unwrap_or_default()
+    } else if spaced {
+        // eslint rule list, up to a `--` description
+        rest.split("--").next().unwrap_or_default()
+    } else {
+        ""
+    };
+    list.split([',', ' '])
+        .map(str::trim)
+        .filter(|code| !code.is_empty() && !code.starts_with('*'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Codes suppressed on `line` by a marker on `marker_line`, or None if the
+/// marker line has no applicable marker.
+fn suppressed_codes(lang: Language, marker_line: &str, next_line: bool) -> Option<Vec<String>> {
+    markers(lang)
+        .iter()
+        .filter(|marker| marker.next_line == next_line)
+        .find_map(|marker| {
+            let (_, rest) = marker_line.split_once(marker.text)?;
+            // NOLINT must not match NOLINTNEXTLINE
+            if rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '-') {
+                return None;
+            }
+            Some(marker_codes(rest, marker.spaced_codes))
+        })
+}
+
+/// Whether an issue is suppressed by a marker on its line (or the line above).
+pub fn is_suppressed(issue: &LintIssue, lines: &[&str]) -> bool {
+    let Some(lang) = issue.language else {
+        return false;
+    };
+    if issue.is_tool_failure() || issue.line == 0 {
+        return false;
+    }
+    let same_line = lines.get(issue.line - 1).map(|l| (*l, false));
+    let previous_line = issue
+        .line
+        .checked_sub(2)
+        .and_then(|idx| lines.get(idx))
+        .map(|l| (*l, true));
+
+    same_line
+        .into_iter()
+        .chain(previous_line)
+        .filter_map(|(line, next_line)| suppressed_codes(lang, line, next_line))
+        .any(|codes| {
+            codes.is_empty()
+                || issue
+                    .code
+                    .as_ref()
+                    .is_some_and(|code| codes.iter().any(|c| c == code))
+        })
+}
+
+/// Drop issues suppressed by inline markers in `content`.
+pub fn filter_suppressed(issues: &mut Vec<LintIssue>, content: &str) {
+    let lines: Vec<&str> = content.lines().collect();
+    issues.retain(|issue| !is_suppressed(issue, &lines));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::types::Severity;
+    use std::path::PathBuf;
+
+    fn issue(lang: Language, line: usize, code: &str) -> LintIssue {
+        let mut issue = LintIssue::new(
+            PathBuf::from("file"),
+            line,
+            "message".to_string(),
+            Severity::Warning,
+        )
+        .with_code(code.to_string());
+        issue.language = Some(lang);
+        issue
+    }
+
+    fn kept(content: &str, issues: Vec<LintIssue>) -> Vec<(usize, String)> {
+        let mut issues = issues;
+        filter_suppressed(&mut issues, content);
+        issues
+            .into_iter()
+            .map(|i| (i.line, i.code.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_noqa() {
+        let content = "import os  # noqa\nx = 1  # noqa: E501, W291\ny = 2\n";
+        let issues = vec![
+            issue(Language::Python, 1, "F401"),
+            issue(Language::Python, 2, "E501"),
+            issue(Language::Python, 2, "trailing-whitespace"),
+            issue(Language::Python, 3, "E501"),
+        ];
+        assert_eq!(
+            kept(content, issues),
+            [
+                (2, "trailing-whitespace".to_string()),
+                (3, "E501".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nolint() {
+        let content = "int a;  // NOLINT\n\
+                       long b;  // NOLINT(google-runtime-int)\n\
+                       // NOLINTNEXTLINE\n\
+                       int c;\n";
+        let issues = vec![
+            issue(Language::Cpp, 1, "todo"),
+            issue(Language::Cpp, 2, "google-runtime-int"),
+            issue(Language::Cpp, 2, "max-line-length"),
+            issue(Language::Cpp, 3, "todo"),
+            issue(Language::Cpp, 4, "todo"),
+        ];
+        // NOLINTNEXTLINE suppresses the next line, not its own
+        assert_eq!(
+            kept(content, issues),
+            [(2, "max-line-length".to_string()), (3, "todo".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_eslint_disable_line() {
+        let content = "console.log(x); // eslint-disable-line no-console -- debug\n\
+                       var y; // eslint-disable-line\n\
+                       // eslint-disable-next-line semi\n\
+                       let z = 1\n";
+        let issues = vec![
+            issue(Language::JavaScript, 1, "no-console"),
+            issue(Language::JavaScript, 1, "semi"),
+            issue(Language::JavaScript, 2, "no-var"),
+            issue(Language::JavaScript, 4, "semi"),
+            issue(Language::JavaScript, 4, "prefer-const"),
+        ];
+        assert_eq!(
+            kept(content, issues),
+            [(1, "semi".to_string()), (4, "prefer-const".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_markers_are_per_language() {
+        // `# noqa` means nothing in C++, and issues without a language are kept
+        let content = "int a;  # noqa\n";
+        let mut untagged = issue(Language::Cpp, 1, "todo");
+        untagged.language = None;
+        let issues = vec![issue(Language::Cpp, 1, "todo"), untagged];
+        assert_eq!(kept(content, issues).len(), 2);
+    }
+}
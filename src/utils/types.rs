@@ -10,9 +10,25 @@
 
 //! Core types for linthis results and configuration.
 
-use crate::Language;
+use crate::{exit_code, Language, LintisError};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Codes that always mean real breakage (syntax, undefined names, compile
+/// errors), per language, as `[severity_overrides]` keys. Issues matching
+/// them are errors however the tool reports them; user overrides still win.
+pub const DEFAULT_ERROR_CODES: &[(Language, &[&str])] = &[
+    // Syntax and IO errors, invalid comparisons, misplaced statements, undefined names
+    (
+        Language::Python,
+        &["ruff:E9*", "ruff:F63*", "ruff:F7*", "ruff:F82*"],
+    ),
+    // rustc error codes (E0308 mismatched types, ...)
+    (Language::Rust, &["clippy:E*"]),
+    (Language::Cpp, &["clang-tidy:clang-diagnostic-error"]),
+    (Language::ObjectiveC, &["clang-tidy:clang-diagnostic-error"]),
+];
 
 /// Issue severity levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +37,36 @@ pub enum Severity {
     Error,
     Warning,
     Info,
+    /// Tool notes and hints (e.g. clippy `note:`); never fails a run
+    Hint,
+}
+
+impl Severity {
+    /// Parse a severity name (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "error" => Some(Severity::Error),
+            "warning" | "warn" => Some(Severity::Warning),
+            "info" => Some(Severity::Info),
+            "hint" | "note" => Some(Severity::Hint),
+            _ => None,
+        }
+    }
+
+    /// Rank used for threshold comparisons (higher is more severe).
+    pub fn rank(&self) -> u8 {
+        match self {
+            Severity::Error => 3,
+            Severity::Warning => 2,
+            Severity::Info => 1,
+            Severity::Hint => 0,
+        }
+    }
+
+    /// Check if this severity is at or above the given threshold.
+    pub fn is_at_least(&self, threshold: Severity) -> bool {
+        self.rank() >= threshold.rank()
+    }
 }
 
 impl std::fmt::Display for Severity {
@@ -29,10 +75,65 @@ impl std::fmt::Display for Severity {
             Severity::Error => write!(f, "error"),
             Severity::Warning => write!(f, "warning"),
             Severity::Info => write!(f, "info"),
+            Severity::Hint => write!(f, "hint"),
+        }
+    }
+}
+
+/// Issue code used when a tool crashed or failed instead of reporting findings
+pub const TOOL_FAILURE_CODE: &str = "tool-failure";
+
+/// Issue code for files a second format pass still changed (`--verify-idempotent`)
+pub const NON_IDEMPOTENT_FORMAT_CODE: &str = "non-idempotent-format";
+
+/// Kind of a message attached to an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelatedKind {
+    Note,
+    Help,
+}
+
+impl std::fmt::Display for RelatedKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelatedKind::Note => write!(f, "note"),
+            RelatedKind::Help => write!(f, "help"),
         }
     }
 }
 
+/// A note or help message a tool attached to an issue, such as clippy's
+/// "note: expected because of this" or a clang-tidy note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedInfo {
+    pub kind: RelatedKind,
+    /// File the message points at, if it has a location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    /// Line number (1-indexed), if it has a location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl RelatedInfo {
+    pub fn new(kind: RelatedKind, message: String) -> Self {
+        Self {
+            kind,
+            file: None,
+            line: None,
+            message,
+        }
+    }
+
+    pub fn with_location(mut self, file: PathBuf, line: usize) -> Self {
+        self.file = Some(file);
+        self.line = Some(line);
+        self
+    }
+}
+
 /// A single lint issue found in a file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LintIssue {
@@ -56,6 +157,11 @@ pub struct LintIssue {
     pub language: Option<Language>,
     /// The source code line where the issue occurs (optional)
     pub code_line: Option<String>,
+    /// Config file the linter used for this file, if known (verbose runs only)
+    pub config_path: Option<PathBuf>,
+    /// Notes and help messages the tool attached to this issue
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedInfo>,
 }
 
 impl LintIssue {
@@ -71,9 +177,48 @@ impl LintIssue {
             source: None,
             language: None,
             code_line: None,
+            config_path: None,
+            related: Vec::new(),
         }
     }
 
+    /// Whether this issue reports a tool failure rather than a finding
+    pub fn is_tool_failure(&self) -> bool {
+        self.code.as_deref() == Some(TOOL_FAILURE_CODE)
+    }
+
+    /// Severity configured for this issue's rule in `[severity_overrides]`.
+    ///
+    /// Keys are a code (`"E501"`) or a code scoped to a source
+    /// (`"cpplint:build/header_guard"`); a trailing `*` matches codes by
+    /// prefix (`"cpplint:build/*"`). The most specific matching key wins:
+    /// source-scoped before unscoped, exact before prefix, longer prefix
+    /// before shorter.
+    pub fn severity_override(&self, overrides: &HashMap<String, Severity>) -> Option<Severity> {
+        let code = self.code.as_deref();
+        overrides
+            .iter()
+            .filter_map(|(key, severity)| {
+                let (source, pattern) = match key.split_once(':') {
+                    Some((source, pattern)) => (Some(source), pattern),
+                    None => (None, key.as_str()),
+                };
+                if source.is_some() && source != self.source.as_deref() {
+                    return None;
+                }
+                let exact = match pattern.strip_suffix('*') {
+                    Some("") => false,
+                    Some(prefix) if code?.starts_with(prefix) => false,
+                    Some(_) => return None,
+                    None if code? == pattern => true,
+                    None => return None,
+                };
+                Some(((source.is_some(), exact, pattern.len()), *severity))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, severity)| severity)
+    }
+
     pub fn with_column(mut self, column: usize) -> Self {
         self.column = Some(column);
         self
@@ -103,6 +248,16 @@ impl LintIssue {
         self.code_line = Some(code_line);
         self
     }
+
+    pub fn with_config_path(mut self, config_path: PathBuf) -> Self {
+        self.config_path = Some(config_path);
+        self
+    }
+
+    pub fn with_related(mut self, related: RelatedInfo) -> Self {
+        self.related.push(related);
+        self
+    }
 }
 
 /// Result of formatting a single file
@@ -160,6 +315,29 @@ pub enum RunModeKind {
     Both,
     CheckOnly,
     FormatOnly,
+    FormatCheck,
+}
+
+/// Wall-clock time spent on a single file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTiming {
+    /// File path
+    pub path: PathBuf,
+    /// Time spent checking/formatting the file in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Lint → format → lint counts of a check-and-format run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreePassSummary {
+    /// Issues found by the first lint pass
+    pub before: usize,
+    /// Files changed by the format pass
+    pub formatted: usize,
+    /// First-pass issues gone after formatting
+    pub fixed: usize,
+    /// Issues found by the final lint pass
+    pub remaining: usize,
 }
 
 /// Aggregated result of a linthis run
@@ -177,14 +355,41 @@ pub struct RunResult {
     pub issues_before_format: usize,
     /// Issues fixed by formatting
     pub issues_fixed: usize,
+    /// Issues newly introduced by formatting
+    #[serde(default)]
+    pub issues_introduced: usize,
+    /// First-pass issues that formatting fixed (as reported before formatting)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed_issues: Vec<LintIssue>,
     /// All format results
     pub format_results: Vec<FormatResult>,
+    /// Number of files whose imports were organized (`organize_imports`)
+    #[serde(default)]
+    pub imports_organized: usize,
+    /// Results of the import-organizing pass, kept apart from `format_results`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub import_results: Vec<FormatResult>,
     /// Total execution time in milliseconds
     pub duration_ms: u64,
     /// Exit code: 0 = success, 1 = issues found, 2 = error
     pub exit_code: i32,
     /// Run mode for appropriate output messages
     pub run_mode: RunModeKind,
+    /// Files that would be changed by formatting (format-check mode)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unformatted_files: Vec<PathBuf>,
+    /// Slowest files, sorted descending (only recorded with `--slowest N`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slowest_files: Vec<FileTiming>,
+    /// The run stopped at the first tool error (`--fail-fast`)
+    #[serde(default)]
+    pub aborted: bool,
+    /// Linters and formatters that were needed but not available
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_tools: Vec<String>,
+    /// Issues hidden because the baseline accepts them
+    #[serde(default)]
+    pub baselined_issues: usize,
 }
 
 impl RunResult {
@@ -203,6 +408,41 @@ impl RunResult {
         self.format_results.push(result);
     }
 
+    pub fn add_import_result(&mut self, result: FormatResult) {
+        if result.changed {
+            self.imports_organized += 1;
+        }
+        self.import_results.push(result);
+    }
+
+    /// The three-pass counts, for runs that both check and format
+    pub fn three_pass_summary(&self) -> Option<ThreePassSummary> {
+        (self.run_mode == RunModeKind::Both).then_some(ThreePassSummary {
+            before: self.issues_before_format,
+            formatted: self.files_formatted,
+            fixed: self.issues_fixed,
+            remaining: self.issues.len(),
+        })
+    }
+
+    /// Number of warning issues
+    pub fn warning_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+            .count()
+    }
+
+    /// Fail an otherwise passing run with more than `max_warnings` warnings
+    /// (`--max-warnings`; None = unlimited).
+    pub fn apply_max_warnings(&mut self, max_warnings: Option<usize>) {
+        if self.exit_code == exit_code::SUCCESS
+            && max_warnings.is_some_and(|max| self.warning_count() > max)
+        {
+            self.exit_code = exit_code::ISSUES;
+        }
+    }
+
     /// Calculate exit code based on results
     pub fn calculate_exit_code(&mut self) {
         self.calculate_exit_code_with_warnings(false);
@@ -210,25 +450,146 @@ impl RunResult {
 
     /// Calculate exit code based on results, with option to fail on warnings
     pub fn calculate_exit_code_with_warnings(&mut self, fail_on_warnings: bool) {
-        let has_errors = self.issues.iter().any(|i| i.severity == Severity::Error);
-        let has_warnings = self.issues.iter().any(|i| i.severity == Severity::Warning);
-        let has_format_errors = self.format_results.iter().any(|r| r.error.is_some());
-
-        if has_format_errors {
-            self.exit_code = 2;
-        } else if has_errors || (fail_on_warnings && has_warnings) {
-            self.exit_code = 1;
+        self.calculate_exit_code_with_thresholds(fail_on_warnings, &HashMap::new());
+    }
+
+    /// Calculate exit code based on results, with per-language thresholds.
+    ///
+    /// An issue fails the run when its severity is at or above the threshold
+    /// for its language. Languages without a threshold (and issues without a
+    /// language) fail on errors, or on warnings with `fail_on_warnings`.
+    /// Hints never fail the run.
+    pub fn calculate_exit_code_with_thresholds(
+        &mut self,
+        fail_on_warnings: bool,
+        thresholds: &HashMap<Language, Severity>,
+    ) {
+        let default_threshold = if fail_on_warnings {
+            Severity::Warning
         } else {
-            self.exit_code = 0;
+            Severity::Error
+        };
+        let has_failing_issues = self.issues.iter().any(|i| {
+            let threshold = i
+                .language
+                .and_then(|lang| thresholds.get(&lang).copied())
+                .unwrap_or(default_threshold);
+            i.severity != Severity::Hint && i.severity.is_at_least(threshold)
+        });
+        let has_format_errors = self
+            .format_results
+            .iter()
+            .chain(&self.import_results)
+            .any(|r| r.error.is_some());
+        let has_tool_failures = self.issues.iter().any(LintIssue::is_tool_failure);
+        let has_unformatted = !self.unformatted_files.is_empty();
+
+        self.exit_code = if has_format_errors || has_tool_failures {
+            exit_code::TOOL_ERROR
+        } else if has_failing_issues || has_unformatted {
+            exit_code::ISSUES
+        } else {
+            exit_code::SUCCESS
+        };
+    }
+
+    /// Escalate issues matching [`DEFAULT_ERROR_CODES`] for their language
+    /// to errors.
+    pub fn apply_default_error_codes(&mut self) {
+        let tables: HashMap<Language, HashMap<String, Severity>> = DEFAULT_ERROR_CODES
+            .iter()
+            .map(|(lang, codes)| {
+                let codes = codes.iter().map(|c| (c.to_string(), Severity::Error));
+                (*lang, codes.collect())
+            })
+            .collect();
+        for issue in self.issues.iter_mut().chain(&mut self.fixed_issues) {
+            let language = issue
+                .language
+                .or_else(|| Language::from_path(&issue.file_path));
+            let table = language.and_then(|lang| tables.get(&lang));
+            if let Some(severity) = table.and_then(|t| issue.severity_override(t)) {
+                issue.severity = severity;
+            }
+        }
+    }
+
+    /// Rewrite issue severities by `[severity_overrides]`
+    /// (see [`LintIssue::severity_override`]).
+    pub fn apply_severity_overrides(&mut self, overrides: &HashMap<String, Severity>) {
+        if overrides.is_empty() {
+            return;
+        }
+        for issue in self.issues.iter_mut().chain(&mut self.fixed_issues) {
+            if let Some(severity) = issue.severity_override(overrides) {
+                issue.severity = severity;
+            }
         }
     }
 
+    /// Write the full result as JSON (`--dump-issues`), to be rendered
+    /// later with `linthis render`.
+    pub fn dump(&self, path: &Path) -> crate::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Read a result written by [`RunResult::dump`].
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            LintisError::Config(format!(
+                "{} is not a dumped run result: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
     /// Count files with issues
     pub fn count_files_with_issues(&mut self) {
         use std::collections::HashSet;
         let unique_files: HashSet<_> = self.issues.iter().map(|i| &i.file_path).collect();
         self.files_with_issues = unique_files.len();
     }
+
+    /// Compare issues found before formatting with the current issues and
+    /// set `issues_fixed`, `fixed_issues` and `issues_introduced`.
+    ///
+    /// Issues are matched per file by source and code (or message when there
+    /// is no code), ignoring positions since formatting moves lines around.
+    pub fn count_issue_changes(&mut self, before: &[LintIssue]) {
+        use std::collections::HashMap;
+
+        fn key(issue: &LintIssue) -> (&PathBuf, Option<&str>, &str) {
+            let rule = issue.code.as_deref().unwrap_or(&issue.message);
+            (&issue.file_path, issue.source.as_deref(), rule)
+        }
+
+        // Positive counts: issues that disappeared; negative: newly appeared
+        let mut delta: HashMap<_, i64> = HashMap::new();
+        for issue in before {
+            *delta.entry(key(issue)).or_default() += 1;
+        }
+        for issue in &self.issues {
+            *delta.entry(key(issue)).or_default() -= 1;
+        }
+
+        self.issues_fixed = delta.values().filter(|d| **d > 0).sum::<i64>() as usize;
+        self.issues_introduced = (-delta.values().filter(|d| **d < 0).sum::<i64>()) as usize;
+
+        // The last issues of each disappeared group count as the fixed ones
+        let mut fixed = Vec::new();
+        for issue in before.iter().rev() {
+            if let Some(remaining) = delta.get_mut(&key(issue)).filter(|d| **d > 0) {
+                *remaining -= 1;
+                fixed.push(issue.clone());
+            }
+        }
+        fixed.reverse();
+        self.fixed_issues = fixed;
+    }
 }
 
 #[cfg(test)]
@@ -252,6 +613,32 @@ mod tests {
         assert_eq!(format!("{}", Severity::Info), "info");
     }
 
+    #[test]
+    fn test_severity_hint() {
+        assert_eq!(format!("{}", Severity::Hint), "hint");
+        assert_eq!(Severity::parse("note"), Some(Severity::Hint));
+        assert_eq!(serde_json::to_string(&Severity::Hint).unwrap(), "\"hint\"");
+        assert!(Severity::Info.is_at_least(Severity::Hint));
+        assert!(!Severity::Hint.is_at_least(Severity::Info));
+    }
+
+    #[test]
+    fn test_severity_parse() {
+        assert_eq!(Severity::parse("error"), Some(Severity::Error));
+        assert_eq!(Severity::parse("WARNING"), Some(Severity::Warning));
+        assert_eq!(Severity::parse("warn"), Some(Severity::Warning));
+        assert_eq!(Severity::parse("info"), Some(Severity::Info));
+        assert_eq!(Severity::parse("fatal"), None);
+    }
+
+    #[test]
+    fn test_severity_is_at_least() {
+        assert!(Severity::Error.is_at_least(Severity::Warning));
+        assert!(Severity::Warning.is_at_least(Severity::Warning));
+        assert!(!Severity::Info.is_at_least(Severity::Warning));
+        assert!(!Severity::Warning.is_at_least(Severity::Error));
+    }
+
     #[test]
     fn test_severity_equality() {
         assert_eq!(Severity::Error, Severity::Error);
@@ -427,6 +814,7 @@ mod tests {
         assert!(result.issues.is_empty());
         assert_eq!(result.issues_before_format, 0);
         assert_eq!(result.issues_fixed, 0);
+        assert_eq!(result.issues_introduced, 0);
         assert!(result.format_results.is_empty());
         assert_eq!(result.duration_ms, 0);
         assert_eq!(result.exit_code, 0);
@@ -471,6 +859,20 @@ mod tests {
         assert_eq!(result.format_results.len(), 1);
     }
 
+    #[test]
+    fn test_run_result_add_import_result() {
+        let mut result = RunResult::new();
+
+        result.add_import_result(FormatResult::changed(PathBuf::from("a.py")));
+        result.add_import_result(FormatResult::unchanged(PathBuf::from("b.py")));
+
+        assert_eq!(result.imports_organized, 1);
+        assert_eq!(result.import_results.len(), 2);
+        // Import passes are not counted as formatting
+        assert_eq!(result.files_formatted, 0);
+        assert!(result.format_results.is_empty());
+    }
+
     #[test]
     fn test_run_result_calculate_exit_code_success() {
         let mut result = RunResult::new();
@@ -506,6 +908,51 @@ mod tests {
         assert_eq!(result.exit_code, 0); // Warnings don't cause exit code 1
     }
 
+    #[test]
+    fn test_run_result_calculate_exit_code_with_hint() {
+        let mut result = RunResult::new();
+        result.add_issue(LintIssue::new(
+            PathBuf::from("test.rs"),
+            3,
+            "consider borrowing here".to_string(),
+            Severity::Hint,
+        ));
+
+        // Hints never fail, even when warnings do
+        result.calculate_exit_code_with_warnings(true);
+        assert_eq!(result.exit_code, 0);
+    }
+
+    #[test]
+    fn test_run_result_exit_code_per_language_thresholds() {
+        let issue = |lang: Language, severity: Severity| {
+            LintIssue::new(PathBuf::from("f"), 1, "msg".to_string(), severity).with_language(lang)
+        };
+        let thresholds = HashMap::from([
+            (Language::Go, Severity::Warning),
+            (Language::Python, Severity::Info),
+        ]);
+        let exit_code = |issues: &[(Language, Severity)], fail_on_warnings: bool| {
+            let mut result = RunResult::new();
+            result.issues = issues.iter().map(|&(l, s)| issue(l, s)).collect();
+            result.calculate_exit_code_with_thresholds(fail_on_warnings, &thresholds);
+            result.exit_code
+        };
+
+        // Go fails on warnings, Rust (no threshold) only on errors
+        assert_eq!(exit_code(&[(Language::Go, Severity::Warning)], false), 1);
+        let mixed = [
+            (Language::Rust, Severity::Warning),
+            (Language::Go, Severity::Info),
+        ];
+        assert_eq!(exit_code(&mixed, false), 0);
+        assert_eq!(exit_code(&[(Language::Rust, Severity::Error)], false), 1);
+        assert_eq!(exit_code(&[(Language::Python, Severity::Info)], false), 1);
+        assert_eq!(exit_code(&[(Language::Python, Severity::Hint)], false), 0);
+        // --fail-on-warnings applies to languages without a threshold
+        assert_eq!(exit_code(&[(Language::Rust, Severity::Warning)], true), 1);
+    }
+
     #[test]
     fn test_run_result_calculate_exit_code_format_error() {
         let mut result = RunResult::new();
@@ -518,6 +965,125 @@ mod tests {
         assert_eq!(result.exit_code, 2);
     }
 
+    #[test]
+    fn test_max_warnings() {
+        let with_warnings = |count: usize| {
+            let mut result = RunResult::new();
+            for line in 0..count {
+                result.add_issue(LintIssue::new(
+                    PathBuf::from("a.py"),
+                    line + 1,
+                    "warn".to_string(),
+                    Severity::Warning,
+                ));
+            }
+            result.calculate_exit_code();
+            result
+        };
+
+        for (count, max, expected) in [
+            (2, Some(3), 0),
+            (3, Some(3), 0),
+            (4, Some(3), 1),
+            (1, Some(0), 1),
+            (100, None, 0),
+        ] {
+            let mut result = with_warnings(count);
+            result.apply_max_warnings(max);
+            assert_eq!(
+                result.exit_code, expected,
+                "{} warnings, max {:?}",
+                count, max
+            );
+        }
+
+        // A failing run stays failed
+        let mut result = with_warnings(1);
+        result.exit_code = 2;
+        result.apply_max_warnings(Some(5));
+        assert_eq!(result.exit_code, 2);
+    }
+
+    #[test]
+    fn test_run_result_calculate_exit_code_unformatted() {
+        let mut result = RunResult::new();
+        result.unformatted_files.push(PathBuf::from("test.rs"));
+
+        result.calculate_exit_code();
+        assert_eq!(result.exit_code, 1);
+    }
+
+    #[test]
+    fn test_ruff_syntax_error_is_always_an_error() {
+        let issue = |file: &str, source: &str, code: &str| {
+            LintIssue::new(PathBuf::from(file), 1, "msg".to_string(), Severity::Warning)
+                .with_source(source.to_string())
+                .with_code(code.to_string())
+        };
+        let mut result = RunResult::new();
+        result.add_issue(issue("a.py", "ruff", "E999").with_language(Language::Python));
+        result.add_issue(issue("b.py", "ruff", "E501"));
+        result.add_issue(issue("c.js", "eslint", "E999"));
+        result.apply_default_error_codes();
+        result.calculate_exit_code_with_thresholds(false, &HashMap::new());
+
+        let severities: Vec<Severity> = result.issues.iter().map(|i| i.severity).collect();
+        assert_eq!(
+            severities,
+            vec![Severity::Error, Severity::Warning, Severity::Warning]
+        );
+        assert_eq!(result.exit_code, exit_code::ISSUES);
+
+        // User overrides are layered on top
+        let overrides = HashMap::from([("ruff:E999".to_string(), Severity::Info)]);
+        result.apply_severity_overrides(&overrides);
+        assert_eq!(result.issues[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_severity_overrides_scoped_to_source() {
+        let issue = |source: &str, code: &str| {
+            LintIssue::new(
+                PathBuf::from("a.cc"),
+                1,
+                "msg".to_string(),
+                Severity::Warning,
+            )
+            .with_source(source.to_string())
+            .with_code(code.to_string())
+        };
+        let mut result = RunResult::new();
+        result.add_issue(issue("cpplint", "build/header_guard"));
+        result.add_issue(issue("cpplint", "build/include_order"));
+        result.add_issue(issue("cpplint", "whitespace/tab"));
+        result.add_issue(issue("eslint", "build/header_guard"));
+        result.add_issue(issue("ruff", "E501"));
+
+        let overrides: HashMap<String, Severity> = [
+            ("cpplint:build/*", Severity::Error),
+            ("cpplint:build/include_order", Severity::Info),
+            ("E501", Severity::Hint),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        result.apply_severity_overrides(&overrides);
+
+        let severities: Vec<Severity> = result.issues.iter().map(|i| i.severity).collect();
+        assert_eq!(
+            severities,
+            vec![
+                Severity::Error,
+                Severity::Info,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Hint,
+            ]
+        );
+        result.calculate_exit_code();
+        assert_eq!(result.exit_code, 1);
+    }
+
     #[test]
     fn test_run_result_count_files_with_issues() {
         let mut result = RunResult::new();
@@ -552,4 +1118,63 @@ mod tests {
         result.count_files_with_issues();
         assert_eq!(result.files_with_issues, 0);
     }
+
+    #[test]
+    fn test_count_issue_changes() {
+        let issue = |file: &str, line: usize, code: &str| {
+            LintIssue::new(
+                PathBuf::from(file),
+                line,
+                format!("{} msg", code),
+                Severity::Warning,
+            )
+            .with_source("ruff".to_string())
+            .with_code(code.to_string())
+        };
+
+        let before = vec![
+            issue("a.py", 1, "E501"),
+            issue("a.py", 7, "E501"),
+            issue("a.py", 3, "W291"),
+            issue("b.py", 2, "F401"),
+        ];
+        let mut result = RunResult::new();
+        // After formatting: one E501 and the W291 are gone, lines shifted,
+        // and the formatter introduced an E203 in b.py
+        result.add_issue(issue("a.py", 2, "E501"));
+        result.add_issue(issue("b.py", 4, "F401"));
+        result.add_issue(issue("b.py", 5, "E203"));
+
+        result.count_issue_changes(&before);
+        assert_eq!(result.issues_fixed, 2);
+        assert_eq!(result.issues_introduced, 1);
+        let fixed: Vec<_> = result
+            .fixed_issues
+            .iter()
+            .map(|i| (i.line, i.code.as_deref().unwrap()))
+            .collect();
+        assert_eq!(fixed, [(7, "E501"), (3, "W291")]);
+    }
+
+    #[test]
+    fn test_count_issue_changes_same_code_different_file() {
+        let before = vec![LintIssue::new(
+            PathBuf::from("a.py"),
+            1,
+            "unused".to_string(),
+            Severity::Warning,
+        )];
+        let mut result = RunResult::new();
+        // Same message in another file is a different issue
+        result.add_issue(LintIssue::new(
+            PathBuf::from("b.py"),
+            1,
+            "unused".to_string(),
+            Severity::Warning,
+        ));
+
+        result.count_issue_changes(&before);
+        assert_eq!(result.issues_fixed, 1);
+        assert_eq!(result.issues_introduced, 1);
+    }
 }
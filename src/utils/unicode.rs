@@ -86,11 +86,10 @@ pub fn break_text_at_width(text: &str, max_width: usize) -> Vec<String> {
 
     for c in text.chars() {
         let char_width = if is_wide_char(c) { 2 } else { 1 };
-        current.push(c);
-        current_width += char_width;
 
-        // Check column width
-        if current_width >= max_width {
+        // Flush before this character would overflow the line. A character
+        // wider than the whole width still gets a line of its own.
+        while !current.is_empty() && current_width + char_width > max_width {
             // Try to find a good break point near the end
             if let Some(break_pos) = find_break_point(&current, break_after) {
                 let (first, rest) = current.split_at(break_pos);
@@ -98,12 +97,15 @@ pub fn break_text_at_width(text: &str, max_width: usize) -> Vec<String> {
                 current = rest.to_string();
                 current_width = get_column_width(&current);
             } else {
-                // No good break point, just break at current position
-                lines.push(current.clone());
-                current.clear();
+                // No good break point (e.g. a long URL): hard break at the
+                // column boundary, which always falls between characters
+                lines.push(std::mem::take(&mut current));
                 current_width = 0;
             }
         }
+
+        current.push(c);
+        current_width += char_width;
     }
 
     if !current.is_empty() {
@@ -242,4 +244,29 @@ mod tests {
         // Should still break even without good break points
         assert!(!result.is_empty());
     }
+
+    #[test]
+    fn test_break_text_unbreakable_token_hard_breaks() {
+        let url = format!("https://example.com/{}", "a".repeat(100));
+        let result = break_text_at_width(&url, 30);
+        assert_eq!(result.len(), 4);
+        assert!(result
+            .iter()
+            .all(|l| !l.is_empty() && get_column_width(l) <= 30));
+        assert_eq!(result.concat(), url);
+
+        // Hard breaks fall between wide characters, never splitting one
+        let cjk = "测".repeat(20);
+        let result = break_text_at_width(&cjk, 15);
+        let widths: Vec<usize> = result.iter().map(|l| get_column_width(l)).collect();
+        assert_eq!(widths, vec![14, 14, 12]);
+        assert_eq!(result.concat(), cjk);
+    }
+
+    #[test]
+    fn test_break_text_width_narrower_than_char() {
+        // Each wide character gets its own line instead of looping forever
+        assert_eq!(break_text_at_width("测试", 1), vec!["测", "试"]);
+        assert_eq!(break_text_at_width("ab", 0), vec!["a", "b"]);
+    }
 }
@@ -13,20 +13,111 @@
 use crate::Language;
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use rayon::prelude::*;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use walkdir::WalkDir;
 
 /// File walker configuration.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct WalkerConfig {
     /// Glob patterns to exclude
     pub exclude_patterns: Vec<String>,
+    /// Glob patterns exempt from all exclusions (`--audit`)
+    pub audit_patterns: Vec<String>,
     /// Only include files with these languages (empty = all)
     pub languages: Vec<Language>,
     /// Maximum directory depth (0 = unlimited)
     pub max_depth: usize,
     /// Follow symbolic links
     pub follow_links: bool,
+    /// Per-language extension sets replacing the built-in ones
+    pub extension_overrides: HashMap<Language, Vec<String>>,
+    /// Skip empty and whitespace-only files (default: true)
+    pub skip_empty: bool,
+}
+
+impl Default for WalkerConfig {
+    fn default() -> Self {
+        Self {
+            exclude_patterns: Vec::new(),
+            audit_patterns: Vec::new(),
+            languages: Vec::new(),
+            max_depth: 0,
+            follow_links: false,
+            extension_overrides: HashMap::new(),
+            skip_empty: true,
+        }
+    }
+}
+
+/// Whether a file is empty or contains only whitespace.
+///
+/// Reads only until the first non-whitespace byte, so regular files cost a
+/// single small read. Unreadable files are not considered blank.
+pub fn is_blank_file(path: &Path) -> bool {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut buf = [0u8; 4096];
+    loop {
+        match file.read(&mut buf) {
+            Ok(0) => return true,
+            Ok(n) => {
+                if !buf[..n].iter().all(u8::is_ascii_whitespace) {
+                    return false;
+                }
+            }
+            Err(_) => return false,
+        }
+    }
+}
+
+/// Remove empty and whitespace-only files from `files`, returning them.
+pub fn drop_blank_files(files: &mut Vec<PathBuf>) -> Vec<PathBuf> {
+    let (blank, kept): (Vec<_>, Vec<_>) = files.drain(..).partition(|f| is_blank_file(f));
+    *files = kept;
+    blank
+}
+
+/// Detect a file's language, applying per-language extension overrides.
+///
+/// A language with an override only claims the extensions it lists, so
+/// removing `h` from Cpp excludes headers, and listing `h` for Objective-C
+/// routes every header there.
+pub fn language_for_path(
+    path: &Path,
+    extension_overrides: &HashMap<Language, Vec<String>>,
+) -> Option<Language> {
+    if extension_overrides.is_empty() {
+        return Language::from_path(path);
+    }
+
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    let claims = |lang: &Language| {
+        extension_overrides
+            .get(lang)
+            .map(|exts| exts.contains(&ext))
+            .unwrap_or_else(|| lang.extensions().contains(&ext.as_str()))
+    };
+
+    // Built-in detection wins when that language still claims the extension
+    if let Some(lang) = Language::from_path(path) {
+        if claims(&lang) {
+            return Some(lang);
+        }
+    }
+
+    // Otherwise route to a language that explicitly lists it
+    let mut claimants: Vec<_> = extension_overrides
+        .iter()
+        .filter(|(_, exts)| exts.contains(&ext))
+        .map(|(lang, _)| *lang)
+        .collect();
+    claimants.sort_by_key(|lang| lang.name());
+    claimants.into_iter().next()
 }
 
 /// Build a GlobSet from patterns.
@@ -80,13 +171,134 @@ fn is_excluded(path: &Path, glob_set: &Option<GlobSet>) -> bool {
     false
 }
 
+/// Check if a path matches an audit pattern, which overrides exclusions.
+///
+/// Like exclusions, patterns match from any component, so "third_party/**"
+/// also audits "/repo/app/third_party/x.cc".
+pub fn is_audited(path: &Path, glob_set: &Option<GlobSet>) -> bool {
+    let Some(gs) = glob_set else {
+        return false;
+    };
+    let components: Vec<_> = path
+        .components()
+        .filter(|c| matches!(c, std::path::Component::Normal(_)))
+        .collect();
+    (0..components.len()).any(|i| gs.is_match(components[i..].iter().collect::<PathBuf>()))
+}
+
+/// Check if files under a directory may match an audit pattern, so the
+/// directory is walked even when excluded. Compares the directory, from any
+/// component, with the literal components of each pattern before its first
+/// wildcard.
+fn may_contain_audited(dir: &Path, patterns: &[String]) -> bool {
+    let dir: Vec<String> = dir
+        .components()
+        .filter_map(|c| match c {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    patterns.iter().any(|pattern| {
+        let literal: Vec<&str> = pattern
+            .split('/')
+            .filter(|c| !c.is_empty() && *c != ".")
+            .take_while(|c| !c.contains(['*', '?', '[', '{']))
+            .collect();
+        (0..dir.len().max(1)).any(|i| dir[i..].iter().zip(&literal).all(|(a, b)| a == b))
+    })
+}
+
+/// Directories that only ever hold files (or artifacts) of specific languages.
+/// When a language filter is active and none of these languages are requested,
+/// the whole subtree is skipped without being walked.
+const LANGUAGE_SPECIFIC_DIRS: &[(&str, &[Language])] = &[
+    (
+        "node_modules",
+        &[Language::JavaScript, Language::TypeScript],
+    ),
+    (
+        "bower_components",
+        &[Language::JavaScript, Language::TypeScript],
+    ),
+    ("__pycache__", &[Language::Python]),
+    (".venv", &[Language::Python]),
+    ("venv", &[Language::Python]),
+    (".tox", &[Language::Python]),
+    (".mypy_cache", &[Language::Python]),
+    (".pytest_cache", &[Language::Python]),
+    (".ruff_cache", &[Language::Python]),
+    (".gradle", &[Language::Java]),
+    ("Pods", &[Language::ObjectiveC, Language::Cpp]),
+    ("Carthage", &[Language::ObjectiveC, Language::Cpp]),
+    ("DerivedData", &[Language::ObjectiveC, Language::Cpp]),
+];
+
+/// Check if a directory may contain files of the requested languages (heuristic).
+fn dir_may_contain_languages(path: &Path, languages: &[Language]) -> bool {
+    if languages.is_empty() {
+        return true;
+    }
+
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return true;
+    };
+
+    LANGUAGE_SPECIFIC_DIRS
+        .iter()
+        .find(|(dir, _)| *dir == name)
+        .map(|(_, langs)| langs.iter().any(|l| languages.contains(l)))
+        .unwrap_or(true)
+}
+
+/// Cheap extension-only pre-filter for the language filter.
+///
+/// Runs before exclusion matching and content-based detection (e.g. for `.h`
+/// headers), so restricted runs don't pay for files they will never process.
+fn extension_may_match(
+    path: &Path,
+    languages: &[Language],
+    extension_overrides: &HashMap<Language, Vec<String>>,
+) -> bool {
+    if languages.is_empty() {
+        return true;
+    }
+
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    let ext = ext.to_lowercase();
+
+    if languages.iter().any(|l| {
+        extension_overrides
+            .get(l)
+            .is_some_and(|exts| exts.contains(&ext))
+    }) {
+        return true;
+    }
+
+    // .h headers may be C++ or Objective-C, resolved later by content detection
+    if ext == "h" {
+        return languages
+            .iter()
+            .any(|l| matches!(l, Language::Cpp | Language::ObjectiveC));
+    }
+
+    languages
+        .iter()
+        .any(|l| l.extensions().contains(&ext.as_str()))
+}
+
 /// Check if a file matches the language filter.
-fn matches_language_filter(path: &Path, languages: &[Language]) -> bool {
+fn matches_language_filter(
+    path: &Path,
+    languages: &[Language],
+    extension_overrides: &HashMap<Language, Vec<String>>,
+) -> bool {
     if languages.is_empty() {
         return true;
     }
 
-    if let Some(lang) = Language::from_path(path) {
+    if let Some(lang) = language_for_path(path, extension_overrides) {
         languages.contains(&lang)
     } else {
         false
@@ -95,7 +307,25 @@ fn matches_language_filter(path: &Path, languages: &[Language]) -> bool {
 
 /// Walk a directory and collect files matching the criteria.
 pub fn walk_files(root: &Path, config: &WalkerConfig) -> Vec<PathBuf> {
+    walk_files_and_dirs(root, config, &mut Vec::new())
+}
+
+/// [`walk_files`], also collecting every directory walked into `dirs`.
+fn walk_files_and_dirs(
+    root: &Path,
+    config: &WalkerConfig,
+    dirs: &mut Vec<PathBuf>,
+) -> Vec<PathBuf> {
     let glob_set = build_glob_set(&config.exclude_patterns);
+    let audit_set = build_glob_set(&config.audit_patterns);
+    // Match relative to the walk root too, so "vendor/**" applies under any root
+    let excluded = |path: &Path| {
+        is_excluded(path, &glob_set)
+            || path
+                .strip_prefix(root)
+                .is_ok_and(|rel| is_excluded(rel, &glob_set))
+    };
+    let dir_audited = |path: &Path| may_contain_audited(path, &config.audit_patterns);
 
     let mut walker = WalkDir::new(root).follow_links(config.follow_links);
 
@@ -106,13 +336,31 @@ pub fn walk_files(root: &Path, config: &WalkerConfig) -> Vec<PathBuf> {
     walker
         .into_iter()
         .filter_entry(|e| {
-            // Skip excluded directories early
-            !is_excluded(e.path(), &glob_set)
+            // Skip directories that can't hold the requested languages
+            if e.depth() > 0
+                && e.file_type().is_dir()
+                && !dir_may_contain_languages(e.path(), &config.languages)
+            {
+                return false;
+            }
+            // Skip excluded directories early, unless audited files may be inside
+            if e.file_type().is_dir() {
+                let walked = !excluded(e.path()) || dir_audited(e.path());
+                if walked {
+                    dirs.push(e.path().to_path_buf());
+                }
+                return walked;
+            }
+            true
         })
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
-        .filter(|e| !is_excluded(e.path(), &glob_set))
-        .filter(|e| matches_language_filter(e.path(), &config.languages))
+        // Short-circuit on extension before any other per-file work
+        .filter(|e| extension_may_match(e.path(), &config.languages, &config.extension_overrides))
+        .filter(|e| !excluded(e.path()) || is_audited(e.path(), &audit_set))
+        .filter(|e| {
+            matches_language_filter(e.path(), &config.languages, &config.extension_overrides)
+        })
         .map(|e| e.path().to_path_buf())
         .collect()
 }
@@ -131,19 +379,30 @@ where
 /// Walk multiple paths (files or directories).
 /// Returns (files, warnings) tuple.
 pub fn walk_paths(paths: &[PathBuf], config: &WalkerConfig) -> (Vec<PathBuf>, Vec<String>) {
+    walk_paths_and_dirs(paths, config, &mut Vec::new())
+}
+
+/// [`walk_paths`], also collecting every directory walked into `dirs`.
+fn walk_paths_and_dirs(
+    paths: &[PathBuf],
+    config: &WalkerConfig,
+    dirs: &mut Vec<PathBuf>,
+) -> (Vec<PathBuf>, Vec<String>) {
     let glob_set = build_glob_set(&config.exclude_patterns);
+    let audit_set = build_glob_set(&config.audit_patterns);
 
     let mut result = Vec::new();
     let mut warnings = Vec::new();
 
     for path in paths {
         if path.is_file() {
-            if is_excluded(path, &glob_set) {
+            if is_excluded(path, &glob_set) && !is_audited(path, &audit_set) {
                 warnings.push(format!(
                     "Path '{}' is excluded by exclude patterns",
                     path.display()
                 ));
-            } else if !matches_language_filter(path, &config.languages) {
+            } else if !matches_language_filter(path, &config.languages, &config.extension_overrides)
+            {
                 warnings.push(format!(
                     "Path '{}' does not match language filter",
                     path.display()
@@ -152,15 +411,179 @@ pub fn walk_paths(paths: &[PathBuf], config: &WalkerConfig) -> (Vec<PathBuf>, Ve
                 result.push(path.clone());
             }
         } else if path.is_dir() {
-            result.extend(walk_files(path, config));
+            result.extend(walk_files_and_dirs(path, config, dirs));
         } else if !path.exists() {
             warnings.push(format!("Path '{}' does not exist", path.display()));
         }
     }
+    if config.skip_empty {
+        drop_blank_files(&mut result);
+    }
 
     (result, warnings)
 }
 
+/// File list cached by [`walk_paths_cached`]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ScanCache {
+    /// Every directory walked, whose mtimes make up the fingerprint
+    dirs: Vec<PathBuf>,
+    /// Fingerprint of the scanned tree when the list was recorded
+    fingerprint: u64,
+    files: Vec<PathBuf>,
+    warnings: Vec<String>,
+}
+
+/// Cached file lists kept in a cache directory; the oldest are pruned beyond this
+const MAX_SCAN_CACHE_FILES: usize = 32;
+
+/// Default directory for cached file lists (`<user cache>/linthis/walk`)
+pub fn scan_cache_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "linthis").map(|dirs| dirs.cache_dir().join("walk"))
+}
+
+/// Hash of everything that selects files: the paths and the walker config.
+///
+/// `skip_empty` is left out: the cached list keeps blank files, which are
+/// dropped after loading because a file's content can change without
+/// touching any directory.
+fn scan_cache_key(paths: &[PathBuf], config: &WalkerConfig) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    std::env::current_dir().ok().hash(&mut hasher);
+    paths.hash(&mut hasher);
+    let mut excludes = config.exclude_patterns.clone();
+    excludes.sort();
+    excludes.hash(&mut hasher);
+    let mut audits = config.audit_patterns.clone();
+    audits.sort();
+    audits.hash(&mut hasher);
+    config.languages.hash(&mut hasher);
+    let mut overrides: Vec<_> = config.extension_overrides.iter().collect();
+    overrides.sort_by_key(|(lang, _)| lang.name());
+    overrides.hash(&mut hasher);
+    config.max_depth.hash(&mut hasher);
+    config.follow_links.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash of the modification times of the walked paths and directories.
+///
+/// Adding, removing or renaming an entry touches its directory's mtime, so
+/// any change to which files exist under a walked directory shows up here.
+fn scan_fingerprint(paths: &[PathBuf], dirs: &[PathBuf]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for path in paths.iter().chain(dirs) {
+        path.hash(&mut hasher);
+        std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Remove all but the `keep` most recently written files in `cache_dir`.
+fn prune_scan_cache(cache_dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(cache_dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .filter(|(_, path)| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    if files.len() <= keep {
+        return;
+    }
+    files.sort_by_key(|(mtime, _)| std::cmp::Reverse(*mtime));
+    for (_, path) in files.into_iter().skip(keep) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Like [`walk_paths`], but reuses the file list cached in `cache_dir` when
+/// the paths, walker config and the mtimes of every walked directory are
+/// unchanged (`--scan-cache`).
+///
+/// At most [`MAX_SCAN_CACHE_FILES`] lists are kept in `cache_dir`.
+/// With no `cache_dir`, or no directory to walk, this is a plain [`walk_paths`].
+pub fn walk_paths_cached(
+    paths: &[PathBuf],
+    config: &WalkerConfig,
+    cache_dir: Option<&Path>,
+) -> (Vec<PathBuf>, Vec<String>) {
+    // Explicit file lists (e.g. staged files) are cheap and rarely repeat
+    let Some(cache_dir) = cache_dir.filter(|_| paths.iter().any(|p| p.is_dir())) else {
+        return walk_paths(paths, config);
+    };
+    let (mut files, warnings) = walk_paths_cached_unfiltered(paths, config, cache_dir);
+    if config.skip_empty {
+        drop_blank_files(&mut files);
+    }
+    (files, warnings)
+}
+
+/// [`walk_paths_cached`] keeping blank files, which is what gets cached.
+fn walk_paths_cached_unfiltered(
+    paths: &[PathBuf],
+    config: &WalkerConfig,
+    cache_dir: &Path,
+) -> (Vec<PathBuf>, Vec<String>) {
+    let cache_file = cache_dir.join(format!("{:016x}.json", scan_cache_key(paths, config)));
+    let cached = std::fs::read_to_string(&cache_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<ScanCache>(&content).ok())
+        .filter(|cache| cache.fingerprint == scan_fingerprint(paths, &cache.dirs));
+    if let Some(cache) = cached {
+        // Deleted files are dropped without a rescan
+        let files = cache.files.into_iter().filter(|f| f.is_file()).collect();
+        return (files, cache.warnings);
+    }
+
+    let config = WalkerConfig {
+        skip_empty: false,
+        ..config.clone()
+    };
+    let mut dirs = Vec::new();
+    let (files, warnings) = walk_paths_and_dirs(paths, &config, &mut dirs);
+    let cache = ScanCache {
+        fingerprint: scan_fingerprint(paths, &dirs),
+        dirs,
+        files,
+        warnings,
+    };
+    // The cache is best effort: failing to write it only costs a rescan
+    if let Ok(content) = serde_json::to_string(&cache) {
+        let _ =
+            std::fs::create_dir_all(cache_dir).and_then(|_| std::fs::write(&cache_file, content));
+        prune_scan_cache(cache_dir, MAX_SCAN_CACHE_FILES);
+    }
+    (cache.files, cache.warnings)
+}
+
+/// Create `files` (relative to `root`, with their parent directories) for
+/// walker tests.
+#[cfg(test)]
+pub(crate) fn write_test_tree(root: &Path, files: &[&str]) {
+    for file in files {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "x\n").unwrap();
+    }
+}
+
+/// Files [`walk_files`] finds under `root`, relative to it and sorted.
+#[cfg(test)]
+pub(crate) fn walk_relative(root: &Path, config: &WalkerConfig) -> Vec<PathBuf> {
+    let mut files: Vec<_> = walk_files(root, config)
+        .into_iter()
+        .map(|p| p.strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    files.sort();
+    files
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,18 +604,307 @@ mod tests {
     #[test]
     fn test_matches_language_filter() {
         let languages = vec![Language::Rust, Language::Python];
+        let none = HashMap::new();
 
         assert!(matches_language_filter(
             Path::new("src/main.rs"),
-            &languages
+            &languages,
+            &none
         ));
         assert!(matches_language_filter(
             Path::new("scripts/build.py"),
-            &languages
+            &languages,
+            &none
+        ));
+        assert!(!matches_language_filter(
+            Path::new("index.js"),
+            &languages,
+            &none
         ));
-        assert!(!matches_language_filter(Path::new("index.js"), &languages));
 
         // Empty filter matches all
-        assert!(matches_language_filter(Path::new("index.js"), &[]));
+        assert!(matches_language_filter(Path::new("index.js"), &[], &none));
+    }
+
+    #[test]
+    fn test_extension_may_match() {
+        let rust_only = vec![Language::Rust];
+        let none = HashMap::new();
+        assert!(extension_may_match(
+            Path::new("src/lib.rs"),
+            &rust_only,
+            &none
+        ));
+        assert!(!extension_may_match(Path::new("app.py"), &rust_only, &none));
+        assert!(!extension_may_match(Path::new("foo.h"), &rust_only, &none));
+        assert!(!extension_may_match(
+            Path::new("Makefile"),
+            &rust_only,
+            &none
+        ));
+
+        // Headers are only candidates when C++ or Objective-C is requested
+        assert!(extension_may_match(
+            Path::new("foo.h"),
+            &[Language::ObjectiveC],
+            &none
+        ));
+        assert!(extension_may_match(
+            Path::new("foo.H"),
+            &[Language::Cpp],
+            &none
+        ));
+
+        // Empty filter matches all
+        assert!(extension_may_match(Path::new("Makefile"), &[], &none));
+
+        // Overrides can add extensions
+        let overrides =
+            HashMap::from([(Language::Python, vec!["py".to_string(), "pyi".to_string()])]);
+        assert!(extension_may_match(
+            Path::new("stub.pyi"),
+            &[Language::Python],
+            &overrides
+        ));
+    }
+
+    #[test]
+    fn test_language_for_path_with_overrides() {
+        let none = HashMap::new();
+        assert_eq!(
+            language_for_path(Path::new("a.pyw"), &none),
+            Some(Language::Python)
+        );
+
+        let overrides = HashMap::from([
+            (Language::Python, vec!["py".to_string()]),
+            (Language::Go, vec!["go".to_string(), "gotmpl".to_string()]),
+        ]);
+        // Excluded from the language's own set
+        assert_eq!(language_for_path(Path::new("a.pyw"), &overrides), None);
+        assert_eq!(
+            language_for_path(Path::new("a.py"), &overrides),
+            Some(Language::Python)
+        );
+        // Routed to a language that lists it
+        assert_eq!(
+            language_for_path(Path::new("x.gotmpl"), &overrides),
+            Some(Language::Go)
+        );
+        // Languages without an override keep their defaults
+        assert_eq!(
+            language_for_path(Path::new("lib.rs"), &overrides),
+            Some(Language::Rust)
+        );
+    }
+
+    #[test]
+    fn test_language_for_path_routes_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let header = dir.path().join("api.h");
+        std::fs::write(&header, "int add(int a, int b);\n").unwrap();
+
+        let overrides = HashMap::from([
+            (Language::Cpp, vec!["c".to_string(), "cpp".to_string()]),
+            (
+                Language::ObjectiveC,
+                vec!["m".to_string(), "mm".to_string(), "h".to_string()],
+            ),
+        ]);
+        assert_eq!(
+            language_for_path(&header, &HashMap::new()),
+            Some(Language::Cpp)
+        );
+        assert_eq!(
+            language_for_path(&header, &overrides),
+            Some(Language::ObjectiveC)
+        );
+    }
+
+    #[test]
+    fn test_dir_may_contain_languages() {
+        let rust_only = vec![Language::Rust];
+        assert!(!dir_may_contain_languages(
+            Path::new("web/node_modules"),
+            &rust_only
+        ));
+        assert!(!dir_may_contain_languages(
+            Path::new("__pycache__"),
+            &rust_only
+        ));
+        assert!(dir_may_contain_languages(Path::new("src"), &rust_only));
+        assert!(dir_may_contain_languages(
+            Path::new("node_modules"),
+            &[Language::TypeScript]
+        ));
+        assert!(dir_may_contain_languages(Path::new("node_modules"), &[]));
+    }
+
+    #[test]
+    fn test_walk_files_language_fast_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/util.py"), "x = 1\n").unwrap();
+        std::fs::write(root.join("src/api.h"), "@interface Foo\n@end\n").unwrap();
+        std::fs::write(root.join("node_modules/pkg/lib.rs"), "fn x() {}\n").unwrap();
+
+        let config = WalkerConfig {
+            languages: vec![Language::Rust],
+            ..Default::default()
+        };
+        let files = walk_files(root, &config);
+
+        assert_eq!(files, vec![root.join("src/main.rs")]);
+    }
+
+    #[test]
+    fn test_walk_files_cpp_extension_override_skips_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("main.cpp"), "int main() {}\n").unwrap();
+        std::fs::write(root.join("util.h"), "int util();\n").unwrap();
+
+        let mut config = WalkerConfig {
+            languages: vec![Language::Cpp],
+            ..Default::default()
+        };
+        let mut files = walk_files(root, &config);
+        files.sort();
+        assert_eq!(files, vec![root.join("main.cpp"), root.join("util.h")]);
+
+        config.extension_overrides =
+            HashMap::from([(Language::Cpp, vec!["cpp".to_string(), "cc".to_string()])]);
+        assert_eq!(walk_files(root, &config), vec![root.join("main.cpp")]);
+    }
+
+    #[test]
+    fn test_walk_paths_cached_reuses_list_for_unchanged_tree() {
+        let tree = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        let root = tree.path().to_path_buf();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.py"), "a = 1\n").unwrap();
+        std::fs::write(root.join("src/b.py"), "b = 2\n").unwrap();
+
+        let config = WalkerConfig::default();
+        let paths = vec![root.clone()];
+        let walk = || walk_paths_cached(&paths, &config, Some(cache.path())).0;
+        let mut files = walk();
+        files.sort();
+        assert_eq!(files, vec![root.join("src/a.py"), root.join("src/b.py")]);
+
+        // Drop an entry from the cached list: an unchanged tree must reuse it
+        let cache_file = std::fs::read_dir(cache.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let mut cached: ScanCache =
+            serde_json::from_str(&std::fs::read_to_string(&cache_file).unwrap()).unwrap();
+        cached.files.retain(|f| f.ends_with("a.py"));
+        std::fs::write(&cache_file, serde_json::to_string(&cached).unwrap()).unwrap();
+        assert_eq!(walk(), vec![root.join("src/a.py")]);
+
+        // Changing a top-level directory invalidates the cache
+        std::fs::write(root.join("src/c.py"), "c = 3\n").unwrap();
+        assert_eq!(walk().len(), 3);
+
+        // So does adding a file in a nested directory
+        std::fs::create_dir_all(root.join("src/pkg/sub")).unwrap();
+        assert_eq!(walk().len(), 3);
+        std::fs::write(root.join("src/pkg/sub/d.py"), "d = 4\n").unwrap();
+        assert_eq!(walk().len(), 4);
+
+        // Without a cache dir the tree is always walked
+        assert_eq!(walk_paths_cached(&paths, &config, None).0.len(), 4);
+    }
+
+    #[test]
+    fn test_prune_scan_cache_keeps_newest() {
+        let cache = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            let file = cache.path().join(format!("{}.json", i));
+            std::fs::write(&file, "{}").unwrap();
+            let mtime = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1000 + i);
+            std::fs::File::options()
+                .write(true)
+                .open(&file)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+        }
+        prune_scan_cache(cache.path(), 2);
+        let mut left: Vec<_> = std::fs::read_dir(cache.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["3.json", "4.json"]);
+    }
+
+    #[test]
+    fn test_walk_paths_skip_empty() {
+        let tree = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        let root = tree.path().to_path_buf();
+        std::fs::write(root.join("empty.py"), "").unwrap();
+        std::fs::write(root.join("blank.py"), "  \n\t\n\n").unwrap();
+        std::fs::write(root.join("code.py"), "\n\nx = 1\n").unwrap();
+
+        let walk = |skip_empty: bool, cache_dir: Option<&Path>| {
+            let config = WalkerConfig {
+                skip_empty,
+                ..Default::default()
+            };
+            let mut files = walk_paths_cached(std::slice::from_ref(&root), &config, cache_dir).0;
+            files.sort();
+            files
+        };
+        assert_eq!(walk(true, None), vec![root.join("code.py")]);
+        assert_eq!(walk(false, None).len(), 3);
+
+        // Explicit files are filtered too
+        let config = WalkerConfig::default();
+        assert!(walk_paths(&[root.join("empty.py")], &config).0.is_empty());
+
+        // A cached list still drops files that have become blank
+        assert_eq!(walk(true, Some(cache.path())), vec![root.join("code.py")]);
+        std::fs::write(root.join("code.py"), "\n").unwrap();
+        assert!(walk(true, Some(cache.path())).is_empty());
+        assert_eq!(walk(false, Some(cache.path())).len(), 3);
+    }
+
+    #[test]
+    fn test_audit_lints_excluded_third_party_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("third_party/lib")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/x")).unwrap();
+        std::fs::write(root.join("src/a.cc"), "int a;\n").unwrap();
+        std::fs::write(root.join("third_party/lib/b.cc"), "int b;\n").unwrap();
+        std::fs::write(root.join("node_modules/x/c.js"), "let c;\n").unwrap();
+
+        let mut config = WalkerConfig {
+            exclude_patterns: crate::utils::default_excludes_without(&[]),
+            ..Default::default()
+        };
+        assert_eq!(walk_files(root, &config), vec![root.join("src/a.cc")]);
+
+        config.audit_patterns = vec!["third_party/**".to_string()];
+        let mut files = walk_files(root, &config);
+        files.sort();
+        assert_eq!(
+            files,
+            vec![root.join("src/a.cc"), root.join("third_party/lib/b.cc")]
+        );
+
+        // Explicitly passed files are audited too
+        let b = root.join("third_party/lib/b.cc");
+        assert_eq!(walk_paths(std::slice::from_ref(&b), &config).0, vec![b]);
     }
 }